@@ -0,0 +1,159 @@
+//! Block Reader Benchmarks
+//!
+//! Streams 10k LZ4-compressed blocks through [`BlockReader`] over a loopback
+//! socket, with and without reusable decompression buffers.
+//!
+//! ## Benchmarks:
+//! - Heap allocations per 10k blocks (printed before the timing runs)
+//! - Time to read 10k blocks with buffer reuse enabled and disabled
+//!
+//! ## Run with:
+//! `cargo bench --bench block_reader_benchmarks`
+
+use bytes::BytesMut;
+use clickhouse_native_client::{
+    column::numeric::ColumnUInt64,
+    compression::compress,
+    connection::Connection,
+    io::block_stream::{
+        BlockReader,
+        BlockWriter,
+    },
+    protocol::CompressionMethod,
+    Block,
+};
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+};
+use std::{
+    alloc::{
+        GlobalAlloc,
+        Layout,
+        System,
+    },
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+    },
+};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+    runtime::Runtime,
+};
+
+const BLOCKS: usize = 10_000;
+const ROWS_PER_BLOCK: u64 = 1_000;
+
+/// Global allocator that counts allocations
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// One compressed block with a single UInt64 column
+fn compressed_block() -> Vec<u8> {
+    let mut col = ColumnUInt64::new();
+    for i in 0..ROWS_PER_BLOCK {
+        col.append(i);
+    }
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(col)).unwrap();
+
+    let mut buffer = BytesMut::new();
+    BlockWriter::new(54449)
+        .write_block_to_buffer(&mut buffer, &block)
+        .unwrap();
+    compress(CompressionMethod::Lz4, &buffer).unwrap().to_vec()
+}
+
+/// Connection that receives `payload` from a loopback peer
+async fn stream_connection(payload: Arc<Vec<u8>>) -> Connection {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        socket.write_all(&payload).await.unwrap();
+    });
+    Connection::new(TcpStream::connect(addr).await.unwrap())
+}
+
+/// Read every block of the stream, returning the total row count
+async fn read_all(payload: Arc<Vec<u8>>, reuse: bool) -> usize {
+    let mut conn = stream_connection(payload).await;
+    let mut reader = BlockReader::new(54449)
+        .with_compression(CompressionMethod::Lz4)
+        .with_reuse_buffers(reuse);
+
+    let mut rows = 0;
+    for _ in 0..BLOCKS {
+        rows += reader.read_block(&mut conn).await.unwrap().row_count();
+    }
+    rows
+}
+
+fn block_reader_streaming(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let payload = Arc::new(compressed_block().repeat(BLOCKS));
+
+    for reuse in [true, false] {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        rt.block_on(read_all(payload.clone(), reuse));
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "reuse_buffers={}: {} allocations for {} blocks",
+            reuse, allocations, BLOCKS
+        );
+    }
+
+    let mut group = c.benchmark_group("block_reader_10k_blocks");
+    group.sample_size(10);
+    for reuse in [true, false] {
+        group.bench_with_input(
+            BenchmarkId::new("reuse_buffers", reuse),
+            &reuse,
+            |b, &reuse| {
+                b.to_async(&rt).iter(|| async {
+                    black_box(read_all(payload.clone(), reuse).await)
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, block_reader_streaming);
+criterion_main!(benches);
@@ -0,0 +1,126 @@
+//! Derive macro for `clickhouse_native_client::Row`.
+//!
+//! Use it through the `derive` feature of `clickhouse-native-client`, which
+//! re-exports it next to the trait.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    format_ident,
+    quote,
+};
+use syn::{
+    ext::IdentExt,
+    parse_macro_input,
+    Data,
+    DeriveInput,
+    Fields,
+    LitStr,
+};
+
+/// Derive `Row` for a struct with named fields
+///
+/// Every field becomes a column named after the field, or after
+/// `#[column(rename = "...")]`. Field types must implement `ToSql` to
+/// build blocks and `FromSql` to read them, as well as `Clone`.
+#[proc_macro_derive(Row, attributes(column))]
+pub fn derive_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields =
+        match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Row can only be derived for structs with named fields",
+                )),
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Row can only be derived for structs",
+                ))
+            }
+        };
+
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    let mut types = Vec::new();
+    let mut columns = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        idents.push(ident);
+        // Prefixed so that fields cannot shadow `block` or `rows`
+        values.push(format_ident!("values_{}", ident));
+        types.push(&field.ty);
+        columns.push(column_name(field)?.unwrap_or_else(|| {
+            LitStr::new(&ident.unraw().to_string(), ident.span())
+        }));
+    }
+
+    let krate = quote!(::clickhouse_native_client);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::Row for #name #ty_generics #where_clause {
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+
+            fn to_block(rows: &[Self]) -> #krate::Result<#krate::Block> {
+                #krate::BlockBuilder::new()
+                    #(
+                        .add_column(
+                            #columns,
+                            <#types as #krate::ToSql>::to_column(
+                                rows.iter().map(|row| row.#idents.clone()).collect(),
+                            )?,
+                        )
+                    )*
+                    .build()
+            }
+
+            fn from_block(block: &#krate::Block) -> #krate::Result<::std::vec::Vec<Self>> {
+                #(
+                    let mut #values = <#types as #krate::FromSql>::from_column(
+                        &#krate::row::block_column(block, #columns)?,
+                    )?
+                    .into_iter();
+                )*
+                ::std::result::Result::Ok(
+                    (0..block.row_count())
+                        .map(|_| Self {
+                            #(
+                                #idents: #values
+                                    .next()
+                                    .expect("columns have as many values as the block has rows"),
+                            )*
+                        })
+                        .collect(),
+                )
+            }
+        }
+    })
+}
+
+/// The name set by `#[column(rename = "...")]`, if any
+fn column_name(field: &syn::Field) -> syn::Result<Option<LitStr>> {
+    let mut rename = None;
+    for attr in
+        field.attrs.iter().filter(|attr| attr.path().is_ident("column"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported column attribute"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
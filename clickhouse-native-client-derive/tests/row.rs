@@ -0,0 +1,185 @@
+use clickhouse_native_client::{
+    column::{
+        ColumnString,
+        ColumnUInt64,
+    },
+    Block,
+    BlockBuilder,
+    Error,
+    Progress,
+    QueryResult,
+    Row,
+    TypedBlock,
+};
+
+#[derive(Row, Clone, Debug, PartialEq)]
+struct Event {
+    id: u64,
+    #[column(rename = "event_name")]
+    name: String,
+    country: Option<String>,
+    score: Option<f64>,
+    active: bool,
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event {
+            id: 1,
+            name: "click".to_string(),
+            country: Some("US".to_string()),
+            score: Some(0.5),
+            active: true,
+        },
+        Event {
+            id: 2,
+            name: "view".to_string(),
+            country: None,
+            score: None,
+            active: false,
+        },
+    ]
+}
+
+#[test]
+fn test_row_to_block() {
+    let block = Event::to_block(&events()).unwrap();
+
+    assert_eq!(block.row_count(), 2);
+    let columns: Vec<(String, String)> = block
+        .iter()
+        .map(|(name, type_, _)| (name.to_string(), type_.name()))
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            ("id", "UInt64"),
+            ("event_name", "String"),
+            ("country", "Nullable(String)"),
+            ("score", "Nullable(Float64)"),
+            ("active", "Bool"),
+        ]
+        .map(|(name, type_)| (name.to_string(), type_.to_string()))
+    );
+}
+
+#[test]
+fn test_row_roundtrip() {
+    let block = Event::to_block(&events()).unwrap();
+    assert_eq!(Event::from_block(&block).unwrap(), events());
+
+    let result = QueryResult {
+        blocks: vec![block.clone(), block],
+        totals: None,
+        extremes: None,
+        progress: Progress::default(),
+        profile: None,
+        profile_events: vec![],
+        logs: vec![],
+    };
+    let rows = Event::from_result(&result).unwrap();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[3], events()[1]);
+}
+
+#[test]
+fn test_row_matches_columns_by_name() {
+    #[derive(Row, Clone, Debug, PartialEq)]
+    struct Named {
+        name: String,
+        id: u64,
+    }
+
+    // Columns in a different order than the fields, plus an extra one
+    let block = BlockBuilder::new()
+        .column::<ColumnUInt64>("id", vec![7])
+        .column::<ColumnString>("extra", vec!["ignored"])
+        .column::<ColumnString>("name", vec!["seven"])
+        .build()
+        .unwrap();
+    assert_eq!(
+        Named::from_block(&block).unwrap(),
+        [Named { name: "seven".to_string(), id: 7 }]
+    );
+
+    let missing = BlockBuilder::new()
+        .column::<ColumnUInt64>("id", vec![7])
+        .build()
+        .unwrap();
+    assert!(matches!(Named::from_block(&missing), Err(Error::Validation(_))));
+}
+
+#[test]
+fn test_row_rejects_mismatched_types() {
+    #[derive(Row, Clone)]
+    struct Wrong {
+        #[allow(dead_code)]
+        id: String,
+    }
+
+    let block = BlockBuilder::new()
+        .column::<ColumnUInt64>("id", vec![1])
+        .build()
+        .unwrap();
+    assert!(matches!(
+        Wrong::from_block(&block),
+        Err(Error::TypeMismatch { .. })
+    ));
+    assert_eq!(
+        Wrong::from_block(&Block::new()).err().map(|e| e.to_string()),
+        Some("Validation error: Block has no column named 'id'".to_string())
+    );
+}
+
+#[test]
+fn test_typed_block_checks_schema_at_read_boundary() {
+    #[derive(Row, Clone, Debug, PartialEq)]
+    struct Point {
+        id: u64,
+        label: String,
+    }
+    assert_eq!(Point::COLUMNS, ["id", "label"]);
+
+    let mut typed = TypedBlock::<Point>::new();
+    typed.push(Point { id: 1, label: "a".to_string() });
+    typed.push(Point { id: 2, label: "b".to_string() });
+    let block = typed.to_block().unwrap();
+    assert_eq!(
+        TypedBlock::<Point>::from_block(&block).unwrap().into_rows(),
+        typed.into_rows()
+    );
+
+    // Same columns in another order
+    let swapped = BlockBuilder::new()
+        .column::<ColumnString>("label", vec!["a"])
+        .column::<ColumnUInt64>("id", vec![1])
+        .build()
+        .unwrap();
+    assert!(matches!(
+        TypedBlock::<Point>::from_block(&swapped),
+        Err(Error::Validation(_))
+    ));
+
+    // An extra column
+    let extra = BlockBuilder::new()
+        .column::<ColumnUInt64>("id", vec![1])
+        .column::<ColumnString>("label", vec!["a"])
+        .column::<ColumnString>("note", vec!["x"])
+        .build()
+        .unwrap();
+    assert!(matches!(
+        TypedBlock::<Point>::from_block(&extra),
+        Err(Error::Validation(_))
+    ));
+
+    // Right names, wrong type
+    let retyped = BlockBuilder::new()
+        .column::<ColumnUInt64>("id", vec![1])
+        .column::<ColumnUInt64>("label", vec![2])
+        .build()
+        .unwrap();
+    assert!(matches!(
+        TypedBlock::<Point>::from_block(&retyped),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
@@ -0,0 +1,22 @@
+//! Fuzz `Type::parse` with arbitrary type strings.
+//!
+//! Run with: `cargo +nightly fuzz run type_parse`
+//!
+//! Parsing must never panic, and every type that parses must parse back to
+//! itself from its own name.
+
+#![no_main]
+
+use clickhouse_native_client::types::Type;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(type_) = Type::parse(input) {
+        let reparsed = Type::parse(&type_.name())
+            .unwrap_or_else(|e| panic!("{:?} -> {}: {}", input, type_.name(), e));
+        assert_eq!(reparsed, type_, "round trip of {:?}", input);
+    }
+});
@@ -8,7 +8,18 @@ use crate::{
     Error,
     Result,
 };
-use std::sync::Arc;
+use std::{
+    collections::{
+        hash_map::DefaultHasher,
+        HashMap,
+        HashSet,
+    },
+    hash::{
+        Hash,
+        Hasher,
+    },
+    sync::Arc,
+};
 
 /// Block metadata used by ClickHouse for distributed query processing.
 #[derive(Debug, Clone, Default)]
@@ -19,10 +30,28 @@ pub struct BlockInfo {
     pub bucket_num: i32,
 }
 
+/// Summary of one column's values, see [`Block::column_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnStats {
+    /// Number of rows
+    pub count: usize,
+    /// Number of NULL values
+    pub null_count: usize,
+    /// Number of distinct values, NULL included. Values are compared by
+    /// hash, so a collision can make this slightly low.
+    pub distinct_estimate: usize,
+}
+
 /// A block is a collection of named columns with the same number of rows
+///
+/// Column names need not be unique (a result of `SELECT 1, 1` has two
+/// columns named `1`); lookups by name return the first column with that
+/// name.
 #[derive(Clone)]
 pub struct Block {
     columns: Vec<ColumnItem>,
+    /// Position of the first column with each name
+    index_by_name: HashMap<String, usize>,
     rows: usize,
     info: BlockInfo,
 }
@@ -36,13 +65,19 @@ struct ColumnItem {
 impl Block {
     /// Create a new empty block
     pub fn new() -> Self {
-        Self { columns: Vec::new(), rows: 0, info: BlockInfo::default() }
+        Self {
+            columns: Vec::new(),
+            index_by_name: HashMap::new(),
+            rows: 0,
+            info: BlockInfo::default(),
+        }
     }
 
     /// Create a block with reserved capacity
     pub fn with_capacity(cols: usize, rows: usize) -> Self {
         Self {
             columns: Vec::with_capacity(cols),
+            index_by_name: HashMap::with_capacity(cols),
             rows,
             info: BlockInfo::default(),
         }
@@ -67,6 +102,7 @@ impl Block {
             )));
         }
 
+        self.index_by_name.entry(name.clone()).or_insert(self.columns.len());
         self.columns.push(ColumnItem { name, column });
         Ok(())
     }
@@ -104,11 +140,18 @@ impl Block {
     }
 
     /// Get column by name
+    ///
+    /// If several columns share the name, the first one is returned.
     pub fn column_by_name(&self, name: &str) -> Option<ColumnRef> {
-        self.columns
-            .iter()
-            .find(|item| item.name == name)
-            .map(|item| item.column.clone())
+        self.column_index(name).and_then(|index| self.column(index))
+    }
+
+    /// Get the index of the column with the given name
+    ///
+    /// If several columns share the name, the index of the first one is
+    /// returned.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.index_by_name.get(name).copied()
     }
 
     /// Get mutable access to column by name
@@ -118,11 +161,35 @@ impl Block {
         &mut self,
         name: &str,
     ) -> Option<&mut (dyn Column + '_)> {
-        let item = self.columns.iter_mut().find(|item| item.name == name)?;
+        let index = self.column_index(name)?;
+        let item = &mut self.columns[index];
         Some(Arc::get_mut(&mut item.column)
             .expect("Cannot get mutable access to shared column - column has multiple references"))
     }
 
+    /// Remove a column by name and return it
+    ///
+    /// The block no longer holds a reference to the returned column, so
+    /// dropping the rest of the block frees the other columns' data. Returns
+    /// None if column with given name is not found.
+    pub fn take_column(&mut self, name: &str) -> Option<ColumnRef> {
+        let index = self.column_index(name)?;
+        let item = self.columns.remove(index);
+        self.index_by_name.clear();
+        for (i, item) in self.columns.iter().enumerate() {
+            self.index_by_name.entry(item.name.clone()).or_insert(i);
+        }
+        if self.columns.is_empty() {
+            self.rows = 0;
+        }
+        Some(item.column)
+    }
+
+    /// Consume the block, returning only the named column
+    pub fn into_column(mut self, name: &str) -> Option<ColumnRef> {
+        self.take_column(name)
+    }
+
     /// Get block info
     pub fn info(&self) -> &BlockInfo {
         &self.info
@@ -137,6 +204,7 @@ impl Block {
     pub fn clear(&mut self) {
         // Clear by removing all columns from the block
         self.columns.clear();
+        self.index_by_name.clear();
         self.rows = 0;
     }
 
@@ -173,6 +241,159 @@ impl Block {
         Ok(first_rows)
     }
 
+    /// Group flattened `Nested` columns by their prefix
+    ///
+    /// ClickHouse returns the fields of a `Nested` column as separate
+    /// `Array(T)` columns named `prefix.field`. This groups such columns by
+    /// the part before the first dot, keeping block order within each group.
+    /// Columns without a dot are not included.
+    pub fn nested_groups(&self) -> HashMap<String, Vec<(String, ColumnRef)>> {
+        let mut groups: HashMap<String, Vec<(String, ColumnRef)>> =
+            HashMap::new();
+
+        for item in &self.columns {
+            if let Some((prefix, field)) = item.name.split_once('.') {
+                if prefix.is_empty() || field.is_empty() {
+                    continue;
+                }
+                groups
+                    .entry(prefix.to_string())
+                    .or_default()
+                    .push((field.to_string(), item.column.clone()));
+            }
+        }
+
+        groups
+    }
+
+    /// Count rows, NULLs and distinct values of the column `name`
+    ///
+    /// Computed on the client over the rows of this block, so a fetched
+    /// sample can be profiled without another query. Fails for a column
+    /// type [`Column::value_at`] cannot read.
+    pub fn column_stats(&self, name: &str) -> Result<ColumnStats> {
+        let column = self.column_by_name(name).ok_or_else(|| {
+            Error::Validation(format!("Block has no column named '{}'", name))
+        })?;
+
+        let mut null_count = 0;
+        let mut hashes = HashSet::new();
+        for index in 0..column.size() {
+            let value = column.value_at(index)?;
+            if value.is_null() {
+                null_count += 1;
+            }
+            // Value holds floats, so it is not Hash itself
+            let mut hasher = DefaultHasher::new();
+            format!("{:?}", value).hash(&mut hasher);
+            hashes.insert(hasher.finish());
+        }
+
+        Ok(ColumnStats {
+            count: column.size(),
+            null_count,
+            distinct_estimate: hashes.len(),
+        })
+    }
+
+    /// Create a block of `len` rows starting at row `begin`
+    ///
+    /// Every column is sliced; names and block info are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if the range goes past
+    /// [`row_count`](Self::row_count).
+    pub fn slice(&self, begin: usize, len: usize) -> Result<Block> {
+        if begin.checked_add(len).is_none_or(|end| end > self.rows) {
+            return Err(Error::InvalidArgument(format!(
+                "Slice out of bounds: begin={}, len={}, rows={}",
+                begin, len, self.rows
+            )));
+        }
+
+        let mut sliced = Block::with_capacity(self.columns.len(), 0);
+        for item in &self.columns {
+            sliced.append_column(
+                item.name.clone(),
+                item.column.slice(begin, len)?,
+            )?;
+        }
+        sliced.set_info(self.info.clone());
+        Ok(sliced)
+    }
+
+    /// Append the rows of `other` to this block
+    ///
+    /// Both blocks must have the same columns: same count, and the same
+    /// name and type at each position. Appending to a block without
+    /// columns takes over `other` as it is. Columns shared with another
+    /// block are copied before they are extended.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the column counts or names differ and
+    /// `Error::TypeMismatch` if a column type differs; the block is left
+    /// unchanged.
+    pub fn append_block(&mut self, other: Block) -> Result<()> {
+        if self.columns.is_empty() {
+            *self = other;
+            return Ok(());
+        }
+        if self.columns.len() != other.columns.len() {
+            return Err(Error::Validation(format!(
+                "Cannot append a block with {} columns to a block with {}",
+                other.columns.len(),
+                self.columns.len()
+            )));
+        }
+        for (item, other_item) in self.columns.iter().zip(&other.columns) {
+            if item.name != other_item.name {
+                return Err(Error::Validation(format!(
+                    "Cannot append column '{}' to column '{}'",
+                    other_item.name, item.name
+                )));
+            }
+            if item.column.column_type() != other_item.column.column_type() {
+                return Err(Error::TypeMismatch {
+                    expected: format!(
+                        "{} for column {}",
+                        item.column.column_type().name(),
+                        item.name
+                    ),
+                    actual: other_item.column.column_type().name(),
+                });
+            }
+        }
+
+        for (item, other_item) in self.columns.iter_mut().zip(other.columns) {
+            if Arc::get_mut(&mut item.column).is_none() {
+                let mut copy = item.column.clone_empty();
+                Arc::get_mut(&mut copy)
+                    .expect("a new column is not shared")
+                    .append_column(item.column.clone())?;
+                item.column = copy;
+            }
+            Arc::get_mut(&mut item.column)
+                .expect("column was just made exclusive")
+                .append_column(other_item.column)?;
+        }
+        self.rows += other.rows;
+        Ok(())
+    }
+
+    /// Merge blocks with the same columns into one, in order
+    ///
+    /// Returns an empty block if `blocks` is empty. See
+    /// [`append_block`](Self::append_block) for the schema rules.
+    pub fn concat(blocks: Vec<Block>) -> Result<Block> {
+        let mut merged = Block::new();
+        for block in blocks {
+            merged.append_block(block)?;
+        }
+        Ok(merged)
+    }
+
     /// Iterate over columns
     pub fn iter(&self) -> BlockIterator<'_> {
         BlockIterator { block: self, index: 0 }
@@ -190,6 +411,79 @@ impl Default for Block {
     }
 }
 
+/// Builder assembling a [`Block`] from plain vectors
+///
+/// Each column is converted with `From<Vec<_>>`, which infers its type, and
+/// row counts are validated once in [`BlockBuilder::build`].
+///
+/// # Example
+/// ```
+/// use clickhouse_native_client::{
+///     column::{ColumnString, ColumnUInt64},
+///     BlockBuilder,
+/// };
+///
+/// let block = BlockBuilder::new()
+///     .column::<ColumnUInt64>("id", vec![1, 2])
+///     .column::<ColumnString>("name", vec!["a", "b"])
+///     .build()
+///     .unwrap();
+/// assert_eq!(block.row_count(), 2);
+/// ```
+#[derive(Default)]
+pub struct BlockBuilder {
+    columns: Vec<(String, ColumnRef)>,
+}
+
+impl BlockBuilder {
+    /// Create a builder without columns
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a column of type `C` built from `values`
+    pub fn column<C>(
+        self,
+        name: impl Into<String>,
+        values: impl Into<C>,
+    ) -> Self
+    where
+        C: Column + 'static,
+    {
+        self.add_column(name, Arc::new(values.into()))
+    }
+
+    /// Add an already built column, e.g. one whose type cannot be inferred
+    pub fn add_column(
+        mut self,
+        name: impl Into<String>,
+        column: ColumnRef,
+    ) -> Self {
+        self.columns.push((name.into(), column));
+        self
+    }
+
+    /// Assemble the block
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if two columns share a name or the
+    /// columns differ in row count.
+    pub fn build(self) -> Result<Block> {
+        let mut block = Block::with_capacity(self.columns.len(), 0);
+        for (name, column) in self.columns {
+            if block.column_by_name(&name).is_some() {
+                return Err(Error::Validation(format!(
+                    "Duplicate column name in block: '{}'",
+                    name
+                )));
+            }
+            block.append_column(name, column)?;
+        }
+        Ok(block)
+    }
+}
+
 /// Iterator over block columns
 pub struct BlockIterator<'a> {
     block: &'a Block,
@@ -226,6 +520,75 @@ mod tests {
     use crate::column::numeric::ColumnUInt64;
     use std::sync::Arc;
 
+    #[test]
+    fn test_block_builder() {
+        use crate::column::{
+            ColumnBool,
+            ColumnString,
+        };
+
+        let block = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", vec![1, 2, 3])
+            .column::<ColumnString>("name", vec!["a", "b", "c"])
+            .add_column("flag", Arc::new(ColumnBool::from_vec(vec![true; 3])))
+            .build()
+            .unwrap();
+
+        assert_eq!(block.column_count(), 3);
+        assert_eq!(block.row_count(), 3);
+        assert_eq!(block.column_name(1), Some("name"));
+        assert_eq!(block.column(0).unwrap().column_type(), &Type::uint64());
+        assert_eq!(block.column(1).unwrap().column_type(), &Type::string());
+
+        let mismatched = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", vec![1, 2])
+            .column::<ColumnString>("name", vec!["a"])
+            .build();
+        assert!(matches!(mismatched, Err(Error::Validation(_))));
+
+        let duplicate = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", vec![1])
+            .column::<ColumnUInt64>("id", vec![2])
+            .build();
+        assert!(matches!(duplicate, Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_column_stats() {
+        use crate::column::{
+            nullable::ColumnNullable,
+            numeric::ColumnUInt32,
+        };
+
+        let mut maybe =
+            ColumnNullable::with_nested(Arc::new(ColumnUInt32::new()));
+        for value in [Some(7), None, Some(7), None, Some(9)] {
+            maybe.append_nullable(value);
+        }
+        let ids = ColumnUInt64::from(vec![5, 3, 8, 3, 1]);
+        assert_eq!(ids.min_max(), Some((1, 8)));
+        assert_eq!(ColumnUInt64::new().min_max(), None);
+
+        let block = BlockBuilder::new()
+            .add_column("id", Arc::new(ids))
+            .add_column("maybe", Arc::new(maybe))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            block.column_stats("id").unwrap(),
+            ColumnStats { count: 5, null_count: 0, distinct_estimate: 4 }
+        );
+        assert_eq!(
+            block.column_stats("maybe").unwrap(),
+            ColumnStats { count: 5, null_count: 2, distinct_estimate: 3 }
+        );
+        assert!(matches!(
+            block.column_stats("missing"),
+            Err(Error::Validation(_))
+        ));
+    }
+
     #[test]
     fn test_block_creation() {
         let block = Block::new();
@@ -318,6 +681,146 @@ mod tests {
         assert!(block.column_by_name("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_block_column_index_with_duplicate_names() {
+        let mut block = Block::new();
+        for (name, value) in [("x", 1u64), ("y", 2), ("x", 3)] {
+            let mut col = ColumnUInt64::new();
+            col.append(value);
+            block.append_column(name, Arc::new(col)).unwrap();
+        }
+
+        // The first column with a name wins
+        assert_eq!(block.column_index("x"), Some(0));
+        assert_eq!(block.column_index("y"), Some(1));
+        assert_eq!(block.column_index("z"), None);
+        let x = block.column_by_name("x").unwrap();
+        assert_eq!(
+            x.as_any().downcast_ref::<ColumnUInt64>().unwrap().at(0),
+            1
+        );
+
+        // Removing it exposes the later duplicate at its new position
+        block.take_column("x").unwrap();
+        assert_eq!(block.column_index("y"), Some(0));
+        assert_eq!(block.column_index("x"), Some(1));
+        let x = block.column_by_name("x").unwrap();
+        assert_eq!(
+            x.as_any().downcast_ref::<ColumnUInt64>().unwrap().at(0),
+            3
+        );
+
+        block.clear();
+        assert_eq!(block.column_index("y"), None);
+    }
+
+    #[test]
+    fn test_block_slice() {
+        use crate::column::{
+            ColumnBool,
+            ColumnString,
+        };
+
+        let mut block = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", (0..10).collect::<Vec<u64>>())
+            .column::<ColumnString>(
+                "name",
+                (0..10).map(|i| format!("n{}", i)).collect::<Vec<_>>(),
+            )
+            .add_column("flag", Arc::new(ColumnBool::from_vec(vec![true; 10])))
+            .build()
+            .unwrap();
+        block.set_info(BlockInfo { is_overflows: 0, bucket_num: 4 });
+
+        let page = block.slice(3, 4).unwrap();
+        assert_eq!(page.column_count(), 3);
+        assert_eq!(page.row_count(), 4);
+        assert_eq!(page.column_name(1), Some("name"));
+        assert_eq!(page.info().bucket_num, 4);
+        for (_, _, column) in &page {
+            assert_eq!(column.size(), 4);
+        }
+        let name = page.column_by_name("name").unwrap();
+        let name = name.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(name.at(1), "n4");
+
+        assert_eq!(block.slice(10, 0).unwrap().row_count(), 0);
+        assert!(matches!(block.slice(8, 3), Err(Error::InvalidArgument(_))));
+        assert!(block.slice(usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_block_concat() {
+        use crate::column::ColumnString;
+
+        let block = |ids: Vec<u64>| {
+            let names: Vec<String> =
+                ids.iter().map(|id| format!("n{}", id)).collect();
+            BlockBuilder::new()
+                .column::<ColumnUInt64>("id", ids)
+                .column::<ColumnString>("name", names)
+                .build()
+                .unwrap()
+        };
+        let first = block(vec![0, 1]);
+        // A column still shared with `first` is copied, not mutated
+        let shared = first.clone();
+
+        let merged =
+            Block::concat(vec![first, block(vec![2, 3, 4]), block(vec![5])])
+                .unwrap();
+        assert_eq!(merged.row_count(), 6);
+        let id = merged.column(0).unwrap();
+        let id = id.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(id.size(), 6);
+        assert_eq!((id.at(1), id.at(2), id.at(4), id.at(5)), (1, 2, 4, 5));
+        let name = merged.column_by_name("name").unwrap();
+        let name = name.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(name.at(0), "n0");
+        assert_eq!(name.at(5), "n5");
+        assert_eq!(shared.row_count(), 2);
+        assert_eq!(shared.column(0).unwrap().size(), 2);
+
+        assert_eq!(Block::concat(Vec::new()).unwrap().column_count(), 0);
+    }
+
+    #[test]
+    fn test_block_append_block_rejects_other_schema() {
+        use crate::column::ColumnString;
+
+        let mut block = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", vec![1u64])
+            .build()
+            .unwrap();
+        let renamed = BlockBuilder::new()
+            .column::<ColumnUInt64>("key", vec![2u64])
+            .build()
+            .unwrap();
+        let retyped = BlockBuilder::new()
+            .column::<ColumnString>("id", vec!["2"])
+            .build()
+            .unwrap();
+        let wider = BlockBuilder::new()
+            .column::<ColumnUInt64>("id", vec![2u64])
+            .column::<ColumnUInt64>("key", vec![2u64])
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            block.append_block(renamed),
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            block.append_block(retyped),
+            Err(Error::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            block.append_block(wider),
+            Err(Error::Validation(_))
+        ));
+        assert_eq!(block.row_count(), 1);
+    }
+
     #[test]
     fn test_block_column_name() {
         let mut block = Block::new();
@@ -348,6 +851,26 @@ mod tests {
         assert_eq!(names, vec!["first", "second"]);
     }
 
+    #[test]
+    fn test_block_nested_groups() {
+        let mut block = Block::new();
+
+        for name in ["id", "a.x", "a.y", "b.z"] {
+            let mut col = ColumnUInt64::new();
+            col.append(1);
+            block.append_column(name, Arc::new(col)).unwrap();
+        }
+
+        let groups = block.nested_groups();
+        assert_eq!(groups.len(), 2);
+
+        let fields: Vec<&str> =
+            groups["a"].iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(fields, vec!["x", "y"]);
+        assert_eq!(groups["b"].len(), 1);
+        assert!(!groups.contains_key("id"));
+    }
+
     #[test]
     fn test_block_info() {
         let mut block = Block::new();
@@ -467,4 +990,32 @@ mod tests {
         // Should return None for non-existent column name
         assert!(block.column_by_name_mut("nonexistent").is_none());
     }
+
+    #[test]
+    fn test_block_take_column() {
+        let mut block = Block::new();
+        for (name, base) in [("a", 0u64), ("b", 10), ("c", 20)] {
+            let mut col = ColumnUInt64::new();
+            col.append(base);
+            col.append(base + 1);
+            block.append_column(name, Arc::new(col)).unwrap();
+        }
+
+        let taken = block.take_column("b").unwrap();
+        assert_eq!(block.column_count(), 2);
+        assert_eq!(block.row_count(), 2);
+        assert_eq!(block.column_name(0), Some("a"));
+        assert_eq!(block.column_name(1), Some("c"));
+        assert!(block.take_column("b").is_none());
+
+        // The block no longer shares the column
+        assert_eq!(Arc::strong_count(&taken), 1);
+        let taken = taken.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(taken.at(0), 10);
+        assert_eq!(taken.at(1), 11);
+
+        let c = block.into_column("c").unwrap();
+        let c = c.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(c.at(1), 21);
+    }
 }
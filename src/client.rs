@@ -1,5 +1,14 @@
 use crate::{
     block::Block,
+    column::{
+        column_value::{
+            get_column_item,
+            ColumnValue,
+        },
+        numeric::ColumnUInt8,
+        string::ColumnString,
+    },
+    compression::Compressor,
     connection::{
         Connection,
         ConnectionOptions,
@@ -12,19 +21,59 @@ use crate::{
         ClientCode,
         CompressionMethod,
         ServerCode,
+        LOG_TARGET,
     },
     query::{
+        parse_profile_events,
+        parse_server_logs,
+        quote_identifier,
+        quote_string_literal,
         ClientInfo,
+        LogEntry,
         Profile,
+        ProfileEvent,
+        ProfileEventKind,
         Progress,
         Query,
+        QueryKind,
         ServerInfo,
+        DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS,
+        DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET,
     },
+    row::FromSql,
+    types::Type,
     Error,
     Result,
 };
-use std::time::Duration;
-use tracing::debug;
+use bytes::BytesMut;
+use futures_core::Stream;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{
+        ready,
+        Context,
+        Poll,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use tokio::io::{
+    AsyncRead,
+    AsyncWrite,
+};
+use tracing::{
+    debug,
+    trace,
+    warn,
+};
 
 #[cfg(feature = "tls")]
 use crate::ssl::SSLOptions;
@@ -64,6 +113,8 @@ pub struct ClientOptions {
     pub compression: Option<CompressionMethod>,
     /// Maximum compression chunk size (default: 65535)
     pub max_compression_chunk_size: usize,
+    /// Custom codec for outgoing blocks (enables compression)
+    pub custom_compression: Option<Arc<dyn Compressor>>,
     /// Client information
     pub client_info: ClientInfo,
     /// Connection timeout and TCP options
@@ -79,6 +130,17 @@ pub struct ClientOptions {
     pub ping_before_query: bool,
     /// Rethrow server exceptions (default: true)
     pub rethrow_exceptions: bool,
+    /// Ping interval for an idle [`SharedClient`] (default: none)
+    pub keepalive_interval: Option<Duration>,
+    /// Reject query settings that have no effect over the native protocol
+    /// instead of only warning (default: false)
+    pub strict_settings: bool,
+    /// Reconnect and retry once when the connection breaks during a query,
+    /// execute or insert (default: false)
+    pub auto_reconnect: bool,
+    /// Let INSERT blocks omit target columns that have a default
+    /// (default: false)
+    pub insert_allow_defaults: bool,
 }
 
 impl Default for ClientOptions {
@@ -92,6 +154,7 @@ impl Default for ClientOptions {
             password: String::new(),
             compression: Some(CompressionMethod::Lz4),
             max_compression_chunk_size: 65535,
+            custom_compression: None,
             client_info: ClientInfo::default(),
             connection_options: ConnectionOptions::default(),
             #[cfg(feature = "tls")]
@@ -100,6 +163,10 @@ impl Default for ClientOptions {
             retry_timeout: Duration::from_secs(5),
             ping_before_query: false,
             rethrow_exceptions: true,
+            keepalive_interval: None,
+            strict_settings: false,
+            auto_reconnect: false,
+            insert_allow_defaults: false,
         }
     }
 }
@@ -141,17 +208,90 @@ impl ClientOptions {
     }
 
     /// Set compression method
+    ///
+    /// Servers too old for compressed blocks are talked to uncompressed,
+    /// see [`Client::effective_compression`].
     pub fn compression(mut self, method: Option<CompressionMethod>) -> Self {
         self.compression = method;
         self
     }
 
+    /// Compress outgoing blocks with a custom codec
+    ///
+    /// The server must understand the codec's method byte. Incoming frames
+    /// with that byte are decoded by the codec, while frames using the
+    /// built-in methods keep working.
+    pub fn custom_compression(
+        mut self,
+        compressor: Arc<dyn Compressor>,
+    ) -> Self {
+        self.custom_compression = Some(compressor);
+        self
+    }
+
+    /// Whether blocks are exchanged in compressed frames
+    pub(crate) fn is_compressed(&self) -> bool {
+        self.compression.is_some_and(|m| m != CompressionMethod::None)
+            || self.custom_compression.is_some()
+    }
+
     /// Set maximum compression chunk size
+    ///
+    /// Outgoing blocks larger than this are sent as several compressed
+    /// frames.
     pub fn max_compression_chunk_size(mut self, size: usize) -> Self {
         self.max_compression_chunk_size = size;
         self
     }
 
+    /// Override the OS user reported to the server (default: `$USER`)
+    pub fn os_user(mut self, os_user: impl Into<String>) -> Self {
+        self.client_info.os_user = os_user.into();
+        self
+    }
+
+    /// Override the client hostname reported to the server
+    ///
+    /// Defaults to this machine's hostname; shows up as
+    /// `client_hostname` in `system.query_log`.
+    pub fn client_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.client_info.client_hostname = hostname.into();
+        self
+    }
+
+    /// Set the quota key this client's queries are accounted to
+    ///
+    /// Sent in the handshake and with every query, for server quotas
+    /// `KEYED BY client_key`.
+    pub fn quota_key(mut self, quota_key: impl Into<String>) -> Self {
+        self.client_info.quota_key = quota_key.into();
+        self
+    }
+
+    /// Send queries as `kind`
+    ///
+    /// A [`QueryKind::Secondary`] query names the user and query ID of its
+    /// initial query in [`ClientInfo::initial_user`] and
+    /// [`ClientInfo::initial_query_id`].
+    pub fn query_kind(mut self, kind: QueryKind) -> Self {
+        self.client_info.query_kind = kind;
+        self
+    }
+
+    /// Set the raw value of the interserver secret field of every query
+    ///
+    /// This is a passthrough only: the client does not implement
+    /// interserver authentication. It neither computes the digest of the
+    /// cluster secret, the query and its initial user, nor logs in as the
+    /// interserver marker user, and servers only check the field on such
+    /// connections. The field exists from server revision 54441 on; a
+    /// query to an older server fails with `Error::UnsupportedFeature`
+    /// while a value is set.
+    pub fn interserver_secret(mut self, secret: impl Into<String>) -> Self {
+        self.client_info.interserver_secret = secret.into();
+        self
+    }
+
     /// Set connection options (timeouts, TCP settings)
     pub fn connection_options(mut self, options: ConnectionOptions) -> Self {
         self.connection_options = options;
@@ -171,6 +311,13 @@ impl ClientOptions {
     }
 
     /// Enable/disable ping before query
+    ///
+    /// Every query is preceded by a ping; if it fails because the idle
+    /// connection was closed, the client reconnects before sending the
+    /// query. This costs a round trip per query but catches connections a
+    /// proxy dropped silently, which [`ClientOptions::tcp_keepalive`] and
+    /// [`ClientOptions::keepalive_interval`] help prevent in the first
+    /// place.
     pub fn ping_before_query(mut self, enabled: bool) -> Self {
         self.ping_before_query = enabled;
         self
@@ -182,6 +329,71 @@ impl ClientOptions {
         self
     }
 
+    /// Send TCP keepalive probes once the connection has been idle for
+    /// `idle`
+    ///
+    /// Lets the operating system detect a dead peer and keeps NAT and proxy
+    /// state alive without any protocol traffic. Shorthand for enabling
+    /// keepalive in [`ConnectionOptions`].
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.connection_options.tcp_keepalive = true;
+        self.connection_options.tcp_keepalive_idle = idle;
+        self
+    }
+
+    /// Ping a [`SharedClient`] in the background once it has been idle
+    /// for `interval`, so the server does not drop the connection
+    pub fn keepalive_interval(mut self, interval: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Fail queries that set output-format-only settings such as `format`
+    /// rather than logging a warning
+    pub fn strict_settings(mut self, enabled: bool) -> Self {
+        self.strict_settings = enabled;
+        self
+    }
+
+    /// Reconnect and retry an operation once when its connection breaks
+    ///
+    /// Applies to [`Client::query`], [`Client::execute`] and the
+    /// [`Client::insert`] family. The new connection is opened like
+    /// [`Client::connect`] does, failing over across the endpoints. A query
+    /// is only retried if none of its blocks reached an `on_data` callback.
+    ///
+    /// Statements are retried even if the server may already have run
+    /// them, so an `execute` or insert can take effect twice. Session state
+    /// such as temporary tables is lost on reconnect.
+    pub fn auto_reconnect(mut self, enabled: bool) -> Self {
+        self.auto_reconnect = enabled;
+        self
+    }
+
+    /// Let INSERT blocks leave out columns the table can fill itself
+    ///
+    /// The server tells the client which columns an INSERT targets. By
+    /// default a block must carry every one of them, and a missing column
+    /// fails with `Error::Validation` before any data is sent. When
+    /// enabled, a column with a `DEFAULT` (or `MATERIALIZED`, `ALIAS`,
+    /// `EPHEMERAL`) expression may be left out: it is simply not sent and
+    /// the server evaluates its expression for every inserted row, e.g.
+    /// `now()` for `created_at DateTime DEFAULT now()`.
+    ///
+    /// Leaving out a column without a default still fails, as the server
+    /// would silently store zero values for it. If the server does not
+    /// describe the table's defaults, omitted columns are left to the
+    /// server.
+    ///
+    /// This matters for [`Client::insert_with_query`] and
+    /// [`Client::prepare_insert`], whose INSERT targets every column of the
+    /// table. [`Client::insert`] names the block's own columns in the
+    /// statement, so the server fills the others regardless.
+    pub fn insert_allow_defaults(mut self, enabled: bool) -> Self {
+        self.insert_allow_defaults = enabled;
+        self
+    }
+
     /// Set SSL/TLS options (requires 'tls' feature)
     #[cfg(feature = "tls")]
     pub fn ssl_options(mut self, options: SSLOptions) -> Self {
@@ -199,17 +411,109 @@ impl ClientOptions {
     }
 }
 
+/// Retry policy for [`Client::query_retrying`]
+///
+/// The delay before retry `n` (counting from 0) is `initial_backoff * 2^n`,
+/// capped at `max_backoff`, with a random jitter of up to half its value
+/// subtracted so that clients failing together do not retry in lockstep.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt (default: 3)
+    pub max_retries: u32,
+    /// Delay before the first retry (default: 100 milliseconds)
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries (default: 5 seconds)
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy with default delays and `max_retries` retries
+    pub fn new(max_retries: u32) -> Self {
+        Self { max_retries, ..Self::default() }
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound for the delay between retries
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Jittered delay before retry number `attempt` (counting from 0)
+    fn backoff(&self, attempt: u32) -> Duration {
+        use std::hash::{
+            BuildHasher,
+            Hasher,
+        };
+
+        let delay = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff);
+        // A freshly seeded hasher is random enough for jitter
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let jitter = (random % 1024) as f64 / 2048.0;
+        delay.mul_f64(1.0 - jitter)
+    }
+}
+
 /// Async ClickHouse client using the native TCP protocol.
 ///
 /// Create a client by calling [`Client::connect`] with [`ClientOptions`].
 /// The client holds a single TCP connection and is not `Clone`; for
 /// concurrent access, create multiple client instances.
+///
+/// If a query future is dropped before it completes (for example by a
+/// `tokio::select!` timeout), the server may still be streaming its
+/// response. The next operation detects this and reconnects to the same
+/// endpoint instead of reading leftover packets; session state such as
+/// temporary tables does not survive the reconnect. Call
+/// [`Client::abort_current`] to stop such a query on the server right
+/// away.
+///
+/// Dropping a client while a query is in flight sends the server a cancel
+/// packet from a background task, as long as a Tokio runtime is still
+/// running; otherwise the server only stops the query once it notices the
+/// closed connection.
 pub struct Client {
     conn: Connection,
     server_info: ServerInfo,
     block_reader: BlockReader,
     block_writer: BlockWriter,
+    /// Built-in compression method agreed on in the handshake
+    compression: Option<CompressionMethod>,
+    /// Whether blocks are exchanged in compressed frames
+    compressed: bool,
     options: ClientOptions,
+    /// Endpoint the connection was established to
+    endpoint: Endpoint,
+    /// Set while a request/response exchange is in flight
+    query_in_progress: bool,
+    /// Set when a [`Cursor`] was dropped between packets before the end
+    /// of its result, so the query can be cancelled and drained
+    cancel_pending: bool,
+    /// Connected over a caller-provided stream, which cannot be redialed
+    caller_stream: bool,
+    /// When the last request was sent, for the keepalive of a
+    /// [`SharedClient`]
+    last_activity: Instant,
 }
 
 impl Client {
@@ -254,7 +558,7 @@ impl Client {
         options: &ClientOptions,
     ) -> Result<Self> {
         // Connect with or without TLS based on options
-        let mut conn = {
+        let conn = {
             #[cfg(feature = "tls")]
             {
                 if let Some(ref ssl_opts) = options.ssl_options {
@@ -296,6 +600,54 @@ impl Client {
             }
         };
 
+        Self::handshake(conn, options, Endpoint::new(host, port), false).await
+    }
+
+    /// Connect over a stream the caller has already established
+    ///
+    /// No TCP connection is dialed: the handshake runs directly over
+    /// `stream`, e.g. a SOCKS proxy connection or an SSH tunnel channel.
+    /// The host and port in `options` are only reported as the endpoint.
+    ///
+    /// A client created this way cannot reconnect by itself, so an
+    /// operation following an interrupted one fails with
+    /// `Error::Connection` instead of recovering.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let stream = tokio::net::TcpStream::connect("127.0.0.1:9000").await?;
+    /// let mut client =
+    ///     Client::connect_with_stream(stream, ClientOptions::default()).await?;
+    /// client.ping().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_stream<S>(
+        stream: S,
+        options: ClientOptions,
+    ) -> Result<Self>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let endpoint = Endpoint::new(options.host.clone(), options.port);
+        Self::handshake(
+            Connection::from_stream(stream),
+            &options,
+            endpoint,
+            true,
+        )
+        .await
+    }
+
+    /// Exchange hello packets over `conn` and set up the block codecs
+    async fn handshake(
+        mut conn: Connection,
+        options: &ClientOptions,
+        endpoint: Endpoint,
+        caller_stream: bool,
+    ) -> Result<Self> {
         // Send hello
         Self::send_hello(&mut conn, options).await?;
 
@@ -305,48 +657,193 @@ impl Client {
         // Send addendum (quota key) if server supports it
         // DBMS_MIN_PROTOCOL_VERSION_WITH_ADDENDUM = 54458
         if server_info.revision >= 54458 {
-            debug!("Sending quota key addendum (empty string)...");
-            conn.write_string("").await?;
+            trace!(target: LOG_TARGET, "Sending quota key addendum...");
+            conn.write_string(&options.client_info.quota_key).await?;
             conn.flush().await?;
-            debug!("Addendum sent");
+            trace!(target: LOG_TARGET, "Addendum sent");
         }
 
         // Create block reader/writer with compression
         let mut block_reader = BlockReader::new(server_info.revision);
-        let mut block_writer = BlockWriter::new(server_info.revision);
+        let mut block_writer = BlockWriter::new(server_info.revision)
+            .with_max_chunk_size(options.max_compression_chunk_size);
 
-        // Enable compression on both reader and writer
-        if let Some(compression) = options.compression {
+        // Enable the negotiated compression on both reader and writer
+        let compressed = negotiate_compression(options, server_info.revision);
+        let compression = if compressed {
+            options.compression.filter(|m| *m != CompressionMethod::None)
+        } else {
+            None
+        };
+        if let Some(compression) = compression {
             block_reader = block_reader.with_compression(compression);
             block_writer = block_writer.with_compression(compression);
         }
+        if let Some(compressor) =
+            options.custom_compression.as_ref().filter(|_| compressed)
+        {
+            block_reader = block_reader.with_compressor(compressor.clone());
+            block_writer = block_writer.with_compressor(compressor.clone());
+        }
 
         Ok(Self {
             conn,
             server_info,
             block_reader,
             block_writer,
+            compression,
+            compressed,
             options: options.clone(),
+            endpoint,
+            query_in_progress: false,
+            cancel_pending: false,
+            caller_stream,
+            last_activity: Instant::now(),
         })
     }
 
+    /// Whether a previous operation was interrupted before it completed
+    ///
+    /// When this returns `true`, the connection may hold unread response
+    /// packets and the next operation will reconnect first.
+    pub fn is_dirty(&self) -> bool {
+        self.query_in_progress
+    }
+
+    /// Reconnect if the last exchange was abandoned midway
+    ///
+    /// A dropped future can stop anywhere, including in the middle of a
+    /// packet, so the leftover stream cannot be drained reliably; a fresh
+    /// connection to the same endpoint is the only safe recovery.
+    async fn recover_if_interrupted(&mut self) -> Result<()> {
+        if self.cancel_pending {
+            self.cancel_pending = false;
+            trace!(target: LOG_TARGET, "Cancelling query abandoned by a cursor");
+            if let Err(e) = self.cancel().await {
+                trace!(
+                    target: LOG_TARGET,
+                    "Draining cancelled query failed: {}",
+                    e
+                );
+            }
+        }
+        if !self.query_in_progress {
+            return Ok(());
+        }
+        if self.caller_stream {
+            return Err(Error::Connection(
+                "Previous operation was interrupted and a caller-provided \
+                 stream cannot be reconnected"
+                    .to_string(),
+            ));
+        }
+        debug!(
+            "Previous query was interrupted, reconnecting to {}:{}",
+            self.endpoint.host, self.endpoint.port
+        );
+        let endpoint = self.endpoint.clone();
+        let options = self.options.clone();
+        *self =
+            Self::try_connect(&endpoint.host, endpoint.port, &options).await?;
+        Ok(())
+    }
+
+    /// Whether an operation that failed with `err` may be retried on a new
+    /// connection, see [`ClientOptions::auto_reconnect`]
+    fn can_auto_reconnect(&self, err: &Error) -> bool {
+        self.options.auto_reconnect
+            && !self.caller_stream
+            && is_connection_error(err)
+    }
+
+    /// Replace a broken connection with a new one, going through the
+    /// endpoint failover and retries of [`Client::connect`]
+    async fn reconnect(&mut self) -> Result<()> {
+        let options = self.options.clone();
+        *self = Self::connect(options).await?;
+        Ok(())
+    }
+
+    /// Read and discard the rest of a cancelled query's response
+    async fn drain_cancelled_query(&mut self) -> Result<()> {
+        let query = Query::new("");
+        let mut info = ResponseInfo::default();
+        loop {
+            match self.next_result_block(&query, None, &mut info).await {
+                Ok(Some(_)) => {}
+                Ok(None) => return Ok(()),
+                // The server may report the cancellation as an exception,
+                // which ends the response as well
+                Err(_) if !self.query_in_progress => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Read the type of the next response packet, cancelling the query
+    /// once `deadline` passes
+    ///
+    /// Expiring here leaves the stream between packets, so the cancelled
+    /// response can still be drained. The rest of each packet is read
+    /// through [`read_within`] with the same deadline.
+    async fn read_packet_type(
+        &mut self,
+        query: &Query,
+        deadline: Option<Instant>,
+    ) -> Result<u64> {
+        let Some(deadline) = deadline else {
+            return self.conn.read_varint().await;
+        };
+        let read =
+            tokio::time::timeout_at(deadline.into(), self.conn.read_varint());
+        if let Ok(packet_type) = read.await {
+            return packet_type;
+        }
+
+        trace!(target: LOG_TARGET, "Query timed out, cancelling");
+        // Boxed because draining reads packets through this function again
+        let cancel = Box::pin(self.cancel());
+        match tokio::time::timeout(CANCEL_DRAIN_TIMEOUT, cancel).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => trace!(
+                target: LOG_TARGET,
+                "Draining timed-out query failed: {}",
+                e
+            ),
+            // The connection stays marked as busy and is reopened before
+            // the next operation
+            Err(_) => {
+                trace!(target: LOG_TARGET, "Timed-out query did not stop in time")
+            }
+        }
+        Err(Error::Timeout(format!(
+            "query did not complete within {:?}",
+            query.timeout().unwrap_or_default()
+        )))
+    }
+
     /// Send hello packet
     async fn send_hello(
         conn: &mut Connection,
         options: &ClientOptions,
     ) -> Result<()> {
-        debug!("Sending client hello...");
+        trace!(target: LOG_TARGET, "Sending client hello...");
         // Write client hello code
         conn.write_varint(ClientCode::Hello as u64).await?;
-        debug!("Sent hello code");
+        trace!(target: LOG_TARGET, "Sent hello code");
 
         // Write client name and version
         conn.write_string(&options.client_info.client_name).await?;
-        debug!("Sent client name: {}", options.client_info.client_name);
+        trace!(
+            target: LOG_TARGET,
+            "Sent client name: {}",
+            options.client_info.client_name
+        );
         conn.write_varint(options.client_info.client_version_major).await?;
         conn.write_varint(options.client_info.client_version_minor).await?;
         conn.write_varint(options.client_info.client_revision).await?;
-        debug!(
+        trace!(
+            target: LOG_TARGET,
             "Sent version: {}.{}.{}",
             options.client_info.client_version_major,
             options.client_info.client_version_minor,
@@ -357,33 +854,36 @@ impl Client {
         conn.write_string(&options.database).await?;
         conn.write_string(&options.user).await?;
         conn.write_string(&options.password).await?;
-        debug!("Sent credentials");
+        trace!(target: LOG_TARGET, "Sent credentials");
 
         conn.flush().await?;
-        debug!("Flushed");
+        trace!(target: LOG_TARGET, "Flushed");
         Ok(())
     }
 
     /// Receive hello packet from server
     async fn receive_hello(conn: &mut Connection) -> Result<ServerInfo> {
-        debug!("Reading server hello...");
+        trace!(target: LOG_TARGET, "Reading server hello...");
         let packet_type = conn.read_varint().await?;
-        debug!("Got packet type: {}", packet_type);
+        trace!(target: LOG_TARGET, packet_type, "Got packet type");
 
         if packet_type != ServerCode::Hello as u64 {
             if packet_type == ServerCode::Exception as u64 {
-                debug!("Server sent exception during handshake!");
+                trace!(
+                    target: LOG_TARGET,
+                    "Server sent exception during handshake!"
+                );
                 let exception = Self::read_exception_from_conn(conn).await?;
-                debug!(
+                trace!(
+                    target: LOG_TARGET,
                     "Exception: code={}, name={}, msg={}",
-                    exception.code, exception.name, exception.display_text
+                    exception.code,
+                    exception.name,
+                    exception.display_text
                 );
-                return Err(Error::Protocol(format!(
-                    "ClickHouse exception during handshake: {} (code {}): {}",
-                    exception.name, exception.code, exception.display_text
-                )));
+                return Err(Error::Server(exception));
             }
-            debug!("Unexpected packet type: {}", packet_type);
+            trace!(target: LOG_TARGET, packet_type, "Unexpected packet type");
             return Err(Error::Protocol(format!(
                 "Expected Hello packet, got {}",
                 packet_type
@@ -391,39 +891,42 @@ impl Client {
         }
 
         // Read server info
-        debug!("Reading server info...");
+        trace!(target: LOG_TARGET, "Reading server info...");
         let name = conn.read_string().await?;
-        debug!("Server name: {}", name);
+        trace!(target: LOG_TARGET, "Server name: {}", name);
         let version_major = conn.read_varint().await?;
         let version_minor = conn.read_varint().await?;
         let revision = conn.read_varint().await?;
-        debug!(
+        trace!(
+            target: LOG_TARGET,
             "Server version: {}.{}, revision: {}",
-            version_major, version_minor, revision
+            version_major,
+            version_minor,
+            revision
         );
 
         let timezone = if revision >= 54058 {
-            debug!("Reading timezone...");
+            trace!(target: LOG_TARGET, "Reading timezone...");
             conn.read_string().await?
         } else {
             String::new()
         };
 
         let display_name = if revision >= 54372 {
-            debug!("Reading display name...");
+            trace!(target: LOG_TARGET, "Reading display name...");
             conn.read_string().await?
         } else {
             String::new()
         };
 
         let version_patch = if revision >= 54401 {
-            debug!("Reading version patch...");
+            trace!(target: LOG_TARGET, "Reading version patch...");
             conn.read_varint().await?
         } else {
             0
         };
 
-        debug!("Server hello complete!");
+        trace!(target: LOG_TARGET, "Server hello complete!");
         Ok(ServerInfo {
             name,
             version_major,
@@ -482,99 +985,82 @@ impl Client {
         if !query_id.is_empty() {
             query = Query::new(query.text()).with_query_id(query_id);
         }
-        self.send_query(&query).await?;
+        self.execute_with_progress(query).await.map(|_| ())
+    }
+
+    /// Execute a query without returning data, and return its final
+    /// progress
+    ///
+    /// The progress packets of the query are merged, so `written_rows`
+    /// is the number of rows written by statements that insert data,
+    /// such as `CREATE TABLE ... AS SELECT` and `INSERT ... SELECT`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let progress = client
+    ///     .execute_with_progress(
+    ///         "CREATE TABLE t ENGINE = Memory AS SELECT number FROM numbers(100)",
+    ///     )
+    ///     .await?;
+    /// assert_eq!(progress.written_rows, 100);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_with_progress(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<Progress> {
+        let query = query.into();
+        match self.execute_once(&query).await {
+            Err(e) if self.can_auto_reconnect(&e) => {
+                debug!("Connection lost during execute ({}), reconnecting", e);
+                self.reconnect().await?;
+                self.execute_once(&query).await
+            }
+            result => result,
+        }
+    }
+
+    /// Send `query` once and read its response, discarding any data
+    async fn execute_once(&mut self, query: &Query) -> Result<Progress> {
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
+        self.send_query(query).await?;
 
         // Read responses until EndOfStream, but don't collect blocks
+        let mut info = ResponseInfo::default();
         loop {
-            let packet_type = self.conn.read_varint().await?;
+            let packet_type = self.read_packet_type(query, deadline).await?;
+            trace!(target: LOG_TARGET, packet_type, "Execute response packet");
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
 
             match packet_type {
                 code if code == ServerCode::Data as u64 => {
                     // Skip data blocks (shouldn't happen for DDL, but handle
                     // gracefully)
-                    if self.server_info.revision >= 50264 {
-                        let _temp_table = self.conn.read_string().await?;
-                    }
                     let _block =
-                        self.block_reader.read_block(&mut self.conn).await?;
+                        read_within(deadline, self.read_data_block()).await?;
                 }
-                code if code == ServerCode::Progress as u64 => {
-                    let progress = self.read_progress().await?;
-
-                    // Invoke progress callback if present
-                    if let Some(callback) = query.get_on_progress() {
-                        callback(&progress);
-                    }
+                code if code == ServerCode::Totals as u64
+                    || code == ServerCode::Extremes as u64 =>
+                {
+                    let _block =
+                        read_within(deadline, self.read_data_block()).await?;
                 }
                 code if code == ServerCode::EndOfStream as u64 => {
+                    self.query_in_progress = false;
                     break;
                 }
-                code if code == ServerCode::Exception as u64 => {
-                    let exception = self.read_exception().await?;
-
-                    // Invoke exception callback if present
-                    if let Some(callback) = query.get_on_exception() {
-                        callback(&exception);
-                    }
-
-                    return Err(Error::Protocol(format!(
-                        "ClickHouse exception: {} (code {}): {}",
-                        exception.name, exception.code, exception.display_text
-                    )));
-                }
-                code if code == ServerCode::ProfileInfo as u64 => {
-                    // Read profile info
-                    let rows = self.conn.read_varint().await?;
-                    let blocks = self.conn.read_varint().await?;
-                    let bytes = self.conn.read_varint().await?;
-                    let applied_limit = self.conn.read_u8().await?;
-                    let rows_before_limit = self.conn.read_varint().await?;
-                    let calculated = self.conn.read_u8().await?;
-
-                    let profile = Profile {
-                        rows,
-                        blocks,
-                        bytes,
-                        applied_limit: applied_limit != 0,
-                        rows_before_limit,
-                        calculated_rows_before_limit: calculated != 0,
-                    };
-
-                    // Invoke profile callback if present
-                    if let Some(callback) = query.get_on_profile() {
-                        callback(&profile);
-                    }
-                }
-                code if code == ServerCode::Log as u64 => {
-                    let _log_tag = self.conn.read_string().await?;
-                    // Log blocks are sent uncompressed
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke server log callback if present
-                    if let Some(callback) = query.get_on_server_log() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::ProfileEvents as u64 => {
-                    let _table_name = self.conn.read_string().await?;
-                    // ProfileEvents blocks are sent uncompressed
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke profile events callback if present
-                    if let Some(callback) = query.get_on_profile_events() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::TableColumns as u64 => {
-                    let _table_name = self.conn.read_string().await?;
-                    let _columns_metadata = self.conn.read_string().await?;
-                }
                 _ => {
                     return Err(Error::Protocol(format!(
                         "Unexpected packet type during execute: {}",
@@ -584,7 +1070,7 @@ impl Client {
             }
         }
 
-        Ok(())
+        Ok(info.progress)
     }
 
     /// Execute a query and return results
@@ -599,168 +1085,306 @@ impl Client {
         self.query_with_id(query, "").await
     }
 
-    /// Execute a query with a specific query ID and return results
+    /// Execute a read-only query, retrying transient failures
     ///
-    /// The query ID is useful for query tracing and debugging.
+    /// Connection errors, timeouts and retryable server errors (such as
+    /// `TOO_MANY_SIMULTANEOUS_QUERIES`) are retried up to
+    /// `policy.max_retries` times with jittered exponential backoff,
+    /// reconnecting to the same endpoint when the connection was lost.
+    ///
+    /// Only statements that clearly cannot modify data are accepted
+    /// (`SELECT`, `WITH`, `SHOW`, `DESCRIBE`, `EXISTS` and `EXPLAIN`);
+    /// anything else fails with `Error::InvalidArgument` without being
+    /// sent. A client created with [`Client::connect_with_stream`] cannot
+    /// reconnect, so connection errors are not retried there.
     ///
     /// # Example
     /// ```no_run
-    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use clickhouse_native_client::{Client, ClientOptions, RetryPolicy};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// # let mut client = Client::connect(ClientOptions::default()).await?;
-    /// let result = client.query_with_id("SELECT 1", "select-123").await?;
+    /// let policy = RetryPolicy::new(5);
+    /// let result =
+    ///     client.query_retrying("SELECT count() FROM system.tables", &policy).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn query_with_id(
+    pub async fn query_retrying(
         &mut self,
         query: impl Into<Query>,
-        query_id: &str,
+        policy: &RetryPolicy,
     ) -> Result<QueryResult> {
-        let mut query = query.into();
-        if !query_id.is_empty() {
-            query = Query::new(query.text()).with_query_id(query_id);
+        let query = query.into();
+        if !is_read_only_query(query.text()) {
+            return Err(Error::InvalidArgument(format!(
+                "query_retrying only accepts read-only queries: {}",
+                query.text()
+            )));
         }
 
-        // Send query
-        self.send_query(&query).await?;
-
-        // Receive results
-        let mut blocks = Vec::new();
-        let mut progress_info = Progress::default();
-
+        let mut attempt = 0;
         loop {
-            let packet_type = self.conn.read_varint().await?;
-            debug!("Query response packet: {}", packet_type);
-
-            match packet_type {
-                code if code == ServerCode::Data as u64 => {
-                    debug!("Received data packet");
-                    // Skip temp table name if protocol supports it (matches
-                    // C++ ReceiveData)
-                    if self.server_info.revision >= 50264 {
-                        // DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES
-                        let _temp_table = self.conn.read_string().await?;
-                    }
-                    let block =
-                        self.block_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke data callback if present
-                    if let Some(callback) = query.get_on_data_cancelable() {
-                        let should_continue = callback(&block);
-                        if !should_continue {
-                            debug!("Query cancelled by data callback");
-                            break;
-                        }
-                    } else if let Some(callback) = query.get_on_data() {
-                        callback(&block);
-                    }
-
-                    if !block.is_empty() {
-                        blocks.push(block);
-                    }
-                }
-                code if code == ServerCode::Progress as u64 => {
-                    debug!("Received progress packet");
-                    let delta = self.read_progress().await?;
-                    progress_info.rows += delta.rows;
-                    progress_info.bytes += delta.bytes;
-                    progress_info.total_rows = delta.total_rows;
-                    progress_info.written_rows += delta.written_rows;
-                    progress_info.written_bytes += delta.written_bytes;
-
-                    // Invoke progress callback if present
-                    if let Some(callback) = query.get_on_progress() {
-                        callback(&progress_info);
-                    }
-                }
-                code if code == ServerCode::EndOfStream as u64 => {
-                    debug!("Received end of stream");
-                    break;
-                }
-                code if code == ServerCode::ProfileInfo as u64 => {
-                    debug!("Received profile info packet");
-                    // Read ProfileInfo fields directly
-                    let rows = self.conn.read_varint().await?;
-                    let blocks = self.conn.read_varint().await?;
-                    let bytes = self.conn.read_varint().await?;
-                    let applied_limit = self.conn.read_u8().await? != 0;
-                    let rows_before_limit = self.conn.read_varint().await?;
-                    let calculated_rows_before_limit =
-                        self.conn.read_u8().await? != 0;
-
-                    let profile = crate::query::Profile {
-                        rows,
-                        blocks,
-                        bytes,
-                        rows_before_limit,
-                        applied_limit,
-                        calculated_rows_before_limit,
-                    };
-
-                    // Invoke profile callback if present
-                    if let Some(callback) = query.get_on_profile() {
-                        callback(&profile);
-                    }
-                }
-                code if code == ServerCode::Log as u64 => {
-                    debug!("Received log packet");
-                    // Skip string first (log tag)
-                    let _log_tag = self.conn.read_string().await?;
-                    // Read the log block (sent uncompressed)
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke server log callback if present
-                    if let Some(callback) = query.get_on_server_log() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::ProfileEvents as u64 => {
-                    debug!("Received profile events packet");
-                    // Skip string first (matches C++ implementation)
-                    let _table_name = self.conn.read_string().await?;
-                    // Read ProfileEvents block (sent uncompressed)
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke profile events callback if present
-                    if let Some(callback) = query.get_on_profile_events() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::TableColumns as u64 => {
-                    debug!("Received table columns packet (ignoring)");
-                    // Skip external table name
-                    let _table_name = self.conn.read_string().await?;
-                    // Skip columns metadata string
-                    let _columns_metadata = self.conn.read_string().await?;
-                }
-                code if code == ServerCode::Exception as u64 => {
-                    debug!("Server returned exception during query, reading details...");
-                    let exception = self.read_exception().await?;
+            match self.query(query.clone()).await {
+                Err(e)
+                    if attempt < policy.max_retries
+                        && is_retryable_error(&e)
+                        && !(self.caller_stream
+                            && is_connection_error(&e)) =>
+                {
+                    let delay = policy.backoff(attempt);
                     debug!(
-                        "Exception: code={}, name={}, msg={}",
-                        exception.code, exception.name, exception.display_text
+                        "Query failed ({}), retrying in {:?} ({}/{})",
+                        e,
+                        delay,
+                        attempt + 1,
+                        policy.max_retries
                     );
-
-                    // Invoke exception callback if present
-                    if let Some(callback) = query.get_on_exception() {
-                        callback(&exception);
-                    }
-
-                    return Err(Error::Protocol(format!(
-                        "ClickHouse exception: {} ({}): {}",
-                        exception.name, exception.code, exception.display_text
-                    )));
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
                 }
-                other => {
-                    debug!("Unexpected packet type: {}", other);
-                    return Err(Error::Protocol(format!(
+                result => return result,
+            }
+        }
+    }
+
+    /// Run a query returning a single column and collect its values
+    ///
+    /// The rows of every result block are decoded into `T` in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the result has more or fewer than one
+    /// column, and `Error::TypeMismatch` if the column type does not map to
+    /// `T`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let names: Vec<String> =
+    ///     client.query_column("SELECT name FROM system.databases").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_column<T: FromSql>(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<Vec<T>> {
+        let result = self.query(query).await?;
+
+        let mut values = Vec::with_capacity(result.total_rows());
+        for block in result.blocks() {
+            let column = match block.column_count() {
+                1 => block.column(0).expect("block has one column"),
+                count => {
+                    return Err(Error::Validation(format!(
+                        "Expected a single column, got {}",
+                        count
+                    )))
+                }
+            };
+            values.extend(T::from_column(&column)?);
+        }
+        Ok(values)
+    }
+
+    /// Run a query returning a single value, such as `SELECT count()`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the result is not exactly one row of
+    /// one column, and `Error::TypeMismatch` if the column type does not
+    /// map to `T`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let tables: u64 =
+    ///     client.query_scalar("SELECT count() FROM system.tables").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_scalar<T: FromSql>(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<T> {
+        let mut values = self.query_column::<T>(query).await?;
+        if values.len() != 1 {
+            return Err(Error::Validation(format!(
+                "Expected a single row, got {}",
+                values.len()
+            )));
+        }
+        Ok(values.remove(0))
+    }
+
+    /// Run a query returning key/value pairs and collect them into a map
+    ///
+    /// The first column holds the keys and the second the values; when a
+    /// key repeats, its last row wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the result does not have exactly two
+    /// columns, and `Error::TypeMismatch` if a column type does not map to
+    /// `K` or `V`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use std::collections::HashMap;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let settings: HashMap<String, String> = client
+    ///     .query_map("SELECT name, value FROM system.settings")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_map<K, V>(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<HashMap<K, V>>
+    where
+        K: FromSql + Eq + std::hash::Hash,
+        V: FromSql,
+    {
+        let result = self.query(query).await?;
+
+        let mut map = HashMap::with_capacity(result.total_rows());
+        for block in result.blocks() {
+            if block.column_count() != 2 {
+                return Err(Error::Validation(format!(
+                    "Expected two columns, got {}",
+                    block.column_count()
+                )));
+            }
+            let keys = K::from_column(&block.column(0).expect("key column"))?;
+            let values =
+                V::from_column(&block.column(1).expect("value column"))?;
+            map.extend(keys.into_iter().zip(values));
+        }
+        Ok(map)
+    }
+
+    /// Execute a query with a specific query ID and return results
+    ///
+    /// The query ID is useful for query tracing and debugging.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let result = client.query_with_id("SELECT 1", "select-123").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_with_id(
+        &mut self,
+        query: impl Into<Query>,
+        query_id: &str,
+    ) -> Result<QueryResult> {
+        let mut query = query.into();
+        if !query_id.is_empty() {
+            query = Query::new(query.text()).with_query_id(query_id);
+        }
+
+        let mut consumed = false;
+        match self.query_once(&query, &mut consumed).await {
+            Err(e) if !consumed && self.can_auto_reconnect(&e) => {
+                debug!("Connection lost during query ({}), reconnecting", e);
+                self.reconnect().await?;
+                self.query_once(&query, &mut consumed).await
+            }
+            result => result,
+        }
+    }
+
+    /// Send `query` once and collect its result
+    ///
+    /// `consumed` is set once a block has been passed to a data callback,
+    /// after which the query can no longer be retried transparently.
+    async fn query_once(
+        &mut self,
+        query: &Query,
+        consumed: &mut bool,
+    ) -> Result<QueryResult> {
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
+
+        // Send query
+        self.send_query(query).await?;
+
+        // Receive results
+        let mut blocks = Vec::new();
+        let mut totals = None;
+        let mut extremes = None;
+        let mut info = ResponseInfo::default();
+
+        loop {
+            let packet_type = self.read_packet_type(query, deadline).await?;
+            trace!(target: LOG_TARGET, packet_type, "Query response packet");
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
+
+            match packet_type {
+                code if code == ServerCode::Data as u64 => {
+                    trace!(target: LOG_TARGET, "Received data packet");
+                    let block =
+                        read_within(deadline, self.read_data_block()).await?;
+
+                    // Invoke data callback if present
+                    if let Some(callback) = query.get_on_data_cancelable() {
+                        *consumed = true;
+                        let should_continue = callback(&block);
+                        if !should_continue {
+                            debug!("Query cancelled by data callback");
+                            self.cancel().await?;
+                            break;
+                        }
+                    } else if let Some(callback) = query.get_on_data() {
+                        *consumed = true;
+                        callback(&block);
+                    }
+
+                    if !block.is_empty() {
+                        blocks.push(block);
+                    }
+                }
+                code if code == ServerCode::Totals as u64 => {
+                    trace!(target: LOG_TARGET, "Received totals packet");
+                    totals = Some(
+                        read_within(deadline, self.read_data_block()).await?,
+                    );
+                }
+                code if code == ServerCode::Extremes as u64 => {
+                    trace!(target: LOG_TARGET, "Received extremes packet");
+                    extremes = Some(
+                        read_within(deadline, self.read_data_block()).await?,
+                    );
+                }
+                code if code == ServerCode::EndOfStream as u64 => {
+                    self.query_in_progress = false;
+                    trace!(target: LOG_TARGET, "Received end of stream");
+                    break;
+                }
+                other => {
+                    trace!(
+                        target: LOG_TARGET,
+                        packet_type = other,
+                        "Unexpected packet type"
+                    );
+                    return Err(Error::Protocol(format!(
                         "Unexpected packet type: {}",
                         other
                     )));
@@ -768,7 +1392,251 @@ impl Client {
             }
         }
 
-        Ok(QueryResult { blocks, progress: progress_info })
+        Ok(QueryResult {
+            blocks,
+            totals,
+            extremes,
+            progress: info.progress,
+            profile: info.profile,
+            profile_events: info.profile_events,
+            logs: info.logs,
+        })
+    }
+
+    /// Execute a query and read its result one block or row at a time
+    ///
+    /// Unlike [`query`](Self::query), blocks are only read from the server
+    /// when the returned [`Cursor`] is advanced, so large results do not
+    /// have to fit in memory. The cursor borrows the client until it is
+    /// dropped; dropping it before the end of the result cancels the query.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let mut cursor = client.query_cursor("SELECT number FROM numbers(10)").await?;
+    /// while let Some(row) = cursor.next_row().await? {
+    ///     println!("{:?}", row);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_cursor(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<Cursor<'_>> {
+        let query = query.into();
+        self.send_query(&query).await?;
+        Ok(Cursor::new(self, query))
+    }
+
+    /// Execute a query and stream its result blocks as they arrive
+    ///
+    /// The returned [`BlockStream`] implements [`Stream`] and reads one
+    /// block off the socket per item, so results of any size can be
+    /// processed with bounded memory. Progress, Log and ProfileEvents
+    /// packets are handled internally (invoking the query's callbacks). A
+    /// server exception is yielded as an `Err` item, after which the
+    /// stream ends.
+    ///
+    /// Dropping the stream early cancels the query, as with
+    /// [`query_cursor`](Self::query_cursor).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use futures_util::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let mut stream = client.query_stream("SELECT * FROM big_table").await?;
+    /// while let Some(block) = stream.next().await {
+    ///     println!("{} rows", block?.row_count());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_stream(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<BlockStream<'_>> {
+        let cursor = self.query_cursor(query).await?;
+        Ok(BlockStream { cursor: Some(cursor), pending: None })
+    }
+
+    /// Execute a query and stream its result blocks, reading ahead
+    ///
+    /// Unlike [`query_stream`](Self::query_stream), a background task
+    /// reads up to `capacity` blocks (at least one) ahead of the consumer,
+    /// so receiving and decoding the next blocks overlaps with processing
+    /// the current one. Once the buffer is full the task waits, which
+    /// bounds memory use.
+    ///
+    /// The task needs to own the connection, so the client is moved into
+    /// the stream; [`BufferedBlockStream::into_client`] hands it back.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use futures_util::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect(ClientOptions::default()).await?;
+    /// let mut stream =
+    ///     client.query_stream_buffered("SELECT * FROM big_table", 4).await?;
+    /// while let Some(block) = stream.next().await {
+    ///     println!("{} rows", block?.row_count());
+    /// }
+    /// let client = stream.into_client().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_stream_buffered(
+        mut self,
+        query: impl Into<Query>,
+        capacity: usize,
+    ) -> Result<BufferedBlockStream> {
+        let query = query.into();
+        self.send_query(&query).await?;
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+        let reader = tokio::spawn(async move {
+            let mut client = self;
+            let mut cursor = Cursor::new(&mut client, query);
+            while let Some(item) = cursor.next_block().await.transpose() {
+                if sender.send(item).await.is_err() {
+                    // The stream was dropped; the cursor cancels the query
+                    break;
+                }
+            }
+            drop(cursor);
+            client
+        });
+        Ok(BufferedBlockStream { receiver, reader })
+    }
+
+    /// Read response packets until the next non-empty data block
+    ///
+    /// Returns `Ok(None)` at end of stream. Callbacks other than the data
+    /// callbacks are invoked as their packets arrive.
+    async fn next_result_block(
+        &mut self,
+        query: &Query,
+        deadline: Option<Instant>,
+        info: &mut ResponseInfo,
+    ) -> Result<Option<Block>> {
+        loop {
+            let packet_type = self.read_packet_type(query, deadline).await?;
+            trace!(target: LOG_TARGET, packet_type, "Cursor response packet");
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, query, info),
+            )
+            .await?
+            {
+                continue;
+            }
+
+            match packet_type {
+                code if code == ServerCode::Data as u64 => {
+                    let block =
+                        read_within(deadline, self.read_data_block()).await?;
+                    if block.row_count() > 0 {
+                        return Ok(Some(block));
+                    }
+                }
+                code if code == ServerCode::Totals as u64
+                    || code == ServerCode::Extremes as u64 =>
+                {
+                    // Not part of the result rows
+                    let _block =
+                        read_within(deadline, self.read_data_block()).await?;
+                }
+                code if code == ServerCode::EndOfStream as u64 => {
+                    self.query_in_progress = false;
+                    return Ok(None);
+                }
+                other => {
+                    return Err(Error::Protocol(format!(
+                        "Unexpected packet type during cursor read: {}",
+                        other
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Execute a query and return only the number of result rows
+    ///
+    /// Data blocks are read from the connection but their column payloads
+    /// are skipped instead of being decoded, which avoids the CPU cost of
+    /// building columns when only the row count is needed. Compressed
+    /// blocks are still decompressed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let rows = client.query_count_only("SELECT * FROM system.numbers LIMIT 1000").await?;
+    /// assert_eq!(rows, 1000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_count_only(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<usize> {
+        let query = query.into();
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
+        self.send_query(&query).await?;
+
+        let mut total_rows = 0;
+        let mut info = ResponseInfo::default();
+
+        loop {
+            let packet_type = self.read_packet_type(&query, deadline).await?;
+            trace!(
+                target: LOG_TARGET,
+                packet_type,
+                "Count-only response packet"
+            );
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, &query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
+
+            match packet_type {
+                code if code == ServerCode::Data as u64 => {
+                    total_rows +=
+                        read_within(deadline, self.read_data_row_count())
+                            .await?;
+                }
+                code if code == ServerCode::Totals as u64
+                    || code == ServerCode::Extremes as u64 =>
+                {
+                    let _block =
+                        read_within(deadline, self.read_data_block()).await?;
+                }
+                code if code == ServerCode::EndOfStream as u64 => {
+                    self.query_in_progress = false;
+                    break;
+                }
+                other => {
+                    return Err(Error::Protocol(format!(
+                        "Unexpected packet type during count-only query: {}",
+                        other
+                    )));
+                }
+            }
+        }
+
+        Ok(total_rows)
     }
 
     /// Execute a SELECT query with external tables for JOIN operations
@@ -828,6 +1696,7 @@ impl Client {
         external_tables: &[crate::ExternalTable],
     ) -> Result<QueryResult> {
         let mut query = query.into();
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
         if !query_id.is_empty() {
             query = Query::new(query.text()).with_query_id(query_id);
         }
@@ -844,27 +1713,35 @@ impl Client {
 
         // Receive results (same as regular query)
         let mut blocks = Vec::new();
-        let mut progress_info = Progress::default();
+        let mut totals = None;
+        let mut extremes = None;
+        let mut info = ResponseInfo::default();
 
         loop {
-            let packet_type = self.conn.read_varint().await?;
-            debug!("Query response packet: {}", packet_type);
+            let packet_type = self.read_packet_type(&query, deadline).await?;
+            trace!(target: LOG_TARGET, packet_type, "Query response packet");
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, &query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
 
             match packet_type {
                 code if code == ServerCode::Data as u64 => {
-                    debug!("Received data packet");
-                    // Skip temp table name if protocol supports it
-                    if self.server_info.revision >= 50264 {
-                        let _temp_table = self.conn.read_string().await?;
-                    }
+                    trace!(target: LOG_TARGET, "Received data packet");
                     let block =
-                        self.block_reader.read_block(&mut self.conn).await?;
+                        read_within(deadline, self.read_data_block()).await?;
 
                     // Invoke data callback if present
                     if let Some(callback) = query.get_on_data_cancelable() {
                         let should_continue = callback(&block);
                         if !should_continue {
                             debug!("Query cancelled by data callback");
+                            self.cancel().await?;
                             break;
                         }
                     } else if let Some(callback) = query.get_on_data() {
@@ -875,99 +1752,23 @@ impl Client {
                         blocks.push(block);
                     }
                 }
-                code if code == ServerCode::Progress as u64 => {
-                    debug!("Received progress packet");
-                    let delta = self.read_progress().await?;
-                    progress_info.rows += delta.rows;
-                    progress_info.bytes += delta.bytes;
-                    progress_info.total_rows = delta.total_rows;
-                    progress_info.written_rows += delta.written_rows;
-                    progress_info.written_bytes += delta.written_bytes;
-
-                    // Invoke progress callback if present
-                    if let Some(callback) = query.get_on_progress() {
-                        callback(&progress_info);
-                    }
+                code if code == ServerCode::Totals as u64 => {
+                    trace!(target: LOG_TARGET, "Received totals packet");
+                    totals = Some(
+                        read_within(deadline, self.read_data_block()).await?,
+                    );
+                }
+                code if code == ServerCode::Extremes as u64 => {
+                    trace!(target: LOG_TARGET, "Received extremes packet");
+                    extremes = Some(
+                        read_within(deadline, self.read_data_block()).await?,
+                    );
                 }
                 code if code == ServerCode::EndOfStream as u64 => {
-                    debug!("Received end of stream");
+                    self.query_in_progress = false;
+                    trace!(target: LOG_TARGET, "Received end of stream");
                     break;
                 }
-                code if code == ServerCode::ProfileInfo as u64 => {
-                    debug!("Received profile info packet");
-                    let rows = self.conn.read_varint().await?;
-                    let blocks = self.conn.read_varint().await?;
-                    let bytes = self.conn.read_varint().await?;
-                    let applied_limit = self.conn.read_u8().await?;
-                    let rows_before_limit = self.conn.read_varint().await?;
-                    let calculated = self.conn.read_u8().await?;
-
-                    let profile = Profile {
-                        rows,
-                        blocks,
-                        bytes,
-                        applied_limit: applied_limit != 0,
-                        rows_before_limit,
-                        calculated_rows_before_limit: calculated != 0,
-                    };
-
-                    // Invoke profile callback if present
-                    if let Some(callback) = query.get_on_profile() {
-                        callback(&profile);
-                    }
-                }
-                code if code == ServerCode::Log as u64 => {
-                    debug!("Received log packet");
-                    let _log_tag = self.conn.read_string().await?;
-                    // Log blocks are sent uncompressed
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke server log callback if present
-                    if let Some(callback) = query.get_on_server_log() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::ProfileEvents as u64 => {
-                    debug!("Received profile events packet");
-                    let _table_name = self.conn.read_string().await?;
-                    // ProfileEvents blocks are sent uncompressed
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-
-                    // Invoke profile events callback if present
-                    if let Some(callback) = query.get_on_profile_events() {
-                        callback(&block);
-                    }
-                }
-                code if code == ServerCode::TableColumns as u64 => {
-                    debug!("Received table columns packet (ignoring)");
-                    // Skip external table name
-                    let _table_name = self.conn.read_string().await?;
-                    // Skip columns metadata string
-                    let _columns_metadata = self.conn.read_string().await?;
-                }
-                code if code == ServerCode::Exception as u64 => {
-                    let exception = self.read_exception().await?;
-                    debug!(
-                        "Received exception: {} - {}",
-                        exception.name, exception.display_text
-                    );
-
-                    // Invoke exception callback if present
-                    if let Some(callback) = query.get_on_exception() {
-                        callback(&exception);
-                    }
-
-                    return Err(Error::Protocol(format!(
-                        "ClickHouse exception: {} (code {}): {}",
-                        exception.name, exception.code, exception.display_text
-                    )));
-                }
                 other => {
                     return Err(Error::Protocol(format!(
                         "Unexpected packet type during query: {}",
@@ -977,7 +1778,15 @@ impl Client {
             }
         }
 
-        Ok(QueryResult { blocks, progress: progress_info })
+        Ok(QueryResult {
+            blocks,
+            totals,
+            extremes,
+            progress: info.progress,
+            profile: info.profile,
+            profile_events: info.profile_events,
+            logs: info.logs,
+        })
     }
 
     /// Send a query packet (always finalized)
@@ -991,109 +1800,48 @@ impl Client {
         query: &Query,
         finalize: bool,
     ) -> Result<()> {
-        debug!("Sending query: {}", query.text());
-        // Write query code
-        self.conn.write_varint(ClientCode::Query as u64).await?;
-
-        // Write query ID
-        self.conn.write_string(query.id()).await?;
-        debug!("Sent query ID");
-
-        // Client info
-        let revision = self.server_info.revision;
-        if revision >= 54032 {
-            debug!("Writing client info...");
-            let info = &self.options.client_info;
-
-            // Write client info fields in the correct order
-            self.conn.write_u8(1).await?; // query_kind = 1 (initial query)
-            self.conn.write_string(&info.initial_user).await?;
-            self.conn.write_string(&info.initial_query_id).await?;
-            self.conn.write_string("127.0.0.1:0").await?; // initial_address (client address:port)
-
-            if revision >= 54449 {
-                self.conn.write_i64(0).await?; // initial_query_start_time
-            }
-
-            self.conn.write_u8(info.interface_type).await?; // interface type (1 = TCP)
-            self.conn.write_string(&info.os_user).await?;
-            self.conn.write_string(&info.client_hostname).await?;
-            self.conn.write_string(&info.client_name).await?;
-            self.conn.write_varint(info.client_version_major).await?;
-            self.conn.write_varint(info.client_version_minor).await?;
-            self.conn.write_varint(info.client_revision).await?;
-
-            if revision >= 54060 {
-                self.conn.write_string(&info.quota_key).await?;
-            }
-            if revision >= 54448 {
-                self.conn.write_varint(0).await?; // distributed_depth
-            }
-            if revision >= 54401 {
-                self.conn.write_varint(info.client_version_patch).await?;
-            }
-            if revision >= 54442 {
-                // OpenTelemetry tracing context
-                if let Some(ctx) = query.tracing_context() {
-                    self.conn.write_u8(1).await?; // have OpenTelemetry
-                                                  // Write trace_id (128-bit)
-                    self.conn.write_u128(ctx.trace_id).await?;
-                    // Write span_id (64-bit)
-                    self.conn.write_u64(ctx.span_id).await?;
-                    // Write tracestate
-                    self.conn.write_string(&ctx.tracestate).await?;
-                    // Write trace_flags
-                    self.conn.write_u8(ctx.trace_flags).await?;
-                } else {
-                    self.conn.write_u8(0).await?; // no OpenTelemetry
-                }
-            }
-            if revision >= 54453 {
-                self.conn.write_varint(0).await?; // collaborate_with_initiator
-                self.conn.write_varint(0).await?; // count_participating_replicas
-                self.conn.write_varint(0).await?; // number_of_current_replica
-            }
-
-            debug!("Client info sent");
-        }
-
-        // Settings
-        if revision >= 54429 {
-            debug!("Writing settings...");
-            for (key, field) in query.settings() {
-                self.conn.write_string(key).await?;
-                self.conn.write_varint(field.flags).await?;
-                self.conn.write_string(&field.value).await?;
-            }
+        // Older servers have no field for parameters, so they would run
+        // the query with its placeholders unbound
+        if !query.parameters().is_empty()
+            && self.server_info.revision
+                < DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS
+        {
+            return Err(Error::UnsupportedFeature(format!(
+                "server does not support query parameters; requires \
+                 revision >= {}",
+                DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS
+            )));
         }
-        // Empty string to mark end of settings
-        self.conn.write_string("").await?;
-        debug!("Settings sent");
 
-        // Interserver secret (for servers >= 54441)
-        if revision >= 54441 {
-            self.conn.write_string("").await?; // empty interserver secret
+        if !self.options.client_info.interserver_secret.is_empty()
+            && self.server_info.revision
+                < DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET
+        {
+            return Err(Error::UnsupportedFeature(format!(
+                "server does not support an interserver secret; requires \
+                 revision >= {}",
+                DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET
+            )));
         }
 
-        // Query stage, compression, text
-        debug!("Writing query stage and text...");
-        self.conn.write_varint(2).await?; // Stage = Complete
-                                          // Enable compression if we have it configured
-        let compression_enabled =
-            if self.options.compression.is_some() { 1u64 } else { 0u64 };
-        self.conn.write_varint(compression_enabled).await?;
-        self.conn.write_string(query.text()).await?;
+        self.check_output_format_settings(query)?;
 
-        // Query parameters (for servers >= 54459)
-        if revision >= 54459 {
-            for (key, value) in query.parameters() {
-                self.conn.write_string(key).await?;
-                self.conn.write_varint(2).await?; // Custom type
-                self.conn.write_quoted_string(value).await?;
-            }
-            // Empty string to mark end of parameters
-            self.conn.write_string("").await?;
+        self.recover_if_interrupted().await?;
+        if self.options.ping_before_query {
+            self.ping_or_reconnect().await?;
         }
+        self.query_in_progress = true;
+        self.last_activity = Instant::now();
+
+        trace!(target: LOG_TARGET, "Sending query: {}", query.text());
+        let mut packet = BytesMut::new();
+        query.write_packet(
+            &mut packet,
+            self.server_info.revision,
+            &self.options.client_info,
+            self.compressed,
+        );
+        self.conn.write_bytes(&packet).await?;
 
         // Conditionally finalize based on parameter
         if finalize {
@@ -1103,6 +1851,34 @@ impl Client {
         Ok(())
     }
 
+    /// Warn about (or, with [`ClientOptions::strict_settings`], reject)
+    /// settings that only shape text output formats. Results always arrive
+    /// as Native blocks, so the server silently ignores them.
+    fn check_output_format_settings(&self, query: &Query) -> Result<()> {
+        let mut ignored: Vec<&str> = query
+            .settings()
+            .keys()
+            .map(String::as_str)
+            .filter(|name| is_output_format_setting(name))
+            .collect();
+        if ignored.is_empty() {
+            return Ok(());
+        }
+        ignored.sort_unstable();
+
+        let message = format!(
+            "settings {} have no effect over the native protocol; read the \
+             result blocks and format them on the client instead (e.g. \
+             QueryResult::write_row_binary)",
+            ignored.join(", ")
+        );
+        if self.options.strict_settings {
+            return Err(Error::InvalidArgument(message));
+        }
+        warn!(target: LOG_TARGET, "{}", message);
+        Ok(())
+    }
+
     /// Finalize query by sending empty block marker
     ///
     /// Must be called after send_query_internal() to complete the query
@@ -1111,20 +1887,13 @@ impl Client {
     async fn finalize_query(&mut self) -> Result<()> {
         // Send empty block to finalize query (as per C++ client)
         // This block must respect the compression setting we told the server
-        debug!("Sending empty block to finalize...");
+        trace!(target: LOG_TARGET, "Sending empty block to finalize...");
         self.conn.write_varint(ClientCode::Data as u64).await?;
         let empty_block = Block::new();
-        // Create writer that matches the compression setting
-        let writer = if let Some(compression) = self.options.compression {
-            BlockWriter::new(self.server_info.revision)
-                .with_compression(compression)
-        } else {
-            BlockWriter::new(self.server_info.revision)
-        };
-        writer.write_block(&mut self.conn, &empty_block).await?;
+        self.block_writer.write_block(&mut self.conn, &empty_block).await?;
 
         self.conn.flush().await?;
-        debug!("Query finalized");
+        trace!(target: LOG_TARGET, "Query finalized");
         Ok(())
     }
 
@@ -1143,7 +1912,11 @@ impl Client {
                 continue;
             }
 
-            debug!("Sending external table: {}", table.name);
+            trace!(
+                target: LOG_TARGET,
+                "Sending external table: {}",
+                table.name
+            );
 
             // Send Data packet type
             self.conn.write_varint(ClientCode::Data as u64).await?;
@@ -1191,23 +1964,32 @@ impl Client {
     ) -> std::pin::Pin<
         Box<
             dyn std::future::Future<Output = Result<crate::query::Exception>>
+                + Send
                 + '_,
         >,
     > {
         use crate::query::Exception;
         Box::pin(async move {
-            debug!("Reading exception code...");
+            trace!(target: LOG_TARGET, "Reading exception code...");
             let code = conn.read_i32().await?;
-            debug!("Exception code: {}", code);
-            debug!("Reading exception name...");
+            trace!(target: LOG_TARGET, "Exception code: {}", code);
+            trace!(target: LOG_TARGET, "Reading exception name...");
             let name = conn.read_string().await?;
-            debug!("Exception name: {}", name);
-            debug!("Reading exception display_text...");
+            trace!(target: LOG_TARGET, "Exception name: {}", name);
+            trace!(target: LOG_TARGET, "Reading exception display_text...");
             let display_text = conn.read_string().await?;
-            debug!("Exception display_text length: {}", display_text.len());
-            debug!("Reading exception stack_trace...");
+            trace!(
+                target: LOG_TARGET,
+                "Exception display_text length: {}",
+                display_text.len()
+            );
+            trace!(target: LOG_TARGET, "Reading exception stack_trace...");
             let stack_trace = conn.read_string().await?;
-            debug!("Exception stack_trace length: {}", stack_trace.len());
+            trace!(
+                target: LOG_TARGET,
+                "Exception stack_trace length: {}",
+                stack_trace.len()
+            );
 
             // Check for nested exception
             let has_nested = conn.read_u8().await?;
@@ -1221,12 +2003,162 @@ impl Client {
         })
     }
 
+    /// Answer a distributed or parallel-replica read task request
+    ///
+    /// Task coordination is not implemented: a `ReadTaskRequest` is
+    /// answered with "no more tasks" so the query carries on reading
+    /// locally. The parallel-replica requests carry a payload this client
+    /// cannot decode, so they fail the query with an explanation.
+    async fn answer_read_task_request(&mut self, code: u64) -> Result<()> {
+        if code != ServerCode::ReadTaskRequest as u64 {
+            return Err(Error::Protocol(format!(
+                "Parallel replicas coordination (packet {}) is not \
+                 supported; disable \
+                 allow_experimental_parallel_reading_from_replicas",
+                code
+            )));
+        }
+
+        debug!("Declining read task request from server");
+        self.conn.write_varint(ClientCode::ReadTaskResponse as u64).await?;
+        self.conn
+            .write_varint(DBMS_CLUSTER_PROCESSING_PROTOCOL_VERSION)
+            .await?;
+        // An empty task means there is nothing left to read
+        self.conn.write_string("").await?;
+        self.conn.flush().await
+    }
+
+    /// Read the payload of a data-like packet (Data, Totals or Extremes):
+    /// the temporary table name, then the block
+    async fn read_data_block(&mut self) -> Result<Block> {
+        if self.server_info.revision >= 50264 {
+            let _temp_table = self.conn.read_string().await?;
+        }
+        self.block_reader.read_block(&mut self.conn).await
+    }
+
+    /// Like [`read_data_block`](Self::read_data_block), but only count the
+    /// rows of the block instead of decoding its columns
+    async fn read_data_row_count(&mut self) -> Result<usize> {
+        if self.server_info.revision >= 50264 {
+            let _temp_table = self.conn.read_string().await?;
+        }
+        self.block_reader.read_block_row_count(&mut self.conn).await
+    }
+
+    /// Read a response packet that carries no result data: Progress,
+    /// ProfileInfo, Log, ProfileEvents, TableColumns, a read task request
+    /// or Exception
+    ///
+    /// Returns `Ok(false)` without reading anything for other packet types,
+    /// which the caller handles. An Exception ends the query and is
+    /// returned as `Error::Server` once the query's callback has seen it.
+    async fn read_side_packet(
+        &mut self,
+        packet_type: u64,
+        query: &Query,
+        info: &mut ResponseInfo,
+    ) -> Result<bool> {
+        match packet_type {
+            code if code == ServerCode::Progress as u64 => {
+                let delta = self.read_progress().await?;
+                if let Some(callback) = query.get_on_raw_progress() {
+                    callback(&delta);
+                }
+                info.progress.merge(&delta);
+
+                if let Some(callback) = query.get_on_progress() {
+                    callback(&info.progress);
+                }
+            }
+            code if code == ServerCode::ProfileInfo as u64 => {
+                let rows = self.conn.read_varint().await?;
+                let blocks = self.conn.read_varint().await?;
+                let bytes = self.conn.read_varint().await?;
+                let applied_limit = self.conn.read_u8().await? != 0;
+                let rows_before_limit = self.conn.read_varint().await?;
+                let calculated_rows_before_limit =
+                    self.conn.read_u8().await? != 0;
+
+                let profile = Profile {
+                    rows,
+                    blocks,
+                    bytes,
+                    rows_before_limit,
+                    applied_limit,
+                    calculated_rows_before_limit,
+                };
+
+                if let Some(callback) = query.get_on_profile() {
+                    callback(&profile);
+                }
+                info.profile = Some(profile);
+            }
+            code if code == ServerCode::Log as u64 => {
+                let _log_tag = self.conn.read_string().await?;
+                // Log blocks are sent uncompressed
+                let mut uncompressed_reader =
+                    BlockReader::new(self.server_info.revision);
+                let block =
+                    uncompressed_reader.read_block(&mut self.conn).await?;
+
+                if let Some(callback) = query.get_on_server_log() {
+                    callback(&block);
+                }
+                if query.collects_logs() {
+                    info.logs.extend(parse_server_logs(&block));
+                }
+            }
+            code if code == ServerCode::ProfileEvents as u64 => {
+                let _table_name = self.conn.read_string().await?;
+                // ProfileEvents blocks are sent uncompressed
+                let mut uncompressed_reader =
+                    BlockReader::new(self.server_info.revision);
+                let block =
+                    uncompressed_reader.read_block(&mut self.conn).await?;
+
+                if let Some(callback) = query.get_on_profile_events() {
+                    callback(&block);
+                }
+                info.profile_events.extend(parse_profile_events(&block));
+            }
+            code if is_read_task_request(code) => {
+                self.answer_read_task_request(code).await?;
+            }
+            code if code == ServerCode::TableColumns as u64 => {
+                // External table name, then the column description
+                let _table_name = self.conn.read_string().await?;
+                info.columns = Some(self.conn.read_string().await?);
+            }
+            code if code == ServerCode::Exception as u64 => {
+                let exception = self.read_exception().await?;
+                self.query_in_progress = false;
+                trace!(
+                    target: LOG_TARGET,
+                    "Exception: code={}, name={}, msg={}",
+                    exception.code,
+                    exception.name,
+                    exception.display_text
+                );
+
+                if let Some(callback) = query.get_on_exception() {
+                    callback(&exception);
+                }
+                return Err(Error::Server(exception));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
     /// Read exception from server
     fn read_exception<'a>(
         &'a mut self,
     ) -> std::pin::Pin<
         Box<
             dyn std::future::Future<Output = Result<crate::query::Exception>>
+                + Send
                 + 'a,
         >,
     > {
@@ -1242,12 +2174,53 @@ impl Client {
     /// block).await?`
     ///
     /// For query tracing, use `insert_with_id()` to specify a query ID.
+    ///
+    /// The block's column types are checked against the table schema the
+    /// server reports before any data is sent; a column that does not fit
+    /// fails with `Error::TypeMismatch` and nothing is inserted.
     pub async fn insert(
         &mut self,
         table_name: &str,
         block: Block,
     ) -> Result<()> {
-        self.insert_with_id(table_name, "", block).await
+        let col_names = insert_column_names(&block)?;
+        let query_text = build_insert_query_text(table_name, &col_names)?;
+        self.insert_with_query(&query_text, block).await
+    }
+
+    /// Insert a block with an INSERT statement written by the caller
+    ///
+    /// `query` is sent exactly as given, e.g. to list only some columns in
+    /// a chosen order, to add `SETTINGS`, or to use `FORMAT Native`; no
+    /// column list is generated. The server answers with the columns it
+    /// expects, and `block` is checked against them and streamed as the
+    /// INSERT's data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` before sending data if `block` has a
+    /// column the server does not expect, or lacks one that has no default
+    /// in the table. The INSERT is then cancelled, leaving the connection
+    /// ready for the next operation.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let block = Block::new();
+    /// client
+    ///     .insert_with_query("INSERT INTO events (name, id) FORMAT Native", block)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_with_query(
+        &mut self,
+        query: &str,
+        block: Block,
+    ) -> Result<()> {
+        self.send_insert(Query::new(query), block).await
     }
 
     /// Insert data into a table with a specific query ID
@@ -1273,68 +2246,351 @@ impl Client {
         query_id: &str,
         block: Block,
     ) -> Result<()> {
-        // Build query with column names from block (matches C++
-        // implementation)
-        let col_names: Vec<String> = (0..block.column_count())
-            .filter_map(|i| block.column_name(i))
-            .map(|n| format!("`{}`", n.replace("`", "``"))) // Escape backticks
+        self.insert_query(
+            Query::new(table_name).with_query_id(query_id),
+            block,
+        )
+        .await
+    }
+
+    /// Insert data using a [`Query`] as the insert target
+    ///
+    /// The query text may be a bare table name, a table name followed by a
+    /// `SETTINGS` clause, or a full `INSERT INTO t SETTINGS ... VALUES`
+    /// statement. The column list is always generated from the block and
+    /// any `SETTINGS` clause is preserved in the generated statement. The
+    /// query's ID and settings are sent along with the INSERT.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the query text already lists columns;
+    /// use [`insert_with_query`](Self::insert_with_query) for that.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block, Query};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let block = Block::new();
+    /// let query = Query::new("INSERT INTO my_table SETTINGS async_insert=1 VALUES")
+    ///     .with_setting("wait_for_async_insert", "1");
+    /// client.insert_query(query, block).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_query(
+        &mut self,
+        query: impl Into<Query>,
+        block: Block,
+    ) -> Result<()> {
+        let query = query.into();
+        let col_names = insert_column_names(&block)?;
+        let query_text = build_insert_query_text(query.text(), &col_names)?;
+        self.send_insert(query.with_text(query_text), block).await
+    }
+
+    /// Insert a block, letting the server convert some of its columns
+    ///
+    /// Columns named in `casts` are sent with the block's own types and
+    /// converted server-side with `CAST(col, 'Type')` through the `input()`
+    /// table function, e.g. to load `String` data into an `Int32` column.
+    /// Other columns are inserted as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `casts` names a column that is not in
+    /// the block. Values the server cannot convert fail the whole insert.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block};
+    /// # use std::collections::HashMap;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let block = Block::new();
+    /// let casts = HashMap::from([("id".to_string(), "Int32".to_string())]);
+    /// client.insert_casting("my_table", block, casts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_casting(
+        &mut self,
+        table_name: &str,
+        block: Block,
+        casts: HashMap<String, String>,
+    ) -> Result<()> {
+        let query_text =
+            build_insert_casting_text(table_name, &block, &casts)?;
+        self.send_insert(Query::new(query_text), block).await
+    }
+
+    /// Insert only some columns of a block
+    ///
+    /// The generated INSERT lists just `present_columns`, in that order, so
+    /// every other column of the table is filled with its server-side
+    /// `DEFAULT` expression instead of a value from the client. Columns of
+    /// `block` not named in `present_columns` are not sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `present_columns` is empty or names a
+    /// column that is not in the block.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let block = Block::new();
+    /// // `created_at DEFAULT now()` is filled in by the server
+    /// client.insert_partial("events", block, &["id"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_partial(
+        &mut self,
+        table_name: &str,
+        block: Block,
+        present_columns: &[&str],
+    ) -> Result<()> {
+        let block = select_block_columns(&block, present_columns)?;
+        self.insert(table_name, block).await
+    }
+
+    /// Start an INSERT whose blocks are sent one at a time
+    ///
+    /// `columns` are the columns every block written to the returned
+    /// [`InsertSession`] carries, in order; the query text is a table name,
+    /// optionally followed by a `SETTINGS` clause, as for
+    /// [`insert_query`](Self::insert_query). The rows become visible when
+    /// [`InsertSession::finish`] succeeds.
+    ///
+    /// If the connection drops before the first block was written, the
+    /// session reconnects and sends the INSERT again. Once a block has been
+    /// written it never retries, since the server may already have received
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `columns` is empty or the query text
+    /// already lists columns.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let batches: Vec<Block> = Vec::new();
+    /// let mut session = client.begin_insert("events", &["id", "name"]).await?;
+    /// for block in batches {
+    ///     session.write(&block).await?;
+    /// }
+    /// session.finish().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_insert(
+        &mut self,
+        query: impl Into<Query>,
+        columns: &[&str],
+    ) -> Result<InsertSession<'_>> {
+        if columns.is_empty() {
+            return Err(Error::Validation(
+                "INSERT session needs at least one column".to_string(),
+            ));
+        }
+
+        let query = query.into();
+        let query_text = build_insert_query_text(query.text(), columns)?;
+        let query = query.with_text(query_text);
+
+        let InsertTarget { header, defaulted } =
+            self.start_insert(&query).await?;
+        Ok(InsertSession {
+            client: self,
+            query,
+            header,
+            defaulted,
+            blocks_written: 0,
+        })
+    }
+
+    /// Start an INSERT into every column of a table
+    ///
+    /// Like [`begin_insert`](Self::begin_insert), but without a column
+    /// list: the server declares the table's columns in the header it
+    /// sends back, available from [`InsertSession::schema`] before any data
+    /// is written. Blocks must then carry those columns in that order.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let session = client.prepare_insert("events").await?;
+    /// for (name, type_) in session.schema() {
+    ///     println!("{}: {}", name, type_.name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prepare_insert(
+        &mut self,
+        query: impl Into<Query>,
+    ) -> Result<InsertSession<'_>> {
+        let query = query.into();
+        let query_text = build_insert_query_text(query.text(), &[])?;
+        let query = query.with_text(query_text);
+
+        let InsertTarget { header, defaulted } =
+            self.start_insert(&query).await?;
+        Ok(InsertSession {
+            client: self,
+            query,
+            header,
+            defaulted,
+            blocks_written: 0,
+        })
+    }
+
+    /// Insert every block of `blocks` with a single INSERT
+    ///
+    /// The INSERT is sent once, with the columns of the first block, and
+    /// each block follows as its own data packet, so loading many chunks
+    /// costs one query round trip. Nothing is sent if `blocks` is empty.
+    /// See [`begin_insert`](Self::begin_insert) to write blocks as they
+    /// are produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the first block has no columns.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions, Block};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// # let chunks: Vec<Block> = Vec::new();
+    /// client.insert_stream("events", chunks).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_stream<I>(
+        &mut self,
+        table_name: &str,
+        blocks: I,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = Block>,
+    {
+        let mut blocks = blocks.into_iter();
+        let Some(first) = blocks.next() else {
+            return Ok(());
+        };
+        let columns: Vec<&str> = (0..first.column_count())
+            .filter_map(|i| first.column_name(i))
             .collect();
 
-        if col_names.is_empty() {
-            return Err(Error::Protocol("Block has no columns".to_string()));
+        let mut session = self.begin_insert(table_name, &columns).await?;
+        session.write(&first).await?;
+        for block in blocks {
+            session.write(&block).await?;
         }
+        session.finish().await
+    }
 
-        let query_text = format!(
-            "INSERT INTO {} ({}) VALUES",
-            table_name,
-            col_names.join(", ")
-        );
+    /// Send a complete INSERT statement and stream `block` as its data
+    async fn send_insert(&mut self, query: Query, block: Block) -> Result<()> {
+        match self.send_insert_once(&query, &block).await {
+            Err(e) if self.can_auto_reconnect(&e) => {
+                debug!("Connection lost during INSERT ({}), reconnecting", e);
+                self.reconnect().await?;
+                self.send_insert_once(&query, &block).await
+            }
+            result => result,
+        }
+    }
 
-        debug!("Sending INSERT query: {}", query_text);
-        let query = Query::new(query_text).with_query_id(query_id);
+    /// Run one INSERT exchange for `block`
+    async fn send_insert_once(
+        &mut self,
+        query: &Query,
+        block: &Block,
+    ) -> Result<()> {
+        let target = self.start_insert(query).await?;
+        let checked = check_insert_columns(
+            &target.header,
+            target.defaulted.as_ref(),
+            block,
+            self.options.insert_allow_defaults,
+        )
+        .and_then(|()| check_insert_types(&target.header, block));
+        if let Err(e) = checked {
+            // Close the INSERT before any data is sent, so nothing is
+            // committed and the connection stays usable. The validation
+            // error is what the caller needs to see either way
+            if let Err(cancel_err) = self.cancel().await {
+                trace!(
+                    target: LOG_TARGET,
+                    "Cancelling rejected INSERT failed: {}",
+                    cancel_err
+                );
+                // The stream may be left mid-response; reconnect before
+                // the next operation
+                self.query_in_progress = true;
+            }
+            return Err(e);
+        }
+        self.write_insert_block(block).await?;
+        self.finish_insert(query).await
+    }
 
-        // Send query
-        self.send_query(&query).await?;
+    /// Send an INSERT statement and wait until the server is ready for
+    /// data, returning the (empty) block describing the target columns
+    async fn start_insert(&mut self, query: &Query) -> Result<InsertTarget> {
+        trace!(target: LOG_TARGET, "Sending INSERT query: {}", query.text());
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
+        self.send_query(query).await?;
 
+        let mut info = ResponseInfo::default();
         // Wait for server to respond with Data packet (matches C++ Insert
         // flow)
-        debug!("Waiting for server Data packet...");
+        trace!(target: LOG_TARGET, "Waiting for server Data packet...");
         loop {
-            let packet_type = self.conn.read_varint().await?;
-            debug!("INSERT wait response packet type: {}", packet_type);
+            let packet_type = self.read_packet_type(query, deadline).await?;
+            trace!(
+                target: LOG_TARGET,
+                packet_type,
+                "INSERT wait response packet type"
+            );
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
 
             match packet_type {
                 code if code == ServerCode::Data as u64 => {
-                    debug!("Received Data packet, ready to send data");
+                    trace!(
+                        target: LOG_TARGET,
+                        "Received Data packet, ready to send data"
+                    );
                     // CRITICAL: Must consume the Data packet's payload to keep
-                    // stream aligned! Skip temp table name
-                    if self.server_info.revision >= 50264 {
-                        let _temp_table = self.conn.read_string().await?;
-                    }
-                    // Read the block (likely empty, but must consume it)
-                    let _block =
-                        self.block_reader.read_block(&mut self.conn).await?;
-                    debug!("Consumed Data packet payload, stream aligned");
-                    break;
-                }
-                code if code == ServerCode::Progress as u64 => {
-                    debug!("Received Progress packet");
-                    let _ = self.read_progress().await?;
-                }
-                code if code == ServerCode::TableColumns as u64 => {
-                    debug!("Received TableColumns packet");
-                    // Skip external table name
-                    let _table_name = self.conn.read_string().await?;
-                    // Skip columns metadata string
-                    let _columns_metadata = self.conn.read_string().await?;
-                }
-                code if code == ServerCode::Exception as u64 => {
-                    debug!("Server returned exception before accepting data");
-                    let exception = self.read_exception().await?;
-                    return Err(Error::Protocol(format!(
-                        "ClickHouse exception: {} (code {}): {}",
-                        exception.name, exception.code, exception.display_text
-                    )));
+                    // stream aligned! The block is empty apart from its
+                    // columns
+                    let header =
+                        read_within(deadline, self.read_data_block()).await?;
+                    trace!(
+                        target: LOG_TARGET,
+                        "Consumed Data packet payload, stream aligned"
+                    );
+                    let defaulted =
+                        info.columns.as_deref().map(parse_defaulted_columns);
+                    return Ok(InsertTarget { header, defaulted });
                 }
                 other => {
                     return Err(Error::Protocol(format!(
@@ -1344,68 +2600,73 @@ impl Client {
                 }
             }
         }
+    }
 
-        // Now send our data block
-        debug!("Sending data block with {} rows", block.row_count());
+    /// Send one data block of the INSERT started by `start_insert`
+    async fn write_insert_block(&mut self, block: &Block) -> Result<()> {
+        trace!(
+            target: LOG_TARGET,
+            "Sending data block with {} rows",
+            block.row_count()
+        );
         self.conn.write_varint(ClientCode::Data as u64).await?;
-        self.block_writer.write_block(&mut self.conn, &block).await?;
+        self.block_writer.write_block(&mut self.conn, block).await
+    }
 
+    /// End the data of the current INSERT and wait for the server to
+    /// commit it
+    async fn finish_insert(&mut self, query: &Query) -> Result<()> {
         // Send empty block to signal end
-        debug!("Sending empty block to signal end");
+        trace!(target: LOG_TARGET, "Sending empty block to signal end");
+        let deadline = query.timeout().map(|timeout| Instant::now() + timeout);
         let empty_block = Block::new();
         self.conn.write_varint(ClientCode::Data as u64).await?;
         self.block_writer.write_block(&mut self.conn, &empty_block).await?;
 
         // Wait for EndOfStream (matches C++ flow)
-        debug!("Waiting for EndOfStream...");
+        let mut info = ResponseInfo::default();
+        trace!(target: LOG_TARGET, "Waiting for EndOfStream...");
         loop {
-            let packet_type = self.conn.read_varint().await?;
-            debug!("INSERT final response packet type: {}", packet_type);
+            let packet_type = self.read_packet_type(query, deadline).await?;
+            trace!(
+                target: LOG_TARGET,
+                packet_type,
+                "INSERT final response packet type"
+            );
+
+            if read_within(
+                deadline,
+                self.read_side_packet(packet_type, query, &mut info),
+            )
+            .await?
+            {
+                continue;
+            }
 
             match packet_type {
                 code if code == ServerCode::EndOfStream as u64 => {
-                    debug!("Received EndOfStream, insert complete");
+                    self.query_in_progress = false;
+                    trace!(
+                        target: LOG_TARGET,
+                        "Received EndOfStream, insert complete"
+                    );
                     break;
                 }
                 code if code == ServerCode::Data as u64 => {
-                    debug!(
+                    trace!(
+                        target: LOG_TARGET,
                         "Received Data packet in INSERT response (skipping)"
                     );
-                    // Skip temp table name if protocol supports it
-                    if self.server_info.revision >= 50264 {
-                        let _temp_table = self.conn.read_string().await?;
-                    }
                     // Read and discard the block
                     let _block =
-                        self.block_reader.read_block(&mut self.conn).await?;
-                }
-                code if code == ServerCode::Progress as u64 => {
-                    debug!("Received Progress packet");
-                    let _ = self.read_progress().await?;
-                }
-                code if code == ServerCode::ProfileEvents as u64 => {
-                    debug!("Received ProfileEvents packet (skipping)");
-                    let _table_name = self.conn.read_string().await?;
-                    let uncompressed_reader =
-                        BlockReader::new(self.server_info.revision);
-                    let _block =
-                        uncompressed_reader.read_block(&mut self.conn).await?;
-                }
-                code if code == ServerCode::TableColumns as u64 => {
-                    debug!("Received TableColumns packet (skipping)");
-                    let _table_name = self.conn.read_string().await?;
-                    let _columns_metadata = self.conn.read_string().await?;
-                }
-                code if code == ServerCode::Exception as u64 => {
-                    debug!("Server returned exception after sending data");
-                    let exception = self.read_exception().await?;
-                    return Err(Error::Protocol(format!(
-                        "ClickHouse exception: {} (code {}): {}",
-                        exception.name, exception.code, exception.display_text
-                    )));
+                        read_within(deadline, self.read_data_block()).await?;
                 }
                 _ => {
-                    debug!("WARNING: Ignoring unexpected packet type: {} - stream may be misaligned", packet_type);
+                    warn!(
+                        target: LOG_TARGET,
+                        packet_type,
+                        "Ignoring unexpected packet type, stream may be misaligned"
+                    );
                 }
             }
         }
@@ -1413,21 +2674,56 @@ impl Client {
         Ok(())
     }
 
+    /// Whether the connection still works, checked with a ping
+    ///
+    /// Never reconnects, so a pool can evict a client that returns
+    /// `false`; that includes a client left dirty by an interrupted
+    /// operation. Set [`ConnectionOptions::recv_timeout`] to bound how long
+    /// the check waits on a connection that silently stopped responding.
+    pub async fn is_alive(&mut self) -> bool {
+        if self.query_in_progress {
+            return false;
+        }
+        match self.ping().await {
+            Ok(()) => true,
+            Err(e) => {
+                debug!("Health check ping failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Ping before a query, reconnecting if the idle connection was closed
+    async fn ping_or_reconnect(&mut self) -> Result<()> {
+        match self.ping().await {
+            Err(e) if !self.caller_stream && is_connection_error(&e) => {
+                debug!("Ping before query failed ({}), reconnecting", e);
+                self.reconnect().await
+            }
+            result => result,
+        }
+    }
+
     /// Ping the server
     pub async fn ping(&mut self) -> Result<()> {
-        debug!("Sending ping...");
+        self.recover_if_interrupted().await?;
+        self.query_in_progress = true;
+        self.last_activity = Instant::now();
+
+        trace!(target: LOG_TARGET, "Sending ping...");
         self.conn.write_varint(ClientCode::Ping as u64).await?;
         self.conn.flush().await?;
-        debug!("Ping sent, waiting for pong...");
+        trace!(target: LOG_TARGET, "Ping sent, waiting for pong...");
 
         let packet_type = self.conn.read_varint().await?;
-        debug!("Got response packet type: {}", packet_type);
+        trace!(target: LOG_TARGET, packet_type, "Got response packet type");
 
         if packet_type == ServerCode::Pong as u64 {
-            debug!("Pong received!");
+            trace!(target: LOG_TARGET, "Pong received!");
+            self.query_in_progress = false;
             Ok(())
         } else {
-            debug!("Unexpected packet: {}", packet_type);
+            trace!(target: LOG_TARGET, packet_type, "Unexpected packet");
             Err(Error::Protocol(format!("Expected Pong, got {}", packet_type)))
         }
     }
@@ -1435,17 +2731,360 @@ impl Client {
     /// Cancel the current query
     ///
     /// Sends a cancel packet to the server to stop any currently running
-    /// query. Note: This is most useful when called with a cancelable
-    /// callback, or when you need to cancel a long-running query from
-    /// outside the query execution flow.
+    /// query, then reads the rest of its response up to the end of stream
+    /// or the exception reporting the cancellation, so the connection is
+    /// ready for the next query. Queries stopped by a cancelable data
+    /// callback are cancelled this way automatically.
     pub async fn cancel(&mut self) -> Result<()> {
-        debug!("Sending cancel...");
+        trace!(target: LOG_TARGET, "Sending cancel...");
         self.conn.write_varint(ClientCode::Cancel as u64).await?;
         self.conn.flush().await?;
-        debug!("Cancel sent");
+        trace!(target: LOG_TARGET, "Cancel sent");
+        if self.query_in_progress {
+            self.drain_cancelled_query().await?;
+        }
         Ok(())
     }
 
+    /// Stop the query an interrupted operation left running on the server
+    ///
+    /// If a [`Cursor`] or stream was dropped between packets, the query is
+    /// cancelled and its response drained as by [`cancel`](Self::cancel),
+    /// leaving the connection ready for use. If a query future was dropped
+    /// midway, the stream cannot be resynchronized: a cancel packet is
+    /// sent, the connection is closed and the next operation reconnects.
+    /// Does nothing when no query is in flight.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// let slow = client.query("SELECT sleepEachRow(1) FROM numbers(60)");
+    /// if tokio::time::timeout(Duration::from_secs(5), slow).await.is_err() {
+    ///     client.abort_current().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn abort_current(&mut self) -> Result<()> {
+        if self.cancel_pending {
+            self.cancel_pending = false;
+            debug!("Cancelling query abandoned by a cursor");
+            return self.cancel().await;
+        }
+        if !self.query_in_progress {
+            return Ok(());
+        }
+        debug!("Aborting query interrupted midway");
+        let mut conn =
+            std::mem::replace(&mut self.conn, detached_connection());
+        conn.write_varint(ClientCode::Cancel as u64).await?;
+        conn.flush().await
+    }
+
+    /// Wait for a mutation (`ALTER TABLE ... UPDATE/DELETE`) to finish
+    ///
+    /// Polls `system.mutations` every `poll_interval` until the mutation
+    /// reports `is_done = 1`. `table` may be qualified as `db.table`;
+    /// otherwise the client's database is used.
+    ///
+    /// # Errors
+    ///
+    /// - `Error::Timeout` if the mutation is still running after `timeout`.
+    /// - `Error::Validation` if no such mutation exists.
+    /// - `Error::Protocol` if the server reports a failure reason for it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// client
+    ///     .wait_mutation(
+    ///         "events",
+    ///         "mutation_3.txt",
+    ///         Duration::from_millis(200),
+    ///         Duration::from_secs(60),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_mutation(
+        &mut self,
+        table: &str,
+        mutation_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<()> {
+        let (database, table) =
+            split_table_name(table, &self.options.database);
+        self.wait_table_mutation(
+            &database,
+            &table,
+            mutation_id,
+            poll_interval,
+            timeout,
+        )
+        .await
+    }
+
+    /// [`wait_mutation`](Self::wait_mutation) with the database and table
+    /// already split and unquoted
+    async fn wait_table_mutation(
+        &mut self,
+        database: &str,
+        table: &str,
+        mutation_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<()> {
+        let sql = format!(
+            "SELECT toUInt8(is_done), latest_fail_reason \
+             FROM system.mutations \
+             WHERE database = {} AND table = {} AND mutation_id = {}",
+            quote_string_literal(database),
+            quote_string_literal(table),
+            quote_string_literal(mutation_id)
+        );
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let result = self.query(sql.as_str()).await?;
+            let block = result
+                .blocks()
+                .iter()
+                .find(|b| b.row_count() > 0)
+                .ok_or_else(|| {
+                    Error::Validation(format!(
+                        "Mutation {} not found for table {}.{}",
+                        mutation_id, database, table
+                    ))
+                })?;
+
+            let is_done = block
+                .column(0)
+                .and_then(|c| {
+                    c.as_any().downcast_ref::<ColumnUInt8>().map(|c| c.at(0))
+                })
+                .ok_or_else(|| {
+                    Error::Protocol(
+                        "Unexpected is_done column in system.mutations"
+                            .to_string(),
+                    )
+                })?;
+            if is_done == 1 {
+                debug!("Mutation {} finished", mutation_id);
+                return Ok(());
+            }
+
+            let fail_reason = block
+                .column(1)
+                .and_then(|c| {
+                    c.as_any().downcast_ref::<ColumnString>().map(|c| c.at(0))
+                })
+                .unwrap_or_default();
+            if !fail_reason.is_empty() {
+                return Err(Error::Protocol(format!(
+                    "Mutation {} on {}.{} failed: {}",
+                    mutation_id, database, table, fail_reason
+                )));
+            }
+
+            if Instant::now() + poll_interval > deadline {
+                return Err(Error::Timeout(format!(
+                    "Mutation {} on {}.{} did not finish within {:?}",
+                    mutation_id, database, table, timeout
+                )));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Run an `ALTER TABLE` mutation and wait until it completes
+    ///
+    /// The statement runs with `mutations_sync = 2`, so the server itself
+    /// waits for the mutation on every replica, for at most `timeout`. The
+    /// ids of the created mutations are found by diffing `system.mutations`
+    /// for the table before and after the statement, and are checked with
+    /// [`Client::wait_mutation`]. A mutation another client started on the
+    /// same table in the meantime is included in the returned ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `stmt` is not an `ALTER TABLE`
+    /// statement or created no mutation.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// client
+    ///     .alter_and_wait(
+    ///         "ALTER TABLE events DELETE WHERE ts < now() - INTERVAL 30 DAY",
+    ///         Duration::from_millis(200),
+    ///         Duration::from_secs(60),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn alter_and_wait(
+        &mut self,
+        stmt: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Vec<String>> {
+        let (database, table) =
+            parse_alter_table_name(stmt).ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "Expected an ALTER TABLE statement, got: {}",
+                    stmt
+                ))
+            })?;
+        let database =
+            database.unwrap_or_else(|| self.options.database.clone());
+
+        let started = Instant::now();
+        let before = self.mutation_ids(&database, &table).await?;
+        let query = Query::new(stmt)
+            .with_setting("mutations_sync", "2")
+            .with_timeout(timeout);
+        self.execute(query).await?;
+        let created: Vec<String> = self
+            .mutation_ids(&database, &table)
+            .await?
+            .into_iter()
+            .filter(|id| !before.contains(id))
+            .collect();
+        if created.is_empty() {
+            return Err(Error::InvalidArgument(format!(
+                "Statement created no mutation on {}.{}: {}",
+                database, table, stmt
+            )));
+        }
+
+        let remaining = timeout.saturating_sub(started.elapsed());
+        for mutation_id in &created {
+            self.wait_table_mutation(
+                &database,
+                &table,
+                mutation_id,
+                poll_interval,
+                remaining,
+            )
+            .await?;
+        }
+        Ok(created)
+    }
+
+    /// All mutation ids currently recorded for a table
+    async fn mutation_ids(
+        &mut self,
+        database: &str,
+        table: &str,
+    ) -> Result<Vec<String>> {
+        self.query_column(format!(
+            "SELECT mutation_id FROM system.mutations \
+             WHERE database = {} AND table = {}",
+            quote_string_literal(database),
+            quote_string_literal(table)
+        ))
+        .await
+    }
+
+    /// List the tables of a database (`SHOW TABLES FROM db`)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// for table in client.show_tables("default").await? {
+    ///     println!("{}", table);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn show_tables(
+        &mut self,
+        database: &str,
+    ) -> Result<Vec<String>> {
+        self.query_column(format!(
+            "SHOW TABLES FROM {}",
+            quote_identifier(database)
+        ))
+        .await
+    }
+
+    /// List all databases (`SHOW DATABASES`)
+    pub async fn show_databases(&mut self) -> Result<Vec<String>> {
+        self.query_column("SHOW DATABASES").await
+    }
+
+    /// Get the `CREATE TABLE` statement of a table (`SHOW CREATE TABLE`)
+    ///
+    /// `table` may be qualified as `db.table`; otherwise the client's
+    /// database is used.
+    pub async fn show_create_table(&mut self, table: &str) -> Result<String> {
+        let (database, table) =
+            split_table_name(table, &self.options.database);
+        self.query_column(format!(
+            "SHOW CREATE TABLE {}.{}",
+            quote_identifier(&database),
+            quote_identifier(&table)
+        ))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            Error::Protocol(format!(
+                "SHOW CREATE TABLE returned no rows for {}.{}",
+                database, table
+            ))
+        })
+    }
+
+    /// Create a session-scoped temporary table
+    ///
+    /// `columns` lists `(name, type)` pairs. The table lives as long as this
+    /// connection and can be filled with [`Client::insert`] and queried by
+    /// its bare name. It is lost if the client reconnects.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use clickhouse_native_client::{Client, ClientOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut client = Client::connect(ClientOptions::default()).await?;
+    /// client
+    ///     .create_temporary_table("ids", &[("id", "UInt64")])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_temporary_table(
+        &mut self,
+        name: &str,
+        columns: &[(&str, &str)],
+    ) -> Result<()> {
+        let stmt = build_create_temporary_table(name, columns)?;
+        self.execute(stmt).await
+    }
+
+    /// Drop a temporary table created on this connection, if it exists
+    pub async fn drop_temporary_table(&mut self, name: &str) -> Result<()> {
+        self.execute(format!(
+            "DROP TEMPORARY TABLE IF EXISTS {}",
+            quote_identifier(name)
+        ))
+        .await
+    }
+
     /// Get server info
     ///
     /// Returns information about the connected ClickHouse server including
@@ -1470,6 +3109,16 @@ impl Client {
         &self.server_info
     }
 
+    /// Compression method actually used on this connection
+    ///
+    /// This is the configured [`ClientOptions::compression`] unless the
+    /// server is too old for compressed blocks, in which case the client
+    /// falls back to uncompressed transfer and returns `None`. `None` is
+    /// also returned when blocks are only compressed by a custom codec.
+    pub fn effective_compression(&self) -> Option<CompressionMethod> {
+        self.compression
+    }
+
     /// Get server version as a tuple (major, minor, patch)
     ///
     /// # Example
@@ -1509,63 +3158,3250 @@ impl Client {
     }
 }
 
-/// Result of a `SELECT` query, containing data blocks and progress
-/// information.
-pub struct QueryResult {
-    /// Result blocks
-    pub blocks: Vec<Block>,
-    /// Progress information
-    pub progress: Progress,
+/// Build the `INSERT` statement text for a target and a column list
+///
+/// `target` may be `table`, `table SETTINGS k=v`, or a full
+/// `INSERT INTO table SETTINGS k=v VALUES` statement, with any whitespace
+/// between the words. The column names are quoted, and the `SETTINGS`
+/// clause is placed between the column list and `VALUES`, as ClickHouse
+/// expects. A target that already lists its columns is rejected, since
+/// the list comes from the data.
+fn build_insert_query_text(target: &str, columns: &[&str]) -> Result<String> {
+    let words = word_ranges(target);
+    let is_word = |index: usize, keyword: &str| {
+        words.get(index).is_some_and(|range| {
+            target[range.clone()].eq_ignore_ascii_case(keyword)
+        })
+    };
+
+    let first = if is_word(0, "INSERT") && is_word(1, "INTO") { 2 } else { 0 };
+    let mut last = words.len();
+    // Only a separate `VALUES` keyword, not a table named `my_values`
+    if last > first + 1 && is_word(last - 1, "VALUES") {
+        last -= 1;
+    }
+    if first >= last {
+        return Err(Error::Validation(format!(
+            "INSERT target names no table: {:?}",
+            target
+        )));
+    }
+    let end = words[last - 1].end;
+    let (table, settings) =
+        match (first + 1..last).find(|&i| is_word(i, "SETTINGS")) {
+            Some(i) => (
+                target[words[first].start..words[i].start].trim_end(),
+                Some(&target[words[i].start..end]),
+            ),
+            None => (&target[words[first].start..end], None),
+        };
+
+    // The arguments of a table function are not a column list
+    let after_table = if is_word(first, "FUNCTION") {
+        skip_parenthesized(table)
+    } else {
+        table
+    };
+    if after_table.contains('(') {
+        return Err(Error::Validation(format!(
+            "INSERT target must not list columns, they are taken from the \
+             data: {:?}",
+            target
+        )));
+    }
+
+    // No column list inserts into every column of the table
+    let columns = if columns.is_empty() {
+        String::new()
+    } else {
+        let quoted: Vec<String> =
+            columns.iter().map(|name| quote_identifier(name)).collect();
+        format!(" ({})", quoted.join(", "))
+    };
+    Ok(match settings {
+        Some(settings) => {
+            format!("INSERT INTO {}{} {} VALUES", table, columns, settings)
+        }
+        None => format!("INSERT INTO {}{} VALUES", table, columns),
+    })
 }
 
-impl QueryResult {
-    /// Get all blocks
-    pub fn blocks(&self) -> &[Block] {
-        &self.blocks
+/// Byte ranges of the whitespace-separated words of `text`
+fn word_ranges(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(begin)) => {
+                words.push(begin..i);
+                start = None;
+            }
+            _ => {}
+        }
     }
+    if let Some(begin) = start {
+        words.push(begin..text.len());
+    }
+    words
+}
 
-    /// Get progress info
-    pub fn progress(&self) -> &Progress {
-        &self.progress
+/// The part of `text` after its first parenthesized group
+fn skip_parenthesized(text: &str) -> &str {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 1 => depth -= 1,
+            ')' if depth == 1 => return &text[i + 1..],
+            _ => {}
+        }
     }
+    ""
+}
 
-    /// Get total number of rows across all blocks
-    pub fn total_rows(&self) -> usize {
-        self.blocks.iter().map(|b| b.row_count()).sum()
+/// Build `INSERT INTO t (...) SELECT ... FROM input(...)` for
+/// [`Client::insert_casting`]
+fn build_insert_casting_text(
+    table_name: &str,
+    block: &Block,
+    casts: &HashMap<String, String>,
+) -> Result<String> {
+    if block.column_count() == 0 {
+        return Err(Error::Protocol("Block has no columns".to_string()));
+    }
+    if let Some(unknown) = casts
+        .keys()
+        .find(|name| !block.iter().any(|(col, _, _)| col == name.as_str()))
+    {
+        return Err(Error::Validation(format!(
+            "Cannot cast column {}: not in block",
+            unknown
+        )));
+    }
+
+    let mut columns = Vec::new();
+    let mut exprs = Vec::new();
+    let mut structure = Vec::new();
+    for (name, type_, _) in block.iter() {
+        let ident = quote_identifier(name);
+        exprs.push(match casts.get(name) {
+            Some(target) => {
+                format!("CAST({}, {})", ident, quote_string_literal(target))
+            }
+            None => ident.clone(),
+        });
+        structure.push(format!("{} {}", ident, type_.name()));
+        columns.push(ident);
     }
+
+    Ok(format!(
+        "INSERT INTO {} ({}) SELECT {} FROM input({})",
+        table_name.trim(),
+        columns.join(", "),
+        exprs.join(", "),
+        quote_string_literal(&structure.join(", "))
+    ))
 }
 
-#[cfg(test)]
-#[cfg_attr(coverage_nightly, coverage(off))]
-mod tests {
-    use super::*;
+/// Copy the named columns of `block`, in the given order, for
+/// [`Client::insert_partial`]
+fn select_block_columns(block: &Block, names: &[&str]) -> Result<Block> {
+    if names.is_empty() {
+        return Err(Error::Validation(
+            "No columns selected for insert".to_string(),
+        ));
+    }
 
-    #[test]
-    fn test_client_options_default() {
-        let opts = ClientOptions::default();
-        assert_eq!(opts.host, "localhost");
-        assert_eq!(opts.port, 9000);
-        assert_eq!(opts.database, "default");
+    let mut selected = Block::new();
+    for &name in names {
+        let column = block.column_by_name(name).ok_or_else(|| {
+            Error::Validation(format!(
+                "Cannot insert column {}: not in block",
+                name
+            ))
+        })?;
+        selected.append_column(name, column)?;
     }
+    selected.set_info(block.info().clone());
+    Ok(selected)
+}
 
-    #[test]
-    fn test_client_options_builder() {
-        let opts = ClientOptions::new("127.0.0.1", 9000)
-            .database("test_db")
-            .user("test_user")
-            .password("test_pass");
+/// Progress, profile and log data of a response, collected by
+/// [`Client::read_side_packet`]
+#[derive(Default)]
+struct ResponseInfo {
+    /// Merged over every Progress packet
+    progress: Progress,
+    /// Last ProfileInfo packet
+    profile: Option<Profile>,
+    profile_events: Vec<ProfileEvent>,
+    /// Server logs, kept only when the query collects them
+    logs: Vec<LogEntry>,
+    /// Column description of the last TableColumns packet
+    columns: Option<String>,
+}
 
-        assert_eq!(opts.host, "127.0.0.1");
-        assert_eq!(opts.database, "test_db");
-        assert_eq!(opts.user, "test_user");
-        assert_eq!(opts.password, "test_pass");
+/// What the server expects as the data of an INSERT
+struct InsertTarget {
+    /// Empty block describing the target columns
+    header: Block,
+    /// Target columns with a default, when the server described them in a
+    /// `TableColumns` packet
+    defaulted: Option<HashSet<String>>,
+}
+
+/// Names of the columns that have a `DEFAULT`-like expression in a table
+/// description as sent in a `TableColumns` packet
+///
+/// The description lists one column per line as a backquoted name and a
+/// type, followed by a tab-separated default kind and expression if the
+/// column has one.
+fn parse_defaulted_columns(metadata: &str) -> HashSet<String> {
+    let mut defaulted = HashSet::new();
+    for line in metadata.lines() {
+        let Some(rest) = line.strip_prefix('`') else {
+            continue;
+        };
+        let mut name = String::new();
+        let mut chars = rest.chars();
+        let mut closed = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => name.extend(chars.next()),
+                '`' => {
+                    closed = true;
+                    break;
+                }
+                c => name.push(c),
+            }
+        }
+        let has_default =
+            chars.as_str().split('\t').nth(1).is_some_and(|kind| {
+                matches!(
+                    kind,
+                    "DEFAULT" | "MATERIALIZED" | "ALIAS" | "EPHEMERAL"
+                )
+            });
+        if closed && has_default {
+            defaulted.insert(name);
+        }
     }
+    defaulted
+}
 
-    #[test]
-    fn test_query_result() {
-        let result =
-            QueryResult { blocks: vec![], progress: Progress::default() };
+/// Check the column names of `block` against the INSERT `header` the
+/// server sent, so a block that does not line up with the target fails
+/// before any data is written
+///
+/// A target column missing from `block` is an error unless
+/// `allow_defaults` is set, see [`ClientOptions::insert_allow_defaults`];
+/// then it is only reported when `defaulted` is known and does not contain
+/// it.
+fn check_insert_columns(
+    header: &Block,
+    defaulted: Option<&HashSet<String>>,
+    block: &Block,
+    allow_defaults: bool,
+) -> Result<()> {
+    if header.column_count() == 0 {
+        return Ok(());
+    }
+    let expected: Vec<&str> = header.iter().map(|(name, _, _)| name).collect();
+    for (name, _, _) in block.iter() {
+        if !expected.contains(&name) {
+            return Err(Error::Validation(format!(
+                "Column {} is not a column of the INSERT target (expected: \
+                 {})",
+                name,
+                expected.join(", ")
+            )));
+        }
+    }
+    for name in expected {
+        if block.column_by_name(name).is_some() {
+            continue;
+        }
+        if !allow_defaults {
+            return Err(Error::Validation(format!(
+                "Column {} of the INSERT target is missing from the block; \
+                 enable ClientOptions::insert_allow_defaults to let the \
+                 server fill columns that have a default",
+                name
+            )));
+        }
+        if defaulted.is_some_and(|defaulted| !defaulted.contains(name)) {
+            return Err(Error::Validation(format!(
+                "Column {} of the INSERT target is missing from the block \
+                 and has no default",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
 
-        assert_eq!(result.total_rows(), 0);
+/// Check the columns of `block` against the INSERT `header` the server
+/// sent, so a wrong column type fails before any data is written
+///
+/// Columns are matched by name. Types the server converts on its own (see
+/// [`insert_type_accepts`]) pass.
+fn check_insert_types(header: &Block, block: &Block) -> Result<()> {
+    for (name, actual, _) in block.iter() {
+        let Some((_, expected, _)) =
+            header.iter().find(|(header_name, _, _)| *header_name == name)
+        else {
+            continue;
+        };
+        if !insert_type_accepts(expected, actual) {
+            return Err(Error::TypeMismatch {
+                expected: format!("{} for column {}", expected.name(), name),
+                actual: actual.name(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Whether the server accepts a block column of type `actual` for a table
+/// column of type `expected`
+///
+/// Besides equal types this allows the conversions native inserts do
+/// server-side: `LowCardinality` on either side, `UInt8` for `Bool`, raw
+/// codes or differently listed items for `Enum8`/`Enum16`, any time zone
+/// for `DateTime`/`DateTime64`, and `T` for `Nullable(T)`.
+fn insert_type_accepts(expected: &Type, actual: &Type) -> bool {
+    use crate::types::TypeCode;
+
+    match (expected, actual) {
+        _ if expected == actual => true,
+        (Type::LowCardinality { nested_type }, _) => {
+            insert_type_accepts(nested_type, actual)
+        }
+        (_, Type::LowCardinality { nested_type }) => {
+            insert_type_accepts(expected, nested_type)
+        }
+        (
+            Type::Simple(TypeCode::Bool | TypeCode::UInt8),
+            Type::Simple(TypeCode::Bool | TypeCode::UInt8),
+        ) => true,
+        (
+            Type::Enum8 { .. } | Type::Simple(TypeCode::Int8),
+            Type::Enum8 { .. } | Type::Simple(TypeCode::Int8),
+        ) => true,
+        (
+            Type::Enum16 { .. } | Type::Simple(TypeCode::Int16),
+            Type::Enum16 { .. } | Type::Simple(TypeCode::Int16),
+        ) => true,
+        (Type::DateTime { .. }, Type::DateTime { .. }) => true,
+        (
+            Type::DateTime64 { precision: a, .. },
+            Type::DateTime64 { precision: b, .. },
+        ) => a == b,
+        (
+            Type::Nullable { nested_type: a },
+            Type::Nullable { nested_type: b },
+        ) => insert_type_accepts(a, b),
+        (Type::Nullable { nested_type }, _) => {
+            insert_type_accepts(nested_type, actual)
+        }
+        (Type::Array { item_type: a }, Type::Array { item_type: b }) => {
+            insert_type_accepts(a, b)
+        }
+        (Type::Tuple { item_types: a }, Type::Tuple { item_types: b }) => {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| insert_type_accepts(a, b))
+        }
+        (
+            Type::Map { key_type: ka, value_type: va },
+            Type::Map { key_type: kb, value_type: vb },
+        ) => insert_type_accepts(ka, kb) && insert_type_accepts(va, vb),
+        _ => false,
+    }
+}
+
+/// Quoted column list of an INSERT generated for `block`, in block order
+/// (matches the C++ implementation)
+fn insert_column_names(block: &Block) -> Result<Vec<&str>> {
+    let col_names: Vec<&str> = (0..block.column_count())
+        .filter_map(|i| block.column_name(i))
+        .collect();
+
+    if col_names.is_empty() {
+        return Err(Error::Protocol("Block has no columns".to_string()));
+    }
+    Ok(col_names)
+}
+
+/// Oldest server revision the client exchanges compressed blocks with
+const DBMS_MIN_REVISION_WITH_BLOCK_COMPRESSION: u64 = 54058;
+
+/// Whether blocks on a connection to a server at `revision` should be
+/// compressed, given the method the client was configured with
+///
+/// Falls back to uncompressed transfer for servers older than
+/// [`DBMS_MIN_REVISION_WITH_BLOCK_COMPRESSION`], so the query packet never
+/// announces frames the server cannot decode.
+fn negotiate_compression(options: &ClientOptions, revision: u64) -> bool {
+    let configured = options.is_compressed();
+    if configured && revision < DBMS_MIN_REVISION_WITH_BLOCK_COMPRESSION {
+        warn!(
+            target: LOG_TARGET,
+            "Server revision {} predates block compression (requires {}), \
+             sending blocks uncompressed",
+            revision,
+            DBMS_MIN_REVISION_WITH_BLOCK_COMPRESSION
+        );
+        return false;
+    }
+    configured
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        if !self.query_in_progress {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        // Best effort: the connection may be closed or stuck midway
+        // through a packet, in which case the server drops it instead
+        let mut conn =
+            std::mem::replace(&mut self.conn, detached_connection());
+        runtime.spawn(async move {
+            let cancel = async {
+                conn.write_varint(ClientCode::Cancel as u64).await?;
+                conn.flush().await
+            };
+            match tokio::time::timeout(CANCEL_DRAIN_TIMEOUT, cancel).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    debug!("Cancelling query of dropped client failed: {}", e)
+                }
+                Err(_) => {
+                    debug!("Cancelling query of dropped client timed out")
+                }
+            }
+        });
+    }
+}
+
+/// Stand-in for a connection handed off or closed: reads end immediately
+/// and writes go nowhere
+fn detached_connection() -> Connection {
+    Connection::from_stream(tokio::io::empty())
+}
+
+/// How long a timed-out query may take to stop after it is cancelled
+const CANCEL_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Finish reading a response packet, giving up once `deadline` passes
+///
+/// Expiring here stops in the middle of a packet, where the stream can no
+/// longer be drained. The query stays marked as in progress, so the
+/// connection counts as dead and is reopened before the next operation.
+async fn read_within<T>(
+    deadline: Option<Instant>,
+    read: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(deadline) = deadline else {
+        return read.await;
+    };
+    tokio::time::timeout_at(deadline.into(), read).await.unwrap_or_else(|_| {
+        Err(Error::Timeout(
+            "query timed out in the middle of a response packet".to_string(),
+        ))
+    })
+}
+
+/// Version of the task exchange sent in a `ReadTaskResponse`
+const DBMS_CLUSTER_PROCESSING_PROTOCOL_VERSION: u64 = 1;
+
+/// Whether `code` is a server request for distributed read tasks, see
+/// [`Client::answer_read_task_request`]
+fn is_read_task_request(code: u64) -> bool {
+    code == ServerCode::ReadTaskRequest as u64
+        || code == ServerCode::MergeTreeAllRangesAnnouncement as u64
+        || code == ServerCode::MergeTreeReadTaskRequest as u64
+}
+
+/// Whether `name` is a setting that only applies to text output formats,
+/// which the native protocol never uses
+fn is_output_format_setting(name: &str) -> bool {
+    const PREFIXES: [&str; 5] = [
+        "output_format_json",
+        "output_format_csv",
+        "output_format_tsv",
+        "output_format_pretty",
+        "output_format_xml",
+    ];
+
+    name == "format"
+        || name == "default_format"
+        || PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Whether `err` means the connection itself failed, rather than the
+/// server rejecting the request
+fn is_connection_error(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::Io(_) | Error::Connection(_) | Error::ServerClosedConnection(_)
+    )
+}
+
+/// Whether `err` is worth retrying for an idempotent query: the
+/// connection failed, the query timed out, or the server reported a
+/// transient condition
+fn is_retryable_error(err: &Error) -> bool {
+    // TIMEOUT_EXCEEDED, TOO_MANY_SIMULTANEOUS_QUERIES, SOCKET_TIMEOUT,
+    // NETWORK_ERROR
+    const RETRYABLE_SERVER_CODES: [i32; 4] = [159, 202, 209, 210];
+
+    match err {
+        Error::Timeout(_) => true,
+        Error::Server(exception) => {
+            RETRYABLE_SERVER_CODES.contains(&exception.code)
+        }
+        _ => is_connection_error(err),
+    }
+}
+
+/// Whether `text` is a statement that cannot modify data, judged by its
+/// first keyword after leading comments and parentheses
+fn is_read_only_query(text: &str) -> bool {
+    let mut rest = text;
+    loop {
+        rest =
+            rest.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, tail)| tail);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            match comment.split_once("*/") {
+                Some((_, tail)) => rest = tail,
+                None => return false,
+            }
+        } else {
+            break;
+        }
+    }
+
+    let keyword: String =
+        rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    ["SELECT", "WITH", "SHOW", "DESCRIBE", "DESC", "EXISTS", "EXPLAIN"]
+        .iter()
+        .any(|read| keyword.eq_ignore_ascii_case(read))
+}
+
+/// Split `db.table` into its parts, falling back to `default_db`
+///
+/// Backticks and double quotes around either part are removed.
+fn split_table_name(name: &str, default_db: &str) -> (String, String) {
+    let unquote =
+        |s: &str| s.trim().trim_matches(|c| c == '`' || c == '"').to_string();
+    match name.split_once('.') {
+        Some((db, table)) => (unquote(db), unquote(table)),
+        None => (default_db.to_string(), unquote(name)),
+    }
+}
+
+/// Build the `CREATE TEMPORARY TABLE` statement for
+/// [`Client::create_temporary_table`]
+fn build_create_temporary_table(
+    name: &str,
+    columns: &[(&str, &str)],
+) -> Result<String> {
+    if columns.is_empty() {
+        return Err(Error::Validation(format!(
+            "Temporary table {} needs at least one column",
+            name
+        )));
+    }
+
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|(col, type_)| format!("{} {}", quote_identifier(col), type_))
+        .collect();
+    Ok(format!(
+        "CREATE TEMPORARY TABLE {} ({})",
+        quote_identifier(name),
+        columns.join(", ")
+    ))
+}
+
+/// Extract the unquoted database (if given) and table name from an
+/// `ALTER TABLE [db.]table ...` statement
+fn parse_alter_table_name(stmt: &str) -> Option<(Option<String>, String)> {
+    let rest = strip_keyword(stmt, "ALTER")?;
+    let rest = strip_keyword(rest, "TABLE")?;
+    let (first, rest) = parse_identifier(rest.trim_start())?;
+    match rest.strip_prefix('.') {
+        Some(rest) => {
+            let (table, _) = parse_identifier(rest)?;
+            Some((Some(first), table))
+        }
+        None => Some((None, first)),
+    }
+}
+
+/// The text after `keyword` and the whitespace following it
+fn strip_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let text = text.trim_start();
+    let rest = text.get(keyword.len()..)?;
+    (text[..keyword.len()].eq_ignore_ascii_case(keyword)
+        && rest.starts_with(char::is_whitespace))
+    .then_some(rest)
+}
+
+/// Parse the identifier at the start of `text`, returning it unquoted
+/// along with the text after it
+///
+/// Backticked and double-quoted identifiers may contain any character,
+/// with the quote escaped by doubling it or by a backslash.
+fn parse_identifier(text: &str) -> Option<(String, &str)> {
+    let quote = text.chars().next()?;
+    if quote != '`' && quote != '"' {
+        let end = text
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(text.len());
+        return (end > 0).then(|| (text[..end].to_string(), &text[end..]));
+    }
+
+    let mut name = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            name.push(chars.next()?.1);
+        } else if c != quote {
+            name.push(c);
+        } else if chars.next_if(|&(_, next)| next == quote).is_some() {
+            name.push(quote);
+        } else {
+            return Some((name, &text[i + 1..]));
+        }
+    }
+    None
+}
+
+/// A [`Client`] shared behind an async mutex
+///
+/// Every client operation takes `&mut self`, so a plain client cannot be
+/// pinged while a service holds it idle. A shared client can: when
+/// [`ClientOptions::keepalive_interval`] is set, a background task pings
+/// the server whenever the connection has been idle for that long, so an
+/// idle connection is not dropped by the server's idle timeout. The task
+/// stops when the shared client is dropped.
+///
+/// # Example
+/// ```no_run
+/// # use clickhouse_native_client::{Client, ClientOptions, SharedClient};
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let options =
+///     ClientOptions::default().keepalive_interval(Duration::from_secs(30));
+/// let client = SharedClient::new(Client::connect(options).await?);
+/// let result = client.lock().await.query("SELECT 1").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SharedClient {
+    client: Arc<tokio::sync::Mutex<Client>>,
+    keepalive: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl SharedClient {
+    /// Share `client`, starting its keepalive task if configured
+    ///
+    /// # Panics
+    ///
+    /// Panics if a keepalive interval is set and this is called outside a
+    /// Tokio runtime.
+    pub fn new(client: Client) -> Self {
+        let interval = client.options.keepalive_interval;
+        let client = Arc::new(tokio::sync::Mutex::new(client));
+        let keepalive = interval.map(|interval| {
+            tokio::spawn(keepalive(Arc::downgrade(&client), interval))
+        });
+        Self { client, keepalive }
+    }
+
+    /// Wait for exclusive access to the client
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, Client> {
+        self.client.lock().await
+    }
+}
+
+impl Drop for SharedClient {
+    fn drop(&mut self) {
+        if let Some(task) = self.keepalive.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Ping the client behind `client` whenever it has been idle for
+/// `interval`, until it is dropped
+async fn keepalive(
+    client: std::sync::Weak<tokio::sync::Mutex<Client>>,
+    interval: Duration,
+) {
+    let mut wait = interval;
+    loop {
+        tokio::time::sleep(wait).await;
+        let Some(client) = client.upgrade() else {
+            return;
+        };
+        // A held lock means the client is in use and needs no ping
+        let Ok(mut client) = client.try_lock() else {
+            wait = interval;
+            continue;
+        };
+        let idle = client.last_activity.elapsed();
+        if idle < interval {
+            wait = interval - idle;
+            continue;
+        }
+        wait = interval;
+        if client.is_dirty() {
+            continue;
+        }
+        trace!(target: LOG_TARGET, "Sending keepalive ping");
+        if let Err(e) = client.ping().await {
+            // The connection stays marked in progress, so the next
+            // operation reconnects
+            debug!("Keepalive ping failed: {}", e);
+        }
+    }
+}
+
+/// Result of a `SELECT` query, containing data blocks and progress
+/// information.
+pub struct QueryResult {
+    /// Result blocks
+    pub blocks: Vec<Block>,
+    /// The `WITH TOTALS` row, sent apart from the result blocks
+    pub totals: Option<Block>,
+    /// Minimum and maximum rows of the result, with the `extremes`
+    /// setting enabled
+    pub extremes: Option<Block>,
+    /// Progress information
+    pub progress: Progress,
+    /// The last ProfileInfo packet, e.g. for
+    /// [`Profile::rows_before_limit`] of a query with `LIMIT`
+    pub profile: Option<Profile>,
+    /// Rows of every ProfileEvents packet, in the order received
+    pub profile_events: Vec<ProfileEvent>,
+    /// Server log lines, if the query was built with
+    /// [`Query::collect_logs`]
+    pub logs: Vec<LogEntry>,
+}
+
+impl QueryResult {
+    /// Get all blocks
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// Get progress info
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Get the profile info the server sent last, if any
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    /// Query-wide value of the profile event `name`, e.g. `SelectedRows`
+    /// or `ReadCompressedBytes`
+    ///
+    /// Increments reported for the whole query (thread `0`) are summed,
+    /// while a gauge keeps its last reported level. Returns `None` if the
+    /// server did not report the event.
+    pub fn profile_event(&self, name: &str) -> Option<i64> {
+        self.profile_events
+            .iter()
+            .filter(|event| event.thread_id == 0 && event.name == name)
+            .fold(None, |total, event| match event.kind {
+                ProfileEventKind::Increment => {
+                    Some(total.unwrap_or(0) + event.value)
+                }
+                ProfileEventKind::Gauge => Some(event.value),
+            })
+    }
+
+    /// Get total number of rows across all blocks
+    pub fn total_rows(&self) -> usize {
+        self.blocks.iter().map(|b| b.row_count()).sum()
+    }
+
+    /// Whether the query returned no rows
+    pub fn is_empty(&self) -> bool {
+        self.total_rows() == 0
+    }
+
+    /// Get the first block that contains rows
+    ///
+    /// The header-only blocks the server sends before the data are skipped.
+    pub fn first_block(&self) -> Option<&Block> {
+        self.blocks.iter().find(|b| b.row_count() > 0)
+    }
+
+    /// Get the values of the first row, one per column
+    ///
+    /// Returns `Ok(None)` for an empty result.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a column type cannot be converted to a [`ColumnValue`]
+    /// (for example arrays or tuples).
+    pub fn first_row(&self) -> Result<Option<Vec<ColumnValue>>> {
+        let Some(block) = self.first_block() else {
+            return Ok(None);
+        };
+        block_row(block, 0).map(Some)
+    }
+
+    /// Write the rows of every block in the `RowBinaryWithNamesAndTypes`
+    /// format
+    ///
+    /// See [`row_binary`](crate::io::row_binary) for the format and the
+    /// matching reader.
+    pub fn write_row_binary<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> Result<()> {
+        crate::io::row_binary::write_row_binary(&self.blocks, writer)
+    }
+}
+
+/// Values of one row of a block, one per column
+fn block_row(block: &Block, row: usize) -> Result<Vec<ColumnValue>> {
+    (0..block.column_count())
+        .map(|i| {
+            let column = block.column(i).ok_or_else(|| {
+                Error::Protocol(format!("Missing column {}", i))
+            })?;
+            get_column_item(column.as_ref(), row)
+        })
+        .collect()
+}
+
+/// Pull-based reader over the result of [`Client::query_cursor`]
+///
+/// Each call to [`next_block`](Self::next_block) or
+/// [`next_row`](Self::next_row) reads only as many packets as needed.
+///
+/// If the cursor is dropped before the result is exhausted, the client
+/// sends `Cancel` and discards the rest of the response before its next
+/// operation. If a `next_*` future was itself dropped midway, the
+/// connection is re-established instead.
+pub struct Cursor<'a> {
+    client: &'a mut Client,
+    query: Query,
+    /// When the query's timeout runs out, if it has one
+    deadline: Option<Instant>,
+    info: ResponseInfo,
+    /// Block currently walked by `next_row`
+    block: Option<Block>,
+    /// Next row of `block` to return
+    row: usize,
+    /// End of stream or an error was reached
+    finished: bool,
+    /// Set while a read is in flight, so a dropped future is detected
+    reading: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(client: &'a mut Client, query: Query) -> Self {
+        Self {
+            client,
+            deadline: query.timeout().map(|timeout| Instant::now() + timeout),
+            query,
+            info: ResponseInfo::default(),
+            block: None,
+            row: 0,
+            finished: false,
+            reading: false,
+        }
+    }
+
+    /// Read the next non-empty block of the result
+    ///
+    /// Returns `Ok(None)` once the result is exhausted. Rows of a block
+    /// partially walked with [`next_row`](Self::next_row) are skipped.
+    pub async fn next_block(&mut self) -> Result<Option<Block>> {
+        self.block = None;
+        if self.finished {
+            return Ok(None);
+        }
+
+        self.reading = true;
+        let result = self
+            .client
+            .next_result_block(&self.query, self.deadline, &mut self.info)
+            .await;
+        self.reading = false;
+
+        if !matches!(result, Ok(Some(_))) {
+            self.finished = true;
+        }
+        result
+    }
+
+    /// Read the values of the next row, one per column
+    ///
+    /// Returns `Ok(None)` once the result is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a column type cannot be converted to a [`ColumnValue`]
+    /// (for example arrays or tuples), or if reading from the server fails.
+    pub async fn next_row(&mut self) -> Result<Option<Vec<ColumnValue>>> {
+        loop {
+            if let Some(block) = &self.block {
+                if self.row < block.row_count() {
+                    let values = block_row(block, self.row)?;
+                    self.row += 1;
+                    return Ok(Some(values));
+                }
+            }
+
+            match self.next_block().await? {
+                Some(block) => {
+                    self.block = Some(block);
+                    self.row = 0;
+                }
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Progress accumulated from the packets read so far
+    pub fn progress(&self) -> &Progress {
+        &self.info.progress
+    }
+
+    /// Whether the whole result has been read
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// In-flight read of a [`BlockStream`], owning the cursor until it is done
+type PendingBlock<'a> = Pin<
+    Box<dyn Future<Output = (Cursor<'a>, Result<Option<Block>>)> + Send + 'a>,
+>;
+
+/// [`Stream`] of result blocks returned by [`Client::query_stream`]
+pub struct BlockStream<'a> {
+    cursor: Option<Cursor<'a>>,
+    pending: Option<PendingBlock<'a>>,
+}
+
+impl BlockStream<'_> {
+    /// Progress accumulated from the packets read so far
+    ///
+    /// Returns `None` while a block is being read.
+    pub fn progress(&self) -> Option<&Progress> {
+        self.cursor.as_ref().map(Cursor::progress)
+    }
+}
+
+impl<'a> Stream for BlockStream<'a> {
+    type Item = Result<Block>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.pending.is_none() {
+            let Some(mut cursor) = this.cursor.take() else {
+                return Poll::Ready(None);
+            };
+            if cursor.is_finished() {
+                this.cursor = Some(cursor);
+                return Poll::Ready(None);
+            }
+            this.pending = Some(Box::pin(async move {
+                let result = cursor.next_block().await;
+                (cursor, result)
+            }));
+        }
+
+        let pending = this.pending.as_mut().expect("pending read");
+        let (cursor, result) = ready!(pending.as_mut().poll(cx));
+        this.pending = None;
+        this.cursor = Some(cursor);
+        Poll::Ready(result.transpose())
+    }
+}
+
+/// [`Stream`] of result blocks returned by
+/// [`Client::query_stream_buffered`], filled by a background reader
+pub struct BufferedBlockStream {
+    receiver: tokio::sync::mpsc::Receiver<Result<Block>>,
+    reader: tokio::task::JoinHandle<Client>,
+}
+
+impl BufferedBlockStream {
+    /// Stop reading and get the client back
+    ///
+    /// If the result was not read to the end, the query is cancelled and
+    /// its remaining blocks are discarded before the client's next
+    /// operation.
+    pub async fn into_client(self) -> Result<Client> {
+        drop(self.receiver);
+        self.reader.await.map_err(|e| {
+            Error::Connection(format!("Block reader task failed: {}", e))
+        })
+    }
+}
+
+impl Stream for BufferedBlockStream {
+    type Item = Result<Block>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for Cursor<'_> {
+    fn drop(&mut self) {
+        if !self.finished && !self.reading {
+            self.client.cancel_pending = true;
+        }
+    }
+}
+
+/// INSERT started by [`Client::begin_insert`], accepting blocks one at a
+/// time
+///
+/// Dropping the session without calling [`finish`](Self::finish) abandons
+/// the INSERT: nothing is committed and the client reconnects before its
+/// next operation.
+pub struct InsertSession<'a> {
+    client: &'a mut Client,
+    query: Query,
+    /// Empty block describing the target columns, sent by the server
+    header: Block,
+    /// Target columns with a default, when the server described them
+    defaulted: Option<HashSet<String>>,
+    /// Blocks written so far; once non-zero the INSERT is never replayed
+    blocks_written: usize,
+}
+
+impl InsertSession<'_> {
+    /// Send a block of rows
+    ///
+    /// A connection error before any block was written reconnects, sends
+    /// the INSERT again and retries the block once. Later connection
+    /// errors are returned as they are, as the server may have received
+    /// earlier blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` without sending anything if `block` has
+    /// a column that is not in the [`header`](Self::header) or lacks one
+    /// the server cannot fill with a default, and `Error::TypeMismatch` if
+    /// a column type of `block` does not fit the header.
+    pub async fn write(&mut self, block: &Block) -> Result<()> {
+        check_insert_columns(
+            &self.header,
+            self.defaulted.as_ref(),
+            block,
+            self.client.options.insert_allow_defaults,
+        )?;
+        check_insert_types(&self.header, block)?;
+        match self.client.write_insert_block(block).await {
+            Ok(()) => {}
+            Err(e)
+                if self.blocks_written == 0
+                    && !self.client.caller_stream
+                    && is_connection_error(&e) =>
+            {
+                debug!(
+                    "INSERT connection lost before any data was sent ({}), \
+                     reconnecting",
+                    e
+                );
+                // The exchange is still marked in progress, so sending
+                // the query again reconnects first
+                let target = self.client.start_insert(&self.query).await?;
+                self.header = target.header;
+                self.defaulted = target.defaulted;
+                self.client.write_insert_block(block).await?;
+            }
+            Err(e) => return Err(e),
+        }
+        self.blocks_written += 1;
+        Ok(())
+    }
+
+    /// End the INSERT and wait until the server has committed it
+    pub async fn finish(self) -> Result<()> {
+        self.client.finish_insert(&self.query).await
+    }
+
+    /// Empty block with the names and types of the target columns, as
+    /// reported by the server
+    pub fn header(&self) -> &Block {
+        &self.header
+    }
+
+    /// Names and types of the target columns, in the order written
+    /// blocks must carry them
+    pub fn schema(&self) -> Vec<(String, Type)> {
+        self.header
+            .iter()
+            .map(|(name, type_, _)| (name.to_string(), type_.clone()))
+            .collect()
+    }
+
+    /// Number of blocks written so far
+    pub fn blocks_written(&self) -> usize {
+        self.blocks_written
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_client_options_default() {
+        let opts = ClientOptions::default();
+        assert_eq!(opts.host, "localhost");
+        assert_eq!(opts.port, 9000);
+        assert_eq!(opts.database, "default");
+    }
+
+    #[test]
+    fn test_client_options_builder() {
+        let opts = ClientOptions::new("127.0.0.1", 9000)
+            .database("test_db")
+            .user("test_user")
+            .password("test_pass");
+
+        assert_eq!(opts.host, "127.0.0.1");
+        assert_eq!(opts.database, "test_db");
+        assert_eq!(opts.user, "test_user");
+        assert_eq!(opts.password, "test_pass");
+    }
+
+    #[test]
+    fn test_client_options_custom_compression() {
+        use crate::compression::Lz4Compressor;
+
+        let opts = ClientOptions::default().compression(None);
+        assert!(!opts.is_compressed());
+
+        let opts = opts.custom_compression(Arc::new(Lz4Compressor));
+        assert!(opts.is_compressed());
+        assert_eq!(
+            opts.custom_compression.as_ref().map(|c| c.method_byte()),
+            Some(0x82)
+        );
+    }
+
+    #[test]
+    fn test_client_options_tcp_keepalive() {
+        let opts = ClientOptions::default();
+        assert!(!opts.connection_options.tcp_keepalive);
+
+        let opts = opts.tcp_keepalive(Duration::from_secs(30));
+        assert!(opts.connection_options.tcp_keepalive);
+        assert_eq!(
+            opts.connection_options.tcp_keepalive_idle,
+            Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn test_client_options_client_info_overrides() {
+        let opts =
+            ClientOptions::default().os_user("etl").client_hostname("box-7");
+        assert_eq!(opts.client_info.os_user, "etl");
+        assert_eq!(opts.client_info.client_hostname, "box-7");
+        assert_ne!(ClientOptions::default().client_info.client_hostname, "");
+    }
+
+    #[test]
+    fn test_build_create_temporary_table() {
+        assert_eq!(
+            build_create_temporary_table(
+                "tmp",
+                &[("id", "UInt64"), ("name", "String")]
+            )
+            .unwrap(),
+            "CREATE TEMPORARY TABLE `tmp` (`id` UInt64, `name` String)"
+        );
+        assert!(matches!(
+            build_create_temporary_table("tmp", &[]),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_insert_casting_text() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let mut block = Block::new();
+        block
+            .append_column(
+                "id",
+                Arc::new(ColumnString::new(crate::types::Type::string())),
+            )
+            .unwrap();
+        block.append_column("n", Arc::new(ColumnUInt64::new())).unwrap();
+
+        let casts = HashMap::from([("id".to_string(), "Int32".to_string())]);
+        assert_eq!(
+            build_insert_casting_text("db.t", &block, &casts).unwrap(),
+            "INSERT INTO db.t (`id`, `n`) SELECT CAST(`id`, 'Int32'), `n` \
+             FROM input('`id` String, `n` UInt64')"
+        );
+
+        let casts = HashMap::from([("nope".to_string(), "Int32".to_string())]);
+        assert!(matches!(
+            build_insert_casting_text("t", &block, &casts),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_block_columns() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(ColumnUInt64::new())).unwrap();
+        block
+            .append_column(
+                "name",
+                Arc::new(ColumnString::new(crate::types::Type::string())),
+            )
+            .unwrap();
+
+        let selected = select_block_columns(&block, &["name", "id"]).unwrap();
+        assert_eq!(selected.column_count(), 2);
+        assert_eq!(selected.column_name(0), Some("name"));
+        assert_eq!(selected.column_name(1), Some("id"));
+
+        let selected = select_block_columns(&block, &["id"]).unwrap();
+        assert_eq!(selected.column_count(), 1);
+
+        assert!(matches!(
+            select_block_columns(&block, &["created_at"]),
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            select_block_columns(&block, &[]),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_insert_query_text() {
+        let cols = ["id", "name"];
+
+        assert_eq!(
+            build_insert_query_text("db.t", &cols).unwrap(),
+            "INSERT INTO db.t (`id`, `name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t SETTINGS async_insert=1", &cols)
+                .unwrap(),
+            "INSERT INTO t (`id`, `name`) SETTINGS async_insert=1 VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text(
+                "INSERT INTO t SETTINGS async_insert=1, wait_for_async_insert=0 VALUES",
+                &cols
+            )
+            .unwrap(),
+            "INSERT INTO t (`id`, `name`) SETTINGS async_insert=1, wait_for_async_insert=0 VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("insert into t values", &cols).unwrap(),
+            "INSERT INTO t (`id`, `name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("INSERT  INTO\tt\nVALUES", &cols).unwrap(),
+            "INSERT INTO t (`id`, `name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t\nSETTINGS async_insert=1", &cols)
+                .unwrap(),
+            "INSERT INTO t (`id`, `name`) SETTINGS async_insert=1 VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t SETTINGS\tasync_insert=1", &cols)
+                .unwrap(),
+            "INSERT INTO t (`id`, `name`) SETTINGS\tasync_insert=1 VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("my_values", &cols).unwrap(),
+            "INSERT INTO my_values (`id`, `name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("db.values", &cols).unwrap(),
+            "INSERT INTO db.values (`id`, `name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("INSERT INTO my_values VALUES", &[])
+                .unwrap(),
+            "INSERT INTO my_values VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t", &[]).unwrap(),
+            "INSERT INTO t VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t SETTINGS async_insert=1", &[]).unwrap(),
+            "INSERT INTO t SETTINGS async_insert=1 VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text("t", &["odd`name"]).unwrap(),
+            "INSERT INTO t (`odd``name`) VALUES"
+        );
+        assert_eq!(
+            build_insert_query_text(
+                "FUNCTION remote('localhost', db.t)",
+                &cols
+            )
+            .unwrap(),
+            "INSERT INTO FUNCTION remote('localhost', db.t) (`id`, `name`) VALUES"
+        );
+
+        for listed in
+            ["t (a, b)", "INSERT INTO t(a) VALUES", "t (a) SETTINGS x=1"]
+        {
+            assert!(matches!(
+                build_insert_query_text(listed, &cols),
+                Err(Error::Validation(_))
+            ));
+        }
+        assert!(matches!(
+            build_insert_query_text("INSERT INTO ", &cols),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_mutation_helpers() {
+        assert_eq!(
+            parse_alter_table_name("ALTER TABLE db.t DELETE WHERE id = 1"),
+            Some((Some("db".to_string()), "t".to_string()))
+        );
+        assert_eq!(
+            parse_alter_table_name("alter table t UPDATE x = 1 WHERE 1"),
+            Some((None, "t".to_string()))
+        );
+        assert_eq!(
+            parse_alter_table_name(
+                "ALTER TABLE `my db`.`t.v1` ON CLUSTER c DELETE WHERE 1"
+            ),
+            Some((Some("my db".to_string()), "t.v1".to_string()))
+        );
+        assert_eq!(
+            parse_alter_table_name(
+                "ALTER  TABLE\n\"a\"\"b\".t DELETE WHERE 1"
+            ),
+            Some((Some("a\"b".to_string()), "t".to_string()))
+        );
+        assert_eq!(
+            parse_alter_table_name(r"ALTER TABLE `a\`b` DELETE WHERE 1"),
+            Some((None, "a`b".to_string()))
+        );
+        assert_eq!(parse_alter_table_name("ALTER TABLE `t DELETE"), None);
+        assert_eq!(parse_alter_table_name("ALTERTABLE t DELETE"), None);
+        assert_eq!(parse_alter_table_name("SELECT 1"), None);
+
+        assert_eq!(
+            split_table_name("`db`.`t`", "default"),
+            ("db".to_string(), "t".to_string())
+        );
+        assert_eq!(
+            split_table_name("t", "default"),
+            ("default".to_string(), "t".to_string())
+        );
+    }
+
+    #[test]
+    fn test_query_result() {
+        let result = QueryResult {
+            blocks: vec![],
+            totals: None,
+            extremes: None,
+            progress: Progress::default(),
+            profile: None,
+            profile_events: vec![],
+            logs: vec![],
+        };
+
+        assert_eq!(result.total_rows(), 0);
+    }
+
+    #[test]
+    fn test_query_result_profile_event_totals() {
+        let event = |thread_id, name: &str, value, kind| ProfileEvent {
+            thread_id,
+            name: name.to_string(),
+            value,
+            kind,
+        };
+        let result = QueryResult {
+            blocks: vec![],
+            totals: None,
+            extremes: None,
+            progress: Progress::default(),
+            profile: None,
+            profile_events: vec![
+                event(0, "SelectedRows", 5, ProfileEventKind::Increment),
+                event(3, "SelectedRows", 5, ProfileEventKind::Increment),
+                event(0, "MemoryTrackerUsage", 100, ProfileEventKind::Gauge),
+                event(0, "SelectedRows", 7, ProfileEventKind::Increment),
+                event(0, "MemoryTrackerUsage", 80, ProfileEventKind::Gauge),
+            ],
+            logs: vec![],
+        };
+
+        assert_eq!(result.profile_event("SelectedRows"), Some(12));
+        assert_eq!(result.profile_event("MemoryTrackerUsage"), Some(80));
+        assert_eq!(result.profile_event("ReadCompressedBytes"), None);
+    }
+
+    #[test]
+    fn test_query_result_first_row() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let empty = QueryResult {
+            blocks: vec![Block::new()],
+            totals: None,
+            extremes: None,
+            progress: Progress::default(),
+            profile: None,
+            profile_events: vec![],
+            logs: vec![],
+        };
+        assert!(empty.is_empty());
+        assert!(empty.first_block().is_none());
+        assert!(empty.first_row().unwrap().is_none());
+
+        let mut ids = ColumnUInt64::new();
+        ids.append(42);
+        let mut names = ColumnString::new(crate::types::Type::string());
+        names.append("answer");
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(ids)).unwrap();
+        block.append_column("name", Arc::new(names)).unwrap();
+
+        let result = QueryResult {
+            blocks: vec![Block::new(), block],
+            totals: None,
+            extremes: None,
+            progress: Progress::default(),
+            profile: None,
+            profile_events: vec![],
+            logs: vec![],
+        };
+        assert!(!result.is_empty());
+        assert_eq!(result.first_block().unwrap().row_count(), 1);
+
+        let row = result.first_row().unwrap().unwrap();
+        assert_eq!(row.len(), 2);
+        assert_eq!(row[0].data, 42u64.to_le_bytes());
+        assert_eq!(row[1].as_string().unwrap(), "answer");
+    }
+
+    /// Serve the handshake of a scripted server: accept the client hello
+    /// and answer with a revision below the addendum threshold
+    async fn scripted_hello(conn: &mut Connection) {
+        scripted_hello_at(conn, 54449).await;
+    }
+
+    /// Answer the client hello as a server at `revision`
+    async fn scripted_hello_at(conn: &mut Connection, revision: u64) {
+        assert_eq!(
+            conn.read_varint().await.unwrap(),
+            ClientCode::Hello as u64
+        );
+        let _client_name = conn.read_string().await.unwrap();
+        for _ in 0..3 {
+            conn.read_varint().await.unwrap();
+        }
+        assert_eq!(conn.read_string().await.unwrap(), "default");
+        assert_eq!(conn.read_string().await.unwrap(), "default");
+        assert_eq!(conn.read_string().await.unwrap(), "");
+
+        conn.write_varint(ServerCode::Hello as u64).await.unwrap();
+        conn.write_string("ClickHouse").await.unwrap();
+        conn.write_varint(23).await.unwrap();
+        conn.write_varint(8).await.unwrap();
+        conn.write_varint(revision).await.unwrap();
+        if revision >= 54058 {
+            conn.write_string("UTC").await.unwrap();
+        }
+        if revision >= 54372 {
+            conn.write_string("scripted").await.unwrap();
+        }
+        if revision >= 54401 {
+            conn.write_varint(1).await.unwrap();
+        }
+        conn.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_stream_handshake_and_ping() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+
+        // Scripted server: accept the hello, answer it, then one ping
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Ping as u64
+            );
+            conn.write_varint(ServerCode::Pong as u64).await.unwrap();
+            conn.flush().await.unwrap();
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        let info = client.server_info();
+        assert_eq!(info.name, "ClickHouse");
+        assert_eq!(
+            (info.version_major, info.version_minor, info.version_patch),
+            (23, 8, 1)
+        );
+        assert_eq!(info.revision, 54449);
+        assert_eq!(info.timezone, "UTC");
+        assert_eq!(info.display_name, "scripted");
+
+        client.ping().await.unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quota_key_is_sent_in_handshake_and_queries() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Hello as u64
+            );
+            let _client_name = conn.read_string().await.unwrap();
+            for _ in 0..3 {
+                conn.read_varint().await.unwrap();
+            }
+            for _ in 0..3 {
+                conn.read_string().await.unwrap();
+            }
+
+            // A revision that expects the quota key addendum
+            conn.write_varint(ServerCode::Hello as u64).await.unwrap();
+            conn.write_string("ClickHouse").await.unwrap();
+            conn.write_varint(23).await.unwrap();
+            conn.write_varint(8).await.unwrap();
+            conn.write_varint(54458).await.unwrap();
+            conn.write_string("UTC").await.unwrap();
+            conn.write_string("scripted").await.unwrap();
+            conn.write_varint(1).await.unwrap();
+            conn.flush().await.unwrap();
+            let addendum = conn.read_string().await.unwrap();
+
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+            let mut sent = Vec::new();
+            while let Ok(byte) = conn.read_u8().await {
+                sent.push(byte);
+            }
+            (addendum, sent)
+        });
+
+        let options =
+            ClientOptions::default().compression(None).quota_key("tenant-42");
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        client.query("SELECT 1").await.unwrap();
+        drop(client);
+
+        let (addendum, sent) = server.await.unwrap();
+        assert_eq!(addendum, "tenant-42");
+        let mut client_info = BytesMut::new();
+        crate::io::buffer_utils::write_string(&mut client_info, "tenant-42");
+        assert!(sent
+            .windows(client_info.len())
+            .any(|w| w == &client_info[..]));
+    }
+
+    #[tokio::test]
+    async fn test_compression_falls_back_for_old_server_revision() {
+        const OLD_REVISION: u64 = 54000;
+
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello_at(&mut conn, OLD_REVISION).await;
+
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+            let mut sent = Vec::new();
+            while let Ok(byte) = conn.read_u8().await {
+                sent.push(byte);
+            }
+            sent
+        });
+
+        let options = ClientOptions::default();
+        assert_eq!(options.compression, Some(CompressionMethod::Lz4));
+        let client_info = options.client_info.clone();
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        assert_eq!(client.effective_compression(), None);
+        let query = Query::new("SELECT 1");
+        client.query(query.clone()).await.unwrap();
+        drop(client);
+
+        // The query packet announces uncompressed blocks
+        let sent = server.await.unwrap();
+        let mut packet = BytesMut::new();
+        query.write_packet(&mut packet, OLD_REVISION, &client_info, false);
+        assert_eq!(&sent[..packet.len()], &packet[..]);
+    }
+
+    #[tokio::test]
+    async fn test_interserver_secret_requires_server_support() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello_at(&mut conn, 54440).await;
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default()
+            .compression(None)
+            .query_kind(QueryKind::Secondary)
+            .interserver_secret("digest");
+        assert_eq!(options.client_info.query_kind, QueryKind::Secondary);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client.execute("SELECT 1").await.unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature(_)), "{err}");
+        assert!(!client.is_dirty());
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_effective_compression_reports_configured_method() {
+        for (method, expected) in [
+            (Some(CompressionMethod::Zstd), Some(CompressionMethod::Zstd)),
+            (Some(CompressionMethod::None), None),
+            (None, None),
+        ] {
+            let (client_io, server_io) = tokio::io::duplex(4096);
+            let server = tokio::spawn(async move {
+                let mut conn = Connection::from_stream(server_io);
+                scripted_hello(&mut conn).await;
+            });
+            let options = ClientOptions::default().compression(method);
+            let client =
+                Client::connect_with_stream(client_io, options).await.unwrap();
+            assert_eq!(client.effective_compression(), expected);
+            server.await.unwrap();
+        }
+    }
+
+    /// Scripted server that never answers a query and returns everything
+    /// the client sent after the handshake, once the client hangs up
+    async fn silent_server(server_io: tokio::io::DuplexStream) -> Vec<u8> {
+        let mut conn = Connection::from_stream(server_io);
+        scripted_hello(&mut conn).await;
+        let mut sent = Vec::new();
+        while let Ok(byte) = conn.read_u8().await {
+            sent.push(byte);
+        }
+        sent
+    }
+
+    #[tokio::test]
+    async fn test_dropping_client_cancels_query_in_flight() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(silent_server(server_io));
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let query = client.query("SELECT sleep(3)");
+        assert!(tokio::time::timeout(Duration::from_millis(20), query)
+            .await
+            .is_err());
+        drop(client);
+
+        let sent = server.await.unwrap();
+        assert_eq!(sent.last(), Some(&(ClientCode::Cancel as u8)));
+    }
+
+    #[tokio::test]
+    async fn test_abort_current_cancels_interrupted_query() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(silent_server(server_io));
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        client.abort_current().await.unwrap();
+
+        let query = client.query("SELECT sleep(3)");
+        assert!(tokio::time::timeout(Duration::from_millis(20), query)
+            .await
+            .is_err());
+        client.abort_current().await.unwrap();
+        // The connection is closed, so the server sees the cancel and EOF
+        let sent = server.await.unwrap();
+        assert_eq!(sent.last(), Some(&(ClientCode::Cancel as u8)));
+        assert!(client.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn test_is_alive_reports_dropped_connection() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Answer one ping, then go away
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Ping as u64
+            );
+            conn.write_varint(ServerCode::Pong as u64).await.unwrap();
+            conn.flush().await.unwrap();
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        assert!(client.is_alive().await);
+        server.await.unwrap();
+        assert!(!client.is_alive().await);
+        assert!(!client.is_alive().await);
+    }
+
+    #[tokio::test]
+    async fn test_ping_before_query_reconnects_stale_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options = ClientOptions::new("127.0.0.1", port)
+            .compression(None)
+            .ping_before_query(true);
+
+        let server = tokio::spawn(async move {
+            // The first connection is closed while the client is idle
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            drop(conn);
+
+            // The fresh connection is used without another ping
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Query as u64
+            );
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+            conn
+        });
+
+        let mut client = Client::connect(options).await.unwrap();
+        let result = client.query("SELECT 1").await.unwrap();
+        assert_eq!(result.total_rows(), 0);
+        assert!(!client.is_dirty());
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_read_task_request_is_declined() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Ask for a task, then finish the query
+            conn.write_varint(ServerCode::ReadTaskRequest as u64)
+                .await
+                .unwrap();
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            let mut sent = Vec::new();
+            while let Ok(byte) = conn.read_u8().await {
+                sent.push(byte);
+            }
+            sent
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        let result = client.query("SELECT 1").await.unwrap();
+        assert_eq!(result.total_rows(), 0);
+        drop(client);
+
+        // Response code, protocol version and an empty task
+        let sent = server.await.unwrap();
+        assert!(sent.ends_with(&[ClientCode::ReadTaskResponse as u8, 1, 0]));
+    }
+
+    #[tokio::test]
+    async fn test_query_accumulates_progress() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Two progress packets; the second revises total_rows down
+            for (rows, bytes, total_rows) in [(10, 80, 100), (15, 120, 90)] {
+                conn.write_varint(ServerCode::Progress as u64).await.unwrap();
+                for value in [rows, bytes, total_rows, 0, 0] {
+                    conn.write_varint(value).await.unwrap();
+                }
+            }
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let raw = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let raw_sink = raw.clone();
+        let query = Query::new("SELECT 1").on_raw_progress(move |progress| {
+            raw_sink.lock().unwrap().push(progress.rows)
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        let result = client.query(query).await.unwrap();
+        drop(client);
+        server.await.unwrap();
+
+        let progress = result.progress();
+        assert_eq!(progress.rows, 25);
+        assert_eq!(progress.bytes, 200);
+        assert_eq!(progress.total_rows, 100);
+        assert_eq!(*raw.lock().unwrap(), [10, 15]);
+    }
+
+    #[tokio::test]
+    async fn test_cancelable_callback_leaves_connection_reusable() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Two blocks for the first query, one for the second
+            for id in [Some(1), Some(2), None, Some(3), None] {
+                let Some(id) = id else {
+                    conn.write_varint(ServerCode::EndOfStream as u64)
+                        .await
+                        .unwrap();
+                    continue;
+                };
+                let mut block = Block::new();
+                block
+                    .append_column(
+                        "id",
+                        Arc::new(ColumnUInt64::from(vec![id])),
+                    )
+                    .unwrap();
+                conn.write_varint(ServerCode::Data as u64).await.unwrap();
+                BlockWriter::new(54449)
+                    .write_block(&mut conn, &block)
+                    .await
+                    .unwrap();
+            }
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let query =
+            Query::new("SELECT id FROM t").on_data_cancelable(|_| false);
+        let result = client.query(query).await.unwrap();
+        assert_eq!(result.total_rows(), 0);
+        assert!(!client.is_dirty());
+
+        // The second query reads its own block, not the rest of the first
+        let result = client.query("SELECT 3").await.unwrap();
+        let block = result.first_block().unwrap();
+        let ids = block.column(0).unwrap();
+        let ids = ids.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(ids.data(), [3]);
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    /// Accept a connection on `listener` that resets as soon as it is
+    /// dropped, serve its handshake and send the header block an INSERT
+    /// waits for
+    async fn scripted_insert_server(listener: &TcpListener) -> Connection {
+        let (socket, _) = listener.accept().await.unwrap();
+        socket.set_zero_linger().unwrap();
+        let mut conn = Connection::new(socket);
+        scripted_hello(&mut conn).await;
+
+        conn.write_varint(ServerCode::Data as u64).await.unwrap();
+        BlockWriter::new(54449)
+            .write_block(&mut conn, &Block::new())
+            .await
+            .unwrap();
+        conn
+    }
+
+    fn insert_session_block() -> Block {
+        use crate::column::numeric::ColumnUInt64;
+
+        let mut ids = ColumnUInt64::new();
+        ids.append(1);
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(ids)).unwrap();
+        block
+    }
+
+    #[tokio::test]
+    async fn test_insert_session_reconnects_before_first_write() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options = ClientOptions::new("127.0.0.1", port).compression(None);
+
+        let (client, server) = tokio::join!(
+            Client::connect(options),
+            scripted_insert_server(&listener)
+        );
+        let mut client = client.unwrap();
+        let mut session = client.begin_insert("t", &["id"]).await.unwrap();
+
+        // The connection drops before any data was sent
+        drop(server);
+
+        let block = insert_session_block();
+        let (written, mut server) = tokio::join!(
+            session.write(&block),
+            scripted_insert_server(&listener)
+        );
+        written.unwrap();
+        assert_eq!(session.blocks_written(), 1);
+
+        server.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+        server.flush().await.unwrap();
+        session.finish().await.unwrap();
+        assert!(!client.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn test_insert_session_does_not_replay_written_blocks() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options = ClientOptions::new("127.0.0.1", port).compression(None);
+
+        let (client, server) = tokio::join!(
+            Client::connect(options),
+            scripted_insert_server(&listener)
+        );
+        let mut client = client.unwrap();
+        let mut session = client.begin_insert("t", &["id"]).await.unwrap();
+
+        let block = insert_session_block();
+        session.write(&block).await.unwrap();
+
+        // The connection drops after a block was sent
+        drop(server);
+
+        let err = session.write(&block).await.err().unwrap();
+        assert!(is_connection_error(&err));
+        assert_eq!(session.blocks_written(), 1);
+
+        // No second connection was attempted
+        let reconnect =
+            tokio::time::timeout(Duration::from_millis(50), listener.accept())
+                .await;
+        assert!(reconnect.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_begin_insert_requires_columns() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        server.await.unwrap();
+        assert!(matches!(
+            client.begin_insert("t", &[]).await.err(),
+            Some(Error::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_query_retrying_recovers_from_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options = ClientOptions::new("127.0.0.1", port).compression(None);
+
+        let server = tokio::spawn(async move {
+            // The first connection drops once the query was sent
+            let (socket, _) = listener.accept().await.unwrap();
+            socket.set_zero_linger().unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Query as u64
+            );
+            drop(conn);
+
+            // The retry reconnects and gets an empty result
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+            conn
+        });
+
+        let mut client = Client::connect(options).await.unwrap();
+        let policy =
+            RetryPolicy::new(2).initial_backoff(Duration::from_millis(1));
+        let result = client.query_retrying("SELECT 1", &policy).await.unwrap();
+        assert_eq!(result.total_rows(), 0);
+        assert!(!client.is_dirty());
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect_retries_select_on_killed_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let options = ClientOptions::new("127.0.0.1", port)
+            .compression(None)
+            .auto_reconnect(true);
+
+        let server = tokio::spawn(async move {
+            // The first connection is killed once the query was sent
+            let (socket, _) = listener.accept().await.unwrap();
+            socket.set_zero_linger().unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            assert_eq!(
+                conn.read_varint().await.unwrap(),
+                ClientCode::Query as u64
+            );
+            drop(conn);
+
+            // The retry reconnects and gets a one-row result
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = Connection::new(socket);
+            scripted_hello(&mut conn).await;
+            let mut block = Block::new();
+            let mut ids = crate::column::numeric::ColumnUInt64::new();
+            ids.append(7);
+            block.append_column("id", Arc::new(ids)).unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &block)
+                .await
+                .unwrap();
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+            conn
+        });
+
+        let mut client = Client::connect(options).await.unwrap();
+        let result = client.query("SELECT 7 AS id").await.unwrap();
+        assert_eq!(result.total_rows(), 1);
+        assert!(!client.is_dirty());
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_query_keeps_totals_and_extremes_apart_from_rows() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let block = |values: &[u64]| {
+            let mut block = Block::new();
+            let column =
+                ColumnUInt64::from_vec(Type::uint64(), values.to_vec());
+            block.append_column("n", Arc::new(column)).unwrap();
+            block
+        };
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Same response for `query` and `query_count_only`
+            for _ in 0..2 {
+                for (code, values) in [
+                    (ServerCode::Data, &[1, 2][..]),
+                    (ServerCode::Totals, &[3][..]),
+                    (ServerCode::Extremes, &[1, 2][..]),
+                ] {
+                    conn.write_varint(code as u64).await.unwrap();
+                    BlockWriter::new(54449)
+                        .write_block(&mut conn, &block(values))
+                        .await
+                        .unwrap();
+                }
+                conn.write_varint(ServerCode::EndOfStream as u64)
+                    .await
+                    .unwrap();
+            }
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let result = client.query("SELECT n").await.unwrap();
+        assert_eq!(result.total_rows(), 2);
+        let totals = result.totals.as_ref().unwrap();
+        let totals = totals.column(0).unwrap();
+        let totals = totals.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(totals.at(0), 3);
+        assert_eq!(result.extremes.as_ref().unwrap().row_count(), 2);
+
+        assert_eq!(client.query_count_only("SELECT n").await.unwrap(), 2);
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_result_keeps_last_profile_info() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            for (rows, rows_before_limit) in [(1, 2), (5, 1000)] {
+                conn.write_varint(ServerCode::ProfileInfo as u64)
+                    .await
+                    .unwrap();
+                conn.write_varint(rows).await.unwrap();
+                conn.write_varint(1).await.unwrap();
+                conn.write_varint(40).await.unwrap();
+                conn.write_u8(1).await.unwrap();
+                conn.write_varint(rows_before_limit).await.unwrap();
+                conn.write_u8(1).await.unwrap();
+            }
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let result = client.query("SELECT 1 LIMIT 5").await.unwrap();
+        let profile = result.profile().unwrap();
+        assert_eq!(profile.rows, 5);
+        assert_eq!(profile.rows_before_limit, 1000);
+        assert!(profile.applied_limit);
+        assert!(profile.calculated_rows_before_limit);
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_column_and_scalar_collect_typed_values() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let block = |values: &[u64], columns: &[&str]| {
+            let mut block = Block::new();
+            for name in columns {
+                let column =
+                    ColumnUInt64::from_vec(Type::uint64(), values.to_vec());
+                block.append_column(*name, Arc::new(column)).unwrap();
+            }
+            block
+        };
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // One response per query, each ending the stream
+            let responses = [
+                vec![block(&[1, 2], &["n"]), block(&[3], &["n"])],
+                vec![block(&[42], &["count()"])],
+                vec![block(&[1, 2], &["n"])],
+                vec![block(&[1], &["a", "b"])],
+                vec![block(&[1], &["n"])],
+            ];
+            for blocks in responses {
+                for block in blocks {
+                    conn.write_varint(ServerCode::Data as u64).await.unwrap();
+                    BlockWriter::new(54449)
+                        .write_block(&mut conn, &block)
+                        .await
+                        .unwrap();
+                }
+                conn.write_varint(ServerCode::EndOfStream as u64)
+                    .await
+                    .unwrap();
+            }
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let values: Vec<u64> = client.query_column("SELECT n").await.unwrap();
+        assert_eq!(values, [1, 2, 3]);
+        let count: u64 = client.query_scalar("SELECT count()").await.unwrap();
+        assert_eq!(count, 42);
+        assert!(matches!(
+            client.query_scalar::<u64>("SELECT n").await,
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            client.query_column::<u64>("SELECT a, b").await,
+            Err(Error::Validation(_))
+        ));
+        assert!(matches!(
+            client.query_column::<String>("SELECT n").await,
+            Err(Error::TypeMismatch { .. })
+        ));
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_map_builds_key_value_map() {
+        use crate::column::{
+            numeric::ColumnUInt64,
+            ColumnString,
+        };
+
+        let block = |keys: Vec<&str>, values: Vec<u64>| {
+            let mut block = Block::new();
+            block
+                .append_column("name", Arc::new(ColumnString::from(keys)))
+                .unwrap();
+            block
+                .append_column(
+                    "value",
+                    Arc::new(ColumnUInt64::from_vec(Type::uint64(), values)),
+                )
+                .unwrap();
+            block
+        };
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut single = Block::new();
+            single
+                .append_column("name", Arc::new(ColumnString::from(vec!["a"])))
+                .unwrap();
+            let responses = [
+                vec![
+                    block(vec!["a", "b"], vec![1, 2]),
+                    block(vec!["a"], vec![3]),
+                ],
+                vec![single],
+            ];
+            for blocks in responses {
+                for block in blocks {
+                    conn.write_varint(ServerCode::Data as u64).await.unwrap();
+                    BlockWriter::new(54449)
+                        .write_block(&mut conn, &block)
+                        .await
+                        .unwrap();
+                }
+                conn.write_varint(ServerCode::EndOfStream as u64)
+                    .await
+                    .unwrap();
+            }
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let map: HashMap<String, u64> =
+            client.query_map("SELECT name, value").await.unwrap();
+        assert_eq!(
+            map,
+            HashMap::from([("a".to_string(), 3), ("b".to_string(), 2)])
+        );
+        let err =
+            client.query_map::<String, u64>("SELECT name").await.unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(err.to_string().contains("two columns"));
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_retrying_rejects_writes() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        server.await.unwrap();
+        assert!(matches!(
+            client
+                .query_retrying(
+                    "INSERT INTO t VALUES (1)",
+                    &RetryPolicy::default()
+                )
+                .await
+                .err(),
+            Some(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_keepalive_outlives_idle_timeout() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Drop the connection once it has been idle for 150ms
+            let mut pings = 0;
+            loop {
+                let idle = Duration::from_millis(150);
+                match tokio::time::timeout(idle, conn.read_varint()).await {
+                    Ok(Ok(code)) => {
+                        assert_eq!(code, ClientCode::Ping as u64);
+                        conn.write_varint(ServerCode::Pong as u64)
+                            .await
+                            .unwrap();
+                        conn.flush().await.unwrap();
+                        pings += 1;
+                    }
+                    // The client hung up
+                    Ok(Err(_)) => return Some(pings),
+                    Err(_) => return None,
+                }
+            }
+        });
+
+        let options = ClientOptions::default()
+            .keepalive_interval(Duration::from_millis(50));
+        let client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let shared = SharedClient::new(client);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        shared.lock().await.ping().await.unwrap();
+
+        drop(shared);
+        let pings = server.await.unwrap().expect("server timed out");
+        assert!(pings > 1);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_insert_returns_server_schema() {
+        use crate::io::block_stream::create_column;
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut header = Block::new();
+            for (name, type_) in [
+                ("id", "UInt64"),
+                ("note", "Nullable(String)"),
+                ("tags", "Array(LowCardinality(String))"),
+            ] {
+                let column = create_column(&Type::parse(type_).unwrap());
+                header.append_column(name, column.unwrap()).unwrap();
+            }
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &header)
+                .await
+                .unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let session = client.prepare_insert("t").await.unwrap();
+
+        let schema: Vec<(String, String)> = session
+            .schema()
+            .into_iter()
+            .map(|(name, type_)| (name, type_.name()))
+            .collect();
+        assert_eq!(
+            schema,
+            [
+                ("id".to_string(), "UInt64".to_string()),
+                ("note".to_string(), "Nullable(String)".to_string()),
+                (
+                    "tags".to_string(),
+                    "Array(LowCardinality(String))".to_string()
+                ),
+            ]
+        );
+        assert_eq!(session.blocks_written(), 0);
+
+        drop(session);
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_rejects_mismatched_column_type() {
+        use crate::{
+            column::ColumnString,
+            io::block_stream::create_column,
+        };
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut header = Block::new();
+            let column = create_column(&Type::uint64()).unwrap();
+            header.append_column("id", column).unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &header)
+                .await
+                .unwrap();
+            // Answer to the cancelled INSERT
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let mut block = Block::new();
+        block
+            .append_column("id", Arc::new(ColumnString::from(vec!["1"])))
+            .unwrap();
+
+        let err = client.insert("t", block).await.unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }), "{err}");
+        let message = err.to_string();
+        assert!(message.contains("UInt64 for column id"), "{message}");
+        assert!(message.contains("String"), "{message}");
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_with_query_sends_statement_verbatim() {
+        use crate::{
+            column::ColumnString,
+            io::block_stream::create_column,
+        };
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut header = Block::new();
+            let column = create_column(&Type::string()).unwrap();
+            header.append_column("name", column).unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &header)
+                .await
+                .unwrap();
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            let mut sent = Vec::new();
+            while let Ok(byte) = conn.read_u8().await {
+                sent.push(byte);
+            }
+            sent
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let mut block = Block::new();
+        block
+            .append_column("name", Arc::new(ColumnString::from(vec!["a"])))
+            .unwrap();
+        let statement = "INSERT INTO events (name) FORMAT Native";
+        client.insert_with_query(statement, block).await.unwrap();
+        drop(client);
+
+        let sent = server.await.unwrap();
+        let mut text = BytesMut::new();
+        crate::io::buffer_utils::write_string(&mut text, statement);
+        assert!(sent.windows(text.len()).any(|w| w == &text[..]));
+    }
+
+    /// Serve an INSERT whose target has `columns`, described to the client
+    /// by `metadata` in a `TableColumns` packet, accept it and return
+    /// whatever the client sends afterwards
+    async fn serve_insert_target(
+        server_io: tokio::io::DuplexStream,
+        columns: &[(&str, Type)],
+        metadata: &str,
+    ) -> Vec<u8> {
+        use crate::io::block_stream::create_column;
+
+        let mut conn = Connection::from_stream(server_io);
+        scripted_hello(&mut conn).await;
+
+        conn.write_varint(ServerCode::TableColumns as u64).await.unwrap();
+        conn.write_string("").await.unwrap();
+        conn.write_string(metadata).await.unwrap();
+        let mut header = Block::new();
+        for (name, type_) in columns {
+            header
+                .append_column(*name, create_column(type_).unwrap())
+                .unwrap();
+        }
+        conn.write_varint(ServerCode::Data as u64).await.unwrap();
+        BlockWriter::new(54449).write_block(&mut conn, &header).await.unwrap();
+        conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+        conn.flush().await.unwrap();
+
+        let mut sent = Vec::new();
+        while let Ok(byte) = conn.read_u8().await {
+            sent.push(byte);
+        }
+        sent
+    }
+
+    fn uint64_block(names: &[&str]) -> Block {
+        use crate::column::ColumnUInt64;
+
+        let mut block = Block::new();
+        for name in names {
+            block
+                .append_column(*name, Arc::new(ColumnUInt64::from(vec![1u64])))
+                .unwrap();
+        }
+        block
+    }
+
+    const INSERT_TARGET_METADATA: &str = "columns format version: 1\n\
+                                          3 columns:\n\
+                                          `id` UInt64\n\
+                                          `note` UInt64\tDEFAULT\t7\n\
+                                          `count` UInt64\n";
+
+    #[tokio::test]
+    async fn test_insert_rejects_missing_column_without_default() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let columns = [
+            ("id", Type::uint64()),
+            ("note", Type::uint64()),
+            ("count", Type::uint64()),
+        ];
+        let server = tokio::spawn(async move {
+            serve_insert_target(server_io, &columns, INSERT_TARGET_METADATA)
+                .await
+        });
+
+        let options = ClientOptions::default()
+            .compression(None)
+            .insert_allow_defaults(true);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client
+            .insert_with_query(
+                "INSERT INTO t FORMAT Native",
+                uint64_block(&["id"]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)), "{err}");
+        let message = err.to_string();
+        assert!(message.contains("count"), "{message}");
+        assert!(!message.contains("note"), "{message}");
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_rejects_unknown_column() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            serve_insert_target(server_io, &[("id", Type::uint64())], "").await
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client
+            .insert_with_query(
+                "INSERT INTO t FORMAT Native",
+                uint64_block(&["id", "extra"]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)), "{err}");
+        assert!(err.to_string().contains("extra"), "{err}");
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_insert_leaves_connection_usable() {
+        use crate::io::block_stream::create_column;
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut header = Block::new();
+            header
+                .append_column("id", create_column(&Type::uint64()).unwrap())
+                .unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &header)
+                .await
+                .unwrap();
+            // Answers to the cancelled INSERT and to the next ping
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.write_varint(ServerCode::Pong as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            let mut sent = Vec::new();
+            while let Ok(byte) = conn.read_u8().await {
+                sent.push(byte);
+            }
+            sent
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client
+            .insert_with_query(
+                "INSERT INTO t FORMAT Native",
+                uint64_block(&["id", "extra"]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)), "{err}");
+
+        // A caller-provided stream cannot be reopened, so this only works
+        // if the INSERT was closed on the wire
+        client.ping().await.unwrap();
+        drop(client);
+
+        let sent = server.await.unwrap();
+        assert!(sent
+            .ends_with(&[ClientCode::Cancel as u8, ClientCode::Ping as u8]));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_insert_keeps_validation_error_if_cancel_fails() {
+        use crate::io::block_stream::create_column;
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let mut header = Block::new();
+            header
+                .append_column("id", create_column(&Type::uint64()).unwrap())
+                .unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &header)
+                .await
+                .unwrap();
+            // Not a valid answer to the cancel
+            conn.write_varint(99).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client
+            .insert_with_query(
+                "INSERT INTO t FORMAT Native",
+                uint64_block(&["id", "extra"]),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)), "{err}");
+        assert!(client.is_dirty());
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_insert_allow_defaults_leaves_out_defaulted_column() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let columns = [
+            ("id", Type::uint64()),
+            ("note", Type::uint64()),
+            ("count", Type::uint64()),
+        ];
+        let server = tokio::spawn(async move {
+            serve_insert_target(server_io, &columns, INSERT_TARGET_METADATA)
+                .await
+        });
+
+        let options = ClientOptions::default()
+            .compression(None)
+            .insert_allow_defaults(true);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        client
+            .insert_with_query(
+                "INSERT INTO t FORMAT Native",
+                uint64_block(&["id", "count"]),
+            )
+            .await
+            .unwrap();
+        drop(client);
+
+        let sent = server.await.unwrap();
+        let name = |name: &str| {
+            let mut bytes = BytesMut::new();
+            crate::io::buffer_utils::write_string(&mut bytes, name);
+            bytes
+        };
+        let contains =
+            |needle: &[u8]| sent.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(&name("count")));
+        assert!(!contains(&name("note")));
+    }
+
+    #[test]
+    fn test_check_insert_columns_allows_defaulted_and_reordered_columns() {
+        let header = uint64_block(&["id", "note", "count"]);
+        let defaulted = parse_defaulted_columns(INSERT_TARGET_METADATA);
+        assert_eq!(defaulted, HashSet::from(["note".to_string()]));
+
+        let block = uint64_block(&["count", "id"]);
+        check_insert_columns(&header, Some(&defaulted), &block, true).unwrap();
+        let err =
+            check_insert_columns(&header, Some(&defaulted), &block, false)
+                .unwrap_err();
+        assert!(err.to_string().contains("insert_allow_defaults"), "{err}");
+        // Without a table description the server decides
+        check_insert_columns(&header, None, &uint64_block(&["id"]), true)
+            .unwrap();
+        assert!(check_insert_columns(
+            &header,
+            Some(&defaulted),
+            &uint64_block(&["note"]),
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_defaulted_columns_handles_escaped_names() {
+        let metadata = "columns format version: 1\n\
+                        2 columns:\n\
+                        `we\\`ird` String\tMATERIALIZED\tupper(x)\n\
+                        `plain` String\tCOMMENT\t'no default'\n";
+        assert_eq!(
+            parse_defaulted_columns(metadata),
+            HashSet::from(["we`ird".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_insert_type_accepts_server_side_conversions() {
+        let accepts = |expected: &str, actual: &str| {
+            insert_type_accepts(
+                &Type::parse(expected).unwrap(),
+                &Type::parse(actual).unwrap(),
+            )
+        };
+
+        assert!(accepts("UInt64", "UInt64"));
+        assert!(accepts("LowCardinality(String)", "String"));
+        assert!(accepts("String", "LowCardinality(String)"));
+        assert!(accepts("Bool", "UInt8"));
+        assert!(accepts("DateTime('UTC')", "DateTime"));
+        assert!(accepts("DateTime64(3, 'UTC')", "DateTime64(3)"));
+        assert!(accepts("Nullable(Int32)", "Int32"));
+        assert!(accepts(
+            "Array(LowCardinality(Nullable(String)))",
+            "Array(Nullable(String))"
+        ));
+        assert!(accepts("Map(String, Bool)", "Map(String, UInt8)"));
+        assert!(accepts("Enum8('a' = 1, 'b' = 2)", "Int8"));
+        assert!(accepts("Enum16('a' = 1)", "Enum16('a' = 1, 'b' = 2)"));
+
+        assert!(!accepts("UInt64", "String"));
+        assert!(!accepts("Int32", "Nullable(Int32)"));
+        assert!(!accepts("DateTime64(3)", "DateTime64(6)"));
+        assert!(!accepts("Tuple(UInt8, String)", "Tuple(UInt8)"));
+        assert!(!accepts("Array(UInt32)", "Array(UInt64)"));
+        assert!(!accepts("Enum8('a' = 1)", "Int16"));
+    }
+
+    #[tokio::test]
+    async fn test_insert_stream_sends_nothing_without_blocks() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+            conn
+        });
+
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        let mut server = server.await.unwrap();
+        client.insert_stream("t", Vec::new()).await.unwrap();
+        assert!(!client.is_dirty());
+
+        // The client wrote nothing after the handshake
+        drop(client);
+        assert!(server.read_u8().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_parameters_need_server_support() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+            conn
+        });
+
+        // The scripted server reports revision 54449
+        let mut client =
+            Client::connect_with_stream(client_io, ClientOptions::default())
+                .await
+                .unwrap();
+        let mut server = server.await.unwrap();
+        let query = Query::new("SELECT {id:UInt64}").with_parameter("id", "1");
+        let err = client.query(query).await.err().unwrap();
+        assert!(matches!(err, Error::UnsupportedFeature(_)));
+        assert_eq!(
+            err.to_string(),
+            "Unsupported feature: server does not support query parameters; \
+             requires revision >= 54459"
+        );
+        assert!(!client.is_dirty());
+
+        // Nothing was sent
+        drop(client);
+        assert!(server.read_u8().await.is_err());
+    }
+
+    /// In-memory connection whose server-to-client direction buffers a
+    /// single byte, so a server write completes only once the client has
+    /// read what came before it
+    fn one_byte_downstream() -> (
+        impl AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        impl AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    ) {
+        let (server_read, client_write) = tokio::io::simplex(64 * 1024);
+        let (client_read, server_write) = tokio::io::simplex(1);
+        (
+            tokio::io::join(client_read, client_write),
+            tokio::io::join(server_read, server_write),
+        )
+    }
+
+    /// Serve the handshake, then `blocks` one-row blocks, each taking
+    /// `delay` to arrive once the client starts reading it
+    async fn serve_slow_blocks(
+        server_io: impl AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        blocks: u64,
+        delay: Duration,
+    ) {
+        use crate::column::numeric::ColumnUInt64;
+
+        let mut conn = Connection::from_stream(server_io);
+        scripted_hello(&mut conn).await;
+        for id in 0..blocks {
+            let mut block = Block::new();
+            block
+                .append_column("id", Arc::new(ColumnUInt64::from(vec![id])))
+                .unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            conn.write_string("").await.unwrap();
+            conn.flush().await.unwrap();
+            tokio::time::sleep(delay).await;
+            BlockWriter::new(54449)
+                .write_block_with_temp_table(&mut conn, &block, false)
+                .await
+                .unwrap();
+        }
+        conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+        conn.flush().await.unwrap();
+    }
+
+    #[test]
+    fn test_block_stream_is_send() {
+        // Held across an `.await` inside `tokio::spawn`
+        fn assert_send<T: Send>() {}
+        assert_send::<BlockStream<'static>>();
+        assert_send::<Cursor<'static>>();
+    }
+
+    #[tokio::test]
+    async fn test_buffered_stream_overlaps_reads_with_consumer() {
+        use crate::column::numeric::ColumnUInt64;
+        use futures_util::StreamExt;
+
+        const BLOCKS: u64 = 6;
+        let delay = Duration::from_millis(40);
+
+        // Lock-step: each block is only read once the consumer asks for it
+        let (client_io, server_io) = one_byte_downstream();
+        let server = tokio::spawn(serve_slow_blocks(server_io, BLOCKS, delay));
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options.clone())
+                .await
+                .unwrap();
+        let started = Instant::now();
+        let mut stream =
+            client.query_stream("SELECT id FROM t").await.unwrap();
+        let mut count = 0;
+        while let Some(block) = stream.next().await {
+            block.unwrap();
+            tokio::time::sleep(delay).await;
+            count += 1;
+        }
+        let lock_step = started.elapsed();
+        assert_eq!(count, BLOCKS);
+        drop(stream);
+        drop(client);
+        server.await.unwrap();
+
+        // Buffered: the next block arrives while the consumer sleeps
+        let (client_io, server_io) = one_byte_downstream();
+        let server = tokio::spawn(serve_slow_blocks(server_io, BLOCKS, delay));
+        let client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let started = Instant::now();
+        let mut stream =
+            client.query_stream_buffered("SELECT id FROM t", 4).await.unwrap();
+        let mut ids = Vec::new();
+        while let Some(block) = stream.next().await {
+            let block = block.unwrap();
+            let column = block.column(0).unwrap();
+            let column =
+                column.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+            ids.push(column.at(0));
+            tokio::time::sleep(delay).await;
+        }
+        let buffered = started.elapsed();
+        assert_eq!(ids, (0..BLOCKS).collect::<Vec<_>>());
+
+        // Lock-step waits for every read and every sleep in turn, while
+        // buffered reads hide all but the last sleep
+        assert!(lock_step >= delay * 2 * BLOCKS as u32);
+        assert!(
+            buffered < delay * 3 * BLOCKS as u32 / 2,
+            "buffered {:?} vs lock-step {:?}",
+            buffered,
+            lock_step
+        );
+
+        let client = stream.into_client().await.unwrap();
+        assert!(!client.is_dirty());
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout_cancels_and_drains() {
+        use crate::column::numeric::ColumnUInt64;
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Answer the slow query only after the client gave up on it,
+            // then the follow-up query right away
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            let mut block = Block::new();
+            block
+                .append_column("id", Arc::new(ColumnUInt64::from(vec![7])))
+                .unwrap();
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            BlockWriter::new(54449)
+                .write_block(&mut conn, &block)
+                .await
+                .unwrap();
+            conn.write_varint(ServerCode::EndOfStream as u64).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let started = Instant::now();
+        let query = Query::new("SELECT sleepEachRow(1) FROM numbers(10)")
+            .with_timeout(Duration::from_millis(50));
+        let err = client.query(query).await.err().unwrap();
+        assert!(matches!(err, Error::Timeout(_)));
+        assert!(started.elapsed() < CANCEL_DRAIN_TIMEOUT);
+        assert!(!client.is_dirty());
+
+        let result = client.query("SELECT 7").await.unwrap();
+        let block = result.first_block().unwrap();
+        let ids = block.column(0).unwrap();
+        let ids = ids.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(ids.data(), [7]);
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout_mid_packet_drops_connection() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            // Start a Data packet, then stall before the block arrives
+            conn.write_varint(ServerCode::Data as u64).await.unwrap();
+            conn.write_string("").await.unwrap();
+            conn.write_varint(1).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+
+        let started = Instant::now();
+        let query = Query::new("SELECT number FROM numbers(10)")
+            .with_timeout(Duration::from_millis(50));
+        let mut cursor = client.query_cursor(query).await.unwrap();
+        let err = cursor.next_block().await.err().unwrap();
+        assert!(matches!(err, Error::Timeout(_)));
+        assert!(started.elapsed() < CANCEL_DRAIN_TIMEOUT);
+        drop(cursor);
+
+        // The stream stopped mid-packet, so the connection is not reused
+        assert!(client.is_dirty());
+        let err = client.query("SELECT 1").await.err().unwrap();
+        assert!(matches!(err, Error::Connection(_)));
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_server_exception_is_structured() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+
+            let exception = crate::query::Exception {
+                code: 60,
+                name: "DB::Exception".to_string(),
+                display_text: "Table default.t does not exist".to_string(),
+                stack_trace: "0. DB::Exception::Exception()".to_string(),
+                nested: None,
+            };
+            let mut packet = BytesMut::new();
+            exception.write_to(&mut packet).unwrap();
+            conn.write_varint(ServerCode::Exception as u64).await.unwrap();
+            conn.write_bytes(&packet).await.unwrap();
+            conn.flush().await.unwrap();
+
+            while conn.read_u8().await.is_ok() {}
+        });
+
+        let options = ClientOptions::default().compression(None);
+        let mut client =
+            Client::connect_with_stream(client_io, options).await.unwrap();
+        let err = client.query("SELECT * FROM t").await.err().unwrap();
+
+        assert_eq!(err.code(), Some(60));
+        let Error::Server(exception) = &err else {
+            panic!("expected a server exception, got {:?}", err);
+        };
+        assert_eq!(exception.name, "DB::Exception");
+        assert_eq!(exception.stack_trace, "0. DB::Exception::Exception()");
+        assert_eq!(
+            err.to_string(),
+            "ClickHouse exception: DB::Exception (code 60): Table default.t \
+             does not exist"
+        );
+        assert!(!client.is_dirty());
+
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_output_format_settings_follow_strictness() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut conn = Connection::from_stream(server_io);
+            scripted_hello(&mut conn).await;
+            conn
+        });
+
+        let mut client = Client::connect_with_stream(
+            client_io,
+            ClientOptions::default().strict_settings(true),
+        )
+        .await
+        .unwrap();
+        let mut server = server.await.unwrap();
+        let query = Query::new("SELECT 1").with_setting("format", "JSON");
+
+        // Lenient clients only warn
+        client.options.strict_settings = false;
+        assert!(client.check_output_format_settings(&query).is_ok());
+
+        client.options.strict_settings = true;
+        let err = client.query(query).await.err().unwrap();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        assert!(err.to_string().contains("settings format have no effect"));
+        assert!(!client.is_dirty());
+
+        // Nothing was sent
+        drop(client);
+        assert!(server.read_u8().await.is_err());
+    }
+
+    #[test]
+    fn test_is_output_format_setting() {
+        assert!(is_output_format_setting("format"));
+        assert!(is_output_format_setting("default_format"));
+        assert!(is_output_format_setting(
+            "output_format_json_quote_64bit_integers"
+        ));
+        assert!(is_output_format_setting(
+            "output_format_csv_crlf_end_of_line"
+        ));
+        assert!(!is_output_format_setting("max_threads"));
+        assert!(!is_output_format_setting(
+            "output_format_native_encode_types_in_binary_format"
+        ));
+    }
+
+    #[test]
+    fn test_is_read_only_query() {
+        assert!(is_read_only_query("SELECT 1"));
+        assert!(is_read_only_query("  with x AS (SELECT 1) SELECT * FROM x"));
+        assert!(is_read_only_query("(SELECT 1) UNION ALL (SELECT 2)"));
+        assert!(is_read_only_query("-- count\n/* rows */ select count()"));
+        assert!(is_read_only_query("DESC t"));
+        assert!(!is_read_only_query("INSERT INTO t SELECT 1"));
+        assert!(!is_read_only_query("ALTER TABLE t DELETE WHERE 1"));
+        assert!(!is_read_only_query("SELECTION"));
+        assert!(!is_read_only_query("/* unterminated SELECT 1"));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(300));
+
+        for (attempt, full) in [(0, 100), (1, 200), (2, 300), (10, 300)] {
+            let delay = policy.backoff(attempt);
+            assert!(delay <= Duration::from_millis(full));
+            assert!(delay >= Duration::from_millis(full / 2));
+        }
+    }
+
+    #[test]
+    fn test_is_read_task_request() {
+        assert!(is_read_task_request(13));
+        assert!(is_read_task_request(15));
+        assert!(is_read_task_request(16));
+        assert!(!is_read_task_request(ServerCode::TableColumns as u64));
     }
 }
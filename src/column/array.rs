@@ -35,6 +35,7 @@
 use super::{
     Column,
     ColumnRef,
+    Value,
 };
 use crate::{
     types::Type,
@@ -190,13 +191,21 @@ impl ColumnArray {
         self.append_len(len);
     }
 
+    /// Get the array at the given index as a column holding just its
+    /// elements, or `None` if out of bounds
+    ///
+    /// The result is a slice of the nested column, so it can be downcast to
+    /// the element column type. For nested arrays it is itself a
+    /// [`ColumnArray`] with one row per inner array.
+    pub fn get_array(&self, index: usize) -> Option<ColumnRef> {
+        let (start, end) = self.get_array_range(index)?;
+        Some(self.nested.slice(start, end - start).expect("Valid slice"))
+    }
+
     /// Get the array at the given index as a sliced column
     pub fn at(&self, index: usize) -> ColumnRef {
-        if let Some((start, end)) = self.get_array_range(index) {
-            self.nested.slice(start, end - start).expect("Valid slice")
-        } else {
-            panic!("Array index out of bounds: {}", index);
-        }
+        self.get_array(index)
+            .unwrap_or_else(|| panic!("Array index out of bounds: {}", index))
     }
 
     /// Get the number of arrays (alias for size())
@@ -280,7 +289,7 @@ impl Column for ColumnArray {
         // Wire format: UInt64 values stored as 8-byte little-endian
         let bytes_needed = rows * 8;
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow reading array offsets: need {} bytes, have {}",
                 bytes_needed,
                 buffer.len()
@@ -373,6 +382,10 @@ impl Column for ColumnArray {
             )));
         }
 
+        if len == 0 {
+            return Ok(self.clone_empty());
+        }
+
         // Calculate the range of nested elements we need
         let nested_start =
             if begin == 0 { 0 } else { self.offsets[begin - 1] as usize };
@@ -431,6 +444,11 @@ where
         Self { inner, _phantom: PhantomData }
     }
 
+    /// Wrap an array column whose nested column is known to be a `T`
+    pub(crate) fn from_inner(inner: ColumnArray) -> Self {
+        Self { inner, _phantom: PhantomData }
+    }
+
     /// Create a new typed array column from an array type
     ///
     /// Returns an error if the nested column type doesn't match T
@@ -520,6 +538,12 @@ where
         self.inner.append_len(len)
     }
 
+    /// Get the array at the given index as a column holding just its
+    /// elements, or `None` if out of bounds
+    pub fn get_array(&self, index: usize) -> Option<ColumnRef> {
+        self.inner.get_array(index)
+    }
+
     /// Get the array at the given index as a sliced column
     pub fn at(&self, index: usize) -> ColumnRef {
         self.inner.at(index)
@@ -611,8 +635,10 @@ where
     }
 
     fn clone_empty(&self) -> ColumnRef {
+        // Keep the type name, which differs from `Array(T)` for geo types
         Arc::new(ColumnArrayT::<T> {
-            inner: ColumnArray::with_nested(
+            inner: ColumnArray::from_parts(
+                self.inner.column_type().clone(),
                 self.inner.nested_ref().clone_empty(),
             ),
             _phantom: PhantomData,
@@ -646,6 +672,10 @@ where
         }))
     }
 
+    fn value_at(&self, index: usize) -> Result<Value> {
+        self.inner.value_at(index)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -975,6 +1005,42 @@ mod tests {
         assert_eq!(arr1_data.size(), 3, "Second array should have 3 elements");
     }
 
+    #[test]
+    fn test_array_of_arrays_rows() {
+        use crate::column::numeric::ColumnInt32;
+
+        // Rows: [[1, 2], [3]] and []
+        let mut col =
+            ColumnArray::new(Type::array(Type::array(Type::int32())));
+        {
+            let inner: &mut ColumnArray = col.nested_mut();
+            inner.nested_mut::<ColumnInt32>().append(1);
+            inner.nested_mut::<ColumnInt32>().append(2);
+            inner.append_len(2);
+            inner.nested_mut::<ColumnInt32>().append(3);
+            inner.append_len(1);
+        }
+        col.append_len(2);
+        col.append_len(0);
+
+        let row = col.get_array(0).unwrap();
+        let row = row.as_any().downcast_ref::<ColumnArray>().unwrap();
+        assert_eq!(row.len(), 2);
+
+        let first = row.get_array(0).unwrap();
+        let first = first.as_any().downcast_ref::<ColumnInt32>().unwrap();
+        assert_eq!(first.data(), &[1, 2]);
+        let second = row.get_array(1).unwrap();
+        let second = second.as_any().downcast_ref::<ColumnInt32>().unwrap();
+        assert_eq!(second.data(), &[3]);
+
+        let empty = col.get_array(1).unwrap();
+        let empty = empty.as_any().downcast_ref::<ColumnArray>().unwrap();
+        assert!(empty.is_empty());
+
+        assert!(col.get_array(2).is_none());
+    }
+
     // ColumnArrayT tests
     #[test]
     fn test_array_t_creation() {
@@ -0,0 +1,223 @@
+//! Bool column implementation
+//!
+//! **ClickHouse Documentation:**
+//! [Bool](https://clickhouse.com/docs/en/sql-reference/data-types/boolean)
+//!
+//! `Bool` is stored like `UInt8` (one byte per value, 0 = false, 1 = true)
+//! but keeps its own type name, so `Array(Bool)`, `Nullable(Bool)` and
+//! `Map(String, Bool)` are written back to the server as `Bool`.
+
+use super::{
+    Column,
+    ColumnRef,
+    ColumnTyped,
+};
+use crate::{
+    types::Type,
+    Error,
+    Result,
+};
+use bytes::BytesMut;
+use std::sync::Arc;
+
+/// Column of `Bool` values
+pub struct ColumnBool {
+    type_: Type,
+    data: Vec<u8>,
+}
+
+impl ColumnBool {
+    /// Create a new empty Bool column.
+    pub fn new() -> Self {
+        Self { type_: Type::bool(), data: Vec::new() }
+    }
+
+    /// Create a column from a vector of values.
+    pub fn from_vec(values: Vec<bool>) -> Self {
+        Self {
+            type_: Type::bool(),
+            data: values.into_iter().map(u8::from).collect(),
+        }
+    }
+
+    /// Append a single value.
+    pub fn append(&mut self, value: bool) {
+        self.data.push(u8::from(value));
+    }
+
+    /// Get value at index (panics if out of bounds)
+    pub fn at(&self, index: usize) -> bool {
+        self.data[index] != 0
+    }
+
+    /// Return the value at the given index, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.data.get(index).map(|&b| b != 0)
+    }
+
+    /// Returns the number of values in this column.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the column contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Return an iterator over the column values.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.data.iter().map(|&b| b != 0)
+    }
+
+    /// Raw one-byte-per-value storage, as sent on the wire.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl From<Vec<bool>> for ColumnBool {
+    fn from(values: Vec<bool>) -> Self {
+        Self::from_vec(values)
+    }
+}
+
+impl Default for ColumnBool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Column for ColumnBool {
+    fn column_type(&self) -> &Type {
+        &self.type_
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    fn reserve(&mut self, new_cap: usize) {
+        self.data.reserve(new_cap);
+    }
+
+    fn append_column(&mut self, other: ColumnRef) -> Result<()> {
+        let other =
+            other.as_any().downcast_ref::<ColumnBool>().ok_or_else(|| {
+                Error::TypeMismatch {
+                    expected: self.type_.name(),
+                    actual: other.column_type().name(),
+                }
+            })?;
+
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+
+    fn load_from_buffer(
+        &mut self,
+        buffer: &mut &[u8],
+        rows: usize,
+    ) -> Result<()> {
+        if buffer.len() < rows {
+            return Err(Error::Incomplete(format!(
+                "Buffer underflow: need {} bytes, have {}",
+                rows,
+                buffer.len()
+            )));
+        }
+
+        self.data.extend_from_slice(&buffer[..rows]);
+        *buffer = &buffer[rows..];
+        Ok(())
+    }
+
+    fn save_to_buffer(&self, buffer: &mut BytesMut) -> Result<()> {
+        buffer.extend_from_slice(&self.data);
+        Ok(())
+    }
+
+    fn clone_empty(&self) -> ColumnRef {
+        Arc::new(ColumnBool::new())
+    }
+
+    fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef> {
+        if begin + len > self.data.len() {
+            return Err(Error::InvalidArgument(format!(
+                "Slice range out of bounds: begin={}, len={}, size={}",
+                begin,
+                len,
+                self.data.len()
+            )));
+        }
+
+        Ok(Arc::new(ColumnBool {
+            type_: self.type_.clone(),
+            data: self.data[begin..begin + len].to_vec(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ColumnTyped<bool> for ColumnBool {
+    fn get(&self, index: usize) -> Option<bool> {
+        ColumnBool::get(self, index)
+    }
+
+    fn append(&mut self, value: bool) {
+        ColumnBool::append(self, value)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool_column_roundtrip() {
+        let col = ColumnBool::from_vec(vec![true, false, true]);
+        assert_eq!(col.column_type().name(), "Bool");
+
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(&buffer[..], &[1, 0, 1]);
+
+        let mut decoded = ColumnBool::new();
+        let mut slice = &buffer[..];
+        decoded.load_from_buffer(&mut slice, 3).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(
+            decoded.iter().collect::<Vec<_>>(),
+            vec![true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_bool_column_nonzero_is_true() {
+        let mut col = ColumnBool::new();
+        let mut slice: &[u8] = &[0, 2, 255];
+        col.load_from_buffer(&mut slice, 3).unwrap();
+        assert!(!col.at(0));
+        assert!(col.at(1));
+        assert!(col.at(2));
+    }
+
+    #[test]
+    fn test_bool_column_underflow() {
+        let mut col = ColumnBool::new();
+        let mut slice: &[u8] = &[1];
+        assert!(col.load_from_buffer(&mut slice, 2).is_err());
+    }
+}
@@ -33,6 +33,11 @@ impl ColumnValue {
         Self { type_code: TypeCode::UInt8, data: value.to_le_bytes().to_vec() }
     }
 
+    /// Create a `Bool` value.
+    pub fn from_bool(value: bool) -> Self {
+        Self { type_code: TypeCode::Bool, data: vec![u8::from(value)] }
+    }
+
     /// Create a `UInt16` value.
     pub fn from_u16(value: u16) -> Self {
         Self {
@@ -158,11 +163,44 @@ fn fnv1a_64(data: &[u8]) -> u64 {
 
 /// Helper functions to extract ColumnValue from specific column types
 use super::{
+    array::ColumnArray,
+    boolean::ColumnBool,
+    date::{
+        ColumnDate,
+        ColumnDate32,
+        ColumnDateTime,
+        ColumnDateTime64,
+    },
+    decimal::ColumnDecimal,
+    enum_column::{
+        ColumnEnum16,
+        ColumnEnum8,
+    },
+    interval::ColumnInterval,
+    ipv4::ColumnIpv4,
+    ipv6::ColumnIpv6,
+    lowcardinality::ColumnLowCardinality,
+    map::ColumnMap,
+    nested::ColumnNested,
+    nothing::ColumnNothing,
     nullable::ColumnNullable,
     numeric::*,
-    string::ColumnString,
+    string::{
+        ColumnFixedString,
+        ColumnString,
+    },
+    tuple::ColumnTuple,
+    uuid::{
+        ColumnUuid,
+        Uuid,
+    },
     Column,
 };
+use crate::types::IntervalUnit;
+use std::net::{
+    Ipv4Addr,
+    Ipv6Addr,
+};
 
 /// Get item from a column by index
 /// Returns ColumnValue representation
@@ -193,6 +231,16 @@ pub fn get_column_item(
                     ))
                 }
             }
+            TypeCode::Bool => {
+                if let Some(col) = column.as_any().downcast_ref::<ColumnBool>()
+                {
+                    Ok(ColumnValue::from_bool(col.at(index)))
+                } else {
+                    Err(Error::Protocol(
+                        "Failed to downcast Bool column".to_string(),
+                    ))
+                }
+            }
             TypeCode::UInt16 => {
                 if let Some(col) =
                     column.as_any().downcast_ref::<ColumnUInt16>()
@@ -378,6 +426,25 @@ pub fn append_column_item(
                         ))
                     }
                 }
+                TypeCode::Bool => {
+                    if let Some(col) =
+                        column.as_any_mut().downcast_mut::<ColumnBool>()
+                    {
+                        match value.data.as_slice() {
+                            [b] => {
+                                col.append(*b != 0);
+                                Ok(())
+                            }
+                            _ => Err(Error::Protocol(
+                                "Invalid Bool data".to_string(),
+                            )),
+                        }
+                    } else {
+                        Err(Error::Protocol(
+                            "Failed to downcast Bool column".to_string(),
+                        ))
+                    }
+                }
                 TypeCode::UInt64 => {
                     if let Some(col) =
                         column.as_any_mut().downcast_mut::<ColumnUInt64>()
@@ -439,11 +506,302 @@ pub fn append_column_item(
     }
 }
 
+/// A value of any column type
+///
+/// Returned by [`Column::value_at`] for code that only learns the column
+/// types at run time, such as exporters or an interactive shell.
+/// `LowCardinality` values are read as the type they wrap.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A NULL of a `Nullable` type, or a `Nothing` value.
+    Null,
+    /// A `Bool` value.
+    Bool(bool),
+    /// An `Int8` to `Int64` value, or an enum value without a name.
+    Int64(i64),
+    /// A `UInt8` to `UInt64` value.
+    UInt64(u64),
+    /// An `Int128` value.
+    Int128(i128),
+    /// A `UInt128` value.
+    UInt128(u128),
+    /// An `Int256` value.
+    Int256(Int256),
+    /// A `UInt256` value.
+    UInt256(UInt256),
+    /// A `Float32` or `Float64` value.
+    Float64(f64),
+    /// A decimal, formatted with its scale, e.g. `"12.50"`.
+    Decimal(String),
+    /// A `String` or `FixedString` holding UTF-8, or an enum name.
+    String(String),
+    /// A `String` or `FixedString` that is not valid UTF-8.
+    Bytes(Vec<u8>),
+    /// A `Date` or `Date32`, in days since 1970-01-01.
+    Date(i32),
+    /// A `DateTime`, in seconds since the Unix epoch.
+    DateTime(i64),
+    /// A `DateTime64`, in units of `10^-precision` seconds since the Unix
+    /// epoch.
+    DateTime64 {
+        /// The number of ticks.
+        value: i64,
+        /// The number of sub-second digits.
+        precision: usize,
+    },
+    /// An interval of `value` units.
+    Interval {
+        /// The number of units.
+        value: i64,
+        /// The unit of the interval.
+        unit: IntervalUnit,
+    },
+    /// A `UUID` value.
+    Uuid(Uuid),
+    /// An `IPv4` address.
+    Ipv4(Ipv4Addr),
+    /// An `IPv6` address.
+    Ipv6(Ipv6Addr),
+    /// An `Array` value, also used for the geo `Ring` and polygon types.
+    Array(Vec<Value>),
+    /// A `Tuple` value, also used for the geo `Point` type.
+    Tuple(Vec<Value>),
+    /// A `Map` value, as key/value pairs in entry order.
+    Map(Vec<(Value, Value)>),
+}
+
+impl Value {
+    /// Whether this is [`Value::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+/// Read the value at `index` of a column of a concrete type from this
+/// crate, or `None` for other column types
+///
+/// `index` must be in bounds; [`Column::value_at`] checks it.
+pub(crate) fn value_of(
+    column: &dyn std::any::Any,
+    index: usize,
+) -> Option<Result<Value>> {
+    macro_rules! numeric {
+        ($($column:ty => $variant:ident),* $(,)?) => {
+            $(
+                if let Some(col) = column.downcast_ref::<$column>() {
+                    return Some(Ok(Value::$variant(col.at(index).into())));
+                }
+            )*
+        };
+    }
+    numeric!(
+        ColumnInt8 => Int64,
+        ColumnInt16 => Int64,
+        ColumnInt32 => Int64,
+        ColumnInt64 => Int64,
+        ColumnUInt8 => UInt64,
+        ColumnUInt16 => UInt64,
+        ColumnUInt32 => UInt64,
+        ColumnUInt64 => UInt64,
+        ColumnInt128 => Int128,
+        ColumnUInt128 => UInt128,
+        ColumnInt256 => Int256,
+        ColumnUInt256 => UInt256,
+        ColumnFloat32 => Float64,
+        ColumnFloat64 => Float64,
+        ColumnBool => Bool,
+        ColumnDate => Date,
+        ColumnDate32 => Date,
+        ColumnDateTime => DateTime,
+        ColumnUuid => Uuid,
+    );
+
+    fn text(bytes: &[u8]) -> Value {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Value::String(s.to_string()),
+            Err(_) => Value::Bytes(bytes.to_vec()),
+        }
+    }
+
+    let value = if let Some(col) = column.downcast_ref::<ColumnString>() {
+        text(col.get_bytes(index)?)
+    } else if let Some(col) = column.downcast_ref::<ColumnFixedString>() {
+        text(col.get_bytes(index)?)
+    } else if let Some(col) = column.downcast_ref::<ColumnDateTime64>() {
+        Value::DateTime64 { value: col.at(index), precision: col.precision() }
+    } else if let Some(col) = column.downcast_ref::<ColumnDecimal>() {
+        Value::Decimal(col.as_string(index))
+    } else if let Some(col) = column.downcast_ref::<ColumnEnum8>() {
+        col.name_at(index).map_or(Value::Int64(col.at(index).into()), |name| {
+            Value::String(name.to_string())
+        })
+    } else if let Some(col) = column.downcast_ref::<ColumnEnum16>() {
+        col.name_at(index).map_or(Value::Int64(col.at(index).into()), |name| {
+            Value::String(name.to_string())
+        })
+    } else if let Some(col) = column.downcast_ref::<ColumnInterval>() {
+        let (value, unit) = col.get(index)?;
+        Value::Interval { value, unit }
+    } else if let Some(col) = column.downcast_ref::<ColumnIpv4>() {
+        Value::Ipv4(col.get(index)?)
+    } else if let Some(col) = column.downcast_ref::<ColumnIpv6>() {
+        Value::Ipv6(col.get(index)?)
+    } else if column.downcast_ref::<ColumnNothing>().is_some() {
+        Value::Null
+    } else if let Some(col) = column.downcast_ref::<ColumnNullable>() {
+        if col.is_null(index) {
+            Value::Null
+        } else {
+            return Some(col.nested_ref().value_at(index));
+        }
+    } else if let Some(col) = column.downcast_ref::<ColumnLowCardinality>() {
+        // A nullable dictionary reads NULL for its first entry
+        return Some(col.dictionary_ref().value_at(col.get_index(index)?));
+    } else if let Some(col) = column.downcast_ref::<ColumnArray>() {
+        return Some(array_values(col, index).map(Value::Array));
+    } else if let Some(col) = column.downcast_ref::<ColumnTuple>() {
+        return Some(
+            (0..col.column_count())
+                .map(|i| col.column_at(i).value_at(index))
+                .collect::<Result<_>>()
+                .map(Value::Tuple),
+        );
+    } else if let Some(col) = column.downcast_ref::<ColumnNested>() {
+        let Some(array) = col.as_array() else {
+            return Some(Err(Error::Protocol(
+                "Nested data is not an array column".to_string(),
+            )));
+        };
+        return Some(array_values(array, index).map(Value::Array));
+    } else if let Some(col) = column.downcast_ref::<ColumnMap>() {
+        let Some(array) = col.as_array() else {
+            return Some(Err(Error::Protocol(
+                "Map data is not an array column".to_string(),
+            )));
+        };
+        return Some(array_values(array, index).and_then(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| match entry {
+                    Value::Tuple(pair) => match <[Value; 2]>::try_from(pair) {
+                        Ok([key, value]) => Ok((key, value)),
+                        Err(_) => Err(Error::Protocol(
+                            "Map entry is not a key/value pair".to_string(),
+                        )),
+                    },
+                    _ => Err(Error::Protocol(
+                        "Map entry is not a tuple".to_string(),
+                    )),
+                })
+                .collect::<Result<_>>()
+                .map(Value::Map)
+        }));
+    } else {
+        return None;
+    };
+    Some(Ok(value))
+}
+
+/// Read the elements of the array at `index`
+fn array_values(column: &ColumnArray, index: usize) -> Result<Vec<Value>> {
+    let (start, end) = column.get_array_range(index).ok_or_else(|| {
+        Error::InvalidArgument(format!("Array index {} out of bounds", index))
+    })?;
+    let nested = column.nested_ref();
+    (start..end).map(|i| nested.value_at(i)).collect()
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_value_at_scalars() {
+        use crate::types::{
+            EnumItem,
+            Type,
+        };
+
+        let column = ColumnUInt16::from(vec![7u16]);
+        assert_eq!(column.value_at(0).unwrap(), Value::UInt64(7));
+        assert!(matches!(column.value_at(1), Err(Error::InvalidArgument(_))));
+
+        let column = ColumnFloat32::from(vec![0.5f32]);
+        assert_eq!(column.value_at(0).unwrap(), Value::Float64(0.5));
+
+        let column =
+            ColumnDecimal::new(Type::decimal(9, 2)).with_data(vec![1250]);
+        assert_eq!(
+            column.value_at(0).unwrap(),
+            Value::Decimal("12.50".into())
+        );
+
+        let items = vec![EnumItem { name: "on".to_string(), value: 1 }];
+        let column =
+            ColumnEnum8::new(Type::enum8(items)).with_data(vec![1, 2]);
+        assert_eq!(column.value_at(0).unwrap(), Value::String("on".into()));
+        assert_eq!(column.value_at(1).unwrap(), Value::Int64(2));
+
+        let mut column = ColumnFixedString::new(Type::fixed_string(2));
+        let data = [0xc3, 0xa9, 0xff, 0];
+        column.load_from_buffer(&mut &data[..], 2).unwrap();
+        assert_eq!(
+            column.value_at(0).unwrap(),
+            Value::String("\u{e9}".into())
+        );
+        assert_eq!(column.value_at(1).unwrap(), Value::Bytes(vec![0xff, 0]));
+    }
+
+    #[test]
+    fn test_value_at_compound() {
+        use crate::{
+            column::nullable::ColumnNullableT,
+            types::Type,
+        };
+
+        // Map(String, Nullable(UInt64)): {'a': 1, 'b': NULL} and {}
+        let keys = ColumnString::from(vec!["a", "b"]);
+        let mut values = ColumnNullableT::<ColumnUInt64>::new(Type::nullable(
+            Type::uint64(),
+        ));
+        values.append(Some(1u64));
+        values.append(None::<u64>);
+        let entries = ColumnTuple::new(
+            Type::tuple(vec![Type::string(), Type::nullable(Type::uint64())]),
+            vec![Arc::new(keys), Arc::new(values)],
+        );
+        let mut array = ColumnArray::with_nested(Arc::new(entries));
+        array.append_len(2);
+        array.append_len(0);
+        let column = ColumnMap::from_array(
+            Type::map(Type::string(), Type::nullable(Type::uint64())),
+            Arc::new(array),
+        );
+
+        assert_eq!(
+            column.value_at(0).unwrap(),
+            Value::Map(vec![
+                (Value::String("a".into()), Value::UInt64(1)),
+                (Value::String("b".into()), Value::Null),
+            ])
+        );
+        assert_eq!(column.value_at(1).unwrap(), Value::Map(Vec::new()));
+
+        // The same entries read through the underlying array
+        let array = column.as_array().unwrap();
+        assert_eq!(
+            array.value_at(0).unwrap(),
+            Value::Array(vec![
+                Value::Tuple(vec![
+                    Value::String("a".into()),
+                    Value::UInt64(1)
+                ]),
+                Value::Tuple(vec![Value::String("b".into()), Value::Null]),
+            ])
+        );
+    }
+
     #[test]
     fn test_column_value_primitives() {
         let v = ColumnValue::from_u64(42);
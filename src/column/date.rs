@@ -27,6 +27,14 @@
 //!
 //! The timezone affects how values are displayed and interpreted, but storage
 //! is always in Unix time.
+//!
+//! ## chrono
+//!
+//! With the `chrono` feature, values can be read and appended as
+//! [`chrono::NaiveDate`] (`Date`, `Date32`) and [`chrono::DateTime<Utc>`]
+//! (`DateTime`, `DateTime64`), e.g. `get_datetime(index)` and
+//! `append_datetime(dt)`. Since storage is Unix time, a column's timezone
+//! never changes the instant a value denotes.
 
 use super::{
     Column,
@@ -672,6 +680,78 @@ impl Column for ColumnDateTime64 {
     }
 }
 
+#[cfg(feature = "chrono")]
+const UNIX_EPOCH_DATE: chrono::NaiveDate =
+    chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+
+#[cfg(feature = "chrono")]
+impl ColumnDate {
+    /// Get the date at index (panics if out of bounds)
+    pub fn get_date(&self, index: usize) -> chrono::NaiveDate {
+        UNIX_EPOCH_DATE + chrono::Days::new(self.at(index) as u64)
+    }
+
+    /// Append a date
+    pub fn append_date(&mut self, date: chrono::NaiveDate) {
+        self.append((date - UNIX_EPOCH_DATE).num_days() as u16);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ColumnDate32 {
+    /// Get the date at index (panics if out of bounds)
+    pub fn get_date(&self, index: usize) -> chrono::NaiveDate {
+        UNIX_EPOCH_DATE + chrono::TimeDelta::days(self.at(index) as i64)
+    }
+
+    /// Append a date
+    pub fn append_date(&mut self, date: chrono::NaiveDate) {
+        self.append((date - UNIX_EPOCH_DATE).num_days() as i32);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ColumnDateTime {
+    /// Get the instant at index (panics if out of bounds)
+    pub fn get_datetime(&self, index: usize) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(self.at(index) as i64, 0)
+            .expect("UInt32 timestamp is in chrono range")
+    }
+
+    /// Append an instant, truncated to whole seconds
+    pub fn append_datetime(
+        &mut self,
+        datetime: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.append(datetime.timestamp() as u32);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ColumnDateTime64 {
+    /// Get the instant at index (panics if out of bounds or if the value is
+    /// outside chrono's range)
+    pub fn get_datetime(&self, index: usize) -> chrono::DateTime<chrono::Utc> {
+        let scale = 10i64.pow(self.precision as u32);
+        let value = self.at(index);
+        let nanos =
+            value.rem_euclid(scale) * 10i64.pow(9 - self.precision as u32);
+        chrono::DateTime::from_timestamp(value.div_euclid(scale), nanos as u32)
+            .expect("DateTime64 value is outside chrono's range")
+    }
+
+    /// Append an instant, truncated to the column's precision
+    pub fn append_datetime(
+        &mut self,
+        datetime: chrono::DateTime<chrono::Utc>,
+    ) {
+        let scale = 10i64.pow(self.precision as u32);
+        let fraction = datetime.timestamp_subsec_nanos() as i64
+            / 10i64.pow(9 - self.precision as u32);
+        self.append(datetime.timestamp() * scale + fraction);
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -743,4 +823,57 @@ mod tests {
         assert_eq!(col.at(0), 1640995200000);
         assert_eq!(col.at(1), 1640995200123);
     }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_dates() {
+        use chrono::NaiveDate;
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let mut col = ColumnDate::new(Type::date());
+        col.append_date(date);
+        assert_eq!(col.at(0), 19782);
+        assert_eq!(col.get_date(0), date);
+
+        let before_epoch = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+        let mut col = ColumnDate32::new(Type::date32());
+        col.append_date(before_epoch);
+        assert_eq!(col.at(0), -25567);
+        assert_eq!(col.get_date(0), before_epoch);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_datetimes() {
+        use chrono::{
+            DateTime,
+            Utc,
+        };
+
+        let instant: DateTime<Utc> =
+            "2024-01-01T00:00:00.123456789Z".parse().unwrap();
+
+        let mut col = ColumnDateTime::new(Type::datetime(None));
+        col.append_datetime(instant);
+        assert_eq!(col.at(0), 1704067200);
+        assert_eq!(col.get_datetime(0).timestamp(), 1704067200);
+
+        let mut col = ColumnDateTime64::new(Type::datetime64(
+            3,
+            Some("UTC".to_string()),
+        ));
+        col.append_datetime(instant);
+        assert_eq!(col.at(0), 1704067200123);
+        assert_eq!(
+            col.get_datetime(0),
+            "2024-01-01T00:00:00.123Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let mut col = ColumnDateTime64::new(Type::datetime64(9, None));
+        let before_epoch: DateTime<Utc> =
+            "1969-12-31T23:59:59.5Z".parse().unwrap();
+        col.append_datetime(before_epoch);
+        assert_eq!(col.at(0), -500_000_000);
+        assert_eq!(col.get_datetime(0), before_epoch);
+    }
 }
@@ -1,6 +1,8 @@
 use super::{
+    numeric::Int256,
     Column,
     ColumnInt128,
+    ColumnInt256,
     ColumnInt32,
     ColumnInt64,
     ColumnRef,
@@ -19,12 +21,13 @@ use std::sync::Arc;
 /// Uses efficient internal representation based on precision:
 /// - precision <= 9: ColumnInt32 (4 bytes per value)
 /// - precision <= 18: ColumnInt64 (8 bytes per value)
-/// - precision > 18: ColumnInt128 (16 bytes per value)
+/// - precision <= 38: ColumnInt128 (16 bytes per value)
+/// - precision > 38: ColumnInt256 (32 bytes per value, `Decimal256`)
 pub struct ColumnDecimal {
     type_: Type,
     precision: usize,
     scale: usize,
-    data: ColumnRef, // Internally delegates to ColumnInt32/Int64/Int128
+    data: ColumnRef, // Internally delegates to ColumnInt32/64/128/256
 }
 
 impl ColumnDecimal {
@@ -45,8 +48,10 @@ impl ColumnDecimal {
             Arc::new(ColumnInt32::new())
         } else if precision <= 18 {
             Arc::new(ColumnInt64::new())
-        } else {
+        } else if precision <= 38 {
             Arc::new(ColumnInt128::new())
+        } else {
+            Arc::new(ColumnInt256::new())
         };
 
         Self { type_, precision, scale, data }
@@ -67,12 +72,18 @@ impl ColumnDecimal {
                 col.append(value as i64);
             }
             self.data = Arc::new(col);
-        } else {
+        } else if self.precision <= 38 {
             let mut col = ColumnInt128::new();
             for value in data {
                 col.append(value);
             }
             self.data = Arc::new(col);
+        } else {
+            let mut col = ColumnInt256::new();
+            for value in data {
+                col.append(Int256::from(value));
+            }
+            self.data = Arc::new(col);
         }
         self
     }
@@ -84,8 +95,13 @@ impl ColumnDecimal {
     /// Returns an error if the string is not a valid decimal or the
     /// fractional part exceeds the column's scale.
     pub fn append_from_string(&mut self, s: &str) -> Result<()> {
-        let value = parse_decimal(s, self.scale)?;
-        self.append(value);
+        if self.precision > 38 {
+            let value = parse_decimal_256(s, self.scale)?;
+            self.append_i256(value);
+        } else {
+            let value = parse_decimal(s, self.scale)?;
+            self.append(value);
+        }
         Ok(())
     }
 
@@ -107,16 +123,39 @@ impl ColumnDecimal {
                 .downcast_mut::<ColumnInt64>()
                 .expect("Expected ColumnInt64");
             col.append(value as i64);
-        } else {
+        } else if self.precision <= 38 {
             let col = data_mut
                 .as_any_mut()
                 .downcast_mut::<ColumnInt128>()
                 .expect("Expected ColumnInt128");
             col.append(value);
+        } else {
+            self.append_i256(Int256::from(value));
         }
     }
 
+    /// Append decimal from a 256-bit raw scaled value.
+    ///
+    /// Columns with precision <= 38 keep only the low 128 bits.
+    pub fn append_i256(&mut self, value: Int256) {
+        if self.precision <= 38 {
+            self.append(low_i128(value));
+            return;
+        }
+
+        let data_mut =
+            Arc::get_mut(&mut self.data).expect("Cannot modify shared column");
+        let col = data_mut
+            .as_any_mut()
+            .downcast_mut::<ColumnInt256>()
+            .expect("Expected ColumnInt256");
+        col.append(value);
+    }
+
     /// Get decimal at index as i128 (raw scaled value)
+    ///
+    /// For precision > 38 only the low 128 bits are returned; use
+    /// [`at_i256`](Self::at_i256) for values that may not fit.
     pub fn at(&self, index: usize) -> i128 {
         if self.precision <= 9 {
             let col = self
@@ -132,19 +171,39 @@ impl ColumnDecimal {
                 .downcast_ref::<ColumnInt64>()
                 .expect("Expected ColumnInt64");
             col.at(index) as i128
-        } else {
+        } else if self.precision <= 38 {
             let col = self
                 .data
                 .as_any()
                 .downcast_ref::<ColumnInt128>()
                 .expect("Expected ColumnInt128");
             col.at(index)
+        } else {
+            low_i128(self.at_i256(index))
         }
     }
 
+    /// Get decimal at index as a 256-bit raw scaled value
+    pub fn at_i256(&self, index: usize) -> Int256 {
+        if self.precision <= 38 {
+            return Int256::from(self.at(index));
+        }
+
+        let col = self
+            .data
+            .as_any()
+            .downcast_ref::<ColumnInt256>()
+            .expect("Expected ColumnInt256");
+        col.at(index)
+    }
+
     /// Format decimal at index as string
     pub fn as_string(&self, index: usize) -> String {
-        format_decimal(self.at(index), self.scale)
+        if self.precision > 38 {
+            format_decimal_256(self.at_i256(index), self.scale)
+        } else {
+            format_decimal(self.at(index), self.scale)
+        }
     }
 
     /// Returns the precision (total number of digits) of this decimal column.
@@ -341,6 +400,124 @@ fn format_decimal(value: i128, scale: usize) -> String {
     }
 }
 
+/// Low 128 bits of a 256-bit value
+fn low_i128(value: Int256) -> i128 {
+    i128::from_le_bytes(value.0[..16].try_into().unwrap())
+}
+
+/// Two's complement negation of a 256-bit value
+fn negate_256(value: Int256) -> Int256 {
+    let mut bytes = value.0;
+    let mut carry = true;
+    for byte in bytes.iter_mut() {
+        let (sum, overflow) = (!*byte).overflowing_add(carry as u8);
+        *byte = sum;
+        carry = overflow;
+    }
+    Int256(bytes)
+}
+
+/// Parse decimal string to a 256-bit scaled integer
+/// "123.45" with scale 2 -> 12345
+fn parse_decimal_256(s: &str, scale: usize) -> Result<Int256> {
+    let s = s.trim();
+    let (negative, s) = if let Some(stripped) = s.strip_prefix('-') {
+        (true, stripped)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    };
+
+    let (integer_part, fractional_part) = s.split_once('.').unwrap_or((s, ""));
+    if integer_part.is_empty()
+        || !integer_part.bytes().all(|b| b.is_ascii_digit())
+        || !fractional_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(Error::Protocol(format!("Invalid decimal format: {}", s)));
+    }
+    if fractional_part.len() > scale {
+        return Err(Error::Protocol(format!(
+            "Decimal fractional part exceeds scale: {} > {}",
+            fractional_part.len(),
+            scale
+        )));
+    }
+
+    // Accumulate the magnitude in little-endian u64 limbs
+    let padding = scale - fractional_part.len();
+    let digits = integer_part
+        .bytes()
+        .chain(fractional_part.bytes())
+        .chain(std::iter::repeat_n(b'0', padding));
+    let mut limbs = [0u64; 4];
+    for digit in digits {
+        let mut carry = u128::from(digit - b'0');
+        for limb in limbs.iter_mut() {
+            let product = u128::from(*limb) * 10 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 {
+            return Err(Error::Protocol(format!(
+                "Decimal value out of range: {}",
+                s
+            )));
+        }
+    }
+
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    let magnitude = Int256(bytes);
+
+    // The magnitude must fit the signed range, where -2^255 is allowed
+    if bytes[31] & 0x80 != 0 && !(negative && magnitude == Int256::MIN) {
+        return Err(Error::Protocol(format!(
+            "Decimal value out of range: {}",
+            s
+        )));
+    }
+
+    Ok(if negative { negate_256(magnitude) } else { magnitude })
+}
+
+/// Format a 256-bit scaled integer to decimal string
+fn format_decimal_256(value: Int256, scale: usize) -> String {
+    let negative = value.0[31] & 0x80 != 0;
+    let magnitude = if negative { negate_256(value) } else { value };
+
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(magnitude.0.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    // Repeatedly divide by 10, collecting digits least significant first
+    let mut digits = Vec::new();
+    while limbs.iter().any(|&l| l != 0) || digits.len() <= scale {
+        let mut remainder = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let current = (remainder << 64) | u128::from(*limb);
+            *limb = (current / 10) as u64;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    digits.reverse();
+
+    let (integer_part, fractional_part) =
+        digits.split_at(digits.len() - scale);
+    let mut result = String::with_capacity(digits.len() + 2);
+    if negative {
+        result.push('-');
+    }
+    result.push_str(std::str::from_utf8(integer_part).unwrap());
+    if scale > 0 {
+        result.push('.');
+        result.push_str(std::str::from_utf8(fractional_part).unwrap());
+    }
+    result
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -670,4 +847,59 @@ mod tests {
         assert_eq!(col38.at(0), 100);
         assert!(col38.data.as_any().is::<ColumnInt128>());
     }
+
+    #[test]
+    fn test_decimal_uses_int256_for_precision_76() {
+        let col = ColumnDecimal::new(Type::decimal(76, 10));
+        assert!(col.data.as_any().is::<ColumnInt256>());
+
+        let col = ColumnDecimal::new(Type::parse("Decimal256(4)").unwrap());
+        assert_eq!(col.precision(), 76);
+        assert!(col.data.as_any().is::<ColumnInt256>());
+    }
+
+    #[test]
+    fn test_decimal256_roundtrip_beyond_i128() {
+        // 40 integer digits plus 10 fractional digits overflow i128
+        let big = "1234567890123456789012345678901234567890.0123456789";
+        let mut col = ColumnDecimal::new(Type::decimal(50, 10));
+        col.append_from_string(big).unwrap();
+        col.append_from_string(&format!("-{}", big)).unwrap();
+        col.append(-5);
+        assert!(col.at_i256(0).to_i128().is_none());
+
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 3 * 32);
+
+        let mut decoded = ColumnDecimal::new(Type::decimal(50, 10));
+        let mut slice = &buffer[..];
+        decoded.load_from_buffer(&mut slice, 3).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(decoded.at_i256(0), col.at_i256(0));
+        assert_eq!(decoded.as_string(0), big);
+        assert_eq!(decoded.as_string(1), format!("-{}", big));
+        assert_eq!(decoded.as_string(2), "-0.0000000005");
+        assert_eq!(decoded.at(2), -5);
+    }
+
+    #[test]
+    fn test_decimal256_parse_and_format_edges() {
+        let max = "57896044618658097711785492504343953926634992332820282019728792003956564819967";
+        let min = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+        assert_eq!(parse_decimal_256(max, 0).unwrap(), Int256::MAX);
+        assert_eq!(parse_decimal_256(min, 0).unwrap(), Int256::MIN);
+        assert_eq!(format_decimal_256(Int256::MAX, 0), max);
+        assert_eq!(format_decimal_256(Int256::MIN, 0), min);
+        let max_plus_one = format!("{}8", &max[..max.len() - 1]);
+        assert!(parse_decimal_256(&max_plus_one, 0).is_err());
+        assert!(parse_decimal_256(&format!("{}0", max), 0).is_err());
+
+        assert_eq!(format_decimal_256(Int256::ZERO, 3), "0.000");
+        assert_eq!(format_decimal_256(Int256::from(-123), 2), "-1.23");
+        assert_eq!(parse_decimal_256("+1.5", 2).unwrap(), Int256::from(150));
+        assert!(parse_decimal_256("1.234", 2).is_err());
+        assert!(parse_decimal_256("1a", 2).is_err());
+        assert!(parse_decimal_256(".5", 2).is_err());
+    }
 }
@@ -36,6 +36,36 @@ impl ColumnEnum8 {
         }
     }
 
+    /// Create a column from raw `i8` enum values.
+    ///
+    /// Values are stored as given, without checking them against the enum
+    /// definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not `Type::Enum8`.
+    pub fn from_values(type_: Type, values: Vec<i8>) -> Self {
+        Self::new(type_).with_data(values)
+    }
+
+    /// Create a column from enum names, resolving each through `type_`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name is not a known variant in this enum type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not `Type::Enum8`.
+    pub fn from_names(type_: Type, names: Vec<&str>) -> Result<Self> {
+        let mut col = Self::new(type_);
+        col.data.reserve(names.len());
+        for name in names {
+            col.append_name(name)?;
+        }
+        Ok(col)
+    }
+
     /// Set the column data from a vector of raw `i8` enum values.
     pub fn with_data(mut self, data: Vec<i8>) -> Self {
         self.data = data;
@@ -54,7 +84,7 @@ impl ColumnEnum8 {
     /// Returns an error if `name` is not a known variant in this enum type.
     pub fn append_name(&mut self, name: &str) -> Result<()> {
         let value = self.type_.get_enum_value(name).ok_or_else(|| {
-            Error::Protocol(format!("Unknown enum name: {}", name))
+            Error::InvalidArgument(format!("Unknown enum name: {}", name))
         })?;
 
         self.data.push(value as i8);
@@ -67,8 +97,11 @@ impl ColumnEnum8 {
     }
 
     /// Get enum name at index (looks up in Type).
+    ///
+    /// Returns `None` if `index` is out of bounds or the stored value has
+    /// no name in the enum type.
     pub fn name_at(&self, index: usize) -> Option<&str> {
-        let value = self.data[index] as i16;
+        let value = *self.data.get(index)? as i16;
         self.type_.get_enum_name(value)
     }
 
@@ -120,7 +153,7 @@ impl Column for ColumnEnum8 {
     ) -> Result<()> {
         let bytes_needed = rows;
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow: need {} bytes for Enum8, have {}",
                 bytes_needed,
                 buffer.len()
@@ -208,6 +241,36 @@ impl ColumnEnum16 {
         }
     }
 
+    /// Create a column from raw `i16` enum values.
+    ///
+    /// Values are stored as given, without checking them against the enum
+    /// definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not `Type::Enum16`.
+    pub fn from_values(type_: Type, values: Vec<i16>) -> Self {
+        Self::new(type_).with_data(values)
+    }
+
+    /// Create a column from enum names, resolving each through `type_`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name is not a known variant in this enum type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not `Type::Enum16`.
+    pub fn from_names(type_: Type, names: Vec<&str>) -> Result<Self> {
+        let mut col = Self::new(type_);
+        col.data.reserve(names.len());
+        for name in names {
+            col.append_name(name)?;
+        }
+        Ok(col)
+    }
+
     /// Set the column data from a vector of raw `i16` enum values.
     pub fn with_data(mut self, data: Vec<i16>) -> Self {
         self.data = data;
@@ -226,7 +289,7 @@ impl ColumnEnum16 {
     /// Returns an error if `name` is not a known variant in this enum type.
     pub fn append_name(&mut self, name: &str) -> Result<()> {
         let value = self.type_.get_enum_value(name).ok_or_else(|| {
-            Error::Protocol(format!("Unknown enum name: {}", name))
+            Error::InvalidArgument(format!("Unknown enum name: {}", name))
         })?;
 
         self.data.push(value);
@@ -239,8 +302,11 @@ impl ColumnEnum16 {
     }
 
     /// Get enum name at index (looks up in Type).
+    ///
+    /// Returns `None` if `index` is out of bounds or the stored value has
+    /// no name in the enum type.
     pub fn name_at(&self, index: usize) -> Option<&str> {
-        let value = self.data[index];
+        let value = *self.data.get(index)?;
         self.type_.get_enum_name(value)
     }
 
@@ -291,7 +357,7 @@ impl Column for ColumnEnum16 {
     ) -> Result<()> {
         let bytes_needed = rows * 2;
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow: need {} bytes for Enum16, have {}",
                 bytes_needed,
                 buffer.len()
@@ -417,4 +483,56 @@ mod tests {
         assert_eq!(col.name_at(0), Some("Small"));
         assert_eq!(col.name_at(1), Some("Large"));
     }
+
+    #[test]
+    fn test_enum_from_names_and_values() {
+        let items = vec![
+            EnumItem { name: "active".to_string(), value: 1 },
+            EnumItem { name: "inactive".to_string(), value: 2 },
+        ];
+        let type_ = Type::enum8(items.clone());
+
+        let col =
+            ColumnEnum8::from_names(type_.clone(), vec!["inactive", "active"])
+                .unwrap();
+        assert_eq!(col.len(), 2);
+        assert_eq!(col.at(0), 2);
+        assert_eq!(col.at(1), 1);
+
+        let col = ColumnEnum8::from_values(type_.clone(), vec![1, 2]);
+        assert_eq!(col.name_at(0), Some("active"));
+        assert_eq!(col.name_at(1), Some("inactive"));
+
+        assert!(
+            ColumnEnum8::from_names(type_, vec!["active", "deleted"]).is_err()
+        );
+
+        let col =
+            ColumnEnum16::from_names(Type::enum16(items), vec!["inactive"])
+                .unwrap();
+        assert_eq!(col.at(0), 2);
+    }
+
+    #[test]
+    fn test_enum8_names_on_read() {
+        let type_ = Type::parse("Enum8('red'=1,'green'=2)").unwrap();
+
+        // Values as a SELECT would deliver them, including one without a name
+        let mut col = ColumnEnum8::new(type_);
+        let mut buffer = &[2u8, 1, 3][..];
+        col.load_from_buffer(&mut buffer, 3).unwrap();
+
+        assert_eq!(col.name_at(0), Some("green"));
+        assert_eq!(col.name_at(1), Some("red"));
+        assert_eq!(col.name_at(2), None);
+        assert_eq!(col.name_at(3), None);
+
+        col.append_name("red").unwrap();
+        assert_eq!(col.at(3), 1);
+        assert!(matches!(
+            col.append_name("blue"),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert_eq!(col.len(), 4);
+    }
 }
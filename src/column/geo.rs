@@ -1,18 +1,255 @@
-//! Geo type helpers for ClickHouse geo column types.
+//! Geo type helpers and columns for ClickHouse geo column types.
 //!
 //! - **Point:** `Tuple(Float64, Float64)`
 //! - **Ring:** `Array(Point)`
 //! - **Polygon:** `Array(Ring)`
 //! - **MultiPolygon:** `Array(Polygon)`
 //!
-//! These types reuse the existing `ColumnTuple` and `ColumnArray`
-//! implementations with specific type constraints. No separate column struct
-//! is needed.
+//! [`ColumnPoint`] stores the two coordinates of each point; the other geo
+//! types are typed arrays over it ([`ColumnRing`], [`ColumnPolygon`],
+//! [`ColumnMultiPolygon`]) that keep the geo type name on the wire.
 
-use crate::types::{
-    Type,
-    TypeCode,
+use super::{
+    column_value::Value,
+    Column,
+    ColumnArray,
+    ColumnArrayT,
+    ColumnFloat64,
+    ColumnRef,
 };
+use crate::{
+    types::{
+        Type,
+        TypeCode,
+    },
+    Error,
+    Result,
+};
+use bytes::BytesMut;
+use std::sync::Arc;
+
+/// A ring as its points, in order.
+pub type Ring = Vec<(f64, f64)>;
+/// A polygon as its rings: the outer boundary first, then any holes.
+pub type Polygon = Vec<Ring>;
+/// A multi-polygon as its polygons.
+pub type MultiPolygon = Vec<Polygon>;
+
+/// Column for the `Point` geo type
+///
+/// Laid out like `Tuple(Float64, Float64)`: all x coordinates, then all y
+/// coordinates.
+pub struct ColumnPoint {
+    type_: Type,
+    x: ColumnFloat64,
+    y: ColumnFloat64,
+}
+
+impl ColumnPoint {
+    /// Create a new empty Point column.
+    pub fn new() -> Self {
+        Self {
+            type_: Type::point(),
+            x: ColumnFloat64::new(),
+            y: ColumnFloat64::new(),
+        }
+    }
+
+    /// Append a point as `(x, y)`.
+    pub fn append(&mut self, point: (f64, f64)) {
+        self.x.append(point.0);
+        self.y.append(point.1);
+    }
+
+    /// Get the point at `index` as `(x, y)`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<(f64, f64)> {
+        Some((*self.x.get(index)?, *self.y.get(index)?))
+    }
+
+    /// Returns the number of points in this column.
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Returns `true` if the column contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+}
+
+impl Default for ColumnPoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Column for ColumnPoint {
+    fn column_type(&self) -> &Type {
+        &self.type_
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+
+    fn clear(&mut self) {
+        self.x.clear();
+        self.y.clear();
+    }
+
+    fn reserve(&mut self, new_cap: usize) {
+        self.x.reserve(new_cap);
+        self.y.reserve(new_cap);
+    }
+
+    fn append_column(&mut self, other: ColumnRef) -> Result<()> {
+        let other =
+            other.as_any().downcast_ref::<ColumnPoint>().ok_or_else(|| {
+                Error::TypeMismatch {
+                    expected: self.type_.name(),
+                    actual: other.column_type().name(),
+                }
+            })?;
+        for index in 0..other.len() {
+            self.append(other.get(index).expect("index in bounds"));
+        }
+        Ok(())
+    }
+
+    fn load_from_buffer(
+        &mut self,
+        buffer: &mut &[u8],
+        rows: usize,
+    ) -> Result<()> {
+        self.x.load_from_buffer(buffer, rows)?;
+        self.y.load_from_buffer(buffer, rows)
+    }
+
+    fn save_to_buffer(&self, buffer: &mut BytesMut) -> Result<()> {
+        self.x.save_to_buffer(buffer)?;
+        self.y.save_to_buffer(buffer)
+    }
+
+    fn clone_empty(&self) -> ColumnRef {
+        Arc::new(ColumnPoint::new())
+    }
+
+    fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef> {
+        if begin + len > self.len() {
+            return Err(Error::InvalidArgument(format!(
+                "Slice out of bounds: begin={}, len={}, size={}",
+                begin,
+                len,
+                self.len()
+            )));
+        }
+        let mut sliced = ColumnPoint::new();
+        for index in begin..begin + len {
+            sliced.append(self.get(index).expect("index in bounds"));
+        }
+        Ok(Arc::new(sliced))
+    }
+
+    fn value_at(&self, index: usize) -> Result<Value> {
+        let (x, y) = self.get(index).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "Index {} out of bounds (size: {})",
+                index,
+                self.len()
+            ))
+        })?;
+        Ok(Value::Tuple(vec![Value::Float64(x), Value::Float64(y)]))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Column for the `Ring` geo type, an array of points.
+pub type ColumnRing = ColumnArrayT<ColumnPoint>;
+/// Column for the `Polygon` geo type, an array of rings.
+pub type ColumnPolygon = ColumnArrayT<ColumnRing>;
+/// Column for the `MultiPolygon` geo type, an array of polygons.
+pub type ColumnMultiPolygon = ColumnArrayT<ColumnPolygon>;
+
+/// Create an empty geo array column whose elements are `nested`
+fn geo_array<T: Column + 'static>(type_: Type, nested: T) -> ColumnArrayT<T> {
+    ColumnArrayT::from_inner(ColumnArray::from_parts(type_, Arc::new(nested)))
+}
+
+impl ColumnRing {
+    /// Create a new empty Ring column.
+    pub fn ring() -> Self {
+        geo_array(Type::ring(), ColumnPoint::new())
+    }
+
+    /// Append a ring.
+    pub fn append(&mut self, ring: &[(f64, f64)]) {
+        self.append_array(|points| {
+            for &point in ring {
+                points.append(point);
+            }
+        })
+        .expect("ring column is not shared");
+    }
+
+    /// Get the ring at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<Ring> {
+        let (start, end) = self.get_array_range(index)?;
+        (start..end).map(|i| self.nested_typed().get(i)).collect()
+    }
+}
+
+impl ColumnPolygon {
+    /// Create a new empty Polygon column.
+    pub fn polygon() -> Self {
+        geo_array(Type::polygon(), ColumnRing::ring())
+    }
+
+    /// Append a polygon.
+    pub fn append(&mut self, polygon: &[Ring]) {
+        self.append_array(|rings| {
+            for ring in polygon {
+                rings.append(ring);
+            }
+        })
+        .expect("polygon column is not shared");
+    }
+
+    /// Get the polygon at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<Polygon> {
+        let (start, end) = self.get_array_range(index)?;
+        (start..end).map(|i| self.nested_typed().get(i)).collect()
+    }
+}
+
+impl ColumnMultiPolygon {
+    /// Create a new empty MultiPolygon column.
+    pub fn multi_polygon() -> Self {
+        geo_array(Type::multi_polygon(), ColumnPolygon::polygon())
+    }
+
+    /// Append a multi-polygon.
+    pub fn append(&mut self, multi_polygon: &[Polygon]) {
+        self.append_array(|polygons| {
+            for polygon in multi_polygon {
+                polygons.append(polygon);
+            }
+        })
+        .expect("multi-polygon column is not shared");
+    }
+
+    /// Get the multi-polygon at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<MultiPolygon> {
+        let (start, end) = self.get_array_range(index)?;
+        (start..end).map(|i| self.nested_typed().get(i)).collect()
+    }
+}
 
 /// Helper to create a Point type (Tuple(Float64, Float64))
 pub fn point_type() -> Type {
@@ -43,6 +280,56 @@ pub fn multi_polygon_type() -> Type {
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
     use super::*;
+    use crate::io::block_stream::create_column;
+
+    #[test]
+    fn test_polygon_roundtrip() {
+        let square: Ring =
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 0.0)];
+        let hole: Ring = vec![(1.0, 1.0), (2.0, 1.0), (1.0, 1.0)];
+        let mut col = ColumnPolygon::polygon();
+        col.append(&[square.clone(), hole.clone()]);
+        col.append(&[]);
+
+        let mut buffer = BytesMut::new();
+        col.save_prefix(&mut buffer).unwrap();
+        col.save_to_buffer(&mut buffer).unwrap();
+
+        let mut loaded =
+            create_column(&Type::parse("Polygon").unwrap()).unwrap();
+        let mut reader = &buffer[..];
+        let loaded_mut = Arc::get_mut(&mut loaded).unwrap();
+        loaded_mut.load_prefix(&mut reader, 2).unwrap();
+        loaded_mut.load_from_buffer(&mut reader, 2).unwrap();
+        assert!(reader.is_empty());
+
+        let loaded = loaded.as_any().downcast_ref::<ColumnPolygon>().unwrap();
+        assert_eq!(loaded.column_type().name(), "Polygon");
+        assert_eq!(loaded.get(0), Some(vec![square, hole]));
+        assert_eq!(loaded.get(1), Some(vec![]));
+        assert_eq!(loaded.get(2), None);
+        assert_eq!(
+            loaded.nested_typed().nested_typed().get(1),
+            Some((4.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_point_value_and_create_column() {
+        let point = create_column(&Type::point()).unwrap();
+        assert!(point.as_any().is::<ColumnPoint>());
+        for name in ["Ring", "MultiPolygon"] {
+            let col = create_column(&Type::parse(name).unwrap()).unwrap();
+            assert_eq!(col.column_type().name(), name);
+        }
+
+        let mut col = ColumnPoint::new();
+        col.append((1.5, -2.0));
+        assert_eq!(
+            col.value_at(0).unwrap(),
+            Value::Tuple(vec![Value::Float64(1.5), Value::Float64(-2.0)])
+        );
+    }
 
     #[test]
     fn test_point_type() {
@@ -0,0 +1,214 @@
+//! Interval column implementation
+//!
+//! **ClickHouse Documentation:**
+//! [Interval](https://clickhouse.com/docs/en/sql-reference/data-types/special-data-types/interval)
+//!
+//! `IntervalSecond`, `IntervalDay`, ... are stored like `Int64` (a signed
+//! count of the unit per value). The unit is part of the type, so it is kept
+//! once per column rather than per value.
+
+use super::{
+    Column,
+    ColumnRef,
+};
+use crate::{
+    types::{
+        IntervalUnit,
+        Type,
+    },
+    Error,
+    Result,
+};
+use bytes::{
+    Buf,
+    BufMut,
+    BytesMut,
+};
+use std::sync::Arc;
+
+/// Column of `Interval*` values
+pub struct ColumnInterval {
+    type_: Type,
+    unit: IntervalUnit,
+    data: Vec<i64>,
+}
+
+impl ColumnInterval {
+    /// Create a new empty interval column with the given unit.
+    pub fn new(unit: IntervalUnit) -> Self {
+        Self { type_: Type::interval(unit), unit, data: Vec::new() }
+    }
+
+    /// Create a column from a vector of counts of `unit`.
+    pub fn from_vec(unit: IntervalUnit, values: Vec<i64>) -> Self {
+        Self { type_: Type::interval(unit), unit, data: values }
+    }
+
+    /// Append a count of the column's unit.
+    pub fn append(&mut self, value: i64) {
+        self.data.push(value);
+    }
+
+    /// Get the count and unit at index (panics if out of bounds)
+    pub fn at(&self, index: usize) -> (i64, IntervalUnit) {
+        (self.data[index], self.unit)
+    }
+
+    /// Return the count and unit at the given index, or `None` if out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<(i64, IntervalUnit)> {
+        self.data.get(index).map(|&v| (v, self.unit))
+    }
+
+    /// Returns the unit of every value in this column.
+    pub fn unit(&self) -> IntervalUnit {
+        self.unit
+    }
+
+    /// Returns the number of values in this column.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the column contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Raw counts, without the unit.
+    pub fn data(&self) -> &[i64] {
+        &self.data
+    }
+}
+
+impl Column for ColumnInterval {
+    fn column_type(&self) -> &Type {
+        &self.type_
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    fn reserve(&mut self, new_cap: usize) {
+        self.data.reserve(new_cap);
+    }
+
+    fn append_column(&mut self, other: ColumnRef) -> Result<()> {
+        let other = other
+            .as_any()
+            .downcast_ref::<ColumnInterval>()
+            .filter(|o| o.unit == self.unit)
+            .ok_or_else(|| Error::TypeMismatch {
+                expected: self.type_.name(),
+                actual: other.column_type().name(),
+            })?;
+
+        self.data.extend_from_slice(&other.data);
+        Ok(())
+    }
+
+    fn load_from_buffer(
+        &mut self,
+        buffer: &mut &[u8],
+        rows: usize,
+    ) -> Result<()> {
+        let bytes_needed = rows * 8;
+        if buffer.len() < bytes_needed {
+            return Err(Error::Incomplete(format!(
+                "Buffer underflow: need {} bytes, have {}",
+                bytes_needed,
+                buffer.len()
+            )));
+        }
+
+        self.data.reserve(rows);
+        for _ in 0..rows {
+            self.data.push(buffer.get_i64_le());
+        }
+        Ok(())
+    }
+
+    fn save_to_buffer(&self, buffer: &mut BytesMut) -> Result<()> {
+        buffer.reserve(self.data.len() * 8);
+        for &value in &self.data {
+            buffer.put_i64_le(value);
+        }
+        Ok(())
+    }
+
+    fn clone_empty(&self) -> ColumnRef {
+        Arc::new(ColumnInterval::new(self.unit))
+    }
+
+    fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef> {
+        if begin + len > self.data.len() {
+            return Err(Error::InvalidArgument(format!(
+                "Slice range out of bounds: begin={}, len={}, size={}",
+                begin,
+                len,
+                self.data.len()
+            )));
+        }
+
+        Ok(Arc::new(ColumnInterval::from_vec(
+            self.unit,
+            self.data[begin..begin + len].to_vec(),
+        )))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_column_roundtrip() {
+        let col = ColumnInterval::from_vec(IntervalUnit::Day, vec![3, -1]);
+        assert_eq!(col.column_type().name(), "IntervalDay");
+
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 16);
+        assert_eq!(&buffer[..8], &3i64.to_le_bytes());
+
+        let mut decoded = ColumnInterval::new(IntervalUnit::Day);
+        let mut slice = &buffer[..];
+        decoded.load_from_buffer(&mut slice, 2).unwrap();
+        assert!(slice.is_empty());
+        assert_eq!(decoded.at(0), (3, IntervalUnit::Day));
+        assert_eq!(decoded.at(1), (-1, IntervalUnit::Day));
+        assert_eq!(decoded.get(2), None);
+    }
+
+    #[test]
+    fn test_interval_append_column_requires_same_unit() {
+        let mut col = ColumnInterval::from_vec(IntervalUnit::Second, vec![1]);
+        let same = ColumnInterval::from_vec(IntervalUnit::Second, vec![2]);
+        col.append_column(Arc::new(same)).unwrap();
+        assert_eq!(col.data(), &[1, 2]);
+
+        let other = ColumnInterval::from_vec(IntervalUnit::Minute, vec![3]);
+        assert!(col.append_column(Arc::new(other)).is_err());
+    }
+
+    #[test]
+    fn test_interval_column_underflow() {
+        let mut col = ColumnInterval::new(IntervalUnit::Hour);
+        let mut slice: &[u8] = &[0; 7];
+        assert!(col.load_from_buffer(&mut slice, 1).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     Column,
     ColumnRef,
+    ColumnTyped,
 };
 use crate::{
     types::Type,
@@ -8,7 +9,13 @@ use crate::{
     Result,
 };
 use bytes::BytesMut;
-use std::sync::Arc;
+use std::{
+    net::{
+        IpAddr,
+        Ipv4Addr,
+    },
+    sync::Arc,
+};
 
 /// Column for IPv4 addresses (stored as UInt32)
 ///
@@ -71,11 +78,41 @@ impl ColumnIpv4 {
             .append(value);
     }
 
+    /// Append an [`Ipv4Addr`]
+    pub fn append_addr(&mut self, addr: Ipv4Addr) {
+        self.append(u32::from(addr));
+    }
+
+    /// Append an [`IpAddr`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ip` is an IPv6 address that is not
+    /// IPv4-mapped (`::ffff:a.b.c.d`).
+    pub fn append_ip(&mut self, ip: IpAddr) -> Result<()> {
+        let addr = match ip {
+            IpAddr::V4(addr) => addr,
+            IpAddr::V6(addr) => addr.to_ipv4_mapped().ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "IPv6 address {} cannot be stored in an IPv4 column",
+                    addr
+                ))
+            })?,
+        };
+        self.append_addr(addr);
+        Ok(())
+    }
+
     /// Get IPv4 at index as u32
     pub fn at(&self, index: usize) -> u32 {
         self.data.at(index)
     }
 
+    /// Get IPv4 at index as an [`Ipv4Addr`], or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<Ipv4Addr> {
+        self.data.get(index).map(|&ip| Ipv4Addr::from(ip))
+    }
+
     /// Format IPv4 at index as dotted decimal string
     pub fn as_string(&self, index: usize) -> String {
         let ip = self.data.at(index);
@@ -190,6 +227,16 @@ impl Column for ColumnIpv4 {
     }
 }
 
+impl ColumnTyped<Ipv4Addr> for ColumnIpv4 {
+    fn get(&self, index: usize) -> Option<Ipv4Addr> {
+        ColumnIpv4::get(self, index)
+    }
+
+    fn append(&mut self, value: Ipv4Addr) {
+        self.append_addr(value)
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -230,4 +277,25 @@ mod tests {
         assert_eq!(col.as_string(0), "255.255.255.255");
         assert_eq!(col.as_string(1), "127.0.0.1");
     }
+
+    #[test]
+    fn test_ipv4_std_net_roundtrip() {
+        let mut col = ColumnIpv4::new(Type::ipv4());
+        col.append_addr(Ipv4Addr::new(192, 168, 1, 1));
+        col.append_ip("10.0.0.1".parse().unwrap()).unwrap();
+        col.append_ip("::ffff:127.0.0.1".parse().unwrap()).unwrap();
+        assert!(col.append_ip("::1".parse().unwrap()).is_err());
+
+        assert_eq!(col.len(), 3);
+        assert_eq!(col.at(0), 0xC0A80101);
+        assert_eq!(col.get(0), Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(col.get(1), Some(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(col.get(2), Some(Ipv4Addr::LOCALHOST));
+        assert_eq!(col.get(3), None);
+
+        // Stored as a little-endian UInt32 on the wire
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(&buffer[..4], &[1, 1, 168, 192]);
+    }
 }
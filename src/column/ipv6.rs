@@ -1,6 +1,7 @@
 use super::{
     Column,
     ColumnRef,
+    ColumnTyped,
 };
 use crate::{
     types::Type,
@@ -8,7 +9,13 @@ use crate::{
     Result,
 };
 use bytes::BytesMut;
-use std::sync::Arc;
+use std::{
+    net::{
+        IpAddr,
+        Ipv6Addr,
+    },
+    sync::Arc,
+};
 
 /// Column for IPv6 addresses (stored as FixedString(16) - 16 bytes)
 ///
@@ -53,11 +60,31 @@ impl ColumnIpv6 {
         self.data.push(bytes);
     }
 
+    /// Append an [`Ipv6Addr`]
+    pub fn append_addr(&mut self, addr: Ipv6Addr) {
+        self.data.push(addr.octets());
+    }
+
+    /// Append an [`IpAddr`], storing IPv4 addresses IPv4-mapped
+    /// (`::ffff:a.b.c.d`) as ClickHouse does.
+    pub fn append_ip(&mut self, ip: IpAddr) {
+        let addr = match ip {
+            IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+            IpAddr::V6(addr) => addr,
+        };
+        self.append_addr(addr);
+    }
+
     /// Get IPv6 at index as 16-byte array
     pub fn at(&self, index: usize) -> [u8; 16] {
         self.data[index]
     }
 
+    /// Get IPv6 at index as an [`Ipv6Addr`], or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<Ipv6Addr> {
+        self.data.get(index).map(|&bytes| Ipv6Addr::from(bytes))
+    }
+
     /// Format IPv6 at index as string
     pub fn as_string(&self, index: usize) -> String {
         format_ipv6(&self.data[index])
@@ -111,7 +138,7 @@ impl Column for ColumnIpv6 {
     ) -> Result<()> {
         let bytes_needed = rows * 16;
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow: need {} bytes for IPv6, have {}",
                 bytes_needed,
                 buffer.len()
@@ -180,6 +207,16 @@ impl Column for ColumnIpv6 {
     }
 }
 
+impl ColumnTyped<Ipv6Addr> for ColumnIpv6 {
+    fn get(&self, index: usize) -> Option<Ipv6Addr> {
+        ColumnIpv6::get(self, index)
+    }
+
+    fn append(&mut self, value: Ipv6Addr) {
+        self.append_addr(value)
+    }
+}
+
 /// Parse IPv6 string to 16-byte array
 fn parse_ipv6(s: &str) -> Result<[u8; 16]> {
     let parts: Vec<&str> = s.split("::").collect();
@@ -362,4 +399,26 @@ mod tests {
         assert_eq!(col.len(), 1);
         assert_eq!(col.at(0), bytes);
     }
+
+    #[test]
+    fn test_ipv6_std_net_roundtrip() {
+        let mut col = ColumnIpv6::new(Type::ipv6());
+        col.append_addr(Ipv6Addr::LOCALHOST);
+        col.append_ip("192.168.1.1".parse().unwrap());
+        col.append_ip("fe80::1".parse().unwrap());
+
+        assert_eq!(col.get(0), Some(Ipv6Addr::LOCALHOST));
+        assert_eq!(col.as_string(0), "::1");
+        assert_eq!(
+            col.get(1),
+            Some(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc0a8, 0x0101))
+        );
+        assert_eq!(col.get(2), Some("fe80::1".parse().unwrap()));
+        assert_eq!(col.get(3), None);
+
+        // Stored as 16 big-endian bytes on the wire
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(&buffer[..16], &Ipv6Addr::LOCALHOST.octets());
+    }
 }
@@ -0,0 +1,217 @@
+use super::{
+    column_value::Value,
+    Column,
+    ColumnRef,
+    ColumnString,
+};
+use crate::{
+    types::Type,
+    Error,
+    Result,
+};
+use bytes::BytesMut;
+use std::sync::Arc;
+
+/// Column for `JSON` documents, held as their text
+///
+/// Uses the String-backed representation: each row is one serialized
+/// document, laid out on the wire exactly like a `String` column. Servers
+/// with the new JSON type send this form when the query sets
+/// `output_format_native_write_json_as_string = 1`; without it their
+/// binary JSON serialization cannot be decoded. Inserted documents are
+/// parsed by the server.
+pub struct ColumnJson {
+    type_: Type,
+    data: ColumnString,
+}
+
+impl ColumnJson {
+    /// Create a new empty JSON column.
+    pub fn new(type_: Type) -> Self {
+        Self { data: ColumnString::new(Type::string()), type_ }
+    }
+
+    /// Create a column from serialized documents.
+    pub fn from_vec(type_: Type, documents: Vec<String>) -> Self {
+        Self { data: ColumnString::from_vec(Type::string(), documents), type_ }
+    }
+
+    /// Append a serialized document such as `{"a": 1}`.
+    ///
+    /// The text is not validated here; the server rejects malformed
+    /// documents on insert.
+    pub fn append(&mut self, document: impl Into<String>) {
+        self.data.append(document);
+    }
+
+    /// Get the document at `index` as text, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.data.get(index)
+    }
+
+    /// Parse the document at `index` (requires the `json` feature).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `index` is out of bounds and
+    /// `Error::Protocol` if the text is not valid JSON.
+    #[cfg(feature = "json")]
+    pub fn get_json(&self, index: usize) -> Result<serde_json::Value> {
+        let text = self.get(index).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "Index {} out of bounds for column of {} rows",
+                index,
+                self.len()
+            ))
+        })?;
+        serde_json::from_str(text).map_err(|e| {
+            Error::Protocol(format!("Invalid JSON document: {}", e))
+        })
+    }
+
+    /// Append a document serialized from `value` (requires the `json`
+    /// feature).
+    #[cfg(feature = "json")]
+    pub fn append_json(&mut self, value: &serde_json::Value) {
+        self.append(value.to_string());
+    }
+
+    /// Returns the number of documents in this column.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the column contains no documents.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterate over the documents as text.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.data.iter()
+    }
+}
+
+impl Column for ColumnJson {
+    fn column_type(&self) -> &Type {
+        &self.type_
+    }
+
+    fn size(&self) -> usize {
+        self.data.size()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    fn reserve(&mut self, new_cap: usize) {
+        self.data.reserve(new_cap);
+    }
+
+    fn append_column(&mut self, other: ColumnRef) -> Result<()> {
+        let other =
+            other.as_any().downcast_ref::<ColumnJson>().ok_or_else(|| {
+                Error::TypeMismatch {
+                    expected: self.type_.name(),
+                    actual: other.column_type().name(),
+                }
+            })?;
+        for document in other.iter() {
+            self.append(document);
+        }
+        Ok(())
+    }
+
+    fn load_from_buffer(
+        &mut self,
+        buffer: &mut &[u8],
+        rows: usize,
+    ) -> Result<()> {
+        self.data.load_from_buffer(buffer, rows)
+    }
+
+    fn save_to_buffer(&self, buffer: &mut BytesMut) -> Result<()> {
+        self.data.save_to_buffer(buffer)
+    }
+
+    fn clone_empty(&self) -> ColumnRef {
+        Arc::new(ColumnJson::new(self.type_.clone()))
+    }
+
+    fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef> {
+        if begin + len > self.len() {
+            return Err(Error::InvalidArgument(format!(
+                "Slice out of bounds: begin={}, len={}, size={}",
+                begin,
+                len,
+                self.len()
+            )));
+        }
+        let documents =
+            self.iter().skip(begin).take(len).map(str::to_string).collect();
+        Ok(Arc::new(ColumnJson::from_vec(self.type_.clone(), documents)))
+    }
+
+    fn value_at(&self, index: usize) -> Result<Value> {
+        self.get(index).map(|text| Value::String(text.to_string())).ok_or_else(
+            || {
+                Error::InvalidArgument(format!(
+                    "Index {} out of bounds for column of {} rows",
+                    index,
+                    self.len()
+                ))
+            },
+        )
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_uses_string_wire_format() {
+        let mut col = ColumnJson::new(Type::json());
+        col.append(r#"{"a":1}"#);
+        col.append(r#"{"b":[true,null]}"#);
+
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        let mut strings = BytesMut::new();
+        ColumnString::from_vec(
+            Type::string(),
+            vec![r#"{"a":1}"#.to_string(), r#"{"b":[true,null]}"#.to_string()],
+        )
+        .save_to_buffer(&mut strings)
+        .unwrap();
+        assert_eq!(buffer, strings);
+
+        let mut loaded = ColumnJson::new(Type::json());
+        loaded.load_from_buffer(&mut &buffer[..], 2).unwrap();
+        assert_eq!(loaded.get(1), Some(r#"{"b":[true,null]}"#));
+        assert_eq!(loaded.column_type().name(), "JSON");
+        assert_eq!(loaded.slice(1, 1).unwrap().size(), 1);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_get_json() {
+        let mut col = ColumnJson::new(Type::json());
+        col.append_json(&serde_json::json!({"user": {"id": 7}}));
+        col.append("not json");
+
+        assert_eq!(col.get_json(0).unwrap()["user"]["id"], 7);
+        assert!(matches!(col.get_json(1), Err(Error::Protocol(_))));
+        assert!(matches!(col.get_json(2), Err(Error::InvalidArgument(_))));
+    }
+}
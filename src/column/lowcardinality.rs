@@ -40,8 +40,10 @@
 //! - See ClickHouse tips: <https://www.tinybird.co/blog-posts/tips-10-null-behavior-with-lowcardinality-columns>
 
 use super::{
+    nullable::ColumnNullable,
     Column,
     ColumnRef,
+    ColumnTyped,
 };
 use crate::{
     types::Type,
@@ -148,6 +150,92 @@ impl ColumnLowCardinality {
         self.indices[index]
     }
 
+    /// Return the dictionary index of `row`, or `None` if out of bounds.
+    pub fn get_index(&self, row: usize) -> Option<usize> {
+        self.indices.get(row).map(|&index| index as usize)
+    }
+
+    /// Return the dictionary entry of `row` as a single-row column, or
+    /// `None` if out of bounds.
+    pub fn get_value(&self, row: usize) -> Option<ColumnRef> {
+        let index = self.get_index(row)?;
+        self.dictionary.slice(index, 1).ok()
+    }
+
+    /// Check whether `row` is NULL. Always `false` unless the dictionary is
+    /// `Nullable`.
+    pub fn is_null(&self, row: usize) -> bool {
+        let Some(index) = self.get_index(row) else {
+            return false;
+        };
+        self.dictionary
+            .as_any()
+            .downcast_ref::<ColumnNullable>()
+            .is_some_and(|dict| dict.is_null(index))
+    }
+
+    /// Get the value of `row`, or `None` if it is NULL or out of bounds
+    ///
+    /// `C` is the dictionary column type, or the nested type for a
+    /// `Nullable` dictionary, e.g. `get::<ColumnString, String>` works for
+    /// both `LowCardinality(String)` and `LowCardinality(Nullable(String))`.
+    pub fn get<C, V>(&self, row: usize) -> Option<V>
+    where
+        C: ColumnTyped<V> + 'static,
+    {
+        let index = self.get_index(row)?;
+        let any = self.dictionary.as_any();
+        match any.downcast_ref::<ColumnNullable>() {
+            Some(dict) => dict.get_opt::<C, V>(index),
+            None => any.downcast_ref::<C>()?.get(index),
+        }
+    }
+
+    /// Decode the distinct values of this column, in dictionary order
+    ///
+    /// Only the dictionary is decoded, so this is much cheaper than
+    /// expanding every row. Entries no row refers to and the NULL key of a
+    /// `Nullable` dictionary are skipped. `C` is chosen as for
+    /// [`ColumnLowCardinality::get`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TypeMismatch` if the dictionary does not hold `C`
+    /// values.
+    pub fn distinct_values<C, V>(&self) -> Result<Vec<V>>
+    where
+        C: ColumnTyped<V> + 'static,
+    {
+        let nullable =
+            self.dictionary.as_any().downcast_ref::<ColumnNullable>();
+        let values = match nullable {
+            Some(dict) => dict.nested_ref(),
+            None => self.dictionary.clone(),
+        };
+        let values = values.as_any().downcast_ref::<C>().ok_or_else(|| {
+            Error::TypeMismatch {
+                expected: std::any::type_name::<C>().to_string(),
+                actual: values.column_type().name(),
+            }
+        })?;
+
+        let mut used = vec![false; self.dictionary.size()];
+        for &index in &self.indices {
+            if let Some(slot) = used.get_mut(index as usize) {
+                *slot = true;
+            }
+        }
+
+        Ok(used
+            .iter()
+            .enumerate()
+            .filter(|&(index, &used)| {
+                used && !nullable.is_some_and(|dict| dict.is_null(index))
+            })
+            .filter_map(|(index, _)| values.get(index))
+            .collect())
+    }
+
     /// Returns the number of values (rows) in this column.
     pub fn len(&self) -> usize {
         self.indices.len()
@@ -285,7 +373,7 @@ impl Column for ColumnLowCardinality {
         // Read key_version (should be 1)
         // Matches C++ LoadPrefix
         if buffer.len() < 8 {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Not enough data for LowCardinality key version".to_string(),
             ));
         }
@@ -322,7 +410,7 @@ impl Column for ColumnLowCardinality {
 
         // Read index_serialization_type
         if buffer.len() < 8 {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Not enough data for LowCardinality index serialization type"
                     .to_string(),
             ));
@@ -349,7 +437,7 @@ impl Column for ColumnLowCardinality {
 
         // Read number of dictionary keys
         if buffer.len() < 8 {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Not enough data for dictionary size".to_string(),
             ));
         }
@@ -369,7 +457,6 @@ impl Column for ColumnLowCardinality {
             })?;
 
             // Check if dictionary is Nullable - if so, load only nested data
-            use super::nullable::ColumnNullable;
             if let Some(nullable_col) =
                 dict_mut.as_any_mut().downcast_mut::<ColumnNullable>()
             {
@@ -385,9 +472,10 @@ impl Column for ColumnLowCardinality {
                     })?;
                 nested_mut.load_from_buffer(buffer, number_of_keys)?;
 
-                // After loading, mark all entries as non-null for now
-                // (The C++ code reconstructs the null bitmap after loading)
-                for _ in 0..number_of_keys {
+                // Rebuild the null bitmap: the server always keeps the NULL
+                // key at dictionary index 0
+                nullable_col.append_null();
+                for _ in 1..number_of_keys {
                     nullable_col.append_non_null();
                 }
             } else {
@@ -421,7 +509,7 @@ impl Column for ColumnLowCardinality {
                 // UInt8 indices
                 for _ in 0..rows {
                     if buffer.is_empty() {
-                        return Err(Error::Protocol(
+                        return Err(Error::Incomplete(
                             "Not enough data for LowCardinality index"
                                 .to_string(),
                         ));
@@ -434,7 +522,7 @@ impl Column for ColumnLowCardinality {
                 // UInt16 indices
                 for _ in 0..rows {
                     if buffer.len() < 2 {
-                        return Err(Error::Protocol(
+                        return Err(Error::Incomplete(
                             "Not enough data for LowCardinality index"
                                 .to_string(),
                         ));
@@ -447,7 +535,7 @@ impl Column for ColumnLowCardinality {
                 // UInt32 indices
                 for _ in 0..rows {
                     if buffer.len() < 4 {
-                        return Err(Error::Protocol(
+                        return Err(Error::Incomplete(
                             "Not enough data for LowCardinality index"
                                 .to_string(),
                         ));
@@ -460,7 +548,7 @@ impl Column for ColumnLowCardinality {
                 // UInt64 indices
                 for _ in 0..rows {
                     if buffer.len() < 8 {
-                        return Err(Error::Protocol(
+                        return Err(Error::Incomplete(
                             "Not enough data for LowCardinality index"
                                 .to_string(),
                         ));
@@ -524,7 +612,6 @@ impl Column for ColumnLowCardinality {
         // IMPORTANT: For Nullable dictionaries, only write the NESTED column
         // data (matching C++ implementation in
         // lowcardinality.cpp::SaveBody)
-        use super::nullable::ColumnNullable;
         if let Some(nullable_col) =
             self.dictionary.as_any().downcast_ref::<ColumnNullable>()
         {
@@ -921,4 +1008,140 @@ mod tests {
         // Full round-trip testing for Nullable LowCardinality is complex
         // due to the nested save format. The integration tests cover this.
     }
+
+    #[test]
+    fn test_lowcardinality_nullable_values() {
+        use crate::column::ColumnString;
+
+        let lc_type = Type::LowCardinality {
+            nested_type: Box::new(Type::Nullable {
+                nested_type: Box::new(Type::Simple(TypeCode::String)),
+            }),
+        };
+
+        // Server layout: the NULL key sits at dictionary index 0
+        let mut buffer = BytesMut::new();
+        buffer.put_u64_le(1); // key_version
+        buffer.put_u64_le(1 << 9); // UInt8 indices, additional keys
+        buffer.put_u64_le(3); // number_of_keys
+        for key in ["", "US", "UK"] {
+            buffer.put_u8(key.len() as u8);
+            buffer.put_slice(key.as_bytes());
+        }
+        buffer.put_u64_le(4); // number_of_rows
+        buffer.put_slice(&[1, 2, 0, 1]);
+
+        let mut col = ColumnLowCardinality::new(lc_type);
+        let mut slice = &buffer[..];
+        col.load_prefix(&mut slice, 4).unwrap();
+        col.load_from_buffer(&mut slice, 4).unwrap();
+        assert!(slice.is_empty());
+
+        let values: Vec<Option<String>> =
+            (0..4).map(|row| col.get::<ColumnString, String>(row)).collect();
+        assert_eq!(
+            values,
+            vec![
+                Some("US".to_string()),
+                Some("UK".to_string()),
+                None,
+                Some("US".to_string()),
+            ]
+        );
+        assert_eq!(col.get_index(2), Some(0));
+        assert!(col.is_null(2));
+        assert!(!col.is_null(0));
+        assert_eq!(col.get_index(4), None);
+        assert!(col.get_value(4).is_none());
+
+        let value = col.get_value(1).unwrap();
+        let value = value.as_any().downcast_ref::<ColumnNullable>().unwrap();
+        assert_eq!(value.get_opt::<ColumnString, String>(0).unwrap(), "UK");
+    }
+
+    #[test]
+    fn test_lowcardinality_distinct_values() {
+        use crate::column::{
+            column_value::ColumnValue,
+            ColumnString,
+            ColumnUInt64,
+        };
+
+        let lc_type = Type::LowCardinality {
+            nested_type: Box::new(Type::Simple(TypeCode::String)),
+        };
+        let mut col = ColumnLowCardinality::new(lc_type);
+        let countries = ["US", "UK", "DE", "FR", "JP"];
+        for row in 0..1000 {
+            let country = countries[row % countries.len()];
+            col.append_unsafe(&ColumnValue::from_string(country)).unwrap();
+        }
+
+        assert_eq!(col.len(), 1000);
+        assert_eq!(
+            col.distinct_values::<ColumnString, String>().unwrap(),
+            countries
+        );
+        assert!(matches!(
+            col.distinct_values::<ColumnUInt64, u64>(),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lowcardinality_nullable_distinct_values() {
+        use crate::column::ColumnString;
+
+        let lc_type = Type::LowCardinality {
+            nested_type: Box::new(Type::Nullable {
+                nested_type: Box::new(Type::Simple(TypeCode::String)),
+            }),
+        };
+
+        // "FR" is in the dictionary but no row refers to it
+        let mut buffer = BytesMut::new();
+        buffer.put_u64_le(1); // key_version
+        buffer.put_u64_le(1 << 9); // UInt8 indices, additional keys
+        buffer.put_u64_le(4); // number_of_keys
+        for key in ["", "US", "FR", "UK"] {
+            buffer.put_u8(key.len() as u8);
+            buffer.put_slice(key.as_bytes());
+        }
+        buffer.put_u64_le(4); // number_of_rows
+        buffer.put_slice(&[3, 0, 1, 3]);
+
+        let mut col = ColumnLowCardinality::new(lc_type);
+        let mut slice = &buffer[..];
+        col.load_prefix(&mut slice, 4).unwrap();
+        col.load_from_buffer(&mut slice, 4).unwrap();
+
+        assert_eq!(
+            col.distinct_values::<ColumnString, String>().unwrap(),
+            ["US", "UK"]
+        );
+    }
+
+    #[test]
+    fn test_lowcardinality_get_string() {
+        use crate::column::{
+            column_value::ColumnValue,
+            ColumnString,
+        };
+
+        let lc_type = Type::LowCardinality {
+            nested_type: Box::new(Type::Simple(TypeCode::String)),
+        };
+        let mut col = ColumnLowCardinality::new(lc_type);
+        for status in ["active", "inactive", "active"] {
+            col.append_unsafe(&ColumnValue::from_string(status)).unwrap();
+        }
+
+        assert_eq!(col.get_index(2), col.get_index(0));
+        assert_eq!(
+            col.get::<ColumnString, String>(1).as_deref(),
+            Some("inactive")
+        );
+        assert!(!col.is_null(0));
+        assert_eq!(col.get::<ColumnString, String>(3), None);
+    }
 }
@@ -7,6 +7,7 @@ use super::{
     Column,
     ColumnArray,
     ColumnRef,
+    ColumnTuple,
 };
 use crate::{
     types::Type,
@@ -99,6 +100,54 @@ impl ColumnMap {
         self.data.slice(index, 1)
     }
 
+    /// Get the number of key/value pairs in the map at the given index
+    pub fn get_map_len(&self, index: usize) -> Option<usize> {
+        self.as_array()?.get_array_len(index)
+    }
+
+    /// Get the keys of the map at the given index, in entry order
+    ///
+    /// The result is a slice of the key column, so it can be downcast to
+    /// the key column type. Entry `i` of [`values_at`](Self::values_at)
+    /// belongs to key `i`.
+    pub fn keys_at(&self, index: usize) -> Result<ColumnRef> {
+        self.entry_column(index, 0)
+    }
+
+    /// Get the values of the map at the given index, in entry order
+    pub fn values_at(&self, index: usize) -> Result<ColumnRef> {
+        self.entry_column(index, 1)
+    }
+
+    /// Element `position` (0 = keys, 1 = values) of the `Tuple(K, V)`
+    /// entries of the map at `index`
+    fn entry_column(
+        &self,
+        index: usize,
+        position: usize,
+    ) -> Result<ColumnRef> {
+        let array = self.as_array().ok_or_else(|| Error::TypeMismatch {
+            expected: "Array(Tuple(K, V))".to_string(),
+            actual: self.data.column_type().name(),
+        })?;
+        let entries = array.get_array(index).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "Map index out of bounds: index={}, size={}",
+                index,
+                array.len()
+            ))
+        })?;
+        entries
+            .as_any()
+            .downcast_ref::<ColumnTuple>()
+            .filter(|tuple| tuple.column_count() == 2)
+            .map(|tuple| tuple.column_at(position))
+            .ok_or_else(|| Error::TypeMismatch {
+                expected: "Tuple(K, V)".to_string(),
+                actual: entries.column_type().name(),
+            })
+    }
+
     /// Returns the number of map entries (rows) in this column.
     pub fn len(&self) -> usize {
         self.data.size()
@@ -270,4 +319,42 @@ mod tests {
 
         assert_eq!(col1.len(), col2.len());
     }
+
+    #[test]
+    fn test_map_entries_at() {
+        use crate::column::{
+            numeric::ColumnUInt64,
+            ColumnString,
+        };
+
+        let map_type = Type::map(Type::string(), Type::uint64());
+
+        // Rows: {'a': 1, 'b': 2} and {}
+        let mut keys = ColumnString::new(Type::string());
+        keys.append("a");
+        keys.append("b");
+        let values = ColumnUInt64::from_vec(Type::uint64(), vec![1, 2]);
+        let entries = ColumnTuple::new(
+            Type::tuple(vec![Type::string(), Type::uint64()]),
+            vec![Arc::new(keys), Arc::new(values)],
+        );
+        let mut array = ColumnArray::with_nested(Arc::new(entries));
+        array.append_len(2);
+        array.append_len(0);
+        let col = ColumnMap::from_array(map_type, Arc::new(array));
+
+        assert_eq!(col.get_map_len(0), Some(2));
+        assert_eq!(col.get_map_len(1), Some(0));
+        assert_eq!(col.get_map_len(2), None);
+
+        let keys = col.keys_at(0).unwrap();
+        let keys = keys.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(keys.iter().collect::<Vec<_>>(), vec!["a", "b"]);
+        let values = col.values_at(0).unwrap();
+        let values = values.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(values.data(), &[1, 2]);
+
+        assert_eq!(col.keys_at(1).unwrap().size(), 0);
+        assert!(matches!(col.values_at(2), Err(Error::InvalidArgument(_))));
+    }
 }
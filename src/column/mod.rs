@@ -39,29 +39,38 @@
 
 /// Array column type (`Array(T)`).
 pub mod array;
+/// Bool column type.
+pub mod boolean;
 /// Column value extraction and insertion helpers.
 pub mod column_value;
 /// Date and DateTime column types.
 pub mod date;
-/// Decimal column types (`Decimal32`, `Decimal64`, `Decimal128`).
+/// Decimal column types (`Decimal32`, `Decimal64`, `Decimal128`,
+/// `Decimal256`).
 pub mod decimal;
 /// Enum8 and Enum16 column types.
 pub mod enum_column;
-/// Geo type helpers (Point, Ring, Polygon, MultiPolygon).
+/// Geo column types (Point, Ring, Polygon, MultiPolygon).
 pub mod geo;
+/// Interval column types (`IntervalSecond`, `IntervalDay`, ...).
+pub mod interval;
 /// IPv4 column type.
 pub mod ipv4;
 /// IPv6 column type.
 pub mod ipv6;
+/// JSON column type (String-backed).
+pub mod json;
 /// LowCardinality column type (dictionary encoding).
 pub mod lowcardinality;
 /// Map column type (`Map(K, V)`).
 pub mod map;
+/// Nested column type (`Nested(a T1, b T2, ...)`).
+pub mod nested;
 /// Nothing/Void column type.
 pub mod nothing;
 /// Nullable column type (`Nullable(T)`).
 pub mod nullable;
-/// Numeric column types (integers, floats, bool).
+/// Numeric column types (integers, floats).
 pub mod numeric;
 /// String and FixedString column types.
 pub mod string;
@@ -75,6 +84,8 @@ pub use array::{
     ColumnArray,
     ColumnArrayT,
 };
+pub use boolean::ColumnBool;
+pub use column_value::Value;
 pub use date::{
     ColumnDate,
     ColumnDate32,
@@ -86,10 +97,19 @@ pub use enum_column::{
     ColumnEnum16,
     ColumnEnum8,
 };
+pub use geo::{
+    ColumnMultiPolygon,
+    ColumnPoint,
+    ColumnPolygon,
+    ColumnRing,
+};
+pub use interval::ColumnInterval;
 pub use ipv4::ColumnIpv4;
 pub use ipv6::ColumnIpv6;
+pub use json::ColumnJson;
 pub use lowcardinality::ColumnLowCardinality;
 pub use map::ColumnMap;
+pub use nested::ColumnNested;
 pub use nothing::ColumnNothing;
 pub use nullable::ColumnNullable;
 pub use numeric::*;
@@ -105,6 +125,7 @@ pub use uuid::{
 
 use crate::{
     types::Type,
+    Error,
     Result,
 };
 use bytes::BytesMut;
@@ -169,6 +190,38 @@ pub trait Column: Send + Sync {
     /// Create a slice of this column
     fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef>;
 
+    /// Read the value at `index` without knowing the concrete column type
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `index` is out of bounds, and
+    /// `Error::NotImplemented` for column types from outside this crate.
+    ///
+    /// # Example
+    /// ```
+    /// use clickhouse_native_client::column::{
+    ///     Column, ColumnString, Value,
+    /// };
+    ///
+    /// let column = ColumnString::from(vec!["a", "b"]);
+    /// assert_eq!(column.value_at(1).unwrap(), Value::String("b".into()));
+    /// ```
+    fn value_at(&self, index: usize) -> Result<Value> {
+        if index >= self.size() {
+            return Err(Error::InvalidArgument(format!(
+                "Index {} out of bounds (size: {})",
+                index,
+                self.size()
+            )));
+        }
+        column_value::value_of(self.as_any(), index).unwrap_or_else(|| {
+            Err(Error::NotImplemented(format!(
+                "value_at for column type {}",
+                self.column_type().name()
+            )))
+        })
+    }
+
     /// Downcast to a concrete column type
     fn as_any(&self) -> &dyn std::any::Any;
 
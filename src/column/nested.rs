@@ -0,0 +1,343 @@
+//! Nested column implementation.
+//!
+//! ClickHouse `Nested(a T1, b T2)` is a set of equal-length arrays, one per
+//! sub-field. On the wire it is `Array(Tuple(T1, T2))`: one offsets array
+//! shared by every sub-field, followed by each sub-field's elements. This
+//! module wraps `ColumnArray` with that tuple element type and splits it
+//! back into one `Array(T)` column per sub-field.
+
+use super::{
+    Column,
+    ColumnArray,
+    ColumnRef,
+    ColumnTuple,
+};
+use crate::{
+    types::Type,
+    Error,
+    Result,
+};
+use bytes::BytesMut;
+use std::sync::Arc;
+
+/// Column for `Nested(...)` type, stored internally as
+/// `Array(Tuple(...))`.
+pub struct ColumnNested {
+    type_: Type,
+    data: ColumnRef, // Array of Tuple(field1, field2, ...)
+}
+
+impl ColumnNested {
+    /// Create a new empty nested column for the given `Nested` type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `type_` is not `Type::Nested`.
+    pub fn new(type_: Type) -> Self {
+        let array_type = type_
+            .nested_storage_type()
+            .expect("ColumnNested requires Nested type");
+        let data: ColumnRef = Arc::new(ColumnArray::new(array_type));
+
+        Self { type_, data }
+    }
+
+    /// Build a nested column from one `Array(T)` column per sub-field.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `type_` is not `Type::Nested`, the
+    /// number of arrays differs from the number of sub-fields, or the
+    /// arrays of one row have different lengths.
+    pub fn from_fields(type_: Type, fields: Vec<ColumnArray>) -> Result<Self> {
+        let Type::Nested { names, item_types } = &type_ else {
+            return Err(Error::Validation(format!(
+                "ColumnNested requires Nested type, got {}",
+                type_.name()
+            )));
+        };
+        if fields.len() != names.len() {
+            return Err(Error::Validation(format!(
+                "{} has {} sub-fields but {} arrays were given",
+                type_.name(),
+                names.len(),
+                fields.len()
+            )));
+        }
+        let offsets = fields.first().map(|f| f.offsets()).unwrap_or(&[]);
+        if let Some(pos) =
+            fields.iter().position(|field| field.offsets() != offsets)
+        {
+            return Err(Error::Validation(format!(
+                "Nested sub-field '{}' has array lengths that differ from \
+                 sub-field '{}'",
+                names[pos], names[0]
+            )));
+        }
+
+        let tuple = ColumnTuple::new(
+            Type::Tuple { item_types: item_types.clone() },
+            fields.iter().map(|field| field.nested_ref()).collect(),
+        );
+        let mut array = ColumnArray::with_nested(Arc::new(tuple));
+        let mut previous = 0;
+        for &offset in offsets {
+            array.append_len(offset - previous);
+            previous = offset;
+        }
+
+        Ok(Self { type_, data: Arc::new(array) })
+    }
+
+    /// Create from existing `Array(Tuple(...))` data
+    pub fn from_array(type_: Type, data: ColumnRef) -> Self {
+        Self { type_, data }
+    }
+
+    /// The sub-field names, in column order
+    pub fn names(&self) -> &[String] {
+        match &self.type_ {
+            Type::Nested { names, .. } => names,
+            _ => &[],
+        }
+    }
+
+    /// Get the data column as a `ColumnRef` (`Arc<dyn Column>`)
+    pub fn data_ref(&self) -> ColumnRef {
+        self.data.clone()
+    }
+
+    /// Get the underlying array column as ColumnArray if possible
+    pub fn as_array(&self) -> Option<&ColumnArray> {
+        self.data.as_any().downcast_ref::<ColumnArray>()
+    }
+
+    /// Get the sub-field `name` as an `Array(T)` column with one array per
+    /// row
+    ///
+    /// The result shares its elements with this column, so it can be read
+    /// like any other array column:
+    ///
+    /// ```ignore
+    /// let values = nested.field("value")?;
+    /// let row0: &ColumnUInt64 = values.nested();
+    /// ```
+    pub fn field(&self, name: &str) -> Result<ColumnArray> {
+        let position =
+            self.names().iter().position(|n| n == name).ok_or_else(|| {
+                Error::InvalidArgument(format!(
+                    "{} has no sub-field named '{}'",
+                    self.type_.name(),
+                    name
+                ))
+            })?;
+        self.field_at(position)
+    }
+
+    /// Get the sub-field at `position` as an `Array(T)` column
+    pub fn field_at(&self, position: usize) -> Result<ColumnArray> {
+        let array = self.as_array().ok_or_else(|| Error::TypeMismatch {
+            expected: "Array(Tuple(...))".to_string(),
+            actual: self.data.column_type().name(),
+        })?;
+        let elements = array.nested_ref();
+        let tuple = elements
+            .as_any()
+            .downcast_ref::<ColumnTuple>()
+            .ok_or_else(|| Error::TypeMismatch {
+                expected: "Tuple(...)".to_string(),
+                actual: elements.column_type().name(),
+            })?;
+        let column = tuple.element(position).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "Sub-field index {} out of bounds for {}",
+                position,
+                self.type_.name()
+            ))
+        })?;
+
+        let mut field = ColumnArray::with_nested(column.clone());
+        let mut previous = 0;
+        for &offset in array.offsets() {
+            field.append_len(offset - previous);
+            previous = offset;
+        }
+        Ok(field)
+    }
+
+    /// Returns the number of rows in this column.
+    pub fn len(&self) -> usize {
+        self.data.size()
+    }
+
+    /// Returns `true` if the column contains no rows.
+    pub fn is_empty(&self) -> bool {
+        self.data.size() == 0
+    }
+}
+
+impl Column for ColumnNested {
+    fn column_type(&self) -> &Type {
+        &self.type_
+    }
+
+    fn size(&self) -> usize {
+        self.data.size()
+    }
+
+    fn clear(&mut self) {
+        self.data = ColumnNested::new(self.type_.clone()).data;
+    }
+
+    fn reserve(&mut self, _new_cap: usize) {
+        // Reserve not supported through ColumnRef without downcasting
+    }
+
+    fn append_column(&mut self, other: ColumnRef) -> Result<()> {
+        let other = other.as_any().downcast_ref::<ColumnNested>().ok_or_else(
+            || Error::TypeMismatch {
+                expected: self.type_.name(),
+                actual: other.column_type().name(),
+            },
+        )?;
+        let data_mut = Arc::get_mut(&mut self.data).ok_or_else(|| {
+            Error::Protocol(
+                "Cannot append to shared nested column".to_string(),
+            )
+        })?;
+        data_mut.append_column(other.data.clone())
+    }
+
+    fn load_prefix(&mut self, buffer: &mut &[u8], rows: usize) -> Result<()> {
+        // Nested LowCardinality sub-fields read their key_version here
+        let data_mut = Arc::get_mut(&mut self.data).ok_or_else(|| {
+            Error::Protocol(
+                "Cannot load prefix for shared nested column".to_string(),
+            )
+        })?;
+        data_mut.load_prefix(buffer, rows)
+    }
+
+    fn load_from_buffer(
+        &mut self,
+        buffer: &mut &[u8],
+        rows: usize,
+    ) -> Result<()> {
+        let data_mut = Arc::get_mut(&mut self.data).ok_or_else(|| {
+            Error::Protocol(
+                "Failed to load Nested column from buffer".to_string(),
+            )
+        })?;
+        data_mut.load_from_buffer(buffer, rows)
+    }
+
+    fn save_prefix(&self, buffer: &mut BytesMut) -> Result<()> {
+        self.data.save_prefix(buffer)
+    }
+
+    fn save_to_buffer(&self, buffer: &mut BytesMut) -> Result<()> {
+        self.data.save_to_buffer(buffer)
+    }
+
+    fn clone_empty(&self) -> ColumnRef {
+        Arc::new(ColumnNested::new(self.type_.clone()))
+    }
+
+    fn slice(&self, begin: usize, len: usize) -> Result<ColumnRef> {
+        Ok(Arc::new(ColumnNested {
+            type_: self.type_.clone(),
+            data: self.data.slice(begin, len)?,
+        }))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::column::{
+        ColumnString,
+        ColumnUInt64,
+    };
+
+    fn nested_type() -> Type {
+        Type::parse("Nested(name String, value UInt64)").unwrap()
+    }
+
+    fn string_arrays(rows: &[&[&str]]) -> ColumnArray {
+        let mut array = ColumnArray::new(Type::array(Type::string()));
+        for row in rows {
+            array.append_array(Arc::new(ColumnString::from(row.to_vec())));
+        }
+        array
+    }
+
+    fn uint64_arrays(rows: &[&[u64]]) -> ColumnArray {
+        let mut array = ColumnArray::new(Type::array(Type::uint64()));
+        for row in rows {
+            array.append_array(Arc::new(ColumnUInt64::from_vec(
+                Type::uint64(),
+                row.to_vec(),
+            )));
+        }
+        array
+    }
+
+    #[test]
+    fn test_nested_roundtrip_splits_into_fields() {
+        let column = ColumnNested::from_fields(
+            nested_type(),
+            vec![
+                string_arrays(&[&["a", "b"], &[], &["c"]]),
+                uint64_arrays(&[&[1, 2], &[], &[3]]),
+            ],
+        )
+        .unwrap();
+
+        let mut buffer = BytesMut::new();
+        column.save_prefix(&mut buffer).unwrap();
+        column.save_to_buffer(&mut buffer).unwrap();
+
+        let mut loaded = ColumnNested::new(nested_type());
+        let mut reader = &buffer[..];
+        loaded.load_prefix(&mut reader, 3).unwrap();
+        loaded.load_from_buffer(&mut reader, 3).unwrap();
+        assert!(reader.is_empty());
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.names(), ["name", "value"]);
+        assert_eq!(
+            loaded.column_type().name(),
+            "Nested(name String, value UInt64)"
+        );
+
+        let names = loaded.field("name").unwrap();
+        assert_eq!(names.offsets(), [2, 2, 3]);
+        let strings: &ColumnString = names.nested();
+        assert_eq!(strings.get(2), Some("c"));
+
+        let values = loaded.field("value").unwrap();
+        let row0 = values.get_array(0).unwrap();
+        let row0 = row0.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(row0.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        assert!(loaded.field("missing").is_err());
+    }
+
+    #[test]
+    fn test_from_fields_rejects_mismatched_lengths() {
+        let result = ColumnNested::from_fields(
+            nested_type(),
+            vec![string_arrays(&[&["a"]]), uint64_arrays(&[&[1, 2]])],
+        );
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+}
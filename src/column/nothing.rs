@@ -93,7 +93,7 @@ impl Column for ColumnNothing {
         // But we need to skip the appropriate bytes (1 byte per row of
         // "nothing")
         if buffer.len() < rows {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Not enough data for Nothing".to_string(),
             ));
         }
@@ -19,6 +19,8 @@ use super::{
     numeric::ColumnUInt8,
     Column,
     ColumnRef,
+    ColumnTyped,
+    Value,
 };
 use crate::{
     types::Type,
@@ -144,6 +146,29 @@ impl ColumnNullable {
         nulls_col.at(index) != 0
     }
 
+    /// Get the value at index, or `None` if it is null or out of bounds
+    ///
+    /// `C` is the nested column type, which decodes the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the nested column is not a `C`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let col: ColumnNullable = /* Nullable(UInt64) */;
+    /// let value: Option<u64> = col.get_opt::<ColumnUInt64, _>(0);
+    /// ```
+    pub fn get_opt<C, T>(&self, index: usize) -> Option<T>
+    where
+        C: ColumnTyped<T> + 'static,
+    {
+        if self.is_null(index) {
+            return None;
+        }
+        self.nested::<C>().get(index)
+    }
+
     /// Get a reference to the nested column as a specific type
     ///
     /// # Example
@@ -192,34 +217,43 @@ impl ColumnNullable {
         self.nulls.clone()
     }
 
-    /// Append a nullable UInt32 value (convenience method for tests)
+    /// Append an optional value to a nullable column whose nested column
+    /// is a `C`
+    ///
+    /// `None` appends a null flag together with a default placeholder in
+    /// the nested column, keeping both columns aligned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the nested column is not a `C` or if either inner column
+    /// is shared. The column is left unchanged in that case.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut col = ColumnNullable::with_nested(Arc::new(ColumnInt64::new()));
+    /// col.append_opt::<ColumnInt64, _>(Some(-1));
+    /// col.append_opt::<ColumnInt64, i64>(None);
+    /// ```
+    pub fn append_opt<C, T>(&mut self, value: Option<T>)
+    where
+        C: ColumnTyped<T> + 'static,
+        T: Default,
+    {
+        assert!(
+            Arc::get_mut(&mut self.nulls).is_some(),
+            "Cannot append to shared nulls column"
+        );
+        let isnull = value.is_none();
+        self.nested_mut::<C>().append(value.unwrap_or_default());
+        self.append(isnull);
+    }
+
+    /// Append a nullable UInt32 value (shorthand for
+    /// [`append_opt`](Self::append_opt) with a `ColumnUInt32` nested column)
     pub fn append_nullable(&mut self, value: Option<u32>) {
         use crate::column::numeric::ColumnUInt32;
 
-        match value {
-            None => {
-                self.append_null();
-                // Still need to add a placeholder to nested column to keep
-                // indices aligned
-                let nested_mut = Arc::get_mut(&mut self.nested)
-                    .expect("Cannot append to shared nullable column - column has multiple references");
-                let col = nested_mut
-                    .as_any_mut()
-                    .downcast_mut::<ColumnUInt32>()
-                    .expect("Nullable nested column is not UInt32");
-                col.append(0); // Placeholder value (ignored due to null flag)
-            }
-            Some(val) => {
-                self.append_non_null();
-                let nested_mut = Arc::get_mut(&mut self.nested)
-                    .expect("Cannot append to shared nullable column - column has multiple references");
-                let col = nested_mut
-                    .as_any_mut()
-                    .downcast_mut::<ColumnUInt32>()
-                    .expect("Nullable nested column is not UInt32");
-                col.append(val);
-            }
-        }
+        self.append_opt::<ColumnUInt32, u32>(value);
     }
 
     /// Check if value at index is null (alias for is_null)
@@ -476,6 +510,31 @@ impl<T: Column + 'static> ColumnNullableT<T> {
         self.inner.is_null(index)
     }
 
+    /// Get the value at index, or `None` if it is null or out of bounds
+    ///
+    /// Works for any nested column implementing [`ColumnTyped`], e.g.
+    /// `ColumnNullableT::<ColumnUInt64>::get` returns `Option<u64>` and
+    /// `ColumnNullableT::<ColumnString>::get` returns `Option<String>`.
+    pub fn get<V>(&self, index: usize) -> Option<V>
+    where
+        T: ColumnTyped<V>,
+    {
+        self.inner.get_opt::<T, V>(index)
+    }
+
+    /// Append a value, or a null for `None`
+    ///
+    /// Works for any nested column implementing [`ColumnTyped`], e.g.
+    /// `ColumnNullableT::<ColumnString>::append` takes `Option<String>`.
+    /// See [`ColumnNullable::append_opt`].
+    pub fn append<V>(&mut self, value: Option<V>)
+    where
+        T: ColumnTyped<V>,
+        V: Default,
+    {
+        self.inner.append_opt::<T, V>(value);
+    }
+
     /// Get the inner ColumnNullable
     pub fn inner(&self) -> &ColumnNullable {
         &self.inner
@@ -556,6 +615,10 @@ impl<T: Column + 'static> Column for ColumnNullableT<T> {
         )))
     }
 
+    fn value_at(&self, index: usize) -> Result<Value> {
+        self.inner.value_at(index)
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -583,6 +646,8 @@ mod tests {
     use crate::{
         column::{
             numeric::{
+                ColumnFloat64,
+                ColumnInt64,
                 ColumnUInt32,
                 ColumnUInt64,
             },
@@ -875,4 +940,81 @@ mod tests {
         assert_eq!(typed.size(), 1);
         assert_eq!(typed.at(0), 42);
     }
+
+    #[test]
+    fn test_nullable_get_opt_numeric_and_string() {
+        let mut nested = ColumnUInt64::new();
+        nested.append(7);
+        nested.append(0);
+        let mut nulls = ColumnUInt8::new();
+        nulls.append(0);
+        nulls.append(1);
+        let col =
+            ColumnNullable::from_parts(Arc::new(nested), Arc::new(nulls))
+                .unwrap();
+        assert_eq!(col.get_opt::<ColumnUInt64, _>(0), Some(7u64));
+        assert_eq!(col.get_opt::<ColumnUInt64, u64>(1), None);
+        assert_eq!(col.get_opt::<ColumnUInt64, u64>(2), None);
+
+        let typed = ColumnNullableT::<ColumnUInt64>::wrap(col);
+        assert_eq!(typed.get(0), Some(7u64));
+        assert_eq!(typed.get::<u64>(1), None);
+
+        let mut nested = ColumnString::new(Type::string());
+        nested.append("a");
+        nested.append("");
+        let mut nulls = ColumnUInt8::new();
+        nulls.append(1);
+        nulls.append(0);
+        let typed =
+            ColumnNullableT::from_parts(Arc::new(nested), Arc::new(nulls))
+                .unwrap();
+        assert_eq!(typed.get::<String>(0), None);
+        assert_eq!(typed.get(1), Some(String::new()));
+    }
+
+    #[test]
+    fn test_nullable_typed_append() {
+        let mut strings = ColumnNullableT::<ColumnString>::from_nested(
+            Arc::new(ColumnString::new(Type::string())),
+        );
+        strings.append(Some("x".to_string()));
+        strings.append::<String>(None);
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings.inner().nested::<ColumnString>().len(), 2);
+        assert_eq!(strings.get(0), Some("x".to_string()));
+        assert_eq!(strings.get::<String>(1), None);
+
+        let mut ints = ColumnNullableT::<ColumnInt64>::from_nested(Arc::new(
+            ColumnInt64::new(),
+        ));
+        ints.append::<i64>(None);
+        ints.append(Some(-5i64));
+        assert_eq!(ints.inner().nested::<ColumnInt64>().len(), 2);
+        assert_eq!(ints.get::<i64>(0), None);
+        assert_eq!(ints.get(1), Some(-5i64));
+
+        let mut floats =
+            ColumnNullable::with_nested(Arc::new(ColumnFloat64::new()));
+        floats.append_opt::<ColumnFloat64, _>(Some(1.5f64));
+        floats.append_opt::<ColumnFloat64, f64>(None);
+        assert_eq!(floats.size(), 2);
+        assert_eq!(floats.get_opt::<ColumnFloat64, _>(0), Some(1.5f64));
+        assert_eq!(floats.get_opt::<ColumnFloat64, f64>(1), None);
+    }
+
+    #[test]
+    fn test_nullable_append_opt_wrong_nested_type_leaves_column_unchanged() {
+        let mut col =
+            ColumnNullable::with_nested(Arc::new(ColumnUInt32::new()));
+        col.append_nullable(Some(1));
+
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                col.append_opt::<ColumnInt64, i64>(None)
+            }));
+        assert!(result.is_err());
+        assert_eq!(col.size(), 1);
+        assert_eq!(col.nested::<ColumnUInt32>().len(), 1);
+    }
 }
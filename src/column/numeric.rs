@@ -2,7 +2,7 @@
 //!
 //! **ClickHouse Documentation:**
 //! - [Integer Types](https://clickhouse.com/docs/en/sql-reference/data-types/int-uint)
-//!   - Int8/16/32/64/128, UInt8/16/32/64/128
+//!   - Int8/16/32/64/128/256, UInt8/16/32/64/128/256
 //! - [Floating-Point Types](https://clickhouse.com/docs/en/sql-reference/data-types/float)
 //!   - Float32, Float64
 //! - [Decimal Types](https://clickhouse.com/docs/en/sql-reference/data-types/decimal)
@@ -19,11 +19,13 @@
 //! | `Int32` | `i32` | 4 bytes | -2³¹ | 2³¹-1 |
 //! | `Int64` | `i64` | 8 bytes | -2⁶³ | 2⁶³-1 |
 //! | `Int128` | `i128` | 16 bytes | -2¹²⁷ | 2¹²⁷-1 |
+//! | `Int256` | [`Int256`] | 32 bytes | -2²⁵⁵ | 2²⁵⁵-1 |
 //! | `UInt8` | `u8` | 1 byte | 0 | 255 |
 //! | `UInt16` | `u16` | 2 bytes | 0 | 65,535 |
 //! | `UInt32` | `u32` | 4 bytes | 0 | 2³²-1 |
 //! | `UInt64` | `u64` | 8 bytes | 0 | 2⁶⁴-1 |
 //! | `UInt128` | `u128` | 16 bytes | 0 | 2¹²⁸-1 |
+//! | `UInt256` | [`UInt256`] | 32 bytes | 0 | 2²⁵⁶-1 |
 //!
 //! ## Floating-Point Types
 //!
@@ -31,9 +33,7 @@
 //! - `Float32` - Single precision (32-bit)
 //! - `Float64` - Double precision (64-bit)
 //!
-//! ## Bool Type
-//!
-//! `Bool` is an alias for `UInt8` where 0 = false, 1 = true.
+//! `Bool` has its own column type, see [`super::boolean::ColumnBool`].
 
 use super::{
     Column,
@@ -75,7 +75,7 @@ macro_rules! impl_fixed_size {
         impl FixedSize for $type {
             fn read_from(buffer: &mut &[u8]) -> Result<Self> {
                 if buffer.len() < std::mem::size_of::<$type>() {
-                    return Err(Error::Protocol(
+                    return Err(Error::Incomplete(
                         "Buffer underflow".to_string(),
                     ));
                 }
@@ -103,7 +103,7 @@ impl_fixed_size!(f64, get_f64_le, put_f64_le);
 impl FixedSize for i128 {
     fn read_from(buffer: &mut &[u8]) -> Result<Self> {
         if buffer.len() < 16 {
-            return Err(Error::Protocol("Buffer underflow".to_string()));
+            return Err(Error::Incomplete("Buffer underflow".to_string()));
         }
         Ok(buffer.get_i128_le())
     }
@@ -116,7 +116,7 @@ impl FixedSize for i128 {
 impl FixedSize for u128 {
     fn read_from(buffer: &mut &[u8]) -> Result<Self> {
         if buffer.len() < 16 {
-            return Err(Error::Protocol("Buffer underflow".to_string()));
+            return Err(Error::Incomplete("Buffer underflow".to_string()));
         }
         Ok(buffer.get_u128_le())
     }
@@ -126,6 +126,121 @@ impl FixedSize for u128 {
     }
 }
 
+/// Signed 256-bit integer as 32 little-endian two's complement bytes
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Int256(pub [u8; 32]);
+
+impl Int256 {
+    /// Zero.
+    pub const ZERO: Self = Self([0; 32]);
+    /// Smallest value, -2²⁵⁵.
+    pub const MIN: Self = {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 0x80;
+        Self(bytes)
+    };
+    /// Largest value, 2²⁵⁵-1.
+    pub const MAX: Self = {
+        let mut bytes = [0xff; 32];
+        bytes[31] = 0x7f;
+        Self(bytes)
+    };
+
+    /// Create a value from its little-endian bytes.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the little-endian bytes.
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Convert to `i128` if the value fits.
+    pub fn to_i128(self) -> Option<i128> {
+        let low = i128::from_le_bytes(self.0[..16].try_into().unwrap());
+        let sign = if low < 0 { 0xff } else { 0 };
+        self.0[16..].iter().all(|&b| b == sign).then_some(low)
+    }
+}
+
+impl From<i128> for Int256 {
+    fn from(value: i128) -> Self {
+        let mut bytes = if value < 0 { [0xff; 32] } else { [0; 32] };
+        bytes[..16].copy_from_slice(&value.to_le_bytes());
+        Self(bytes)
+    }
+}
+
+/// Unsigned 256-bit integer as 32 little-endian bytes
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UInt256(pub [u8; 32]);
+
+impl UInt256 {
+    /// Zero, also the smallest value.
+    pub const ZERO: Self = Self([0; 32]);
+    /// Largest value, 2²⁵⁶-1.
+    pub const MAX: Self = Self([0xff; 32]);
+
+    /// Create a value from its little-endian bytes.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the little-endian bytes.
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Convert to `u128` if the value fits.
+    pub fn to_u128(self) -> Option<u128> {
+        self.0[16..]
+            .iter()
+            .all(|&b| b == 0)
+            .then(|| u128::from_le_bytes(self.0[..16].try_into().unwrap()))
+    }
+}
+
+impl From<u128> for UInt256 {
+    fn from(value: u128) -> Self {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&value.to_le_bytes());
+        Self(bytes)
+    }
+}
+
+macro_rules! impl_fixed_size_256 {
+    ($type:ident) => {
+        impl FixedSize for $type {
+            fn read_from(buffer: &mut &[u8]) -> Result<Self> {
+                if buffer.len() < 32 {
+                    return Err(Error::Incomplete(
+                        "Buffer underflow".to_string(),
+                    ));
+                }
+                let mut bytes = [0u8; 32];
+                buffer.copy_to_slice(&mut bytes);
+                Ok(Self(bytes))
+            }
+
+            fn write_to(&self, buffer: &mut BytesMut) {
+                buffer.put_slice(&self.0);
+            }
+        }
+
+        impl ToType for $type {
+            fn to_type() -> Type {
+                Type::Simple(crate::types::TypeCode::$type)
+            }
+        }
+    };
+}
+
+impl_fixed_size_256!(Int256);
+impl_fixed_size_256!(UInt256);
+
 /// Generic column for numeric types
 pub struct ColumnVector<T: FixedSize> {
     type_: Type,
@@ -163,6 +278,9 @@ impl<T: FixedSize + Clone + Send + Sync + 'static> ColumnVector<T> {
     }
 
     /// Get value at index (panics if out of bounds - for tests)
+    ///
+    /// Floats come back bit for bit as they were stored, so NaN has to be
+    /// checked with `is_nan()`, as `==` is always false for it.
     pub fn at(&self, index: usize) -> T {
         self.data[index].clone()
     }
@@ -198,6 +316,16 @@ impl<T: FixedSize + Clone + Send + Sync + 'static> ColumnVector<T> {
     }
 }
 
+impl<T: FixedSize + Ord + Copy> ColumnVector<T> {
+    /// Smallest and largest value, or `None` if the column is empty
+    pub fn min_max(&self) -> Option<(T, T)> {
+        let (&first, rest) = self.data.split_first()?;
+        Some(rest.iter().fold((first, first), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        }))
+    }
+}
+
 /// Type-inferred constructors for ColumnVector
 /// Implements the type map pattern from C++ `Type::CreateSimple<T>()`
 impl<T: FixedSize + ToType + Clone + Send + Sync + 'static> ColumnVector<T> {
@@ -241,6 +369,24 @@ impl<T: FixedSize + ToType + Clone + Send + Sync + 'static> Default
     }
 }
 
+/// Build a column from a vector in one call, with the type inferred from T
+///
+/// # Examples
+///
+/// ```
+/// use clickhouse_native_client::column::ColumnUInt64;
+///
+/// let col = ColumnUInt64::from(vec![1, 2, 3]);
+/// assert_eq!(col.at(2), 3);
+/// ```
+impl<T: FixedSize + ToType + Clone + Send + Sync + 'static> From<Vec<T>>
+    for ColumnVector<T>
+{
+    fn from(data: Vec<T>) -> Self {
+        Self { type_: T::to_type(), data }
+    }
+}
+
 impl<T: FixedSize + ToType> Column for ColumnVector<T> {
     fn column_type(&self) -> &Type {
         &self.type_
@@ -282,7 +428,7 @@ impl<T: FixedSize + ToType> Column for ColumnVector<T> {
         let bytes_needed = rows * std::mem::size_of::<T>();
 
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow: need {} bytes, have {}",
                 bytes_needed,
                 buffer.len()
@@ -378,6 +524,8 @@ pub type ColumnUInt32 = ColumnVector<u32>;
 pub type ColumnUInt64 = ColumnVector<u64>;
 /// Column of `UInt128` values (16-byte unsigned integers, little-endian).
 pub type ColumnUInt128 = ColumnVector<u128>;
+/// Column of `UInt256` values (32-byte unsigned integers, little-endian).
+pub type ColumnUInt256 = ColumnVector<UInt256>;
 
 /// Column of `Int8` values (1-byte signed integers).
 pub type ColumnInt8 = ColumnVector<i8>;
@@ -389,6 +537,8 @@ pub type ColumnInt32 = ColumnVector<i32>;
 pub type ColumnInt64 = ColumnVector<i64>;
 /// Column of `Int128` values (16-byte signed integers, little-endian).
 pub type ColumnInt128 = ColumnVector<i128>;
+/// Column of `Int256` values (32-byte signed integers, little-endian).
+pub type ColumnInt256 = ColumnVector<Int256>;
 
 /// Column of `Float32` values (IEEE 754 single-precision, little-endian).
 pub type ColumnFloat32 = ColumnVector<f32>;
@@ -403,6 +553,155 @@ pub type ColumnDate = ColumnVector<u16>;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_int256_conversions() {
+        assert_eq!(Int256::from(-1i128).0, [0xff; 32]);
+        assert_eq!(Int256::from(-5i128).to_i128(), Some(-5));
+        assert_eq!(Int256::from(i128::MAX).to_i128(), Some(i128::MAX));
+        assert_eq!(Int256::MIN.to_i128(), None);
+        assert_eq!(Int256::MAX.to_i128(), None);
+
+        assert_eq!(UInt256::from(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(UInt256::MAX.to_u128(), None);
+        assert_eq!(UInt256::ZERO, UInt256::default());
+    }
+
+    #[test]
+    fn test_int256_columns_roundtrip() {
+        let mut col = ColumnUInt256::new();
+        col.append(UInt256::ZERO);
+        col.append(UInt256::MAX);
+        col.append(UInt256::from(1u128 << 100));
+        assert_eq!(col.column_type().name(), "UInt256");
+
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 3 * 32);
+        assert_eq!(&buffer[32..64], &[0xff; 32]);
+
+        let mut decoded = ColumnUInt256::new();
+        let mut reader = &buffer[..];
+        decoded.load_from_buffer(&mut reader, 3).unwrap();
+        assert!(reader.is_empty());
+        assert_eq!(decoded.data(), col.data());
+
+        let mut col = ColumnInt256::new();
+        col.append(Int256::MIN);
+        col.append(Int256::from(-2i128));
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        assert_eq!(buffer[31], 0x80);
+
+        let mut decoded = ColumnInt256::new();
+        let mut reader = &buffer[..];
+        decoded.load_from_buffer(&mut reader, 2).unwrap();
+        assert_eq!(decoded.at(0), Int256::MIN);
+        assert_eq!(decoded.at(1).to_i128(), Some(-2));
+    }
+
+    /// Serialized bytes of a single-value column
+    fn column_bytes<T: FixedSize + ToType>(value: T) -> Vec<u8> {
+        let mut col = ColumnVector::<T>::new();
+        col.append(value);
+        let mut buffer = BytesMut::new();
+        col.save_to_buffer(&mut buffer).unwrap();
+        buffer.to_vec()
+    }
+
+    #[test]
+    fn test_columns_write_little_endian() {
+        assert_eq!(column_bytes(1u8), [1]);
+        assert_eq!(column_bytes(-1i8), [0xff]);
+        assert_eq!(column_bytes(0x0102u16), [0x02, 0x01]);
+        assert_eq!(column_bytes(-1i16), [0xff, 0xff]);
+        assert_eq!(column_bytes(1u32), [1, 0, 0, 0]);
+        assert_eq!(column_bytes(-2i32), [0xfe, 0xff, 0xff, 0xff]);
+        assert_eq!(
+            column_bytes(0x0102030405060708u64),
+            [8, 7, 6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(column_bytes(i64::MIN), [0, 0, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(column_bytes(1.0f32), [0, 0, 0x80, 0x3f]);
+        assert_eq!(column_bytes(-2.0f64), [0, 0, 0, 0, 0, 0, 0, 0xc0]);
+
+        let value = 0x0102030405060708090a0b0c0d0e0f10u128;
+        let expected: Vec<u8> = (1..=16).rev().collect();
+        assert_eq!(column_bytes(value), expected);
+        assert_eq!(column_bytes(value as i128), expected);
+        assert_eq!(column_bytes(-1i128), [0xff; 16]);
+        let mut expected = [0u8; 16];
+        expected[0] = 1;
+        assert_eq!(column_bytes(1u128), expected);
+        let mut expected = [0u8; 16];
+        expected[15] = 0x80;
+        assert_eq!(column_bytes(i128::MIN), expected);
+    }
+
+    #[test]
+    fn test_columns_read_little_endian() {
+        let mut col = ColumnInt16::new();
+        col.load_from_buffer(&mut &[0xfe, 0xff, 0x01, 0x02][..], 2).unwrap();
+        assert_eq!(col.at(0), -2);
+        assert_eq!(col.at(1), 0x0201);
+
+        let mut col = ColumnUInt128::new();
+        let bytes: Vec<u8> = (1..=16).rev().collect();
+        col.load_from_buffer(&mut &bytes[..], 1).unwrap();
+        assert_eq!(col.at(0), 0x0102030405060708090a0b0c0d0e0f10);
+    }
+
+    #[test]
+    fn test_float_special_values_roundtrip() {
+        // A NaN with a payload and sign bit, besides the usual specials
+        let nan_payload = f64::from_bits(0xfff8_0000_dead_beef);
+        let values = vec![
+            f64::NAN,
+            nan_payload,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            -0.0,
+        ];
+
+        let mut buffer = BytesMut::new();
+        ColumnFloat64::from(values.clone())
+            .save_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!(&buffer[..8], f64::NAN.to_le_bytes());
+
+        let mut col = ColumnFloat64::new();
+        col.load_from_buffer(&mut &buffer[..], values.len()).unwrap();
+        let bits: Vec<u64> = col.iter().map(|v| v.to_bits()).collect();
+        let expected: Vec<u64> = values.iter().map(|v| v.to_bits()).collect();
+        assert_eq!(bits, expected);
+        assert!(col.at(0).is_nan());
+        assert!(col.at(3).is_infinite() && col.at(3).is_sign_negative());
+
+        let values = vec![f32::NAN, f32::INFINITY, f32::NEG_INFINITY];
+        let mut buffer = BytesMut::new();
+        ColumnFloat32::from(values.clone())
+            .save_to_buffer(&mut buffer)
+            .unwrap();
+        let mut col = ColumnFloat32::new();
+        col.load_from_buffer(&mut &buffer[..], values.len()).unwrap();
+        assert!(col.at(0).is_nan());
+        assert_eq!(col.at(1), f32::INFINITY);
+        assert_eq!(col.at(2), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_column_from_vec() {
+        let col = ColumnUInt64::from((0..1000).collect::<Vec<u64>>());
+        assert_eq!(col.size(), 1000);
+        assert_eq!(col.column_type(), &Type::uint64());
+        assert_eq!(col.at(0), 0);
+        assert_eq!(col.at(500), 500);
+        assert_eq!(col.at(999), 999);
+
+        let col = ColumnFloat64::from(vec![0.5, -1.5]);
+        assert_eq!(col.column_type(), &Type::float64());
+        assert_eq!(col.at(1), -1.5);
+    }
+
     #[test]
     fn test_column_creation() {
         // Test type-inferred constructor
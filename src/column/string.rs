@@ -29,6 +29,7 @@
 use super::{
     Column,
     ColumnRef,
+    ColumnTyped,
 };
 use crate::{
     io::buffer_utils,
@@ -127,6 +128,31 @@ impl ColumnFixedString {
         Some(String::from_utf8_lossy(&bytes[..trimmed]).to_string())
     }
 
+    /// Returns the raw bytes at the given index, including any zero
+    /// padding, or `None` if out of bounds.
+    ///
+    /// The slice is always exactly [`fixed_size`](Self::fixed_size) bytes.
+    pub fn get_bytes(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.size() {
+            return None;
+        }
+
+        let start = index * self.string_size;
+        Some(&self.data[start..start + self.string_size])
+    }
+
+    /// Returns the string at the given index without its zero padding.
+    ///
+    /// Unlike [`get`](Self::get), this borrows from the column and returns
+    /// `None` if the value is not valid UTF-8 instead of replacing invalid
+    /// sequences.
+    pub fn get_str(&self, index: usize) -> Option<&str> {
+        let bytes = self.get_bytes(index)?;
+        let trimmed =
+            bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..trimmed]).ok()
+    }
+
     /// Get value at index (for tests)
     pub fn at(&self, index: usize) -> String {
         self.get(index).unwrap()
@@ -193,7 +219,7 @@ impl Column for ColumnFixedString {
         let total_bytes = self.string_size * rows;
 
         if buffer.len() < total_bytes {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Not enough data for {} FixedString({}) values: need {}, have {}",
                 rows, self.string_size, total_bytes, buffer.len()
             )));
@@ -241,6 +267,16 @@ impl Column for ColumnFixedString {
     }
 }
 
+impl ColumnTyped<String> for ColumnFixedString {
+    fn get(&self, index: usize) -> Option<String> {
+        ColumnFixedString::get(self, index)
+    }
+
+    fn append(&mut self, value: String) {
+        ColumnFixedString::append(self, value)
+    }
+}
+
 /// Column for variable-length strings
 pub struct ColumnString {
     type_: Type,
@@ -281,6 +317,12 @@ impl ColumnString {
         self.data.get(index).map(|s| s.as_str())
     }
 
+    /// Returns the bytes of the string at the given index, or `None` if out
+    /// of bounds.
+    pub fn get_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.data.get(index).map(|s| s.as_bytes())
+    }
+
     /// Get value at index (for tests)
     pub fn at(&self, index: usize) -> String {
         self.data[index].clone()
@@ -308,6 +350,21 @@ impl Default for ColumnString {
     }
 }
 
+impl From<Vec<String>> for ColumnString {
+    fn from(data: Vec<String>) -> Self {
+        Self::from_vec(Type::string(), data)
+    }
+}
+
+impl From<Vec<&str>> for ColumnString {
+    fn from(data: Vec<&str>) -> Self {
+        Self::from_vec(
+            Type::string(),
+            data.into_iter().map(String::from).collect(),
+        )
+    }
+}
+
 impl Column for ColumnString {
     fn column_type(&self) -> &Type {
         &self.type_
@@ -349,7 +406,7 @@ impl Column for ColumnString {
             let len = buffer_utils::read_varint(buffer)? as usize;
 
             if buffer.len() < len {
-                return Err(Error::Protocol(format!(
+                return Err(Error::Incomplete(format!(
                     "Not enough data for string: need {}, have {}",
                     len,
                     buffer.len()
@@ -406,6 +463,16 @@ impl Column for ColumnString {
     }
 }
 
+impl ColumnTyped<String> for ColumnString {
+    fn get(&self, index: usize) -> Option<String> {
+        ColumnString::get(self, index).map(str::to_string)
+    }
+
+    fn append(&mut self, value: String) {
+        ColumnString::append(self, value)
+    }
+}
+
 // Helper functions removed - using buffer_utils module
 
 #[cfg(test)]
@@ -466,6 +533,21 @@ mod tests {
         assert_eq!(col2.get(1), Some("world".to_string()));
     }
 
+    #[test]
+    fn test_fixed_string_borrowed_getters() {
+        let mut col = ColumnFixedString::new(Type::fixed_string(4));
+        col.append("ab".to_string());
+        let mut slice: &[u8] = &[0xff, 0xfe, 0, 0];
+        col.load_from_buffer(&mut slice, 1).unwrap();
+
+        assert_eq!(col.get_bytes(0), Some(&b"ab\0\0"[..]));
+        assert_eq!(col.get_str(0), Some("ab"));
+        assert_eq!(col.get_bytes(1), Some(&[0xff, 0xfe, 0, 0][..]));
+        assert_eq!(col.get_str(1), None);
+        assert_eq!(col.get_bytes(2), None);
+        assert_eq!(col.get_str(2), None);
+    }
+
     #[test]
     fn test_string_creation() {
         let col = ColumnString::new(Type::string());
@@ -483,6 +565,18 @@ mod tests {
         assert_eq!(col.get(0), Some("hello"));
         assert_eq!(col.get(1), Some("world"));
         assert_eq!(col.get(2), Some("rust"));
+        assert_eq!(col.get_bytes(2), Some(&b"rust"[..]));
+        assert_eq!(col.get_bytes(3), None);
+    }
+
+    #[test]
+    fn test_string_from_vec() {
+        let col = ColumnString::from(vec!["a", "b"]);
+        assert_eq!(col.column_type(), &Type::string());
+        assert_eq!(col.get(1), Some("b"));
+
+        let col = ColumnString::from(vec![String::from("c")]);
+        assert_eq!(col.at(0), "c");
     }
 
     #[test]
@@ -38,6 +38,21 @@ impl ColumnTuple {
         self.columns[index].clone()
     }
 
+    /// Return the element column at `index`, or `None` if the tuple has
+    /// fewer elements.
+    pub fn element(&self, index: usize) -> Option<&ColumnRef> {
+        self.columns.get(index)
+    }
+
+    /// Return one single-row column per element for the given row, or
+    /// `None` if out of bounds.
+    pub fn get_row(&self, row: usize) -> Option<Vec<ColumnRef>> {
+        if row >= self.len() {
+            return None;
+        }
+        self.columns.iter().map(|c| c.slice(row, 1).ok()).collect()
+    }
+
     /// Get mutable reference to a specific column (for appending)
     pub fn column_at_mut(&mut self, index: usize) -> &mut dyn Column {
         Arc::get_mut(&mut self.columns[index])
@@ -192,6 +207,7 @@ mod tests {
     use super::*;
     use crate::{
         column::{
+            ColumnFloat64,
             ColumnString,
             ColumnUInt64,
         },
@@ -243,4 +259,31 @@ mod tests {
         assert_eq!(sliced_col1.at(0), 2);
         assert_eq!(sliced_col1.at(1), 3);
     }
+
+    #[test]
+    fn test_tuple_element_access() {
+        let tuple_type = Type::tuple(vec![Type::float64(), Type::float64()]);
+        let first = ColumnFloat64::new().with_data(vec![1.5, 0.0]);
+        let second = ColumnFloat64::new().with_data(vec![2.5, -1.0]);
+        let tuple = ColumnTuple::new(
+            tuple_type,
+            vec![Arc::new(first) as ColumnRef, Arc::new(second) as ColumnRef],
+        );
+
+        let values: Vec<f64> = (0..2)
+            .map(|i| {
+                let element = tuple.element(i).unwrap();
+                element.as_any().downcast_ref::<ColumnFloat64>().unwrap().at(0)
+            })
+            .collect();
+        assert_eq!(values, vec![1.5, 2.5]);
+        assert!(tuple.element(2).is_none());
+
+        let row = tuple.get_row(1).unwrap();
+        assert_eq!(row.len(), 2);
+        let second = row[1].as_any().downcast_ref::<ColumnFloat64>().unwrap();
+        assert_eq!(second.size(), 1);
+        assert_eq!(second.at(0), -1.0);
+        assert!(tuple.get_row(2).is_none());
+    }
 }
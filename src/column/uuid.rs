@@ -163,7 +163,7 @@ impl Column for ColumnUuid {
     ) -> Result<()> {
         let bytes_needed = rows * 16;
         if buffer.len() < bytes_needed {
-            return Err(Error::Protocol(format!(
+            return Err(Error::Incomplete(format!(
                 "Buffer underflow: need {} bytes for UUID, have {}",
                 bytes_needed,
                 buffer.len()
@@ -9,6 +9,11 @@
 //! The `compress` function produces a complete frame (checksum + header +
 //! data). The `decompress` function accepts a complete frame and returns the
 //! uncompressed payload.
+//!
+//! Codecs plug in through the [`Compressor`] trait, which only transforms
+//! payloads; framing and checksums are handled here. [`Lz4Compressor`] and
+//! [`ZstdCompressor`] are the built-in implementations, and custom codecs are
+//! used via [`compress_with`] and [`decompress_with`].
 
 use crate::{
     protocol::CompressionMethod,
@@ -22,6 +27,7 @@ use bytes::{
     BytesMut,
 };
 use cityhash_rs::cityhash_102_128;
+use std::fmt::Debug;
 
 /// Compression header size (9 bytes: 1 byte method + 4 bytes compressed + 4
 /// bytes uncompressed)
@@ -39,7 +45,170 @@ enum CompressionMethodByte {
 }
 
 /// Maximum compressed block size (1GB)
-const MAX_COMPRESSED_SIZE: usize = 0x40000000;
+pub(crate) const MAX_COMPRESSED_SIZE: usize = 0x40000000;
+
+/// Magic number that starts an LZ4 *frame* (`lz4::Encoder` output).
+///
+/// ClickHouse never sends frames: the payload after its own header is a raw
+/// LZ4 block, and the uncompressed size comes from the header instead of a
+/// block size prefix.
+const LZ4_FRAME_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
+
+/// A compression codec for the payload of a ClickHouse compressed frame.
+///
+/// Implementations only transform the payload. The frame checksum and the
+/// 9-byte header are written by [`compress_with`] and validated by
+/// [`decompress_with`], which dispatches on [`Compressor::method_byte`].
+pub trait Compressor: Send + Sync + Debug {
+    /// Method byte identifying this codec in the frame header
+    fn method_byte(&self) -> u8;
+
+    /// Compress `data` into a payload
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Decompress a payload into exactly `uncompressed_size` bytes
+    fn decompress(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+    ) -> Result<Vec<u8>>;
+
+    /// Decompress a payload, appending the result to `out`
+    ///
+    /// The default goes through [`Compressor::decompress`]; codecs that can
+    /// write into a caller-provided buffer should override it to avoid the
+    /// intermediate allocation.
+    fn decompress_into(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        out.extend_from_slice(&self.decompress(data, uncompressed_size)?);
+        Ok(())
+    }
+}
+
+/// Built-in LZ4 codec (raw LZ4 block, method byte 0x82)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lz4Compressor;
+
+impl Compressor for Lz4Compressor {
+    fn method_byte(&self) -> u8 {
+        CompressionMethodByte::Lz4 as u8
+    }
+
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let max_compressed_size = lz4::block::compress_bound(data.len())?;
+        let mut compressed = vec![0u8; max_compressed_size];
+
+        let compressed_size = lz4::block::compress_to_buffer(
+            data,
+            None,
+            false,
+            &mut compressed,
+        )?;
+
+        compressed.truncate(compressed_size);
+        Ok(compressed)
+    }
+
+    fn decompress(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+    ) -> Result<Vec<u8>> {
+        if data.starts_with(&LZ4_FRAME_MAGIC) {
+            return Err(Error::Compression(
+                "LZ4 frame format is not supported, expected a raw LZ4 block"
+                    .to_string(),
+            ));
+        }
+
+        Ok(lz4::block::decompress(data, Some(uncompressed_size as i32))?)
+    }
+
+    fn decompress_into(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        if data.starts_with(&LZ4_FRAME_MAGIC) {
+            return Err(Error::Compression(
+                "LZ4 frame format is not supported, expected a raw LZ4 block"
+                    .to_string(),
+            ));
+        }
+
+        let start = out.len();
+        out.resize(start + uncompressed_size, 0);
+        match lz4::block::decompress_to_buffer(
+            data,
+            Some(uncompressed_size as i32),
+            &mut out[start..],
+        ) {
+            Ok(written) => {
+                out.truncate(start + written);
+                Ok(())
+            }
+            Err(e) => {
+                out.truncate(start);
+                Err(e.into())
+            }
+        }
+    }
+}
+
+/// Built-in ZSTD codec (level 3, method byte 0x90)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZstdCompressor;
+
+impl Compressor for ZstdCompressor {
+    fn method_byte(&self) -> u8 {
+        CompressionMethodByte::Zstd as u8
+    }
+
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        zstd::bulk::compress(data, 3) // Compression level 3
+            .map_err(|e| {
+                Error::Compression(format!("ZSTD compression failed: {}", e))
+            })
+    }
+
+    fn decompress(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+    ) -> Result<Vec<u8>> {
+        zstd::bulk::decompress(data, uncompressed_size).map_err(|e| {
+            Error::Compression(format!("ZSTD decompression failed: {}", e))
+        })
+    }
+
+    fn decompress_into(
+        &self,
+        data: &[u8],
+        uncompressed_size: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        let start = out.len();
+        out.resize(start + uncompressed_size, 0);
+        match zstd::bulk::decompress_to_buffer(data, &mut out[start..]) {
+            Ok(written) => {
+                out.truncate(start + written);
+                Ok(())
+            }
+            Err(e) => {
+                out.truncate(start);
+                Err(Error::Compression(format!(
+                    "ZSTD decompression failed: {}",
+                    e
+                )))
+            }
+        }
+    }
+}
 
 /// Compress data using the specified method.
 ///
@@ -55,8 +224,8 @@ pub fn compress(method: CompressionMethod, data: &[u8]) -> Result<Bytes> {
             // No compression, but still add header
             compress_none(data)
         }
-        CompressionMethod::Lz4 => compress_lz4(data),
-        CompressionMethod::Zstd => compress_zstd(data),
+        CompressionMethod::Lz4 => compress_with(&Lz4Compressor, data),
+        CompressionMethod::Zstd => compress_with(&ZstdCompressor, data),
     }
 }
 
@@ -73,6 +242,39 @@ pub fn compress(method: CompressionMethod, data: &[u8]) -> Result<Bytes> {
 /// - The compression method byte is unrecognized.
 /// - The underlying LZ4 or ZSTD decoder fails.
 pub fn decompress(data: &[u8]) -> Result<Bytes> {
+    decompress_with(data, None)
+}
+
+/// Decompress a complete frame, trying `custom` before the built-in codecs.
+///
+/// A frame whose method byte equals `custom.method_byte()` is decoded by the
+/// custom codec; all other frames are handled as in [`decompress`].
+///
+/// # Errors
+///
+/// Same as [`decompress`], plus any error returned by the custom codec.
+pub fn decompress_with(
+    data: &[u8],
+    custom: Option<&dyn Compressor>,
+) -> Result<Bytes> {
+    let mut out = Vec::new();
+    decompress_into(data, custom, &mut out)?;
+    Ok(Bytes::from(out))
+}
+
+/// Decompress a complete frame, appending the payload to `out`.
+///
+/// Behaves like [`decompress_with`] but lets the caller reuse one buffer
+/// across frames. On error `out` is left as it was.
+///
+/// # Errors
+///
+/// Same as [`decompress_with`].
+pub fn decompress_into(
+    data: &[u8],
+    custom: Option<&dyn Compressor>,
+    out: &mut Vec<u8>,
+) -> Result<()> {
     if data.len() < CHECKSUM_SIZE + HEADER_SIZE {
         return Err(Error::Compression(
             "Data too small for checksum and compression header".to_string(),
@@ -108,6 +310,15 @@ pub fn decompress(data: &[u8]) -> Result<Bytes> {
     // The remaining data after header
     let compressed_data = &data_without_checksum[HEADER_SIZE..];
 
+    if let Some(compressor) = custom.filter(|c| c.method_byte() == method) {
+        return decompress_payload(
+            compressor,
+            compressed_data,
+            uncompressed_size,
+            out,
+        );
+    }
+
     match method {
         0x02 => {
             // No compression
@@ -118,15 +329,26 @@ pub fn decompress(data: &[u8]) -> Result<Bytes> {
                     compressed_data.len()
                 )));
             }
-            Ok(Bytes::copy_from_slice(compressed_data))
+            out.extend_from_slice(compressed_data);
+            Ok(())
         }
         0x82 => {
             // LZ4
-            decompress_lz4(compressed_data, uncompressed_size)
+            decompress_payload(
+                &Lz4Compressor,
+                compressed_data,
+                uncompressed_size,
+                out,
+            )
         }
         0x90 => {
             // ZSTD
-            decompress_zstd(compressed_data, uncompressed_size)
+            decompress_payload(
+                &ZstdCompressor,
+                compressed_data,
+                uncompressed_size,
+                out,
+            )
         }
         _ => Err(Error::Compression(format!(
             "Unknown compression method: 0x{:02x}",
@@ -135,72 +357,23 @@ pub fn decompress(data: &[u8]) -> Result<Bytes> {
     }
 }
 
-/// Compress using LZ4
-fn compress_lz4(data: &[u8]) -> Result<Bytes> {
-    let max_compressed_size = lz4::block::compress_bound(data.len())?;
-    let mut compressed = vec![0u8; max_compressed_size];
-
-    let compressed_size =
-        lz4::block::compress_to_buffer(data, None, false, &mut compressed)?;
-
-    compressed.truncate(compressed_size);
-
-    // Build header + compressed data
-    let mut header_and_data =
-        BytesMut::with_capacity(HEADER_SIZE + compressed_size);
-
-    // Write header
-    header_and_data.put_u8(CompressionMethodByte::Lz4 as u8);
-    header_and_data.put_u32_le((HEADER_SIZE + compressed_size) as u32); // Total size including header
-    header_and_data.put_u32_le(data.len() as u32); // Uncompressed size
-
-    // Write compressed data
-    header_and_data.put_slice(&compressed);
-
-    // Compute CityHash128 checksum of header + compressed data
-    let checksum = cityhash_102_128(&header_and_data);
-
-    // Build final output with checksum
-    // CityHash128 returns u128, write as (high64, low64) - reverse of typical
-    // order
-    let mut output =
-        BytesMut::with_capacity(CHECKSUM_SIZE + header_and_data.len());
-    output.put_u64_le((checksum >> 64) as u64); // High 64 bits first
-    output.put_u64_le(checksum as u64); // Low 64 bits second
-    output.put_slice(&header_and_data);
-
-    Ok(output.freeze())
-}
-
-/// Decompress LZ4 data
-fn decompress_lz4(data: &[u8], uncompressed_size: usize) -> Result<Bytes> {
-    let decompressed =
-        lz4::block::decompress(data, Some(uncompressed_size as i32))?;
-
-    if decompressed.len() != uncompressed_size {
-        return Err(Error::Compression(format!(
-            "LZ4 decompression size mismatch: expected {}, got {}",
-            uncompressed_size,
-            decompressed.len()
-        )));
-    }
-
-    Ok(Bytes::from(decompressed))
-}
-
-/// Compress using ZSTD
-fn compress_zstd(data: &[u8]) -> Result<Bytes> {
-    let compressed = zstd::bulk::compress(data, 3) // Compression level 3
-        .map_err(|e| {
-            Error::Compression(format!("ZSTD compression failed: {}", e))
-        })?;
+/// Compress data into a complete frame using a [`Compressor`].
+///
+/// # Errors
+///
+/// Returns any error reported by the codec.
+pub fn compress_with(
+    compressor: &dyn Compressor,
+    data: &[u8],
+) -> Result<Bytes> {
+    let compressed = compressor.compress(data)?;
 
     // Build header + compressed data
     let mut header_and_data =
         BytesMut::with_capacity(HEADER_SIZE + compressed.len());
 
     // Write header
-    header_and_data.put_u8(CompressionMethodByte::Zstd as u8);
+    header_and_data.put_u8(compressor.method_byte());
     header_and_data.put_u32_le((HEADER_SIZE + compressed.len()) as u32); // Total size including header
     header_and_data.put_u32_le(data.len() as u32); // Uncompressed size
 
@@ -222,22 +395,29 @@ fn compress_zstd(data: &[u8]) -> Result<Bytes> {
     Ok(output.freeze())
 }
 
-/// Decompress ZSTD data
-fn decompress_zstd(data: &[u8], uncompressed_size: usize) -> Result<Bytes> {
-    let decompressed = zstd::bulk::decompress(data, uncompressed_size)
-        .map_err(|e| {
-            Error::Compression(format!("ZSTD decompression failed: {}", e))
-        })?;
-
-    if decompressed.len() != uncompressed_size {
+/// Decompress a payload onto `out` and check it has the advertised size
+fn decompress_payload(
+    compressor: &dyn Compressor,
+    data: &[u8],
+    uncompressed_size: usize,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    let start = out.len();
+    compressor.decompress_into(data, uncompressed_size, out)?;
+
+    let decompressed = out.len() - start;
+    if decompressed != uncompressed_size {
+        out.truncate(start);
         return Err(Error::Compression(format!(
-            "ZSTD decompression size mismatch: expected {}, got {}",
+            "Decompression size mismatch for method 0x{:02x}: expected {}, \
+             got {}",
+            compressor.method_byte(),
             uncompressed_size,
-            decompressed.len()
+            decompressed
         )));
     }
 
-    Ok(Bytes::from(decompressed))
+    Ok(())
 }
 
 /// No compression (just adds header)
@@ -271,6 +451,26 @@ fn compress_none(data: &[u8]) -> Result<Bytes> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decompress_into_appends_and_keeps_prefix_on_error() {
+        let data = b"Hello, ClickHouse!".repeat(10);
+        let frame = compress(CompressionMethod::Lz4, &data).unwrap();
+
+        let mut out = b"prefix".to_vec();
+        decompress_into(&frame, None, &mut out).unwrap();
+        assert_eq!(&out[..6], b"prefix");
+        assert_eq!(&out[6..], &data[..]);
+
+        // Corrupt the payload: the buffer must be left untouched
+        let mut corrupted = frame.to_vec();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0xff;
+        corrupted[CHECKSUM_SIZE + HEADER_SIZE] ^= 0xff;
+        let before = out.clone();
+        assert!(decompress_into(&corrupted, None, &mut out).is_err());
+        assert_eq!(out, before);
+    }
+
     #[test]
     fn test_compress_decompress_none() {
         let original = b"Hello, ClickHouse!";
@@ -294,6 +494,134 @@ mod tests {
         assert!(compressed.len() < original.len());
     }
 
+    /// Frame of a single LZ4-compressed block as the server sends it: the
+    /// string "ClickHouse" encoded as one literal-only LZ4 sequence.
+    const SERVER_LZ4_FRAME: [u8; 36] = [
+        0xf2, 0x3c, 0xf7, 0x77, 0x2e, 0x62, 0xd5,
+        0x1d, // checksum high64
+        0xcb, 0x3e, 0x97, 0xa5, 0x07, 0x66, 0x73, 0x17, // checksum low64
+        0x82, // method: LZ4
+        0x14, 0x00, 0x00, 0x00, // compressed size (header + payload)
+        0x0a, 0x00, 0x00, 0x00, // uncompressed size
+        0xa0, b'C', b'l', b'i', b'c', b'k', b'H', b'o', b'u', b's', b'e',
+    ];
+
+    #[test]
+    fn test_decompress_server_lz4_frame() {
+        let decompressed = decompress(&SERVER_LZ4_FRAME).unwrap();
+        assert_eq!(&decompressed[..], b"ClickHouse");
+    }
+
+    #[test]
+    fn test_compress_lz4_matches_server_layout() {
+        // Encoding the same input must produce the exact same bytes as the
+        // server, i.e. a raw block with no size prefix and no frame header.
+        let compressed =
+            compress(CompressionMethod::Lz4, b"ClickHouse").unwrap();
+        assert_eq!(&compressed[..], &SERVER_LZ4_FRAME[..]);
+    }
+
+    #[test]
+    fn test_compress_lz4_is_raw_block() {
+        let original = b"Hello, ClickHouse! ".repeat(100);
+        let compressed = compress(CompressionMethod::Lz4, &original).unwrap();
+
+        let payload = &compressed[CHECKSUM_SIZE + HEADER_SIZE..];
+        assert!(!payload.starts_with(&LZ4_FRAME_MAGIC));
+
+        let compressed_size = u32::from_le_bytes(
+            compressed[CHECKSUM_SIZE + 1..CHECKSUM_SIZE + 5]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        assert_eq!(compressed_size, HEADER_SIZE + payload.len());
+
+        let block =
+            lz4::block::decompress(payload, Some(original.len() as i32))
+                .unwrap();
+        assert_eq!(block, original);
+    }
+
+    #[test]
+    fn test_decompress_rejects_lz4_frame_format() {
+        let original = b"Hello, ClickHouse!";
+        let mut encoder =
+            lz4::EncoderBuilder::new().build(Vec::new()).unwrap();
+        std::io::Write::write_all(&mut encoder, original).unwrap();
+        let (lz4_frame, result) = encoder.finish();
+        result.unwrap();
+
+        let mut data = vec![0u8; CHECKSUM_SIZE];
+        data.push(CompressionMethodByte::Lz4 as u8);
+        data.extend_from_slice(
+            &((HEADER_SIZE + lz4_frame.len()) as u32).to_le_bytes(),
+        );
+        data.extend_from_slice(&(original.len() as u32).to_le_bytes());
+        data.extend_from_slice(&lz4_frame);
+
+        let err = decompress(&data).unwrap_err();
+        assert!(err.to_string().contains("frame format"));
+    }
+
+    /// Codec that stores the payload unchanged under a private method byte
+    #[derive(Debug)]
+    struct PassthroughCompressor;
+
+    impl Compressor for PassthroughCompressor {
+        fn method_byte(&self) -> u8 {
+            0x7f
+        }
+
+        fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+
+        fn decompress(
+            &self,
+            data: &[u8],
+            _uncompressed_size: usize,
+        ) -> Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+    }
+
+    #[test]
+    fn test_custom_compressor_roundtrip() {
+        let original = b"custom codec payload".repeat(10);
+
+        let compressed =
+            compress_with(&PassthroughCompressor, &original).unwrap();
+        assert_eq!(compressed[CHECKSUM_SIZE], 0x7f);
+
+        let decompressed =
+            decompress_with(&compressed, Some(&PassthroughCompressor))
+                .unwrap();
+        assert_eq!(&decompressed[..], &original[..]);
+
+        // Built-in frames still decode when a custom codec is registered
+        let lz4 = compress(CompressionMethod::Lz4, &original).unwrap();
+        let decompressed =
+            decompress_with(&lz4, Some(&PassthroughCompressor)).unwrap();
+        assert_eq!(&decompressed[..], &original[..]);
+
+        // Without the codec the method byte is unknown
+        assert!(decompress(&compressed).is_err());
+    }
+
+    #[test]
+    fn test_builtin_compressors_match_compress() {
+        let original = b"Hello, ClickHouse! ".repeat(20);
+
+        assert_eq!(
+            compress_with(&Lz4Compressor, &original).unwrap(),
+            compress(CompressionMethod::Lz4, &original).unwrap()
+        );
+        assert_eq!(
+            compress_with(&ZstdCompressor, &original).unwrap(),
+            compress(CompressionMethod::Zstd, &original).unwrap()
+        );
+    }
+
     #[test]
     fn test_compress_decompress_zstd() {
         let original =
@@ -129,6 +129,17 @@ pub struct Connection {
 impl Connection {
     /// Create a new connection from a TCP stream
     pub fn new(stream: TcpStream) -> Self {
+        Self::from_stream(stream)
+    }
+
+    /// Create a new connection from any bidirectional byte stream
+    ///
+    /// Used for streams established outside this crate, such as proxy or
+    /// tunnel connections, and in-memory pipes in tests.
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         let (read_half, write_half) = tokio::io::split(stream);
 
         Self {
@@ -148,18 +159,7 @@ impl Connection {
     pub fn new_tls(
         stream: tokio_rustls::client::TlsStream<TcpStream>,
     ) -> Self {
-        let (read_half, write_half) = tokio::io::split(stream);
-
-        Self {
-            reader: BufReader::with_capacity(
-                DEFAULT_READ_BUFFER_SIZE,
-                Box::new(read_half) as Box<dyn AsyncRead + Unpin + Send>,
-            ),
-            writer: BufWriter::with_capacity(
-                DEFAULT_WRITE_BUFFER_SIZE,
-                Box::new(write_half) as Box<dyn AsyncWrite + Unpin + Send>,
-            ),
-        }
+        Self::from_stream(stream)
     }
 
     /// Connect to a ClickHouse server with default options
@@ -3,6 +3,7 @@
 //! All fallible operations in this crate return [`Result<T>`], which is an
 //! alias for `std::result::Result<T, Error>`.
 
+use crate::query::Exception;
 use thiserror::Error;
 
 /// Errors that can occur when using the ClickHouse client.
@@ -16,11 +17,30 @@ pub enum Error {
     #[error("Connection error: {0}")]
     Connection(String),
 
+    /// The server closed the connection while a block was being received.
+    ///
+    /// This usually means the server gave up on a slow reader: its
+    /// `send_timeout` (or the client-facing `receive_timeout`) is shorter
+    /// than the time the client takes to consume the result. Client-side
+    /// read timeouts are reported as [`Error::Io`] instead.
+    #[error(
+        "Server closed the connection during data transfer ({0}); consider \
+         increasing send_timeout/receive_timeout on the server"
+    )]
+    ServerClosedConnection(String),
+
     /// A protocol-level error, such as an unexpected packet type or
     /// malformed data from the server.
     #[error("Protocol error: {0}")]
     Protocol(String),
 
+    /// Data being parsed from memory ended before the value it holds.
+    ///
+    /// A block split over several compressed frames is parsed again once
+    /// the next frame arrives; anywhere else this means truncated data.
+    #[error("Incomplete data: {0}")]
+    Incomplete(String),
+
     /// An error during LZ4 or ZSTD compression/decompression.
     #[error("Compression error: {0}")]
     Compression(String),
@@ -38,15 +58,29 @@ pub enum Error {
     #[error("Validation error: {0}")]
     Validation(String),
 
-    /// An error returned by the ClickHouse server (exception).
-    #[error("Server error {code}: {message}")]
-    Server {
-        /// ClickHouse error code.
-        code: i32,
-        /// Error message from the server.
-        message: String,
+    /// An exception returned by the ClickHouse server, with its code,
+    /// name, stack trace and nested exceptions.
+    #[error("ClickHouse exception: {0}")]
+    Server(Exception),
+
+    /// An operation did not complete within the allotted time.
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    /// The server reported a column type this crate cannot decode yet.
+    ///
+    /// Kept separate from [`Error::Protocol`] so callers can skip or cast
+    /// such columns instead of treating the stream as corrupt.
+    #[error("Unsupported type: {type_string}")]
+    UnsupportedType {
+        /// The type string as written by the server.
+        type_string: String,
     },
 
+    /// The server is too old for a feature the request uses.
+    #[error("Unsupported feature: {0}")]
+    UnsupportedFeature(String),
+
     /// A feature or type that has not been implemented yet.
     #[error("Not implemented: {0}")]
     NotImplemented(String),
@@ -64,5 +98,16 @@ pub enum Error {
     Utf8(#[from] std::str::Utf8Error),
 }
 
+impl Error {
+    /// The ClickHouse error code if this is a server exception, e.g. 159
+    /// for `TIMEOUT_EXCEEDED` or 60 for `UNKNOWN_TABLE`.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Error::Server(exception) => Some(exception.code),
+            _ => None,
+        }
+    }
+}
+
 /// A type alias for `std::result::Result<T, Error>`.
 pub type Result<T> = std::result::Result<T, Error>;
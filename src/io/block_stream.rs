@@ -6,11 +6,17 @@ use crate::{
     column::ColumnRef,
     compression::{
         compress,
-        decompress,
+        compress_with,
+        decompress_into,
+        Compressor,
+        MAX_COMPRESSED_SIZE,
     },
     connection::Connection,
     io::buffer_utils,
-    protocol::CompressionMethod,
+    protocol::{
+        CompressionMethod,
+        LOG_TARGET,
+    },
     types::Type,
     Error,
     Result,
@@ -21,18 +27,42 @@ use bytes::{
     BytesMut,
 };
 use std::sync::Arc;
-use tracing::debug;
+use tracing::trace;
 
 /// Minimum revision constants
 const DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES: u64 = 50264;
 const DBMS_MIN_REVISION_WITH_BLOCK_INFO: u64 = 51903;
 const DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION: u64 = 54454;
 
+/// Turn an EOF or reset from the peer into `Error::ServerClosedConnection`.
+///
+/// Only errors raised by the server going away are mapped; client-side
+/// timeouts (`TimedOut`/`WouldBlock`) stay plain I/O errors.
+fn map_server_close(err: Error) -> Error {
+    match err {
+        Error::Io(e)
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+            ) =>
+        {
+            Error::ServerClosedConnection(e.to_string())
+        }
+        other => other,
+    }
+}
+
 /// Create a column instance for the given type
-/// This is used internally by column types like Array and Nullable
+///
+/// Used by both block read paths and by compound columns such as Array
+/// and Nullable to build their nested columns.
 pub fn create_column(type_: &Type) -> Result<ColumnRef> {
     use crate::column::{
         array::ColumnArray,
+        boolean::ColumnBool,
         date::{
             ColumnDate,
             ColumnDate32,
@@ -44,10 +74,19 @@ pub fn create_column(type_: &Type) -> Result<ColumnRef> {
             ColumnEnum16,
             ColumnEnum8,
         },
+        geo::{
+            ColumnMultiPolygon,
+            ColumnPoint,
+            ColumnPolygon,
+            ColumnRing,
+        },
+        interval::ColumnInterval,
         ipv4::ColumnIpv4,
         ipv6::ColumnIpv6,
+        json::ColumnJson,
         lowcardinality::ColumnLowCardinality,
         map::ColumnMap,
+        nested::ColumnNested,
         nothing::ColumnNothing,
         nullable::ColumnNullable,
         numeric::*,
@@ -63,20 +102,24 @@ pub fn create_column(type_: &Type) -> Result<ColumnRef> {
             use crate::types::TypeCode;
             match code {
                 TypeCode::UInt8 => Ok(Arc::new(ColumnUInt8::new())),
+                TypeCode::Bool => Ok(Arc::new(ColumnBool::new())),
                 TypeCode::UInt16 => Ok(Arc::new(ColumnUInt16::new())),
                 TypeCode::UInt32 => Ok(Arc::new(ColumnUInt32::new())),
                 TypeCode::UInt64 => Ok(Arc::new(ColumnUInt64::new())),
                 TypeCode::UInt128 => Ok(Arc::new(ColumnUInt128::new())),
+                TypeCode::UInt256 => Ok(Arc::new(ColumnUInt256::new())),
                 TypeCode::Int8 => Ok(Arc::new(ColumnInt8::new())),
                 TypeCode::Int16 => Ok(Arc::new(ColumnInt16::new())),
                 TypeCode::Int32 => Ok(Arc::new(ColumnInt32::new())),
                 TypeCode::Int64 => Ok(Arc::new(ColumnInt64::new())),
                 TypeCode::Int128 => Ok(Arc::new(ColumnInt128::new())),
+                TypeCode::Int256 => Ok(Arc::new(ColumnInt256::new())),
                 TypeCode::Float32 => Ok(Arc::new(ColumnFloat32::new())),
                 TypeCode::Float64 => Ok(Arc::new(ColumnFloat64::new())),
                 TypeCode::String => {
                     Ok(Arc::new(ColumnString::new(type_.clone())))
                 }
+                TypeCode::Json => Ok(Arc::new(ColumnJson::new(type_.clone()))),
                 TypeCode::Date => Ok(Arc::new(ColumnDate::new(type_.clone()))),
                 TypeCode::Date32 => {
                     Ok(Arc::new(ColumnDate32::new(type_.clone())))
@@ -88,53 +131,14 @@ pub fn create_column(type_: &Type) -> Result<ColumnRef> {
                     Ok(Arc::new(ColumnNothing::new(type_.clone())))
                 }
                 // Geo types are compound types built from Tuple and Array
-                // They use the same column implementation but preserve the geo
-                // type name
-                TypeCode::Point => {
-                    // Point is Tuple(Float64, Float64)
-                    let columns: Vec<ColumnRef> = vec![
-                        Arc::new(ColumnFloat64::new()) as ColumnRef,
-                        Arc::new(ColumnFloat64::new()) as ColumnRef,
-                    ];
-                    Ok(Arc::new(crate::column::ColumnTuple::new(
-                        type_.clone(),
-                        columns,
-                    )))
-                }
-                TypeCode::Ring => {
-                    // Ring is Array(Point) - manually create with Point nested
-                    // type
-                    let point_type = Type::Simple(TypeCode::Point);
-                    let nested = create_column(&point_type)?;
-                    Ok(Arc::new(ColumnArray::from_parts(
-                        type_.clone(),
-                        nested,
-                    )))
-                }
-                TypeCode::Polygon => {
-                    // Polygon is Array(Ring) - manually create with Ring
-                    // nested type
-                    let ring_type = Type::Simple(TypeCode::Ring);
-                    let nested = create_column(&ring_type)?;
-                    Ok(Arc::new(ColumnArray::from_parts(
-                        type_.clone(),
-                        nested,
-                    )))
-                }
+                // that preserve the geo type name
+                TypeCode::Point => Ok(Arc::new(ColumnPoint::new())),
+                TypeCode::Ring => Ok(Arc::new(ColumnRing::ring())),
+                TypeCode::Polygon => Ok(Arc::new(ColumnPolygon::polygon())),
                 TypeCode::MultiPolygon => {
-                    // MultiPolygon is Array(Polygon) - manually create with
-                    // Polygon nested type
-                    let polygon_type = Type::Simple(TypeCode::Polygon);
-                    let nested = create_column(&polygon_type)?;
-                    Ok(Arc::new(ColumnArray::from_parts(
-                        type_.clone(),
-                        nested,
-                    )))
+                    Ok(Arc::new(ColumnMultiPolygon::multi_polygon()))
                 }
-                _ => Err(Error::Protocol(format!(
-                    "Unsupported type: {}",
-                    type_.name()
-                ))),
+                _ => Err(Error::UnsupportedType { type_string: type_.name() }),
             }
         }
         Type::FixedString { .. } => {
@@ -160,11 +164,13 @@ pub fn create_column(type_: &Type) -> Result<ColumnRef> {
             // Use specialized ColumnDecimal with precision and scale
             Ok(Arc::new(ColumnDecimal::new(type_.clone())))
         }
+        Type::Interval { unit } => Ok(Arc::new(ColumnInterval::new(*unit))),
         Type::Nullable { .. } => {
             Ok(Arc::new(ColumnNullable::new(type_.clone())))
         }
         Type::Array { .. } => Ok(Arc::new(ColumnArray::new(type_.clone()))),
         Type::Map { .. } => Ok(Arc::new(ColumnMap::new(type_.clone()))),
+        Type::Nested { .. } => Ok(Arc::new(ColumnNested::new(type_.clone()))),
         Type::LowCardinality { .. } => {
             Ok(Arc::new(ColumnLowCardinality::new(type_.clone())))
         }
@@ -183,15 +189,32 @@ pub fn create_column(type_: &Type) -> Result<ColumnRef> {
 }
 
 /// Reader for blocks from network
+///
+/// Compressed frames are read and decompressed into scratch buffers owned
+/// by the reader, so streaming many blocks does not allocate per block.
 pub struct BlockReader {
     server_revision: u64,
     compression: Option<CompressionMethod>,
+    compressor: Option<Arc<dyn Compressor>>,
+    /// Keep scratch buffers between blocks (default: true)
+    reuse_buffers: bool,
+    /// Raw compressed frame (checksum + header + payload)
+    frame_buf: Vec<u8>,
+    /// Decompressed data of the block being assembled
+    block_buf: Vec<u8>,
 }
 
 impl BlockReader {
     /// Create a new block reader
     pub fn new(server_revision: u64) -> Self {
-        Self { server_revision, compression: None }
+        Self {
+            server_revision,
+            compression: None,
+            compressor: None,
+            reuse_buffers: true,
+            frame_buf: Vec::new(),
+            block_buf: Vec::new(),
+        }
     }
 
     /// Enable compression
@@ -200,28 +223,59 @@ impl BlockReader {
         self
     }
 
-    /// Read and decompress a single compressed frame from the connection.
+    /// Enable compression and decode frames with a custom codec
+    ///
+    /// Frames carrying the codec's method byte are decoded by it; frames
+    /// using built-in methods are still accepted.
+    pub fn with_compressor(mut self, compressor: Arc<dyn Compressor>) -> Self {
+        self.compressor = Some(compressor);
+        self
+    }
+
+    /// Keep decompression scratch buffers between blocks
+    ///
+    /// Enabled by default. With reuse, the buffers grow to the largest block
+    /// seen and stay allocated; disable it to release them after each block
+    /// when one unusually large block should not pin its memory.
+    pub fn with_reuse_buffers(mut self, reuse: bool) -> Self {
+        self.reuse_buffers = reuse;
+        self
+    }
+
+    /// Whether blocks arrive in compressed frames
+    fn is_compressed(&self) -> bool {
+        self.compression.is_some() || self.compressor.is_some()
+    }
+
+    /// Read a compressed frame and append its payload to `block_buf`.
     async fn read_compressed_frame(
-        &self,
+        &mut self,
         conn: &mut Connection,
-    ) -> Result<bytes::Bytes> {
-        let checksum = conn.read_bytes(16).await?;
-        let method = conn.read_u8().await?;
-        let compressed_size = conn.read_u32().await? as usize;
-        let uncompressed_size = conn.read_u32().await?;
-
+    ) -> Result<()> {
+        let mut header = [0u8; 16 + 9];
+        conn.read_exact(&mut header).await?;
+        let compressed_size =
+            u32::from_le_bytes(header[17..21].try_into().unwrap()) as usize;
         let compressed_data_len = compressed_size.saturating_sub(9);
-        let compressed_data = conn.read_bytes(compressed_data_len).await?;
 
-        let mut full_block =
-            BytesMut::with_capacity(16 + 9 + compressed_data_len);
-        full_block.extend_from_slice(&checksum);
-        full_block.put_u8(method);
-        full_block.put_u32_le(compressed_size as u32);
-        full_block.put_u32_le(uncompressed_size);
-        full_block.extend_from_slice(&compressed_data);
+        self.frame_buf.clear();
+        self.frame_buf.extend_from_slice(&header);
+        self.frame_buf.resize(header.len() + compressed_data_len, 0);
+        conn.read_exact(&mut self.frame_buf[header.len()..]).await?;
+
+        decompress_into(
+            &self.frame_buf,
+            self.compressor.as_deref(),
+            &mut self.block_buf,
+        )
+    }
 
-        decompress(&full_block)
+    /// Drop the scratch buffers unless they are meant to be reused
+    fn release_buffers(&mut self) {
+        if !self.reuse_buffers {
+            self.frame_buf = Vec::new();
+            self.block_buf = Vec::new();
+        }
     }
 
     /// Read a block from the connection.
@@ -233,30 +287,63 @@ impl BlockReader {
     ///
     /// Note: Caller is responsible for skipping temp table name if needed
     /// (matches C++ ReadBlock / CompressedInput).
-    pub async fn read_block(&self, conn: &mut Connection) -> Result<Block> {
-        if self.compression.is_none() {
+    ///
+    /// A server-side close in the middle of the block is reported as
+    /// [`Error::ServerClosedConnection`].
+    pub async fn read_block(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<Block> {
+        let result =
+            self.read_block_impl(conn).await.map_err(map_server_close);
+        self.release_buffers();
+        result
+    }
+
+    async fn read_block_impl(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<Block> {
+        if !self.is_compressed() {
             return self.read_block_direct(conn).await;
         }
 
-        let mut accumulated: Vec<u8> = Vec::new();
+        let block = self
+            .read_compressed_block(conn, Self::parse_block_from_buffer)
+            .await?;
+        trace!(
+            target: LOG_TARGET,
+            columns = block.column_count(),
+            rows = block.row_count(),
+            bytes = self.block_buf.len(),
+            "Read compressed block"
+        );
+        Ok(block)
+    }
+
+    /// Read compressed frames until `parse` succeeds on the decompressed
+    /// data accumulated so far, which then holds one whole block
+    ///
+    /// `parse` runs on the data from its start after every frame; running
+    /// out of data means the block continues in the next frame.
+    async fn read_compressed_block<T>(
+        &mut self,
+        conn: &mut Connection,
+        parse: impl Fn(&Self, &mut &[u8]) -> Result<T>,
+    ) -> Result<T> {
+        // Start from an empty buffer so nothing from the previous block
+        // can be parsed as part of this one
+        self.block_buf.clear();
         const MAX_FRAMES: usize = 4096;
 
         for _ in 0..MAX_FRAMES {
-            let frame = self.read_compressed_frame(conn).await?;
-            accumulated.extend_from_slice(&frame);
-
-            let mut slice: &[u8] = &accumulated;
-            match self.parse_block_from_buffer(&mut slice) {
-                Ok(block) => return Ok(block),
-                Err(e) => {
-                    let msg = e.to_string();
-                    let is_underflow = msg.contains("Not enough data")
-                        || msg.contains("Buffer underflow")
-                        || msg.contains("Unexpected end");
-                    if !is_underflow {
-                        return Err(e);
-                    }
-                }
+            self.read_compressed_frame(conn).await?;
+
+            let mut slice: &[u8] = &self.block_buf;
+            match parse(self, &mut slice) {
+                Ok(value) => return Ok(value),
+                Err(Error::Incomplete(_)) => {}
+                Err(e) => return Err(e),
             }
         }
 
@@ -265,6 +352,168 @@ impl BlockReader {
         ))
     }
 
+    /// Read a block from the connection and return only its row count.
+    ///
+    /// The block header is parsed but column payloads are skipped using the
+    /// known per-type wire widths instead of being decoded into columns.
+    /// Compressed frames still have to be decompressed to find the end of
+    /// the block.
+    pub async fn read_block_row_count(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<usize> {
+        let result = self
+            .read_block_row_count_impl(conn)
+            .await
+            .map_err(map_server_close);
+        self.release_buffers();
+        result
+    }
+
+    async fn read_block_row_count_impl(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<usize> {
+        if !self.is_compressed() {
+            return self.skip_block_direct(conn).await;
+        }
+
+        self.read_compressed_block(conn, Self::skip_block_in_buffer).await
+    }
+
+    /// Skip an uncompressed block on the connection, returning its row count
+    async fn skip_block_direct(&self, conn: &mut Connection) -> Result<usize> {
+        if self.server_revision >= DBMS_MIN_REVISION_WITH_BLOCK_INFO {
+            self.read_block_info(conn).await?;
+        }
+
+        let num_columns = conn.read_varint().await? as usize;
+        let num_rows = conn.read_varint().await? as usize;
+
+        for _ in 0..num_columns {
+            let _name = conn.read_string().await?;
+            let type_name = conn.read_string().await?;
+
+            let column_type = Type::parse(&type_name)?;
+            let kinds =
+                self.read_custom_serialization(conn, &column_type).await?;
+
+            if num_rows > 0 {
+                let mut data = BytesMut::new();
+                self.read_column_prefix_async(conn, &column_type, &mut data)
+                    .await?;
+                self.read_column_body_async(
+                    conn,
+                    &column_type,
+                    kinds.as_ref(),
+                    num_rows,
+                    &mut data,
+                )
+                .await?;
+            }
+        }
+
+        Ok(num_rows)
+    }
+
+    /// Skip a block in a decompressed buffer, returning its row count
+    fn skip_block_in_buffer(&self, buffer: &mut &[u8]) -> Result<usize> {
+        if self.server_revision >= DBMS_MIN_REVISION_WITH_BLOCK_INFO {
+            self.read_block_info_from_buffer(buffer)?;
+        }
+
+        let num_columns = buffer_utils::read_varint(buffer)? as usize;
+        let num_rows = buffer_utils::read_varint(buffer)? as usize;
+
+        for _ in 0..num_columns {
+            let _name = buffer_utils::read_string(buffer)?;
+            let type_name = buffer_utils::read_string(buffer)?;
+
+            let mut has_custom = false;
+            if self.server_revision
+                >= DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION
+            {
+                if buffer.is_empty() {
+                    return Err(Error::Incomplete(
+                        "Unexpected end of block data".to_string(),
+                    ));
+                }
+                has_custom = buffer[0] != 0;
+                buffer.advance(1);
+            }
+
+            let column_type = Type::parse(&type_name)?;
+            let kinds = if has_custom {
+                Some(read_serialization_kinds(buffer, &column_type)?)
+            } else {
+                None
+            };
+
+            if num_rows > 0 {
+                skip_column_prefix(buffer, &column_type)?;
+                match &kinds {
+                    Some(kinds) => densify_column_data(
+                        buffer,
+                        &column_type,
+                        kinds,
+                        num_rows,
+                        &mut BytesMut::new(),
+                    )?,
+                    None => skip_column_data(buffer, &column_type, num_rows)?,
+                }
+            }
+        }
+
+        Ok(num_rows)
+    }
+
+    /// Read column prefix data from the connection into `out`
+    /// (uncompressed blocks)
+    fn read_column_prefix_async<'a>(
+        &'a self,
+        conn: &'a mut Connection,
+        type_: &'a Type,
+        out: &'a mut BytesMut,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            match type_ {
+                Type::LowCardinality { .. } => {
+                    // key_version (UInt64)
+                    out.extend_from_slice(&conn.read_bytes(8).await?);
+                }
+                Type::Array { item_type } => {
+                    self.read_column_prefix_async(conn, item_type, out)
+                        .await?;
+                }
+                Type::Nullable { nested_type } => {
+                    self.read_column_prefix_async(conn, nested_type, out)
+                        .await?;
+                }
+                Type::Tuple { item_types } => {
+                    for item_type in item_types {
+                        self.read_column_prefix_async(conn, item_type, out)
+                            .await?;
+                    }
+                }
+                Type::Map { key_type, value_type } => {
+                    self.read_column_prefix_async(conn, key_type, out).await?;
+                    self.read_column_prefix_async(conn, value_type, out)
+                        .await?;
+                }
+                Type::Nested { item_types, .. } => {
+                    for item_type in item_types {
+                        self.read_column_prefix_async(conn, item_type, out)
+                            .await?;
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    }
+
     /// Read block directly from connection (uncompressed)
     async fn read_block_direct(&self, conn: &mut Connection) -> Result<Block> {
         let mut block = Block::new();
@@ -284,47 +533,152 @@ impl BlockReader {
             let name = conn.read_string().await?;
             let type_name = conn.read_string().await?;
 
-            // Check for custom serialization
-            if self.server_revision
-                >= DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION
-            {
-                let custom_len = conn.read_u8().await?;
-                if custom_len > 0 {
-                    return Err(Error::Protocol(
-                        "Custom serialization not supported".to_string(),
-                    ));
-                }
-            }
-
-            // Parse the type
+            // Parse the type and check for custom serialization
             let column_type = Type::parse(&type_name)?;
+            let kinds =
+                self.read_custom_serialization(conn, &column_type).await?;
 
             // Create column and load data
-            let column = self.create_column_by_type(&column_type)?;
+            let mut column = create_column(&column_type)?;
 
             if num_rows > 0 {
-                // Read column data directly from async stream
-                // For uncompressed blocks, we can read data type by type
-                self.load_column_data_async(conn, &column_type, num_rows)
+                // The stream carries no length for a column, so walk its
+                // wire layout type by type to collect exactly its bytes,
+                // then decode them like a decompressed block
+                let mut data = BytesMut::new();
+                self.read_column_prefix_async(conn, &column_type, &mut data)
                     .await?;
+                self.read_column_body_async(
+                    conn,
+                    &column_type,
+                    kinds.as_ref(),
+                    num_rows,
+                    &mut data,
+                )
+                .await?;
+
+                let column_mut =
+                    Arc::get_mut(&mut column).ok_or_else(|| {
+                        Error::Protocol("Column not mutable".to_string())
+                    })?;
+                let mut slice: &[u8] = &data;
+                column_mut.load_prefix(&mut slice, num_rows)?;
+                column_mut.load_from_buffer(&mut slice, num_rows)?;
             }
 
             block.append_column(name, column)?;
         }
 
+        trace!(
+            target: LOG_TARGET,
+            columns = num_columns,
+            rows = num_rows,
+            "Read uncompressed block"
+        );
         Ok(block)
     }
 
-    /// Load column data from async connection (for uncompressed blocks)
+    /// Read the custom serialization flag and, when set, the kinds that
+    /// follow it
+    async fn read_custom_serialization(
+        &self,
+        conn: &mut Connection,
+        type_: &Type,
+    ) -> Result<Option<SerializationKinds>> {
+        if self.server_revision < DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION
+            || conn.read_u8().await? == 0
+        {
+            return Ok(None);
+        }
+        let kinds = conn.read_bytes(serialization_kinds_len(type_)).await?;
+        read_serialization_kinds(&mut &kinds[..], type_).map(Some)
+    }
+
+    /// Read a column body into `out` in the regular layout, expanding data
+    /// sent with custom serialization
+    async fn read_column_body_async(
+        &self,
+        conn: &mut Connection,
+        type_: &Type,
+        kinds: Option<&SerializationKinds>,
+        num_rows: usize,
+        out: &mut BytesMut,
+    ) -> Result<()> {
+        let Some(kinds) = kinds else {
+            return self
+                .load_column_data_async(conn, type_, num_rows, out)
+                .await;
+        };
+        let mut custom = BytesMut::new();
+        self.load_custom_data_async(conn, type_, kinds, num_rows, &mut custom)
+            .await?;
+        densify_column_data(&mut &custom[..], type_, kinds, num_rows, out)
+    }
+
+    /// Read column data sent with custom serialization into `out`, as is
+    fn load_custom_data_async<'a>(
+        &'a self,
+        conn: &'a mut Connection,
+        type_: &'a Type,
+        kinds: &'a SerializationKinds,
+        num_rows: usize,
+        out: &'a mut BytesMut,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>,
+    > {
+        Box::pin(async move {
+            match (kinds, type_) {
+                (SerializationKinds::Default, _) => {
+                    self.load_column_data_async(conn, type_, num_rows, out)
+                        .await
+                }
+                (SerializationKinds::Sparse, _) => {
+                    // Group sizes, one per non-default value plus the
+                    // flagged trailing run of defaults
+                    let mut values = 0;
+                    loop {
+                        let group = conn.read_varint().await?;
+                        buffer_utils::write_varint(out, group);
+                        if group & SPARSE_END_OF_GRANULE_FLAG != 0 {
+                            break;
+                        }
+                        values += 1;
+                    }
+                    self.load_column_data_async(conn, type_, values, out).await
+                }
+                (
+                    SerializationKinds::Tuple(kinds),
+                    Type::Tuple { item_types },
+                ) => {
+                    for (item_type, kinds) in item_types.iter().zip(kinds) {
+                        self.load_custom_data_async(
+                            conn, item_type, kinds, num_rows, out,
+                        )
+                        .await?;
+                    }
+                    Ok(())
+                }
+                _ => Err(Error::Protocol(format!(
+                    "Serialization kinds do not match type {}",
+                    type_.name()
+                ))),
+            }
+        })
+    }
+
+    /// Read column data from async connection into `out` (for
+    /// uncompressed blocks)
     fn load_column_data_async<'a>(
         &'a self,
         conn: &'a mut Connection,
         type_: &'a Type,
         num_rows: usize,
-    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + 'a>>
-    {
+        out: &'a mut BytesMut,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>,
+    > {
         Box::pin(async move {
-            self.load_column_data_impl(conn, type_, num_rows).await
+            self.load_column_data_impl(conn, type_, num_rows, out).await
         })
     }
 
@@ -334,30 +688,38 @@ impl BlockReader {
         conn: &mut Connection,
         type_: &Type,
         num_rows: usize,
+        out: &mut BytesMut,
     ) -> Result<()> {
         use crate::types::TypeCode;
 
         // Try to use the storage_size_bytes helper for fixed-size types
         if let Some(size_per_row) = type_.storage_size_bytes() {
             // Fixed-size type - read all rows at once
-            let _ = conn.read_bytes(num_rows * size_per_row).await?;
+            out.extend_from_slice(
+                &conn.read_bytes(byte_len(num_rows, size_per_row)?).await?,
+            );
             return Ok(());
         }
 
         // Handle variable-length and complex types
         match type_ {
-            Type::Simple(TypeCode::String) => {
+            Type::Simple(TypeCode::String | TypeCode::Json) => {
                 // String - variable length, read each string
                 for _ in 0..num_rows {
-                    let len = conn.read_varint().await? as usize;
-                    let _ = conn.read_bytes(len).await?;
+                    let len = conn.read_varint().await?;
+                    buffer_utils::write_varint(out, len);
+                    out.extend_from_slice(
+                        &conn.read_bytes(string_len(len)?).await?,
+                    );
                 }
             }
             Type::Nullable { nested_type } => {
                 // Read null mask first (one byte per row)
-                let _ = conn.read_bytes(num_rows).await?;
+                out.extend_from_slice(
+                    &conn.read_bytes(byte_len(num_rows, 1)?).await?,
+                );
                 // Then read nested data (recursive call via boxed wrapper)
-                self.load_column_data_async(conn, nested_type, num_rows)
+                self.load_column_data_async(conn, nested_type, num_rows, out)
                     .await?;
             }
             Type::Array { item_type } => {
@@ -370,7 +732,9 @@ impl BlockReader {
                 }
 
                 // Read offsets array (UInt64 per row)
-                let offsets_data = conn.read_bytes(num_rows * 8).await?;
+                let offsets_data =
+                    conn.read_bytes(byte_len(num_rows, 8)?).await?;
+                out.extend_from_slice(&offsets_data);
 
                 // Parse the last offset to get total item count
                 // Offsets are cumulative, so last offset = total items
@@ -389,18 +753,68 @@ impl BlockReader {
 
                 // Recursively read nested column data
                 if total_items > 0 {
-                    self.load_column_data_async(conn, item_type, total_items)
-                        .await?;
+                    self.load_column_data_async(
+                        conn,
+                        item_type,
+                        total_items,
+                        out,
+                    )
+                    .await?;
                 }
             }
             Type::Tuple { item_types } => {
                 // Tuple wire format: each element serialized sequentially
                 // Read each tuple element's column data
                 for item_type in item_types {
-                    self.load_column_data_async(conn, item_type, num_rows)
-                        .await?;
+                    self.load_column_data_async(
+                        conn, item_type, num_rows, out,
+                    )
+                    .await?;
                 }
             }
+            Type::Simple(TypeCode::Ring) => {
+                let storage_type = Type::array(Type::point());
+                self.load_column_data_async(
+                    conn,
+                    &storage_type,
+                    num_rows,
+                    out,
+                )
+                .await?;
+            }
+            Type::Simple(TypeCode::Polygon) => {
+                let storage_type = Type::array(Type::ring());
+                self.load_column_data_async(
+                    conn,
+                    &storage_type,
+                    num_rows,
+                    out,
+                )
+                .await?;
+            }
+            Type::Simple(TypeCode::MultiPolygon) => {
+                let storage_type = Type::array(Type::polygon());
+                self.load_column_data_async(
+                    conn,
+                    &storage_type,
+                    num_rows,
+                    out,
+                )
+                .await?;
+            }
+            Type::Nested { .. } => {
+                // Nested wire format is Array(Tuple(...))
+                let storage_type = type_
+                    .nested_storage_type()
+                    .expect("Nested type has a storage type");
+                self.load_column_data_async(
+                    conn,
+                    &storage_type,
+                    num_rows,
+                    out,
+                )
+                .await?;
+            }
             Type::Map { key_type, value_type } => {
                 // Map wire format is Array(Tuple(K, V))
                 // We read it as: offsets array + tuple data
@@ -410,7 +824,9 @@ impl BlockReader {
                 }
 
                 // Read offsets array (UInt64 per row)
-                let offsets_data = conn.read_bytes(num_rows * 8).await?;
+                let offsets_data =
+                    conn.read_bytes(byte_len(num_rows, 8)?).await?;
+                out.extend_from_slice(&offsets_data);
 
                 // Parse the last offset to get total number of map entries
                 let last_offset_bytes =
@@ -429,20 +845,70 @@ impl BlockReader {
                 // Read tuple data: key column + value column
                 if total_entries > 0 {
                     // Read key column
-                    self.load_column_data_async(conn, key_type, total_entries)
-                        .await?;
+                    self.load_column_data_async(
+                        conn,
+                        key_type,
+                        total_entries,
+                        out,
+                    )
+                    .await?;
                     // Read value column
                     self.load_column_data_async(
                         conn,
                         value_type,
                         total_entries,
+                        out,
                     )
                     .await?;
                 }
             }
             Type::FixedString { size } => {
                 // FixedString - fixed size per row
-                let _ = conn.read_bytes(num_rows * size).await?;
+                out.extend_from_slice(
+                    &conn.read_bytes(byte_len(num_rows, *size)?).await?,
+                );
+            }
+            Type::Simple(TypeCode::Void) => {
+                // Nothing - one placeholder byte per row
+                out.extend_from_slice(
+                    &conn.read_bytes(byte_len(num_rows, 1)?).await?,
+                );
+            }
+            Type::LowCardinality { nested_type } => {
+                // index_serialization_type, then the dictionary size
+                let index_serialization_type = conn.read_u64().await?;
+                let number_of_keys = conn.read_u64().await?;
+                out.put_u64_le(index_serialization_type);
+                out.put_u64_le(number_of_keys);
+                let number_of_keys = number_of_keys as usize;
+
+                // Dictionary keys are serialized without a null map
+                let dictionary_type = match nested_type.as_ref() {
+                    Type::Nullable { nested_type } => nested_type.as_ref(),
+                    other => other,
+                };
+                if number_of_keys > 0 {
+                    self.load_column_data_async(
+                        conn,
+                        dictionary_type,
+                        number_of_keys,
+                        out,
+                    )
+                    .await?;
+                }
+
+                let number_of_rows = conn.read_u64().await?;
+                out.put_u64_le(number_of_rows);
+                let index_width =
+                    low_cardinality_index_width(index_serialization_type)?;
+                out.extend_from_slice(
+                    &conn
+                        .read_bytes(byte_len(
+                            number_of_rows as usize,
+                            index_width,
+                        )?)
+                        .await?,
+                );
             }
             _ => {
                 return Err(Error::Protocol(format!(
@@ -489,29 +955,29 @@ impl BlockReader {
             let type_name = buffer_utils::read_string(buffer)?;
 
             // Check for custom serialization
+            let mut has_custom = false;
             if self.server_revision
                 >= DBMS_MIN_REVISION_WITH_CUSTOM_SERIALIZATION
             {
                 if buffer.is_empty() {
-                    return Err(Error::Protocol(
+                    return Err(Error::Incomplete(
                         "Unexpected end of block data".to_string(),
                     ));
                 }
-                let custom_len = buffer[0];
+                has_custom = buffer[0] != 0;
                 buffer.advance(1);
-
-                if custom_len > 0 {
-                    return Err(Error::Protocol(
-                        "Custom serialization not supported".to_string(),
-                    ));
-                }
             }
 
             // Parse the type
             let column_type = Type::parse(&type_name)?;
+            let kinds = if has_custom {
+                Some(read_serialization_kinds(buffer, &column_type)?)
+            } else {
+                None
+            };
 
             // Create column and load data
-            let mut column = self.create_column_by_type(&column_type)?;
+            let mut column = create_column(&column_type)?;
 
             if num_rows > 0 {
                 let column_mut =
@@ -523,7 +989,21 @@ impl BlockReader {
                 column_mut.load_prefix(buffer, num_rows)?;
 
                 // Load column body data
-                column_mut.load_from_buffer(buffer, num_rows)?;
+                match &kinds {
+                    Some(kinds) => {
+                        let mut dense = BytesMut::new();
+                        densify_column_data(
+                            buffer,
+                            &column_type,
+                            kinds,
+                            num_rows,
+                            &mut dense,
+                        )?;
+                        column_mut
+                            .load_from_buffer(&mut &dense[..], num_rows)?;
+                    }
+                    None => column_mut.load_from_buffer(buffer, num_rows)?,
+                }
             }
 
             block.append_column(name, column)?;
@@ -540,7 +1020,7 @@ impl BlockReader {
         let _num1 = buffer_utils::read_varint(buffer)?;
 
         if buffer.is_empty() {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Unexpected end reading block info".to_string(),
             ));
         }
@@ -550,7 +1030,7 @@ impl BlockReader {
         let _num2 = buffer_utils::read_varint(buffer)?;
 
         if buffer.len() < 4 {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Unexpected end reading bucket_num".to_string(),
             ));
         }
@@ -562,137 +1042,25 @@ impl BlockReader {
 
         Ok(BlockInfo { is_overflows, bucket_num })
     }
-
-    /// Create a column by type
-    fn create_column_by_type(&self, type_: &Type) -> Result<ColumnRef> {
-        use crate::column::{
-            array::ColumnArray,
-            date::{
-                ColumnDate,
-                ColumnDate32,
-                ColumnDateTime,
-                ColumnDateTime64,
-            },
-            decimal::ColumnDecimal,
-            enum_column::{
-                ColumnEnum16,
-                ColumnEnum8,
-            },
-            ipv4::ColumnIpv4,
-            ipv6::ColumnIpv6,
-            lowcardinality::ColumnLowCardinality,
-            map::ColumnMap,
-            nothing::ColumnNothing,
-            nullable::ColumnNullable,
-            numeric::*,
-            string::{
-                ColumnFixedString,
-                ColumnString,
-            },
-            uuid::ColumnUuid,
-        };
-
-        match type_ {
-            Type::Simple(code) => {
-                use crate::types::TypeCode;
-                match code {
-                    TypeCode::UInt8 => Ok(Arc::new(ColumnUInt8::new())),
-                    TypeCode::UInt16 => Ok(Arc::new(ColumnUInt16::new())),
-                    TypeCode::UInt32 => Ok(Arc::new(ColumnUInt32::new())),
-                    TypeCode::UInt64 => Ok(Arc::new(ColumnUInt64::new())),
-                    TypeCode::UInt128 => Ok(Arc::new(ColumnUInt128::new())),
-                    TypeCode::Int8 => Ok(Arc::new(ColumnInt8::new())),
-                    TypeCode::Int16 => Ok(Arc::new(ColumnInt16::new())),
-                    TypeCode::Int32 => Ok(Arc::new(ColumnInt32::new())),
-                    TypeCode::Int64 => Ok(Arc::new(ColumnInt64::new())),
-                    TypeCode::Int128 => Ok(Arc::new(ColumnInt128::new())),
-                    TypeCode::Float32 => Ok(Arc::new(ColumnFloat32::new())),
-                    TypeCode::Float64 => Ok(Arc::new(ColumnFloat64::new())),
-                    TypeCode::String => {
-                        Ok(Arc::new(ColumnString::new(type_.clone())))
-                    }
-                    TypeCode::Date => {
-                        Ok(Arc::new(ColumnDate::new(type_.clone())))
-                    }
-                    TypeCode::Date32 => {
-                        Ok(Arc::new(ColumnDate32::new(type_.clone())))
-                    }
-                    TypeCode::UUID => {
-                        Ok(Arc::new(ColumnUuid::new(type_.clone())))
-                    }
-                    TypeCode::IPv4 => {
-                        Ok(Arc::new(ColumnIpv4::new(type_.clone())))
-                    }
-                    TypeCode::IPv6 => {
-                        Ok(Arc::new(ColumnIpv6::new(type_.clone())))
-                    }
-                    TypeCode::Void => {
-                        Ok(Arc::new(ColumnNothing::new(type_.clone())))
-                    }
-                    _ => Err(Error::Protocol(format!(
-                        "Unsupported type: {}",
-                        type_.name()
-                    ))),
-                }
-            }
-            Type::FixedString { .. } => {
-                Ok(Arc::new(ColumnFixedString::new(type_.clone())))
-            }
-            Type::DateTime { .. } => {
-                // Use specialized ColumnDateTime with timezone support
-                Ok(Arc::new(ColumnDateTime::new(type_.clone())))
-            }
-            Type::DateTime64 { .. } => {
-                // Use specialized ColumnDateTime64 with precision and timezone
-                Ok(Arc::new(ColumnDateTime64::new(type_.clone())))
-            }
-            Type::Enum8 { .. } => {
-                // Use specialized ColumnEnum8 with name-value mapping
-                Ok(Arc::new(ColumnEnum8::new(type_.clone())))
-            }
-            Type::Enum16 { .. } => {
-                // Use specialized ColumnEnum16 with name-value mapping
-                Ok(Arc::new(ColumnEnum16::new(type_.clone())))
-            }
-            Type::Decimal { .. } => {
-                // Use specialized ColumnDecimal with precision and scale
-                Ok(Arc::new(ColumnDecimal::new(type_.clone())))
-            }
-            Type::Nullable { .. } => {
-                Ok(Arc::new(ColumnNullable::new(type_.clone())))
-            }
-            Type::Array { .. } => {
-                Ok(Arc::new(ColumnArray::new(type_.clone())))
-            }
-            Type::Map { .. } => Ok(Arc::new(ColumnMap::new(type_.clone()))),
-            Type::LowCardinality { .. } => {
-                Ok(Arc::new(ColumnLowCardinality::new(type_.clone())))
-            }
-            Type::Tuple { item_types } => {
-                // Create empty columns for each tuple element
-                let mut columns = Vec::new();
-                for item_type in item_types {
-                    columns.push(create_column(item_type)?);
-                }
-                Ok(Arc::new(crate::column::ColumnTuple::new(
-                    type_.clone(),
-                    columns,
-                )))
-            }
-        }
-    }
 }
 
 /// Writer for blocks to network
 pub struct BlockWriter {
     server_revision: u64,
     compression: Option<CompressionMethod>,
+    compressor: Option<Arc<dyn Compressor>>,
+    max_chunk_size: usize,
 }
 
 impl BlockWriter {
     /// Create a new block writer
     pub fn new(server_revision: u64) -> Self {
-        Self { server_revision, compression: None }
+        Self {
+            server_revision,
+            compression: None,
+            compressor: None,
+            max_chunk_size: 65535,
+        }
     }
 
     /// Enable compression
@@ -701,6 +1069,24 @@ impl BlockWriter {
         self
     }
 
+    /// Compress outgoing blocks with a custom codec
+    ///
+    /// Takes precedence over [`BlockWriter::with_compression`].
+    pub fn with_compressor(mut self, compressor: Arc<dyn Compressor>) -> Self {
+        self.compressor = Some(compressor);
+        self
+    }
+
+    /// Limit how many uncompressed bytes go into one compressed frame
+    /// (default: 65535)
+    ///
+    /// Larger blocks are split into several frames, each with its own
+    /// header and checksum. Zero puts every block in a single frame.
+    pub fn with_max_chunk_size(mut self, size: usize) -> Self {
+        self.max_chunk_size = size;
+        self
+    }
+
     /// Write a block to the connection
     pub async fn write_block(
         &self,
@@ -721,7 +1107,8 @@ impl BlockWriter {
         block: &Block,
         write_temp_table_name: bool,
     ) -> Result<()> {
-        debug!(
+        trace!(
+            target: LOG_TARGET,
             "Writing block: {} columns, {} rows",
             block.column_count(),
             block.row_count()
@@ -731,35 +1118,71 @@ impl BlockWriter {
         if write_temp_table_name
             && self.server_revision >= DBMS_MIN_REVISION_WITH_TEMPORARY_TABLES
         {
-            debug!("Writing empty temp table name");
+            trace!(target: LOG_TARGET, "Writing empty temp table name");
             conn.write_string("").await?;
         }
 
         // Serialize block to buffer
         let mut buffer = BytesMut::new();
         self.write_block_to_buffer(&mut buffer, block)?;
-        debug!("Block serialized to {} bytes", buffer.len());
-
-        // Compress if needed
-        if let Some(compression_method) = self.compression {
-            let compressed = compress(compression_method, &buffer)?;
-            debug!("Compressed to {} bytes (includes 16-byte checksum + 9-byte header)", compressed.len());
-            // Compressed data already includes checksum + header, write it
-            // directly
-            conn.write_bytes(&compressed).await?;
+        trace!(
+            target: LOG_TARGET,
+            "Block serialized to {} bytes",
+            buffer.len()
+        );
+
+        // Compress if needed, one frame per chunk
+        let chunk_size = match self.max_chunk_size {
+            0 => buffer.len().max(1),
+            size => size,
+        };
+        if let Some(compressor) = &self.compressor {
+            for chunk in buffer.chunks(chunk_size) {
+                let compressed = compress_with(compressor.as_ref(), chunk)?;
+                trace!(
+                    target: LOG_TARGET,
+                    "Compressed to {} bytes with custom method 0x{:02x}",
+                    compressed.len(),
+                    compressor.method_byte()
+                );
+                conn.write_bytes(&compressed).await?;
+            }
+        } else if let Some(compression_method) = self.compression {
+            for chunk in buffer.chunks(chunk_size) {
+                let compressed = compress(compression_method, chunk)?;
+                trace!(
+                    target: LOG_TARGET,
+                    "Compressed to {} bytes (includes 16-byte checksum + 9-byte header)",
+                    compressed.len()
+                );
+                // Compressed data already includes checksum + header, write
+                // it directly
+                conn.write_bytes(&compressed).await?;
+            }
         } else {
             // Write uncompressed
-            debug!("Writing uncompressed block");
+            trace!(target: LOG_TARGET, "Writing uncompressed block");
             conn.write_bytes(&buffer).await?;
         }
 
         conn.flush().await?;
-        debug!("Block write complete");
+        trace!(target: LOG_TARGET, "Block write complete");
         Ok(())
     }
 
-    /// Write block to buffer
-    fn write_block_to_buffer(
+    /// Size in bytes of the serialized block, without sending it
+    ///
+    /// Counts the same bytes [`BlockWriter::write_block_to_buffer`]
+    /// produces: uncompressed, without the temp table name. Useful for
+    /// sizing insert batches and budgeting memory.
+    pub fn serialized_size(&self, block: &Block) -> Result<usize> {
+        let mut buffer = BytesMut::new();
+        self.write_block_to_buffer(&mut buffer, block)?;
+        Ok(buffer.len())
+    }
+
+    /// Serialize a block (uncompressed, without temp table name) to a buffer
+    pub fn write_block_to_buffer(
         &self,
         buffer: &mut BytesMut,
         block: &Block,
@@ -800,7 +1223,360 @@ impl BlockWriter {
     }
 }
 
-// Helper functions - now using centralized buffer_utils
+/// Set on the last group size of a sparse column's offsets stream
+const SPARSE_END_OF_GRANULE_FLAG: u64 = 1 << 62;
+
+/// Serialization kinds announced for a column with custom serialization
+///
+/// Servers only send sparse columns to clients at revision 54465 or later.
+/// At the default [`ClientInfo::client_revision`] they densify them before
+/// sending, so sparse data arrives only when a newer revision is advertised.
+///
+/// [`ClientInfo::client_revision`]: crate::query::ClientInfo::client_revision
+#[derive(Clone, Debug, PartialEq)]
+enum SerializationKinds {
+    /// Regular (dense) serialization
+    Default,
+    /// Only non-default values are sent, with their positions
+    Sparse,
+    /// A tuple with its own kind per element
+    Tuple(Vec<SerializationKinds>),
+}
+
+/// Number of kind bytes sent for a column of `type_`
+fn serialization_kinds_len(type_: &Type) -> usize {
+    match type_ {
+        Type::Tuple { item_types } => {
+            1 + item_types.iter().map(serialization_kinds_len).sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
+/// Read the serialization kinds of a column of `type_`
+fn read_serialization_kinds(
+    buffer: &mut &[u8],
+    type_: &Type,
+) -> Result<SerializationKinds> {
+    if buffer.is_empty() {
+        return Err(Error::Incomplete(
+            "Unexpected end reading serialization kind".to_string(),
+        ));
+    }
+    let kind = buffer[0];
+    buffer.advance(1);
+
+    match (kind, type_) {
+        (0, Type::Tuple { item_types }) => item_types
+            .iter()
+            .map(|item_type| read_serialization_kinds(buffer, item_type))
+            .collect::<Result<_>>()
+            .map(SerializationKinds::Tuple),
+        (0, _) => Ok(SerializationKinds::Default),
+        (1, _) if sparse_default_value(type_).is_some() => {
+            Ok(SerializationKinds::Sparse)
+        }
+        _ => Err(Error::Protocol(format!(
+            "Unsupported custom serialization kind {} for type {}",
+            kind,
+            type_.name()
+        ))),
+    }
+}
+
+/// Dense encoding of the default value of a type that can be sparse
+fn sparse_default_value(type_: &Type) -> Option<Vec<u8>> {
+    use crate::types::TypeCode;
+
+    match type_ {
+        Type::Simple(TypeCode::String | TypeCode::Json) => Some(vec![0]),
+        Type::FixedString { size } => Some(vec![0; *size]),
+        Type::Nullable { .. } | Type::LowCardinality { .. } => None,
+        _ => type_.storage_size_bytes().map(|size| vec![0; size]),
+    }
+}
+
+/// Rewrite column data sent with custom serialization into the regular
+/// layout, so the column can be decoded as usual
+fn densify_column_data(
+    buffer: &mut &[u8],
+    type_: &Type,
+    kinds: &SerializationKinds,
+    rows: usize,
+    out: &mut BytesMut,
+) -> Result<()> {
+    match (kinds, type_) {
+        (SerializationKinds::Default, _) => {
+            let start = *buffer;
+            skip_column_data(buffer, type_, rows)?;
+            out.extend_from_slice(&start[..start.len() - buffer.len()]);
+            Ok(())
+        }
+        (SerializationKinds::Tuple(kinds), Type::Tuple { item_types }) => {
+            for (item_type, kinds) in item_types.iter().zip(kinds) {
+                densify_column_data(buffer, item_type, kinds, rows, out)?;
+            }
+            Ok(())
+        }
+        (SerializationKinds::Sparse, _) => {
+            densify_sparse_data(buffer, type_, rows, out)
+        }
+        _ => Err(Error::Protocol(format!(
+            "Serialization kinds do not match type {}",
+            type_.name()
+        ))),
+    }
+}
+
+/// Expand sparse data: value positions as group sizes (runs of defaults
+/// before each value, the last one flagged), then the non-default values
+fn densify_sparse_data(
+    buffer: &mut &[u8],
+    type_: &Type,
+    rows: usize,
+    out: &mut BytesMut,
+) -> Result<()> {
+    let default = sparse_default_value(type_).ok_or_else(|| {
+        Error::Protocol(format!(
+            "Sparse serialization not supported for type {}",
+            type_.name()
+        ))
+    })?;
+
+    let overflow = || {
+        Error::Protocol(
+            "Sparse column offsets overflow the row count".to_string(),
+        )
+    };
+    let mut positions = Vec::new();
+    let mut row = 0usize;
+    loop {
+        let group = buffer_utils::read_varint(buffer)?;
+        row = usize::try_from(group & !SPARSE_END_OF_GRANULE_FLAG)
+            .ok()
+            .and_then(|gap| row.checked_add(gap))
+            .ok_or_else(overflow)?;
+        if group & SPARSE_END_OF_GRANULE_FLAG != 0 {
+            break;
+        }
+        positions.push(row);
+        row = row.checked_add(1).ok_or_else(overflow)?;
+    }
+    if row != rows {
+        return Err(Error::Protocol(format!(
+            "Sparse column covers {} rows, expected {}",
+            row, rows
+        )));
+    }
+
+    let mut positions = positions.into_iter().peekable();
+    for row in 0..rows {
+        if positions.next_if_eq(&row).is_some() {
+            let start = *buffer;
+            skip_column_data(buffer, type_, 1)?;
+            out.extend_from_slice(&start[..start.len() - buffer.len()]);
+        } else {
+            out.extend_from_slice(&default);
+        }
+    }
+    Ok(())
+}
+
+/// Width in bytes of a LowCardinality index from its serialization type
+fn low_cardinality_index_width(
+    index_serialization_type: u64,
+) -> Result<usize> {
+    match index_serialization_type & 0xFF {
+        0 => Ok(1),
+        1 => Ok(2),
+        2 => Ok(4),
+        3 => Ok(8),
+        other => Err(Error::Protocol(format!(
+            "Unknown LowCardinality index type: {}",
+            other
+        ))),
+    }
+}
+
+/// Size in bytes of `count` values of `width` bytes each
+///
+/// Both come from the wire, so a malformed block fails here instead of
+/// overflowing, or of allocating more than any valid frame can hold.
+fn byte_len(count: usize, width: usize) -> Result<usize> {
+    count
+        .checked_mul(width)
+        .filter(|&len| len <= MAX_COMPRESSED_SIZE)
+        .ok_or_else(|| {
+            Error::Protocol(format!(
+                "Column data size out of range: {} values of {} bytes",
+                count, width
+            ))
+        })
+}
+
+/// Check a string length read from the connection before a buffer of
+/// that size is allocated
+fn string_len(len: u64) -> Result<usize> {
+    // Compare before narrowing, so a huge length cannot wrap on 32-bit
+    if len > MAX_COMPRESSED_SIZE as u64 {
+        return Err(Error::Protocol(format!(
+            "String length out of range: {} bytes",
+            len
+        )));
+    }
+    Ok(len as usize)
+}
+
+/// Advance the buffer by `len` bytes without reading them
+fn skip_bytes(buffer: &mut &[u8], len: usize) -> Result<()> {
+    if buffer.len() < len {
+        return Err(Error::Incomplete(format!(
+            "Not enough data to skip: need {}, have {}",
+            len,
+            buffer.len()
+        )));
+    }
+    buffer.advance(len);
+    Ok(())
+}
+
+/// Read a little-endian UInt64 from the buffer
+fn read_u64_le(buffer: &mut &[u8]) -> Result<u64> {
+    if buffer.len() < 8 {
+        return Err(Error::Incomplete(
+            "Not enough data for UInt64".to_string(),
+        ));
+    }
+    Ok(buffer.get_u64_le())
+}
+
+/// Read the last of `rows` UInt64 offsets, skipping the whole offsets array
+fn skip_offsets(buffer: &mut &[u8], rows: usize) -> Result<usize> {
+    let len = byte_len(rows, 8)?;
+    if buffer.len() < len {
+        return Err(Error::Incomplete(format!(
+            "Not enough data for offsets: need {}, have {}",
+            len,
+            buffer.len()
+        )));
+    }
+    let mut last = &buffer[len - 8..len];
+    let total = last.get_u64_le() as usize;
+    buffer.advance(len);
+    Ok(total)
+}
+
+/// Skip column prefix data in a buffer without decoding it
+fn skip_column_prefix(buffer: &mut &[u8], type_: &Type) -> Result<()> {
+    match type_ {
+        Type::LowCardinality { .. } => skip_bytes(buffer, 8),
+        Type::Array { item_type } => skip_column_prefix(buffer, item_type),
+        Type::Nullable { nested_type } => {
+            skip_column_prefix(buffer, nested_type)
+        }
+        Type::Tuple { item_types } => {
+            for item_type in item_types {
+                skip_column_prefix(buffer, item_type)?;
+            }
+            Ok(())
+        }
+        Type::Map { key_type, value_type } => {
+            skip_column_prefix(buffer, key_type)?;
+            skip_column_prefix(buffer, value_type)
+        }
+        Type::Nested { item_types, .. } => {
+            for item_type in item_types {
+                skip_column_prefix(buffer, item_type)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Skip `rows` values of column body data in a buffer without decoding them
+fn skip_column_data(
+    buffer: &mut &[u8],
+    type_: &Type,
+    rows: usize,
+) -> Result<()> {
+    use crate::types::TypeCode;
+
+    if let Some(size_per_row) = type_.storage_size_bytes() {
+        return skip_bytes(buffer, byte_len(rows, size_per_row)?);
+    }
+
+    match type_ {
+        Type::Simple(TypeCode::String | TypeCode::Json) => {
+            for _ in 0..rows {
+                let len = buffer_utils::read_varint(buffer)? as usize;
+                skip_bytes(buffer, len)?;
+            }
+            Ok(())
+        }
+        Type::Simple(TypeCode::Void) => skip_bytes(buffer, rows),
+        Type::Simple(TypeCode::Ring) => {
+            let total = skip_offsets(buffer, rows)?;
+            skip_column_data(buffer, &Type::point(), total)
+        }
+        Type::Simple(TypeCode::Polygon) => {
+            let total = skip_offsets(buffer, rows)?;
+            skip_column_data(buffer, &Type::ring(), total)
+        }
+        Type::Simple(TypeCode::MultiPolygon) => {
+            let total = skip_offsets(buffer, rows)?;
+            skip_column_data(buffer, &Type::polygon(), total)
+        }
+        Type::Nullable { nested_type } => {
+            skip_bytes(buffer, rows)?;
+            skip_column_data(buffer, nested_type, rows)
+        }
+        Type::Array { item_type } => {
+            let total = skip_offsets(buffer, rows)?;
+            skip_column_data(buffer, item_type, total)
+        }
+        Type::Tuple { item_types } => {
+            for item_type in item_types {
+                skip_column_data(buffer, item_type, rows)?;
+            }
+            Ok(())
+        }
+        Type::Map { key_type, value_type } => {
+            let total = skip_offsets(buffer, rows)?;
+            skip_column_data(buffer, key_type, total)?;
+            skip_column_data(buffer, value_type, total)
+        }
+        Type::Nested { item_types, .. } => {
+            let total = skip_offsets(buffer, rows)?;
+            for item_type in item_types {
+                skip_column_data(buffer, item_type, total)?;
+            }
+            Ok(())
+        }
+        Type::LowCardinality { nested_type } => {
+            let index_serialization_type = read_u64_le(buffer)?;
+            let number_of_keys = read_u64_le(buffer)? as usize;
+
+            // Dictionary keys are serialized without a null map
+            let dictionary_type = match nested_type.as_ref() {
+                Type::Nullable { nested_type } => nested_type.as_ref(),
+                other => other,
+            };
+            skip_column_data(buffer, dictionary_type, number_of_keys)?;
+
+            let number_of_rows = read_u64_le(buffer)? as usize;
+            let index_width =
+                low_cardinality_index_width(index_serialization_type)?;
+            skip_bytes(buffer, byte_len(number_of_rows, index_width)?)
+        }
+        _ => Err(Error::Protocol(format!(
+            "Cannot skip data for type: {}",
+            type_.name()
+        ))),
+    }
+}
+
+// Helper functions - now using centralized buffer_utils
 // (Functions removed - using buffer_utils::{read_varint, write_varint,
 // read_string, write_string})
 
@@ -857,6 +1633,291 @@ mod tests {
         assert_eq!(decoded_block.column_name(0), Some("test_col"));
     }
 
+    #[test]
+    fn test_skip_block_in_buffer_counts_rows() {
+        use crate::column::{
+            array::ColumnArray,
+            column_value::ColumnValue,
+            lowcardinality::ColumnLowCardinality,
+            nullable::ColumnNullable,
+            string::ColumnString,
+        };
+
+        let mut ids = ColumnUInt64::new();
+        let mut names = ColumnString::new(Type::string());
+        let mut tags = ColumnArray::new(Type::array(Type::string()));
+        let mut maybe = ColumnNullable::new(Type::nullable(Type::uint32()));
+        let mut lc =
+            ColumnLowCardinality::new(Type::low_cardinality(Type::string()));
+        for i in 0..5u64 {
+            ids.append(i);
+            names.append(format!("name_{}", i));
+            let mut items = ColumnString::new(Type::string());
+            for j in 0..i {
+                items.append(format!("tag_{}", j));
+            }
+            tags.append_array(Arc::new(items));
+            maybe.append_nullable(if i % 2 == 0 {
+                Some(i as u32)
+            } else {
+                None
+            });
+            let label = if i % 2 == 0 { "even" } else { "odd" };
+            lc.append_unsafe(&ColumnValue::from_string(label)).unwrap();
+        }
+
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(ids)).unwrap();
+        block.append_column("name", Arc::new(names)).unwrap();
+        block.append_column("tags", Arc::new(tags)).unwrap();
+        block.append_column("maybe", Arc::new(maybe)).unwrap();
+        block.append_column("lc", Arc::new(lc)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+        buffer.put_u8(0xAB); // trailing byte must be left unread
+
+        let reader = BlockReader::new(54449);
+        let mut slice = &buffer[..];
+        let rows = reader.skip_block_in_buffer(&mut slice).unwrap();
+
+        assert_eq!(rows, 5);
+        assert_eq!(slice, &[0xAB]);
+    }
+
+    #[test]
+    fn test_skip_block_in_buffer_underflow() {
+        let mut col = ColumnUInt64::new();
+        col.append(1);
+        col.append(2);
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(col)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+
+        let reader = BlockReader::new(54449);
+        let mut slice = &buffer[..buffer.len() - 1];
+        let err = reader.skip_block_in_buffer(&mut slice).unwrap_err();
+        assert!(err.to_string().contains("Not enough data"));
+    }
+
+    /// Serve `payload` on a local socket, then close it
+    async fn truncated_stream_connection(payload: Vec<u8>) -> Connection {
+        use tokio::{
+            io::AsyncWriteExt,
+            net::{
+                TcpListener,
+                TcpStream,
+            },
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(&payload).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+
+        Connection::new(TcpStream::connect(addr).await.unwrap())
+    }
+
+    fn sample_block_bytes(compression: Option<CompressionMethod>) -> Vec<u8> {
+        let mut col = ColumnUInt64::new();
+        for i in 0..1000 {
+            col.append(i);
+        }
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(col)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+        match compression {
+            Some(method) => compress(method, &buffer).unwrap().to_vec(),
+            None => buffer.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reused_buffers_do_not_leak_between_blocks() {
+        let mut small = ColumnUInt64::new();
+        for i in [42, 43, 44] {
+            small.append(i);
+        }
+        let mut small_block = Block::new();
+        small_block.append_column("id", Arc::new(small)).unwrap();
+        let mut small_bytes = BytesMut::new();
+        BlockWriter::new(54449)
+            .write_block_to_buffer(&mut small_bytes, &small_block)
+            .unwrap();
+
+        for reuse in [true, false] {
+            // A large block followed by a smaller one
+            let mut payload = sample_block_bytes(Some(CompressionMethod::Lz4));
+            payload.extend_from_slice(
+                &compress(CompressionMethod::Lz4, &small_bytes).unwrap(),
+            );
+            let mut conn = truncated_stream_connection(payload).await;
+
+            let mut reader = BlockReader::new(54449)
+                .with_compression(CompressionMethod::Lz4)
+                .with_reuse_buffers(reuse);
+            let first = reader.read_block(&mut conn).await.unwrap();
+            assert_eq!(first.row_count(), 1000);
+            assert_eq!(reader.block_buf.capacity() > 0, reuse);
+
+            let second = reader.read_block(&mut conn).await.unwrap();
+            assert_eq!(second.row_count(), 3);
+            let col = second.column(0).unwrap();
+            let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+            assert_eq!(
+                (0..3).map(|i| col.at(i)).collect::<Vec<_>>(),
+                vec![42, 43, 44]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_close_during_block_payload() {
+        let bytes = sample_block_bytes(None);
+        let mut conn =
+            truncated_stream_connection(bytes[..bytes.len() / 2].to_vec())
+                .await;
+
+        let err = BlockReader::new(54449).read_block(&mut conn).await;
+        assert!(matches!(err, Err(Error::ServerClosedConnection(_))));
+    }
+
+    #[tokio::test]
+    async fn test_server_close_during_compressed_block_payload() {
+        let bytes = sample_block_bytes(Some(CompressionMethod::Lz4));
+        let mut conn =
+            truncated_stream_connection(bytes[..bytes.len() - 8].to_vec())
+                .await;
+
+        let mut reader =
+            BlockReader::new(54449).with_compression(CompressionMethod::Lz4);
+        match reader.read_block(&mut conn).await {
+            Err(err @ Error::ServerClosedConnection(_)) => {
+                assert!(err.to_string().contains("send_timeout"));
+            }
+            Err(other) => panic!("unexpected error: {}", other),
+            Ok(_) => panic!("truncated block must not decode"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_compressor_block_roundtrip() {
+        use tokio::net::{
+            TcpListener,
+            TcpStream,
+        };
+
+        #[derive(Debug)]
+        struct Passthrough;
+
+        impl Compressor for Passthrough {
+            fn method_byte(&self) -> u8 {
+                0x7f
+            }
+
+            fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+                Ok(data.to_vec())
+            }
+
+            fn decompress(
+                &self,
+                data: &[u8],
+                _uncompressed_size: usize,
+            ) -> Result<Vec<u8>> {
+                Ok(data.to_vec())
+            }
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (client, server) =
+            tokio::join!(TcpStream::connect(addr), listener.accept());
+        let mut sender = Connection::new(client.unwrap());
+        let mut receiver = Connection::new(server.unwrap().0);
+
+        let mut col = ColumnUInt64::new();
+        col.append(7);
+        col.append(11);
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(col)).unwrap();
+
+        let compressor: Arc<dyn Compressor> = Arc::new(Passthrough);
+        BlockWriter::new(54449)
+            .with_compressor(compressor.clone())
+            .write_block(&mut sender, &block)
+            .await
+            .unwrap();
+
+        // The reader expects the caller to consume the temp table name
+        assert_eq!(receiver.read_string().await.unwrap(), "");
+        let decoded = BlockReader::new(54449)
+            .with_compressor(compressor)
+            .read_block(&mut receiver)
+            .await
+            .unwrap();
+        assert_eq!(decoded.row_count(), 2);
+        let col = decoded.column(0).unwrap();
+        let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(col.at(0), 7);
+        assert_eq!(col.at(1), 11);
+    }
+
+    #[test]
+    fn test_array_bool_roundtrip_wire_format() {
+        use crate::column::{
+            array::ColumnArray,
+            boolean::ColumnBool,
+        };
+
+        let mut col = ColumnArray::new(Type::array(Type::bool()));
+        col.append_array(Arc::new(ColumnBool::from_vec(vec![true, false])));
+        col.append_array(Arc::new(ColumnBool::from_vec(vec![true])));
+
+        let mut block = Block::new();
+        block.append_column("flags", Arc::new(col)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+
+        // Type name, then offsets [2, 3] and one byte per element
+        let mut expected = Vec::new();
+        expected.push(11u8);
+        expected.extend_from_slice(b"Array(Bool)");
+        expected.extend_from_slice(&2u64.to_le_bytes());
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(&[1, 0, 1]);
+        assert!(buffer.ends_with(&expected));
+
+        let reader = BlockReader::new(54449);
+        let mut read_buffer = &buffer[..];
+        let decoded =
+            reader.parse_block_from_buffer(&mut read_buffer).unwrap();
+        assert_eq!(decoded.row_count(), 2);
+
+        let col = decoded.column(0).unwrap();
+        assert_eq!(col.column_type().name(), "Array(Bool)");
+        let array = col.as_any().downcast_ref::<ColumnArray>().unwrap();
+        let rows: Vec<Vec<bool>> = (0..array.len())
+            .map(|i| {
+                let row = array.at(i);
+                let row = row.as_any().downcast_ref::<ColumnBool>().unwrap();
+                row.iter().collect()
+            })
+            .collect();
+        assert_eq!(rows, vec![vec![true, false], vec![true]]);
+    }
+
     #[test]
     fn test_block_roundtrip_multiple_columns() {
         let mut block = Block::new();
@@ -886,4 +1947,427 @@ mod tests {
         assert_eq!(decoded.column_count(), 2);
         assert_eq!(decoded.row_count(), 2);
     }
+
+    #[tokio::test]
+    async fn test_serialized_size_matches_bytes_written() {
+        use crate::column::string::ColumnString;
+        use tokio::io::AsyncReadExt;
+
+        let mut id = ColumnUInt64::new();
+        let mut name = ColumnString::new(Type::string());
+        for i in 0..3u64 {
+            id.append(i);
+            name.append(format!("row {}", i));
+        }
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(id)).unwrap();
+        block.append_column("name", Arc::new(name)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let size = writer.serialized_size(&block).unwrap();
+        // Block info (8) + counts (2) + "id" UInt64 column (3+7+24)
+        // + "name" String column (5+7+3*6)
+        assert_eq!(size, 8 + 2 + 34 + 30);
+
+        let (client_io, mut server_io) = tokio::io::duplex(4096);
+        let mut conn = Connection::from_stream(client_io);
+        writer.write_block(&mut conn, &block).await.unwrap();
+        drop(conn);
+
+        let mut written = Vec::new();
+        server_io.read_to_end(&mut written).await.unwrap();
+        // Everything after the empty temp table name
+        assert_eq!(written[0], 0);
+        assert_eq!(written.len() - 1, size);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_block_is_split_into_chunks() {
+        use tokio::io::{
+            AsyncReadExt,
+            AsyncWriteExt,
+        };
+
+        let mut id = ColumnUInt64::new();
+        for i in 0..1000u64 {
+            id.append(i);
+        }
+        let mut block = Block::new();
+        block.append_column("id", Arc::new(id)).unwrap();
+
+        let writer = BlockWriter::new(54449)
+            .with_compression(CompressionMethod::Lz4)
+            .with_max_chunk_size(1024);
+        let size = writer.serialized_size(&block).unwrap();
+
+        let (client_io, mut server_io) = tokio::io::duplex(64 * 1024);
+        let mut conn = Connection::from_stream(client_io);
+        writer.write_block(&mut conn, &block).await.unwrap();
+        drop(conn);
+        let mut written = Vec::new();
+        server_io.read_to_end(&mut written).await.unwrap();
+
+        // Walk the frames after the empty temp table name: 16-byte
+        // checksum, method byte, compressed size (with the 9-byte header)
+        // and uncompressed size
+        let mut frames = Vec::new();
+        let mut rest = &written[1..];
+        while !rest.is_empty() {
+            let compressed =
+                u32::from_le_bytes(rest[17..21].try_into().unwrap()) as usize;
+            let original =
+                u32::from_le_bytes(rest[21..25].try_into().unwrap()) as usize;
+            frames.push(original);
+            rest = &rest[16 + compressed..];
+        }
+        assert_eq!(frames.len(), size.div_ceil(1024));
+        assert!(frames.iter().all(|&len| len <= 1024));
+        assert_eq!(frames.iter().sum::<usize>(), size);
+
+        // The reader reassembles the frames into one block
+        let (client_io, mut server_io) = tokio::io::duplex(64 * 1024);
+        server_io.write_all(&written[1..]).await.unwrap();
+        drop(server_io);
+        let mut conn = Connection::from_stream(client_io);
+        let mut reader =
+            BlockReader::new(54449).with_compression(CompressionMethod::Lz4);
+        let decoded = reader.read_block(&mut conn).await.unwrap();
+        let ids = decoded.column(0).unwrap();
+        let ids = ids.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(ids.len(), 1000);
+        assert_eq!(ids.at(999), 999);
+    }
+
+    #[tokio::test]
+    async fn test_uncompressed_block_decodes_complex_columns() {
+        use crate::column::{
+            array::ColumnArray,
+            column_value::ColumnValue,
+            lowcardinality::ColumnLowCardinality,
+            map::ColumnMap,
+            nullable::ColumnNullableT,
+            string::ColumnString,
+            tuple::ColumnTuple,
+        };
+
+        let strings = |values: &[&str]| -> ColumnRef {
+            Arc::new(ColumnString::from_vec(
+                Type::string(),
+                values.iter().map(|s| s.to_string()).collect(),
+            ))
+        };
+        let numbers = |values: &[u64]| -> ColumnRef {
+            let mut col = ColumnUInt64::new();
+            for &v in values {
+                col.append(v);
+            }
+            Arc::new(col)
+        };
+
+        let mut array = ColumnArray::new(Type::array(Type::string()));
+        array.append_array(strings(&["a", "bc"]));
+        array.append_array(strings(&[]));
+        array.append_array(strings(&["def"]));
+
+        let tuple_type = Type::tuple(vec![Type::uint64(), Type::string()]);
+        let tuple = ColumnTuple::new(
+            tuple_type,
+            vec![numbers(&[1, 2, 3]), strings(&["x", "y", "z"])],
+        );
+
+        let mut nullable = ColumnNullableT::<ColumnString>::new(
+            Type::nullable(Type::string()),
+        );
+        nullable.append(Some("n".to_string()));
+        nullable.append::<String>(None);
+        nullable.append(Some(String::new()));
+
+        let mut low_cardinality =
+            ColumnLowCardinality::new(Type::low_cardinality(Type::string()));
+        low_cardinality
+            .append_values(["p", "q", "p"].map(ColumnValue::from_string))
+            .unwrap();
+
+        let entries = ColumnTuple::new(
+            Type::tuple(vec![Type::string(), Type::uint64()]),
+            vec![strings(&["k1", "k2", "k3"]), numbers(&[10, 20, 30])],
+        );
+        let mut entries = ColumnArray::with_nested(Arc::new(entries));
+        for len in [2, 0, 1] {
+            entries.append_len(len);
+        }
+        let map = ColumnMap::from_array(
+            Type::map(Type::string(), Type::uint64()),
+            Arc::new(entries),
+        );
+
+        let mut block = Block::new();
+        block.append_column("id", numbers(&[7, 8, 9])).unwrap();
+        block.append_column("arr", Arc::new(array)).unwrap();
+        block.append_column("tup", Arc::new(tuple)).unwrap();
+        block.append_column("opt", Arc::new(nullable)).unwrap();
+        block.append_column("lc", Arc::new(low_cardinality)).unwrap();
+        block.append_column("map", Arc::new(map)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+
+        let mut conn = truncated_stream_connection(buffer.to_vec()).await;
+        let decoded = BlockReader::new(54449).read_block(&mut conn).await;
+        let decoded = decoded.unwrap();
+        assert_eq!(decoded.column_count(), 6);
+        assert_eq!(decoded.row_count(), 3);
+
+        let arr = decoded.column(1).unwrap();
+        let arr = arr.as_any().downcast_ref::<ColumnArray>().unwrap();
+        let first = arr.at(0);
+        let first = first.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(first.at(0), "a");
+        assert_eq!(first.at(1), "bc");
+        assert_eq!(arr.at(1).size(), 0);
+
+        // Every column decodes back to the bytes that were sent
+        let mut reencoded = BytesMut::new();
+        writer.write_block_to_buffer(&mut reencoded, &decoded).unwrap();
+        assert_eq!(reencoded, buffer);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_block_decodes_decimal_uuid_and_map() {
+        use crate::column::{
+            array::ColumnArray,
+            decimal::ColumnDecimal,
+            map::ColumnMap,
+            string::ColumnString,
+            tuple::ColumnTuple,
+            uuid::ColumnUuid,
+            Value,
+        };
+
+        let mut decimals = ColumnDecimal::new(Type::decimal(10, 2));
+        decimals.append_from_string("12.50").unwrap();
+        decimals.append_from_string("-0.01").unwrap();
+
+        let mut uuids = ColumnUuid::new(Type::uuid());
+        uuids
+            .append_from_string("550e8400-e29b-41d4-a716-446655440000")
+            .unwrap();
+        uuids
+            .append_from_string("00000000-0000-0000-0000-000000000001")
+            .unwrap();
+        let first_uuid = uuids.at(0);
+
+        let mut values = ColumnUInt64::new();
+        for v in [1, 2, 3] {
+            values.append(v);
+        }
+        let entries = ColumnTuple::new(
+            Type::tuple(vec![Type::string(), Type::uint64()]),
+            vec![
+                Arc::new(ColumnString::from(vec!["a", "b", "c"])),
+                Arc::new(values),
+            ],
+        );
+        let mut entries = ColumnArray::with_nested(Arc::new(entries));
+        entries.append_len(1);
+        entries.append_len(2);
+        let map = ColumnMap::from_array(
+            Type::map(Type::string(), Type::uint64()),
+            Arc::new(entries),
+        );
+
+        let mut block = Block::new();
+        block.append_column("price", Arc::new(decimals)).unwrap();
+        block.append_column("id", Arc::new(uuids)).unwrap();
+        block.append_column("attrs", Arc::new(map)).unwrap();
+
+        let writer = BlockWriter::new(54449);
+        let mut buffer = BytesMut::new();
+        writer.write_block_to_buffer(&mut buffer, &block).unwrap();
+        let compressed = compress(CompressionMethod::Lz4, &buffer).unwrap();
+
+        let mut conn = truncated_stream_connection(compressed.to_vec()).await;
+        let mut reader =
+            BlockReader::new(54449).with_compression(CompressionMethod::Lz4);
+        let decoded = reader.read_block(&mut conn).await.unwrap();
+        assert_eq!(decoded.row_count(), 2);
+
+        let price = decoded.column(0).unwrap();
+        assert_eq!(price.value_at(0).unwrap(), Value::Decimal("12.50".into()));
+        let id = decoded.column(1).unwrap();
+        assert_eq!(id.value_at(0).unwrap(), Value::Uuid(first_uuid));
+        let attrs = decoded.column(2).unwrap();
+        assert_eq!(
+            attrs.value_at(1).unwrap(),
+            Value::Map(vec![
+                (Value::String("b".into()), Value::UInt64(2)),
+                (Value::String("c".into()), Value::UInt64(3)),
+            ])
+        );
+
+        let mut reencoded = BytesMut::new();
+        writer.write_block_to_buffer(&mut reencoded, &decoded).unwrap();
+        assert_eq!(reencoded, buffer);
+    }
+
+    /// Block with a sparse UInt64 column `[0, 7, 0, 0, 9]` and a tuple
+    /// whose String element is sparse `[("a", ""), ("b", "x"), ...]`
+    fn sparse_block_bytes(kind: u8) -> Vec<u8> {
+        let mut buffer = BytesMut::new();
+        // Block info
+        buffer_utils::write_varint(&mut buffer, 1);
+        buffer.put_u8(0);
+        buffer_utils::write_varint(&mut buffer, 2);
+        buffer.put_i32_le(-1);
+        buffer_utils::write_varint(&mut buffer, 0);
+        buffer_utils::write_varint(&mut buffer, 2);
+        buffer_utils::write_varint(&mut buffer, 5);
+
+        buffer_utils::write_string(&mut buffer, "n");
+        buffer_utils::write_string(&mut buffer, "UInt64");
+        buffer.put_u8(1);
+        buffer.put_u8(kind);
+        for group in [1, 2, SPARSE_END_OF_GRANULE_FLAG] {
+            buffer_utils::write_varint(&mut buffer, group);
+        }
+        buffer.put_u64_le(7);
+        buffer.put_u64_le(9);
+
+        buffer_utils::write_string(&mut buffer, "t");
+        buffer_utils::write_string(&mut buffer, "Tuple(String, String)");
+        buffer.put_u8(1);
+        buffer.put_slice(&[0, 0, 1]);
+        for value in ["a", "b", "c", "d", "e"] {
+            buffer_utils::write_string(&mut buffer, value);
+        }
+        for group in [1, 3 | SPARSE_END_OF_GRANULE_FLAG] {
+            buffer_utils::write_varint(&mut buffer, group);
+        }
+        buffer_utils::write_string(&mut buffer, "x");
+        buffer.to_vec()
+    }
+
+    fn assert_sparse_block(block: &Block) {
+        use crate::column::{
+            string::ColumnString,
+            tuple::ColumnTuple,
+        };
+
+        assert_eq!(block.row_count(), 5);
+        let n = block.column(0).unwrap();
+        let n = n.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(
+            (0..5).map(|i| n.at(i)).collect::<Vec<_>>(),
+            [0, 7, 0, 0, 9]
+        );
+
+        let t = block.column(1).unwrap();
+        let t = t.as_any().downcast_ref::<ColumnTuple>().unwrap();
+        let second = t.column_at(1);
+        let second = second.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(
+            (0..5).map(|i| second.at(i)).collect::<Vec<_>>(),
+            ["", "x", "", "", ""]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sparse_serialization_is_expanded() {
+        let bytes = sparse_block_bytes(1);
+
+        let reader = BlockReader::new(54454);
+        let block = reader.parse_block_from_buffer(&mut &bytes[..]).unwrap();
+        assert_sparse_block(&block);
+
+        let mut reader = BlockReader::new(54454);
+        let mut conn = truncated_stream_connection(bytes.clone()).await;
+        assert_sparse_block(&reader.read_block(&mut conn).await.unwrap());
+
+        let mut conn = truncated_stream_connection(bytes).await;
+        assert_eq!(reader.read_block_row_count(&mut conn).await.unwrap(), 5);
+    }
+
+    #[test]
+    fn test_malformed_sizes_are_errors_not_overflows() {
+        // Two group sizes whose sum overflows the row counter
+        let mut data = BytesMut::new();
+        for _ in 0..2 {
+            buffer_utils::write_varint(&mut data, !SPARSE_END_OF_GRANULE_FLAG);
+        }
+        let mut out = BytesMut::new();
+        assert!(matches!(
+            densify_sparse_data(&mut &data[..], &Type::uint64(), 5, &mut out),
+            Err(Error::Protocol(_))
+        ));
+
+        assert!(matches!(
+            skip_column_data(&mut &[][..], &Type::uint64(), usize::MAX),
+            Err(Error::Protocol(_))
+        ));
+        assert!(matches!(
+            byte_len(MAX_COMPRESSED_SIZE / 8 + 1, 8),
+            Err(Error::Protocol(_))
+        ));
+        assert!(matches!(string_len(u64::MAX), Err(Error::Protocol(_))));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_string_length_is_rejected_before_reading() {
+        // A String column whose first value claims to be 4 GiB long
+        let mut data = BytesMut::new();
+        buffer_utils::write_varint(&mut data, 1 << 32);
+        let mut conn = truncated_stream_connection(data.to_vec()).await;
+
+        let reader = BlockReader::new(54449);
+        let mut out = BytesMut::new();
+        let err = reader
+            .load_column_data_impl(&mut conn, &Type::string(), 1, &mut out)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Protocol(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_datetime64_keeps_precision_and_timezone() {
+        use crate::column::date::ColumnDateTime64;
+
+        let type_ = Type::parse("DateTime64(6, 'Asia/Tokyo')").unwrap();
+        let column = ColumnDateTime64::new(type_.clone())
+            .with_data(vec![1_700_000_000_123_456, -1]);
+        let mut block = Block::new();
+        block.append_column("ts", Arc::new(column)).unwrap();
+        let mut bytes = BytesMut::new();
+        BlockWriter::new(54449)
+            .write_block_to_buffer(&mut bytes, &block)
+            .unwrap();
+
+        let mut reader = BlockReader::new(54449);
+        let parsed = reader.parse_block_from_buffer(&mut &bytes[..]).unwrap();
+        let mut conn = truncated_stream_connection(bytes.to_vec()).await;
+        let streamed = reader.read_block(&mut conn).await.unwrap();
+
+        for block in [parsed, streamed] {
+            let ts = block.column(0).unwrap();
+            assert_eq!(ts.column_type(), &type_);
+            assert_eq!(ts.column_type().name(), "DateTime64(6, 'Asia/Tokyo')");
+            let ts = ts.as_any().downcast_ref::<ColumnDateTime64>().unwrap();
+            assert_eq!(ts.precision(), 6);
+            assert_eq!(ts.timezone(), Some("Asia/Tokyo"));
+            assert_eq!(ts.at(0), 1_700_000_000_123_456);
+            assert_eq!(ts.at(1), -1);
+        }
+    }
+
+    #[test]
+    fn test_unknown_serialization_kind_is_rejected() {
+        let bytes = sparse_block_bytes(7);
+        match BlockReader::new(54454).parse_block_from_buffer(&mut &bytes[..])
+        {
+            Err(err) => assert!(err.to_string().contains(
+                "Unsupported custom serialization kind 7 for type UInt64"
+            )),
+            Ok(_) => panic!("unknown kind must not decode"),
+        }
+    }
 }
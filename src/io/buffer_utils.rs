@@ -23,7 +23,7 @@ pub fn read_varint(buffer: &mut &[u8]) -> Result<u64> {
 
     loop {
         if buffer.is_empty() {
-            return Err(Error::Protocol(
+            return Err(Error::Incomplete(
                 "Unexpected end of buffer reading varint".to_string(),
             ));
         }
@@ -31,6 +31,10 @@ pub fn read_varint(buffer: &mut &[u8]) -> Result<u64> {
         let byte = buffer[0];
         buffer.advance(1);
 
+        // The 10th byte holds only the top bit of a u64
+        if shift == 63 && byte > 1 {
+            return Err(Error::Protocol("Varint overflow".to_string()));
+        }
         result |= ((byte & 0x7F) as u64) << shift;
 
         if byte & 0x80 == 0 {
@@ -38,9 +42,6 @@ pub fn read_varint(buffer: &mut &[u8]) -> Result<u64> {
         }
 
         shift += 7;
-        if shift >= 64 {
-            return Err(Error::Protocol("Varint overflow".to_string()));
-        }
     }
 
     Ok(result)
@@ -72,15 +73,17 @@ pub fn write_varint(buffer: &mut BytesMut, mut value: u64) {
 /// This is the synchronous version used for parsing in-memory buffers.
 /// For async I/O, use `WireFormat::read_string` instead.
 pub fn read_string(buffer: &mut &[u8]) -> Result<String> {
-    let len = read_varint(buffer)? as usize;
+    let len = read_varint(buffer)?;
 
-    if buffer.len() < len {
-        return Err(Error::Protocol(format!(
+    // Compare before narrowing, so a huge length cannot wrap on 32-bit
+    if (buffer.len() as u64) < len {
+        return Err(Error::Incomplete(format!(
             "Not enough data for string: need {}, have {}",
             len,
             buffer.len()
         )));
     }
+    let len = len as usize;
 
     let string_data = &buffer[..len];
     let s = String::from_utf8(string_data.to_vec()).map_err(|e| {
@@ -100,6 +103,34 @@ pub fn write_string(buffer: &mut BytesMut, s: &str) {
     buffer.put_slice(s.as_bytes());
 }
 
+/// Write a quoted string for query parameters
+///
+/// Format: varint(length) + quoted string. The server unquotes the value and
+/// then reads it in the escaped text format, so `\t`, `\n` and `\` are
+/// escaped twice while `\0`, `\b` and `'` are written as `\xNN`.
+///
+/// This is the synchronous version used for constructing in-memory buffers.
+/// For async I/O, use `WireFormat::write_quoted_string` instead.
+pub fn write_quoted_string(buffer: &mut BytesMut, s: &str) {
+    let mut quoted = Vec::with_capacity(s.len() + 2);
+    quoted.push(b'\'');
+    for &byte in s.as_bytes() {
+        match byte {
+            b'\0' => quoted.extend_from_slice(b"\\x00"),
+            b'\x08' => quoted.extend_from_slice(b"\\x08"),
+            b'\t' => quoted.extend_from_slice(b"\\\\t"),
+            b'\n' => quoted.extend_from_slice(b"\\\\n"),
+            b'\'' => quoted.extend_from_slice(b"\\x27"),
+            b'\\' => quoted.extend_from_slice(b"\\\\\\\\"),
+            byte => quoted.push(byte),
+        }
+    }
+    quoted.push(b'\'');
+
+    write_varint(buffer, quoted.len() as u64);
+    buffer.put_slice(&quoted);
+}
+
 /// Write a varint to a raw `Vec<u8>` (convenience for tests)
 pub fn write_varint_to_vec(buf: &mut Vec<u8>, mut value: u64) {
     loop {
@@ -171,6 +202,37 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_varint_tenth_byte_overflow() {
+        let mut max = BytesMut::new();
+        write_varint(&mut max, u64::MAX);
+        assert_eq!(max.len(), 10);
+        let mut slice = &max[..];
+        assert_eq!(read_varint(&mut slice).unwrap(), u64::MAX);
+
+        // Bits past the 64th are rejected instead of dropped
+        let mut buf = max.clone();
+        buf[9] = 0x02;
+        let mut slice = &buf[..];
+        assert!(read_varint(&mut slice).is_err());
+
+        // Truncated varints error at every length
+        for len in 0..max.len() {
+            let mut slice = &max[..len];
+            assert!(read_varint(&mut slice).is_err());
+        }
+    }
+
+    #[test]
+    fn test_string_length_beyond_buffer() {
+        let mut buf = BytesMut::new();
+        write_varint(&mut buf, u64::MAX);
+        buf.put_slice(b"abc");
+
+        let mut slice = &buf[..];
+        assert!(read_string(&mut slice).is_err());
+    }
+
     #[test]
     fn test_string_truncated() {
         let mut buf = BytesMut::new();
@@ -6,6 +6,8 @@
 /// Block reader/writer for streaming data between client and server.
 pub mod block_stream;
 pub mod buffer_utils;
+/// `RowBinaryWithNamesAndTypes` import and export.
+pub mod row_binary;
 
 pub use block_stream::{
     BlockReader,
@@ -0,0 +1,913 @@
+//! `RowBinaryWithNamesAndTypes` import and export.
+//!
+//! The format is row-oriented: a header with the column count (varint),
+//! the column names and the type names, followed by every value of the
+//! first row, then of the second, and so on. It is what
+//! `clickhouse-client --format RowBinaryWithNamesAndTypes` reads and
+//! writes.
+//!
+//! A value is encoded like a one-row column of the Native format, except
+//! that:
+//! - `Nullable` values are a null flag, followed by the value only when it is
+//!   not NULL
+//! - arrays and maps start with their length as a varint
+//! - `LowCardinality` values are written as the type they wrap
+//!
+//! Columns are converted through their Native encoding, so every type the
+//! block reader and writer support works here too.
+//!
+//! # Example
+//! ```no_run
+//! # use clickhouse_native_client::{Client, ClientOptions};
+//! # use clickhouse_native_client::io::row_binary::read_row_binary;
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # let mut client = Client::connect(ClientOptions::default()).await?;
+//! let result = client.query("SELECT number, toString(number) FROM numbers(10)").await?;
+//! let mut file = std::fs::File::create("numbers.bin")?;
+//! result.write_row_binary(&mut file)?;
+//!
+//! let block = read_row_binary(&mut std::fs::File::open("numbers.bin")?)?;
+//! assert_eq!(block.row_count(), 10);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    block::Block,
+    column::ColumnRef,
+    io::{
+        block_stream::create_column,
+        buffer_utils,
+    },
+    types::{
+        Type,
+        TypeCode,
+    },
+    Error,
+    Result,
+};
+use bytes::{
+    Buf,
+    BufMut,
+    BytesMut,
+};
+use std::{
+    io::{
+        Read,
+        Write,
+    },
+    sync::Arc,
+};
+
+/// `LowCardinality` key version sent in the column prefix
+const SHARED_DICTIONARIES_WITH_ADDITIONAL_KEYS: u64 = 1;
+/// `LowCardinality` flag: the dictionary is sent along with the indices
+const HAS_ADDITIONAL_KEYS_BIT: u64 = 1 << 9;
+/// `LowCardinality` index type code for UInt64 indices
+const INDEX_TYPE_UINT64: u64 = 3;
+
+/// Write `blocks` in the `RowBinaryWithNamesAndTypes` format
+///
+/// The header is taken from the first block; an empty slice writes a
+/// header without columns.
+///
+/// # Errors
+///
+/// Returns `Error::Validation` if the blocks do not all have the same
+/// column types, and `Error::Io` if writing fails.
+pub fn write_row_binary<W: Write>(
+    blocks: &[Block],
+    writer: &mut W,
+) -> Result<()> {
+    let mut buffer = BytesMut::new();
+    let Some(first) = blocks.first() else {
+        buffer_utils::write_varint(&mut buffer, 0);
+        writer.write_all(&buffer)?;
+        return Ok(());
+    };
+
+    let types: Vec<Type> =
+        first.iter().map(|(_, type_, _)| type_.clone()).collect();
+    buffer_utils::write_varint(&mut buffer, types.len() as u64);
+    for (name, _, _) in first.iter() {
+        buffer_utils::write_string(&mut buffer, name);
+    }
+    for type_ in &types {
+        buffer_utils::write_string(&mut buffer, &type_.name());
+    }
+    writer.write_all(&buffer)?;
+
+    for block in blocks {
+        let block_types: Vec<&Type> =
+            block.iter().map(|(_, type_, _)| type_).collect();
+        if block_types.len() != types.len()
+            || block_types.iter().zip(&types).any(|(a, b)| *a != b)
+        {
+            return Err(Error::Validation(
+                "All blocks must have the same column types".to_string(),
+            ));
+        }
+
+        let columns = block
+            .iter()
+            .map(|(_, type_, column)| Values::from_column(type_, &column))
+            .collect::<Result<Vec<_>>>()?;
+        buffer.clear();
+        for row in 0..block.row_count() {
+            for column in &columns {
+                column.write_value(row, &mut buffer)?;
+            }
+        }
+        writer.write_all(&buffer)?;
+    }
+    Ok(())
+}
+
+/// Read data in the `RowBinaryWithNamesAndTypes` format into a block
+///
+/// Reads `reader` to the end.
+///
+/// # Errors
+///
+/// Returns `Error::Protocol` if the data is truncated or malformed, a
+/// parse error for an unknown type name, and `Error::Io` if reading fails.
+pub fn read_row_binary<R: Read>(reader: &mut R) -> Result<Block> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let mut buffer = &data[..];
+
+    let count = buffer_utils::read_varint(&mut buffer)? as usize;
+    let names = (0..count)
+        .map(|_| buffer_utils::read_string(&mut buffer))
+        .collect::<Result<Vec<_>>>()?;
+    let types = (0..count)
+        .map(|_| Type::parse(&buffer_utils::read_string(&mut buffer)?))
+        .collect::<Result<Vec<_>>>()?;
+
+    if count == 0 && !buffer.is_empty() {
+        return Err(Error::Protocol(
+            "RowBinary data has rows but no columns".to_string(),
+        ));
+    }
+
+    let mut columns = types
+        .iter()
+        .map(|type_| Ok(Values::new(&Layout::of(type_)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let mut rows = 0;
+    while !buffer.is_empty() {
+        for column in &mut columns {
+            column.read_value(&mut buffer)?;
+        }
+        rows += 1;
+    }
+
+    let mut block = Block::new();
+    for ((name, type_), values) in names.into_iter().zip(&types).zip(&columns)
+    {
+        block.append_column(name, values.to_column(type_, rows)?)?;
+    }
+    Ok(block)
+}
+
+/// Layouts of a row of fixed column types, for decoding RowBinary one row
+/// at a time
+#[derive(Debug)]
+pub(crate) struct RowLayout {
+    types: Vec<Type>,
+    layouts: Vec<Layout>,
+}
+
+impl RowLayout {
+    pub(crate) fn new(types: Vec<Type>) -> Result<Self> {
+        let layouts = types.iter().map(Layout::of).collect::<Result<_>>()?;
+        Ok(Self { types, layouts })
+    }
+
+    pub(crate) fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    /// Size in bytes of the row at the start of `buffer`, or `None` if
+    /// `buffer` ends before the row does
+    pub(crate) fn row_size(&self, buffer: &[u8]) -> Result<Option<usize>> {
+        let mut rest = buffer;
+        for layout in &self.layouts {
+            if !layout.skip_value(&mut rest)? {
+                return Ok(None);
+            }
+        }
+        Ok(Some(buffer.len() - rest.len()))
+    }
+
+    /// Decode one row into a one-row column per type
+    pub(crate) fn decode_row(&self, row: &[u8]) -> Result<Vec<ColumnRef>> {
+        let mut buffer = row;
+        let columns = self
+            .types
+            .iter()
+            .zip(&self.layouts)
+            .map(|(type_, layout)| {
+                let mut values = Values::new(layout);
+                values.read_value(&mut buffer)?;
+                values.to_column(type_, 1)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !buffer.is_empty() {
+            return Err(Error::Protocol(format!(
+                "{} trailing bytes after RowBinary row",
+                buffer.len()
+            )));
+        }
+        Ok(columns)
+    }
+}
+
+/// Shape of a type's Native encoding
+#[derive(Debug)]
+enum Layout {
+    /// Values of a fixed size in bytes
+    Fixed(usize),
+    /// Varint length followed by the bytes
+    String,
+    Nullable(Box<Layout>),
+    Array(Box<Layout>),
+    Tuple(Vec<Layout>),
+    /// Dictionary of the wrapped type, which may be nullable
+    LowCardinality(Box<Layout>),
+}
+
+impl Layout {
+    fn of(type_: &Type) -> Result<Self> {
+        Ok(match type_ {
+            Type::Simple(TypeCode::String | TypeCode::Json) => Layout::String,
+            // Geo types are named tuples and arrays
+            Type::Simple(TypeCode::Point) => {
+                Layout::Tuple(vec![Layout::Fixed(8), Layout::Fixed(8)])
+            }
+            Type::Simple(TypeCode::Ring) => Layout::Array(Box::new(
+                Layout::of(&Type::Simple(TypeCode::Point))?,
+            )),
+            Type::Simple(TypeCode::Polygon) => Layout::Array(Box::new(
+                Layout::of(&Type::Simple(TypeCode::Ring))?,
+            )),
+            Type::Simple(TypeCode::MultiPolygon) => Layout::Array(Box::new(
+                Layout::of(&Type::Simple(TypeCode::Polygon))?,
+            )),
+            Type::Nullable { nested_type } => {
+                Layout::Nullable(Box::new(Layout::of(nested_type)?))
+            }
+            Type::Array { item_type } => {
+                Layout::Array(Box::new(Layout::of(item_type)?))
+            }
+            Type::Tuple { item_types } => Layout::Tuple(
+                item_types.iter().map(Layout::of).collect::<Result<_>>()?,
+            ),
+            Type::Map { key_type, value_type } => {
+                Layout::Array(Box::new(Layout::Tuple(vec![
+                    Layout::of(key_type)?,
+                    Layout::of(value_type)?,
+                ])))
+            }
+            Type::Nested { item_types, .. } => {
+                Layout::Array(Box::new(Layout::Tuple(
+                    item_types
+                        .iter()
+                        .map(Layout::of)
+                        .collect::<Result<_>>()?,
+                )))
+            }
+            Type::LowCardinality { nested_type } => {
+                Layout::LowCardinality(Box::new(Layout::of(nested_type)?))
+            }
+            _ => match type_.storage_size_bytes() {
+                Some(size) => Layout::Fixed(size),
+                None => {
+                    return Err(Error::Validation(format!(
+                        "Type {} is not supported in RowBinary",
+                        type_.name()
+                    )))
+                }
+            },
+        })
+    }
+
+    /// Skip the next RowBinary value of `buffer`, returning `false` if
+    /// `buffer` ends before the value does
+    fn skip_value(&self, buffer: &mut &[u8]) -> Result<bool> {
+        fn skip(buffer: &mut &[u8], len: usize) -> bool {
+            if buffer.len() < len {
+                return false;
+            }
+            *buffer = &buffer[len..];
+            true
+        }
+
+        fn length(buffer: &mut &[u8]) -> Result<Option<u64>> {
+            // Only a complete varint is decoded; a malformed one still fails
+            let complete = buffer.iter().take(10).any(|byte| byte & 0x80 == 0);
+            if complete || buffer.len() >= 10 {
+                buffer_utils::read_varint(buffer).map(Some)
+            } else {
+                Ok(None)
+            }
+        }
+
+        Ok(match self {
+            Layout::Fixed(size) => skip(buffer, *size),
+            Layout::String => match length(buffer)? {
+                Some(len) => {
+                    usize::try_from(len).is_ok_and(|len| skip(buffer, len))
+                }
+                None => false,
+            },
+            Layout::Nullable(nested) => match buffer.split_first() {
+                None => false,
+                Some((&is_null, rest)) => {
+                    *buffer = rest;
+                    is_null != 0 || nested.skip_value(buffer)?
+                }
+            },
+            // Written as the wrapped type
+            Layout::LowCardinality(nested) => nested.skip_value(buffer)?,
+            Layout::Array(nested) => match length(buffer)? {
+                Some(len) => {
+                    for _ in 0..len {
+                        if !nested.skip_value(buffer)? {
+                            return Ok(false);
+                        }
+                    }
+                    true
+                }
+                None => false,
+            },
+            Layout::Tuple(items) => {
+                for item in items {
+                    if !item.skip_value(buffer)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+        })
+    }
+
+    /// Whether a `LowCardinality` of this layout is nullable, and the
+    /// layout of its dictionary, which never holds the null flags
+    fn dictionary(&self) -> (bool, &Layout) {
+        match self {
+            Layout::Nullable(nested) => (true, nested),
+            layout => (false, layout),
+        }
+    }
+}
+
+/// Values of a leaf column, stored back to back as they are encoded
+#[derive(Debug)]
+struct Leaf {
+    /// Size of every value, or `None` for strings
+    size: Option<usize>,
+    data: Vec<u8>,
+    /// End offset of every value in `data`
+    ends: Vec<usize>,
+}
+
+impl Leaf {
+    fn value(&self, index: usize) -> Result<&[u8]> {
+        let end = *self.ends.get(index).ok_or_else(|| {
+            Error::Protocol(format!("Value index {} is out of range", index))
+        })?;
+        let start = index.checked_sub(1).map_or(0, |prev| self.ends[prev]);
+        Ok(&self.data[start..end])
+    }
+
+    /// Append the next encoded value of `buffer`
+    fn read(&mut self, buffer: &mut &[u8]) -> Result<()> {
+        let len = match self.size {
+            Some(size) => size,
+            None => {
+                let mut rest = *buffer;
+                let len = buffer_utils::read_varint(&mut rest)?;
+                (buffer.len() - rest.len()).saturating_add(len as usize)
+            }
+        };
+        self.data.extend_from_slice(take(buffer, len)?);
+        self.ends.push(self.data.len());
+        Ok(())
+    }
+
+    fn push_default(&mut self) {
+        // Zero bytes for fixed-size values, a zero length for strings
+        self.data.resize(self.data.len() + self.size.unwrap_or(1), 0);
+        self.ends.push(self.data.len());
+    }
+}
+
+/// Values of a column, split out of its Native encoding so that they can
+/// be written and read one row at a time
+#[derive(Debug)]
+enum Values {
+    Leaf(Leaf),
+    Nullable {
+        nulls: Vec<u8>,
+        nested: Box<Values>,
+    },
+    Array {
+        offsets: Vec<u64>,
+        nested: Box<Values>,
+    },
+    Tuple(Vec<Values>),
+    /// With a nullable type, `keys` holds the non-null wrapped type and
+    /// index 0 stands for NULL
+    LowCardinality {
+        nullable: bool,
+        keys: Box<Values>,
+        indices: Vec<u64>,
+    },
+}
+
+impl Values {
+    fn new(layout: &Layout) -> Self {
+        match layout {
+            Layout::Fixed(size) => Values::Leaf(Leaf {
+                size: Some(*size),
+                data: Vec::new(),
+                ends: Vec::new(),
+            }),
+            Layout::String => Values::Leaf(Leaf {
+                size: None,
+                data: Vec::new(),
+                ends: Vec::new(),
+            }),
+            Layout::Nullable(nested) => Values::Nullable {
+                nulls: Vec::new(),
+                nested: Box::new(Values::new(nested)),
+            },
+            Layout::Array(nested) => Values::Array {
+                offsets: Vec::new(),
+                nested: Box::new(Values::new(nested)),
+            },
+            Layout::Tuple(items) => {
+                Values::Tuple(items.iter().map(Values::new).collect())
+            }
+            Layout::LowCardinality(nested) => {
+                let (nullable, dictionary) = nested.dictionary();
+                let mut keys = Values::new(dictionary);
+                if nullable {
+                    keys.push_default();
+                }
+                Values::LowCardinality {
+                    nullable,
+                    keys: Box::new(keys),
+                    indices: Vec::new(),
+                }
+            }
+        }
+    }
+
+    fn from_column(type_: &Type, column: &ColumnRef) -> Result<Self> {
+        let mut native = BytesMut::new();
+        column.save_prefix(&mut native)?;
+        column.save_to_buffer(&mut native)?;
+
+        let layout = Layout::of(type_)?;
+        let mut buffer = &native[..];
+        skip_prefix(&layout, &mut buffer)?;
+        Values::load(&layout, &mut buffer, column.size())
+    }
+
+    /// Split `rows` Native-encoded values off `buffer`, after the prefix
+    fn load(layout: &Layout, buffer: &mut &[u8], rows: usize) -> Result<Self> {
+        Ok(match layout {
+            Layout::Fixed(_) | Layout::String => {
+                let mut values = Values::new(layout);
+                if let Values::Leaf(leaf) = &mut values {
+                    for _ in 0..rows {
+                        leaf.read(buffer)?;
+                    }
+                }
+                values
+            }
+            Layout::Nullable(nested) => Values::Nullable {
+                nulls: take(buffer, rows)?.to_vec(),
+                nested: Box::new(Values::load(nested, buffer, rows)?),
+            },
+            Layout::Array(nested) => {
+                let offsets = (0..rows)
+                    .map(|_| Ok(take(buffer, 8)?.get_u64_le()))
+                    .collect::<Result<Vec<_>>>()?;
+                let items = offsets.last().copied().unwrap_or(0) as usize;
+                Values::Array {
+                    offsets,
+                    nested: Box::new(Values::load(nested, buffer, items)?),
+                }
+            }
+            Layout::Tuple(items) => Values::Tuple(
+                items
+                    .iter()
+                    .map(|item| Values::load(item, buffer, rows))
+                    .collect::<Result<_>>()?,
+            ),
+            Layout::LowCardinality(nested) => {
+                let (nullable, dictionary) = nested.dictionary();
+                let index_type = take(buffer, 8)?.get_u64_le() & 0xFF;
+                if index_type > INDEX_TYPE_UINT64 {
+                    return Err(Error::Protocol(format!(
+                        "Unknown LowCardinality index type: {}",
+                        index_type
+                    )));
+                }
+                let index_size = 1 << index_type;
+                let key_count = take(buffer, 8)?.get_u64_le() as usize;
+                let keys = Values::load(dictionary, buffer, key_count)?;
+                let _rows = take(buffer, 8)?.get_u64_le();
+                let indices = (0..rows)
+                    .map(|_| {
+                        Ok(take(buffer, index_size)?.get_uint_le(index_size))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Values::LowCardinality {
+                    nullable,
+                    keys: Box::new(keys),
+                    indices,
+                }
+            }
+        })
+    }
+
+    /// Encode the value of `row` in RowBinary
+    fn write_value(&self, row: usize, out: &mut BytesMut) -> Result<()> {
+        match self {
+            Values::Leaf(leaf) => out.put_slice(leaf.value(row)?),
+            Values::Nullable { nulls, nested } => {
+                if nulls.get(row).copied().unwrap_or(0) != 0 {
+                    out.put_u8(1);
+                } else {
+                    out.put_u8(0);
+                    nested.write_value(row, out)?;
+                }
+            }
+            Values::Array { offsets, nested } => {
+                let start = row.checked_sub(1).map_or(0, |prev| offsets[prev])
+                    as usize;
+                let end = *offsets.get(row).ok_or_else(|| {
+                    Error::Protocol(format!(
+                        "Array row {} is out of range",
+                        row
+                    ))
+                })? as usize;
+                buffer_utils::write_varint(out, (end - start) as u64);
+                for item in start..end {
+                    nested.write_value(item, out)?;
+                }
+            }
+            Values::Tuple(items) => {
+                for item in items {
+                    item.write_value(row, out)?;
+                }
+            }
+            Values::LowCardinality { nullable, keys, indices } => {
+                let index = *indices.get(row).ok_or_else(|| {
+                    Error::Protocol(format!(
+                        "LowCardinality row {} is out of range",
+                        row
+                    ))
+                })? as usize;
+                if *nullable {
+                    if index == 0 {
+                        out.put_u8(1);
+                        return Ok(());
+                    }
+                    out.put_u8(0);
+                }
+                keys.write_value(index, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the next RowBinary value of `buffer`
+    fn read_value(&mut self, buffer: &mut &[u8]) -> Result<()> {
+        match self {
+            Values::Leaf(leaf) => leaf.read(buffer)?,
+            Values::Nullable { nulls, nested } => {
+                let is_null = take(buffer, 1)?[0];
+                nulls.push(is_null);
+                if is_null != 0 {
+                    nested.push_default();
+                } else {
+                    nested.read_value(buffer)?;
+                }
+            }
+            Values::Array { offsets, nested } => {
+                let len = buffer_utils::read_varint(buffer)?;
+                for _ in 0..len {
+                    nested.read_value(buffer)?;
+                }
+                offsets.push(offsets.last().copied().unwrap_or(0) + len);
+            }
+            Values::Tuple(items) => {
+                for item in items {
+                    item.read_value(buffer)?;
+                }
+            }
+            Values::LowCardinality { nullable, keys, indices } => {
+                if *nullable && take(buffer, 1)?[0] != 0 {
+                    indices.push(0);
+                } else {
+                    keys.read_value(buffer)?;
+                    indices.push(keys.len() as u64 - 1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the value NULL is stored as
+    fn push_default(&mut self) {
+        match self {
+            Values::Leaf(leaf) => leaf.push_default(),
+            Values::Nullable { nulls, nested } => {
+                nulls.push(1);
+                nested.push_default();
+            }
+            Values::Array { offsets, .. } => {
+                offsets.push(offsets.last().copied().unwrap_or(0));
+            }
+            Values::Tuple(items) => {
+                items.iter_mut().for_each(Values::push_default);
+            }
+            Values::LowCardinality { nullable, keys, indices } => {
+                if !*nullable {
+                    keys.push_default();
+                }
+                indices.push(if *nullable {
+                    0
+                } else {
+                    keys.len() as u64 - 1
+                });
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Values::Leaf(leaf) => leaf.ends.len(),
+            Values::Nullable { nulls, .. } => nulls.len(),
+            Values::Array { offsets, .. } => offsets.len(),
+            Values::Tuple(items) => items.first().map_or(0, Values::len),
+            Values::LowCardinality { indices, .. } => indices.len(),
+        }
+    }
+
+    fn save_prefix(&self, out: &mut BytesMut) {
+        match self {
+            Values::Leaf(_) => {}
+            Values::Nullable { nested, .. } | Values::Array { nested, .. } => {
+                nested.save_prefix(out)
+            }
+            Values::Tuple(items) => {
+                items.iter().for_each(|item| item.save_prefix(out))
+            }
+            Values::LowCardinality { .. } => {
+                out.put_u64_le(SHARED_DICTIONARIES_WITH_ADDITIONAL_KEYS)
+            }
+        }
+    }
+
+    fn save_body(&self, out: &mut BytesMut) {
+        match self {
+            Values::Leaf(leaf) => out.put_slice(&leaf.data),
+            Values::Nullable { nulls, nested } => {
+                out.put_slice(nulls);
+                nested.save_body(out);
+            }
+            Values::Array { offsets, nested } => {
+                offsets.iter().for_each(|offset| out.put_u64_le(*offset));
+                nested.save_body(out);
+            }
+            Values::Tuple(items) => {
+                items.iter().for_each(|item| item.save_body(out))
+            }
+            Values::LowCardinality { keys, indices, .. } => {
+                out.put_u64_le(INDEX_TYPE_UINT64 | HAS_ADDITIONAL_KEYS_BIT);
+                out.put_u64_le(keys.len() as u64);
+                keys.save_body(out);
+                out.put_u64_le(indices.len() as u64);
+                indices.iter().for_each(|index| out.put_u64_le(*index));
+            }
+        }
+    }
+
+    /// Load the values into a new column of `type_`
+    fn to_column(&self, type_: &Type, rows: usize) -> Result<ColumnRef> {
+        let mut native = BytesMut::new();
+        self.save_prefix(&mut native);
+        self.save_body(&mut native);
+
+        let mut column = create_column(type_)?;
+        let column_mut = Arc::get_mut(&mut column).ok_or_else(|| {
+            Error::Protocol("Cannot load into shared column".to_string())
+        })?;
+        let mut buffer = &native[..];
+        column_mut.load_prefix(&mut buffer, rows)?;
+        column_mut.load_from_buffer(&mut buffer, rows)?;
+        Ok(column)
+    }
+}
+
+/// Skip the Native column prefix of `layout`
+fn skip_prefix(layout: &Layout, buffer: &mut &[u8]) -> Result<()> {
+    match layout {
+        Layout::Fixed(_) | Layout::String => {}
+        Layout::Nullable(nested) | Layout::Array(nested) => {
+            skip_prefix(nested, buffer)?
+        }
+        Layout::Tuple(items) => {
+            for item in items {
+                skip_prefix(item, buffer)?;
+            }
+        }
+        Layout::LowCardinality(_) => {
+            let version = take(buffer, 8)?.get_u64_le();
+            if version != SHARED_DICTIONARIES_WITH_ADDITIONAL_KEYS {
+                return Err(Error::Protocol(format!(
+                    "Invalid LowCardinality key version: {}",
+                    version
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split the next `len` bytes off `buffer`
+fn take<'a>(buffer: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if buffer.len() < len {
+        return Err(Error::Protocol(
+            "Unexpected end of RowBinary data".to_string(),
+        ));
+    }
+    let (head, tail) = buffer.split_at(len);
+    *buffer = tail;
+    Ok(head)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::{
+        column::{
+            ColumnFloat64,
+            ColumnLowCardinality,
+            ColumnNullable,
+            ColumnString,
+            ColumnUInt64,
+        },
+        BlockBuilder,
+    };
+
+    /// Two rows of a mixed-type table, encoded by hand
+    fn mixed_row_binary() -> Vec<u8> {
+        let columns = [
+            ("id", "UInt64"),
+            ("name", "String"),
+            ("score", "Nullable(Int32)"),
+            ("tags", "Array(String)"),
+            ("status", "LowCardinality(String)"),
+            ("country", "LowCardinality(Nullable(String))"),
+            ("pair", "Tuple(UInt8, String)"),
+            ("attrs", "Map(String, UInt64)"),
+            ("code", "FixedString(2)"),
+            ("flag", "Bool"),
+            ("ratio", "Float64"),
+        ];
+        let mut buffer = BytesMut::new();
+        buffer_utils::write_varint(&mut buffer, columns.len() as u64);
+        for (name, _) in columns {
+            buffer_utils::write_string(&mut buffer, name);
+        }
+        for (_, type_) in columns {
+            buffer_utils::write_string(&mut buffer, type_);
+        }
+
+        // Row 1
+        buffer.put_u64_le(1);
+        buffer_utils::write_string(&mut buffer, "alice");
+        buffer.put_u8(0);
+        buffer.put_i32_le(-7);
+        buffer_utils::write_varint(&mut buffer, 2);
+        buffer_utils::write_string(&mut buffer, "a");
+        buffer_utils::write_string(&mut buffer, "b");
+        buffer_utils::write_string(&mut buffer, "active");
+        buffer.put_u8(0);
+        buffer_utils::write_string(&mut buffer, "US");
+        buffer.put_u8(3);
+        buffer_utils::write_string(&mut buffer, "x");
+        buffer_utils::write_varint(&mut buffer, 1);
+        buffer_utils::write_string(&mut buffer, "k");
+        buffer.put_u64_le(10);
+        buffer.put_slice(b"AB");
+        buffer.put_u8(1);
+        buffer.put_f64_le(0.5);
+
+        // Row 2
+        buffer.put_u64_le(2);
+        buffer_utils::write_string(&mut buffer, "");
+        buffer.put_u8(1);
+        buffer_utils::write_varint(&mut buffer, 0);
+        buffer_utils::write_string(&mut buffer, "active");
+        buffer.put_u8(1);
+        buffer.put_u8(4);
+        buffer_utils::write_string(&mut buffer, "y");
+        buffer_utils::write_varint(&mut buffer, 0);
+        buffer.put_slice(b"CD");
+        buffer.put_u8(0);
+        buffer.put_f64_le(f64::NAN);
+
+        buffer.to_vec()
+    }
+
+    #[test]
+    fn test_mixed_block_roundtrip() {
+        let data = mixed_row_binary();
+        let block = read_row_binary(&mut &data[..]).unwrap();
+        assert_eq!(block.row_count(), 2);
+        assert_eq!(block.column_count(), 11);
+
+        let ids = block.column_by_name("id").unwrap();
+        let ids = ids.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        assert_eq!(ids.data(), [1, 2]);
+
+        let scores = block.column_by_name("score").unwrap();
+        let scores = scores.as_any().downcast_ref::<ColumnNullable>().unwrap();
+        assert!(!scores.is_null(0));
+        assert!(scores.is_null(1));
+
+        let countries = block.column_by_name("country").unwrap();
+        let countries =
+            countries.as_any().downcast_ref::<ColumnLowCardinality>().unwrap();
+        assert_eq!(
+            countries.get::<ColumnString, String>(0),
+            Some("US".to_string())
+        );
+        assert!(countries.is_null(1));
+
+        let ratios = block.column_by_name("ratio").unwrap();
+        let ratios = ratios.as_any().downcast_ref::<ColumnFloat64>().unwrap();
+        assert!(ratios.at(1).is_nan());
+
+        // Writing the block back gives the same bytes
+        let mut written = Vec::new();
+        write_row_binary(&[block], &mut written).unwrap();
+        assert_eq!(written, data);
+    }
+
+    #[test]
+    fn test_write_several_blocks() {
+        let block = |ids: Vec<u64>, names: Vec<&str>| {
+            BlockBuilder::new()
+                .column::<ColumnUInt64>("id", ids)
+                .column::<ColumnString>("name", names)
+                .build()
+                .unwrap()
+        };
+        let blocks =
+            [block(vec![1], vec!["a"]), block(vec![2, 3], vec!["b", "c"])];
+
+        let mut written = Vec::new();
+        write_row_binary(&blocks, &mut written).unwrap();
+        let read = read_row_binary(&mut &written[..]).unwrap();
+        assert_eq!(read.row_count(), 3);
+        let names = read.column_by_name("name").unwrap();
+        let names = names.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(names.iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+        let other = BlockBuilder::new()
+            .column::<ColumnString>("id", vec!["1"])
+            .build()
+            .unwrap();
+        assert!(matches!(
+            write_row_binary(&[read, other], &mut Vec::new()),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_rejects_truncated_rows() {
+        let data = mixed_row_binary();
+        assert!(matches!(
+            read_row_binary(&mut &data[..data.len() - 1]),
+            Err(Error::Protocol(_))
+        ));
+
+        let mut empty = Vec::new();
+        write_row_binary(&[], &mut empty).unwrap();
+        assert_eq!(empty, [0]);
+        assert_eq!(
+            read_row_binary(&mut &empty[..]).unwrap().column_count(),
+            0
+        );
+    }
+}
@@ -16,9 +16,8 @@
 //! # Quick Start
 //!
 //! ```no_run
-//! use clickhouse_native_client::{Client, ClientOptions, Block};
+//! use clickhouse_native_client::{Client, ClientOptions, BlockBuilder};
 //! use clickhouse_native_client::column::numeric::ColumnUInt64;
-//! use std::sync::Arc;
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! // Connect to ClickHouse
@@ -31,11 +30,9 @@
 //! client.execute("CREATE TABLE IF NOT EXISTS test (id UInt64) ENGINE = Memory").await?;
 //!
 //! // Insert data
-//! let mut col = ColumnUInt64::new();
-//! col.append(1);
-//! col.append(2);
-//! let mut block = Block::new();
-//! block.append_column("id", Arc::new(col))?;
+//! let block = BlockBuilder::new()
+//!     .column::<ColumnUInt64>("id", vec![1, 2])
+//!     .build()?;
 //! client.insert("test", block).await?;
 //!
 //! // Query data
@@ -50,20 +47,25 @@
 //! # Feature Flags
 //!
 //! - **`tls`** - Enables TLS/SSL connections via `rustls` and `tokio-rustls`.
+//! - **`derive`** - Enables `#[derive(Row)]` to map structs to blocks.
+//! - **`json`** - Enables `ColumnJson::get_json` via `serde_json`.
 //!
 //! # Modules
 //!
 //! - [`client`] - Async client API (`Client`, `ClientOptions`)
-//! - [`block`] - Data blocks (`Block`, `BlockInfo`)
+//! - [`block`] - Data blocks (`Block`, `BlockBuilder`, `BlockInfo`)
 //! - [`mod@column`] - Column types for all ClickHouse data types
 //! - [`query`] - Query builder and protocol messages
+//! - [`row`] - Struct to block mapping (`Row`, `TypedBlock`, `ToSql`,
+//!   `FromSql`)
 //! - [`types`] - ClickHouse type system and parser
 //! - [`compression`] - LZ4/ZSTD compression
 //! - [`protocol`] - Protocol constants (packet types, revisions)
 //! - [`error`] - Error types and `Result` alias
 //! - [`connection`] - Async TCP/TLS connection wrapper
 //! - [`wire_format`] - Wire protocol encoding helpers
-//! - [`io`] - Block reader/writer for async I/O
+//! - [`io`] - Block reader/writer for async I/O, `RowBinaryWithNamesAndTypes`
+//!   import/export
 //! - `ssl` - TLS/SSL options (requires `tls` feature)
 
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
@@ -86,6 +88,8 @@ pub mod io;
 pub mod protocol;
 /// Query builder and protocol messages.
 pub mod query;
+/// Mapping between Rust structs and blocks.
+pub mod row;
 /// Re-exports from the connection module.
 pub mod socket;
 /// ClickHouse type system and type string parser.
@@ -99,13 +103,21 @@ pub mod ssl;
 
 pub use block::{
     Block,
+    BlockBuilder,
     BlockInfo,
+    ColumnStats,
 };
 pub use client::{
+    BlockStream,
+    BufferedBlockStream,
     Client,
     ClientOptions,
+    Cursor,
     Endpoint,
+    InsertSession,
     QueryResult,
+    RetryPolicy,
+    SharedClient,
 };
 pub use connection::ConnectionOptions;
 pub use error::{
@@ -113,21 +125,41 @@ pub use error::{
     Result,
 };
 pub use query::{
+    parse_profile_events,
+    parse_server_logs,
+    quote_identifier,
+    quote_string_literal,
     DataCallback,
     DataCancelableCallback,
     Exception,
     ExceptionCallback,
     ExternalTable,
+    LogEntry,
+    LogLevel,
     Profile,
     ProfileCallback,
+    ProfileEvent,
+    ProfileEventKind,
     ProfileEventsCallback,
     Progress,
     ProgressCallback,
     Query,
+    QueryKind,
     QuerySettingsField,
+    ResourceHints,
     ServerLogCallback,
     TracingContext,
 };
+pub use row::{
+    FromSql,
+    Row,
+    ToSql,
+    TypedBlock,
+};
 
 #[cfg(feature = "tls")]
 pub use ssl::SSLOptions;
+
+/// Derive macro for [`Row`] (requires the `derive` feature).
+#[cfg(feature = "derive")]
+pub use clickhouse_native_client_derive::Row;
@@ -1,3 +1,8 @@
+/// `tracing` target of the wire-level protocol events (handshake, queries
+/// sent, packets received). They are logged at `trace` level, e.g. enable
+/// them with `RUST_LOG=clickhouse_native_client::protocol=trace`.
+pub const LOG_TARGET: &str = "clickhouse_native_client::protocol";
+
 /// Types of packets received from server
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u64)]
@@ -32,6 +37,10 @@ pub enum ServerCode {
     ReadTaskRequest = 13,
     /// Profile events from the server (always uncompressed).
     ProfileEvents = 14,
+    /// Parallel replicas: ranges of parts announced by a replica.
+    MergeTreeAllRangesAnnouncement = 15,
+    /// Parallel replicas: request for the next ranges to read.
+    MergeTreeReadTaskRequest = 16,
 }
 
 impl TryFrom<u64> for ServerCode {
@@ -54,6 +63,8 @@ impl TryFrom<u64> for ServerCode {
             12 => Ok(ServerCode::PartUUIDs),
             13 => Ok(ServerCode::ReadTaskRequest),
             14 => Ok(ServerCode::ProfileEvents),
+            15 => Ok(ServerCode::MergeTreeAllRangesAnnouncement),
+            16 => Ok(ServerCode::MergeTreeReadTaskRequest),
             _ => Err(crate::Error::Protocol(format!(
                 "Unknown server code: {}",
                 value
@@ -77,6 +88,8 @@ pub enum ClientCode {
     Cancel = 3,
     /// Ping the server to check the connection is alive.
     Ping = 4,
+    /// Answer to a server `ReadTaskRequest`.
+    ReadTaskResponse = 9,
 }
 
 /// Should we compress Blocks of data
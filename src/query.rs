@@ -1,6 +1,9 @@
 use crate::{
     block::Block,
+    column::Value,
     io::buffer_utils,
+    protocol::ClientCode,
+    row::ToSql,
     Error,
     Result,
 };
@@ -10,10 +13,19 @@ use bytes::{
     BytesMut,
 };
 use std::{
+    borrow::Cow,
     collections::HashMap,
+    fmt,
     sync::Arc,
+    time::Duration,
 };
 
+/// First revision whose Query packet carries query parameters
+pub(crate) const DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS: u64 = 54459;
+
+/// First revision whose Query packet carries an interserver secret
+pub(crate) const DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET: u64 = 54441;
+
 /// Query settings field with flags
 ///
 /// Settings can have flags that indicate their importance and scope:
@@ -70,6 +82,17 @@ impl QuerySettingsField {
     pub fn is_obsolete(&self) -> bool {
         (self.flags & Self::OBSOLETE) != 0
     }
+
+    /// Value as written in the Query packet
+    ///
+    /// The server parses custom settings as a dumped `Field` rather than
+    /// plain text, so their value is sent as a quoted string literal.
+    pub(crate) fn wire_value(&self) -> Cow<'_, str> {
+        if !self.is_custom() {
+            return Cow::Borrowed(&self.value);
+        }
+        Cow::Owned(quote_string_literal(&self.value))
+    }
 }
 
 /// Query settings map
@@ -128,6 +151,158 @@ impl TracingContext {
     pub fn is_enabled(&self) -> bool {
         self.trace_id != 0
     }
+
+    /// Parse a W3C `traceparent` header such as
+    /// `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+    ///
+    /// The tracestate stays empty; set it from the `tracestate` header with
+    /// [`TracingContext::tracestate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if the header is malformed: fields
+    /// that are not lowercase hex of the right length, version `ff`, an
+    /// all-zero trace or parent ID, or extra fields on version `00`.
+    pub fn from_traceparent(header: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            Error::InvalidArgument(format!(
+                "Invalid traceparent '{}': {}",
+                header, reason
+            ))
+        };
+
+        let mut fields = header.trim().split('-');
+        let (Some(version), Some(trace_id), Some(span_id), Some(flags)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid("expected four '-' separated fields"));
+        };
+        let version = parse_lower_hex(version, 2)
+            .ok_or_else(|| invalid("version is not 2 hex digits"))?;
+        if version == 0xff {
+            return Err(invalid("version ff is not allowed"));
+        }
+        // Later versions may append fields, version 00 has exactly four
+        if version == 0 && fields.next().is_some() {
+            return Err(invalid("unexpected fields after the trace flags"));
+        }
+        let trace_id = parse_lower_hex(trace_id, 32)
+            .ok_or_else(|| invalid("trace ID is not 32 hex digits"))?;
+        let span_id = parse_lower_hex(span_id, 16)
+            .ok_or_else(|| invalid("parent ID is not 16 hex digits"))?;
+        let trace_flags = parse_lower_hex(flags, 2)
+            .ok_or_else(|| invalid("trace flags are not 2 hex digits"))?;
+        if trace_id == 0 || span_id == 0 {
+            return Err(invalid("trace and parent IDs must not be all zeros"));
+        }
+
+        Ok(Self {
+            trace_id,
+            span_id: span_id as u64,
+            tracestate: String::new(),
+            trace_flags: trace_flags as u8,
+        })
+    }
+
+    /// Format as a version `00` W3C `traceparent` header
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.span_id, self.trace_flags
+        )
+    }
+}
+
+/// Parse exactly `digits` lowercase hex digits, as the W3C trace context
+/// requires
+fn parse_lower_hex(text: &str, digits: usize) -> Option<u128> {
+    let valid = text.len() == digits
+        && text
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    valid.then(|| u128::from_str_radix(text, 16).ok()).flatten()
+}
+
+/// Typed resource limits for a single query
+///
+/// Each field maps to the ClickHouse setting of the same name; `None` leaves
+/// the server default in place.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceHints {
+    /// Maximum number of query processing threads (`max_threads`)
+    pub max_threads: Option<u64>,
+    /// Maximum memory usage in bytes (`max_memory_usage`), 0 = unlimited
+    pub max_memory_usage: Option<u64>,
+    /// Query priority (`priority`), 0 = no priority, lower runs first
+    pub priority: Option<i64>,
+}
+
+impl ResourceHints {
+    /// Check that all set values are accepted by the server
+    pub fn validate(&self) -> Result<()> {
+        if self.max_threads == Some(0) {
+            return Err(Error::Validation(
+                "max_threads must be positive, use None for the server \
+                 default"
+                    .to_string(),
+            ));
+        }
+        if let Some(priority) = self.priority {
+            if priority < 0 {
+                return Err(Error::Validation(format!(
+                    "priority must be non-negative, got {}",
+                    priority
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Settings (name, value) pairs for the fields that are set
+    pub fn to_settings(&self) -> Vec<(&'static str, String)> {
+        let mut settings = Vec::new();
+        if let Some(v) = self.max_threads {
+            settings.push(("max_threads", v.to_string()));
+        }
+        if let Some(v) = self.max_memory_usage {
+            settings.push(("max_memory_usage", v.to_string()));
+        }
+        if let Some(v) = self.priority {
+            settings.push(("priority", v.to_string()));
+        }
+        settings
+    }
+}
+
+/// Quote an identifier (table, column, database name) with backticks
+///
+/// Embedded backticks are doubled, so the result is always a single valid
+/// identifier regardless of its content.
+pub fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Quote a value as a single-quoted ClickHouse string literal
+///
+/// Backslashes, single quotes and control characters are backslash-escaped,
+/// so the result can be embedded in SQL text verbatim.
+pub fn quote_string_literal(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('\'');
+    for c in s.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\0' => quoted.push_str("\\0"),
+            '\x08' => quoted.push_str("\\b"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
 }
 
 /// Query structure for building and executing queries
@@ -145,6 +320,8 @@ pub struct Query {
     tracing_context: Option<TracingContext>,
     /// Progress callback
     on_progress: Option<ProgressCallback>,
+    /// Raw (per-packet) progress callback
+    on_raw_progress: Option<ProgressCallback>,
     /// Profile callback
     on_profile: Option<ProfileCallback>,
     /// Profile events callback
@@ -157,6 +334,10 @@ pub struct Query {
     on_data: Option<DataCallback>,
     /// Cancelable data callback
     on_data_cancelable: Option<DataCancelableCallback>,
+    /// Time allowed for the whole response (optional)
+    timeout: Option<Duration>,
+    /// Keep server log lines on the result
+    collect_logs: bool,
 }
 
 impl Query {
@@ -169,12 +350,15 @@ impl Query {
             parameters: HashMap::new(),
             tracing_context: None,
             on_progress: None,
+            on_raw_progress: None,
             on_profile: None,
             on_profile_events: None,
             on_server_log: None,
             on_exception: None,
             on_data: None,
             on_data_cancelable: None,
+            timeout: None,
+            collect_logs: false,
         }
     }
 }
@@ -198,6 +382,24 @@ impl Query {
         self
     }
 
+    /// Give up on the response after `timeout`
+    ///
+    /// Applies to every way of running the query, including cursors,
+    /// streams and INSERTs. On expiry the query is cancelled on the server
+    /// and the call fails with `Error::Timeout`. If the timeout hits in the
+    /// middle of a packet, or the cancelled response cannot be drained
+    /// promptly, the connection is reopened before the next operation.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Replace the query text, keeping ID, settings and callbacks
+    pub(crate) fn with_text(mut self, query_text: impl Into<String>) -> Self {
+        self.query_text = query_text.into();
+        self
+    }
+
     /// Set a query setting with value (no flags)
     pub fn with_setting(
         mut self,
@@ -208,6 +410,25 @@ impl Query {
         self
     }
 
+    /// Set a numeric query setting, e.g. `.with_setting_num("max_threads",
+    /// 4)`
+    pub fn with_setting_num<T: fmt::Display>(
+        self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Self {
+        self.with_setting(key, value.to_string())
+    }
+
+    /// Set a boolean query setting, sent as `1` or `0`
+    pub fn with_setting_bool(
+        self,
+        key: impl Into<String>,
+        value: bool,
+    ) -> Self {
+        self.with_setting(key, if value { "1" } else { "0" })
+    }
+
     /// Set a query setting with value and flags
     pub fn with_setting_flags(
         mut self,
@@ -230,6 +451,18 @@ impl Query {
         self
     }
 
+    /// Apply typed resource hints as query settings
+    ///
+    /// Returns `Error::Validation` if any hint is out of range.
+    pub fn with_resources(mut self, hints: ResourceHints) -> Result<Self> {
+        hints.validate()?;
+        for (key, value) in hints.to_settings() {
+            self.settings
+                .insert(key.to_string(), QuerySettingsField::new(value));
+        }
+        Ok(self)
+    }
+
     /// Set a query parameter
     pub fn with_parameter(
         mut self,
@@ -240,6 +473,29 @@ impl Query {
         self
     }
 
+    /// Bind a typed query parameter
+    ///
+    /// The value is formatted as the ClickHouse literal for its type, so
+    /// `{ids:Array(UInt32)}` can be bound to `vec![1u32, 2, 3]` and strings
+    /// need no quoting:
+    ///
+    /// ```
+    /// use clickhouse_native_client::Query;
+    ///
+    /// let query = Query::new("SELECT {ids:Array(UInt32)}, {name:String}")
+    ///     .with_param("ids", vec![1u32, 2, 3])
+    ///     .with_param("name", "O'Brien");
+    /// assert_eq!(query.parameters()["ids"], "[1,2,3]");
+    /// assert_eq!(query.parameters()["name"], "O'Brien");
+    /// ```
+    pub fn with_param<T: ToSql>(
+        self,
+        name: impl Into<String>,
+        value: T,
+    ) -> Self {
+        self.with_parameter(name, value.to_param())
+    }
+
     /// Set OpenTelemetry tracing context
     pub fn with_tracing_context(mut self, context: TracingContext) -> Self {
         self.tracing_context = Some(context);
@@ -271,6 +527,11 @@ impl Query {
         &self.parameters
     }
 
+    /// Get the response timeout
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Set progress callback
     pub fn on_progress<F>(mut self, callback: F) -> Self
     where
@@ -280,6 +541,18 @@ impl Query {
         self
     }
 
+    /// Set raw progress callback
+    ///
+    /// Unlike [`Query::on_progress`], which sees the running total, this
+    /// receives each Progress packet exactly as the server sent it.
+    pub fn on_raw_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.on_raw_progress = Some(Arc::new(callback));
+        self
+    }
+
     /// Set profile callback
     pub fn on_profile<F>(mut self, callback: F) -> Self
     where
@@ -307,6 +580,16 @@ impl Query {
         self
     }
 
+    /// Keep the server log lines of this query on
+    /// [`QueryResult::logs`](crate::QueryResult::logs)
+    ///
+    /// The server only sends logs when the `send_logs_level` setting is
+    /// set, e.g. `.with_setting("send_logs_level", "debug")`.
+    pub fn collect_logs(mut self, enabled: bool) -> Self {
+        self.collect_logs = enabled;
+        self
+    }
+
     /// Set exception callback
     pub fn on_exception<F>(mut self, callback: F) -> Self
     where
@@ -334,12 +617,123 @@ impl Query {
         self
     }
 
+    /// Bytes of the Query packet a client would send for this query
+    ///
+    /// Encodes the packet for a server at `server_revision` with
+    /// [`ClientInfo::default`] and compression disabled, without
+    /// connecting. The empty Data block sent after the packet is not
+    /// included. Useful for asserting the wire encoding of settings,
+    /// parameters and tracing context in tests.
+    pub fn serialize_for_test(&self, server_revision: u64) -> Vec<u8> {
+        let mut buffer = BytesMut::new();
+        self.write_packet(
+            &mut buffer,
+            server_revision,
+            &ClientInfo::default(),
+            false,
+        );
+        buffer.to_vec()
+    }
+
+    /// Encode the Query packet for a server at `revision`
+    pub(crate) fn write_packet(
+        &self,
+        buffer: &mut BytesMut,
+        revision: u64,
+        info: &ClientInfo,
+        compression: bool,
+    ) {
+        buffer_utils::write_varint(buffer, ClientCode::Query as u64);
+        buffer_utils::write_string(buffer, &self.query_id);
+
+        // Client info
+        if revision >= 54032 {
+            buffer.put_u8(info.query_kind as u8);
+            buffer_utils::write_string(buffer, &info.initial_user);
+            buffer_utils::write_string(buffer, &info.initial_query_id);
+            // initial_address (client address:port)
+            buffer_utils::write_string(buffer, "127.0.0.1:0");
+            if revision >= 54449 {
+                buffer.put_i64_le(0); // initial_query_start_time
+            }
+            buffer.put_u8(info.interface_type); // interface type (1 = TCP)
+            buffer_utils::write_string(buffer, &info.os_user);
+            buffer_utils::write_string(buffer, &info.client_hostname);
+            buffer_utils::write_string(buffer, &info.client_name);
+            buffer_utils::write_varint(buffer, info.client_version_major);
+            buffer_utils::write_varint(buffer, info.client_version_minor);
+            buffer_utils::write_varint(buffer, info.client_revision);
+
+            if revision >= 54060 {
+                buffer_utils::write_string(buffer, &info.quota_key);
+            }
+            if revision >= 54448 {
+                buffer_utils::write_varint(buffer, 0); // distributed_depth
+            }
+            if revision >= 54401 {
+                buffer_utils::write_varint(buffer, info.client_version_patch);
+            }
+            if revision >= 54442 {
+                // OpenTelemetry tracing context
+                if let Some(ctx) = &self.tracing_context {
+                    buffer.put_u8(1); // have OpenTelemetry
+                    buffer.put_u128_le(ctx.trace_id);
+                    buffer.put_u64_le(ctx.span_id);
+                    buffer_utils::write_string(buffer, &ctx.tracestate);
+                    buffer.put_u8(ctx.trace_flags);
+                } else {
+                    buffer.put_u8(0); // no OpenTelemetry
+                }
+            }
+            if revision >= 54453 {
+                // collaborate_with_initiator, count_participating_replicas,
+                // number_of_current_replica
+                for _ in 0..3 {
+                    buffer_utils::write_varint(buffer, 0);
+                }
+            }
+        }
+
+        // Settings, terminated by an empty name
+        if revision >= 54429 {
+            for (key, field) in &self.settings {
+                buffer_utils::write_string(buffer, key);
+                buffer_utils::write_varint(buffer, field.flags);
+                buffer_utils::write_string(buffer, &field.wire_value());
+            }
+        }
+        buffer_utils::write_string(buffer, "");
+
+        // Interserver secret
+        if revision >= DBMS_MIN_REVISION_WITH_INTERSERVER_SECRET {
+            buffer_utils::write_string(buffer, &info.interserver_secret);
+        }
+
+        buffer_utils::write_varint(buffer, 2); // Stage = Complete
+        buffer_utils::write_varint(buffer, compression as u64);
+        buffer_utils::write_string(buffer, &self.query_text);
+
+        // Query parameters, terminated by an empty name
+        if revision >= DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS {
+            for (key, value) in &self.parameters {
+                buffer_utils::write_string(buffer, key);
+                buffer_utils::write_varint(buffer, 2); // Custom type
+                buffer_utils::write_quoted_string(buffer, value);
+            }
+            buffer_utils::write_string(buffer, "");
+        }
+    }
+
     // Internal getters for Client to invoke callbacks
 
     pub(crate) fn get_on_progress(&self) -> Option<&ProgressCallback> {
         self.on_progress.as_ref()
     }
 
+    pub(crate) fn get_on_raw_progress(&self) -> Option<&ProgressCallback> {
+        self.on_raw_progress.as_ref()
+    }
+
     pub(crate) fn get_on_profile(&self) -> Option<&ProfileCallback> {
         self.on_profile.as_ref()
     }
@@ -354,6 +748,10 @@ impl Query {
         self.on_server_log.as_ref()
     }
 
+    pub(crate) fn collects_logs(&self) -> bool {
+        self.collect_logs
+    }
+
     pub(crate) fn get_on_exception(&self) -> Option<&ExceptionCallback> {
         self.on_exception.as_ref()
     }
@@ -369,13 +767,25 @@ impl Query {
     }
 }
 
+/// Whether a query was started by this client or is run on behalf of
+/// another query
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum QueryKind {
+    /// Query started by the client sending it.
+    #[default]
+    Initial = 1,
+    /// Query sent to a shard for an initial query running elsewhere.
+    Secondary = 2,
+}
+
 /// Client information sent during handshake
 #[derive(Clone, Debug)]
 pub struct ClientInfo {
     /// Interface type (1 = TCP).
     pub interface_type: u8,
-    /// Query kind identifier.
-    pub query_kind: u8,
+    /// Query kind sent with every query.
+    pub query_kind: QueryKind,
     /// User who initiated the query.
     pub initial_user: String,
     /// Query ID of the initial query.
@@ -395,29 +805,67 @@ pub struct ClientInfo {
     /// Client patch version number.
     pub client_version_patch: u64,
     /// Client protocol revision number.
+    ///
+    /// Servers only send sparse columns at revision 54465 or later; below
+    /// that they are sent densified.
     pub client_revision: u64,
+    /// Raw value of the interserver secret field of every query, empty for
+    /// none, see [`ClientOptions::interserver_secret`]. Only servers at
+    /// revision 54441 or later have this field.
+    ///
+    /// [`ClientOptions::interserver_secret`]: crate::ClientOptions::interserver_secret
+    pub interserver_secret: String,
 }
 
 impl Default for ClientInfo {
     fn default() -> Self {
         Self {
             interface_type: 1, // TCP
-            query_kind: 0,
+            query_kind: QueryKind::Initial,
             initial_user: String::new(),
             initial_query_id: String::new(),
             quota_key: String::new(),
             os_user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
                 .unwrap_or_else(|_| "default".to_string()),
-            client_hostname: "localhost".to_string(),
+            client_hostname: local_hostname(),
             client_name: "clickhouse-rust".to_string(),
             client_version_major: 1,
             client_version_minor: 0,
             client_version_patch: 0,
             client_revision: 54459, /* DBMS_MIN_PROTOCOL_VERSION_WITH_PARAMETERS */
+            interserver_secret: String::new(),
         }
     }
 }
 
+/// Hostname of this machine, falling back to `localhost` if unknown
+#[cfg(unix)]
+fn local_hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the pointer and length describe a valid writable buffer
+    let rc = unsafe {
+        libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len())
+    };
+    if rc != 0 {
+        return "localhost".to_string();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    match std::str::from_utf8(&buf[..len]) {
+        Ok(name) if !name.is_empty() => name.to_string(),
+        _ => "localhost".to_string(),
+    }
+}
+
+/// Hostname of this machine, falling back to `localhost` if unknown
+#[cfg(not(unix))]
+fn local_hostname() -> String {
+    std::env::var("COMPUTERNAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
 impl ClientInfo {
     /// Serialize to buffer
     pub fn write_to(&self, buffer: &mut BytesMut) -> Result<()> {
@@ -455,7 +903,7 @@ impl ClientInfo {
 
         Ok(Self {
             interface_type,
-            query_kind: 0,
+            query_kind: QueryKind::Initial,
             initial_user: String::new(),
             initial_query_id: String::new(),
             quota_key: String::new(),
@@ -466,6 +914,7 @@ impl ClientInfo {
             client_version_minor,
             client_version_patch: 0,
             client_revision,
+            interserver_secret: String::new(),
         })
     }
 }
@@ -581,6 +1030,210 @@ pub struct Profile {
     pub calculated_rows_before_limit: bool,
 }
 
+/// How the value of a [`ProfileEvent`] is to be read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfileEventKind {
+    /// Amount a counter grew by since the previous ProfileEvents packet.
+    Increment,
+    /// Current level of a metric, such as memory usage.
+    Gauge,
+}
+
+/// One row of a ProfileEvents packet
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileEvent {
+    /// Server thread the value belongs to; `0` stands for the whole query.
+    pub thread_id: u64,
+    /// Event name, e.g. `SelectedRows` or `ReadCompressedBytes`.
+    pub name: String,
+    /// Counter increment or gauge level.
+    pub value: i64,
+    /// Whether `value` is an increment or a gauge.
+    pub kind: ProfileEventKind,
+}
+
+/// Decode the block of a ProfileEvents packet, as passed to
+/// [`Query::on_profile_events`]
+///
+/// The block has one row per thread and event, with `thread_id`, `type`,
+/// `name` and `value` columns. Rows that cannot be read are skipped, and a
+/// block without these columns yields no events.
+///
+/// # Example
+/// ```no_run
+/// # use clickhouse_native_client::{parse_profile_events, Query};
+/// let query = Query::new("SELECT count() FROM system.numbers_mt LIMIT 10")
+///     .on_profile_events(|block| {
+///         for event in parse_profile_events(block) {
+///             println!("{} = {}", event.name, event.value);
+///         }
+///         true
+///     });
+/// ```
+pub fn parse_profile_events(block: &Block) -> Vec<ProfileEvent> {
+    let columns = (
+        block.column_by_name("thread_id"),
+        block.column_by_name("type"),
+        block.column_by_name("name"),
+        block.column_by_name("value"),
+    );
+    let (Some(thread_ids), Some(kinds), Some(names), Some(values)) = columns
+    else {
+        return Vec::new();
+    };
+
+    (0..block.row_count())
+        .filter_map(|row| {
+            let thread_id = match thread_ids.value_at(row).ok()? {
+                Value::UInt64(id) => id,
+                _ => return None,
+            };
+            // Enum8('increment' = 1, 'gauge' = 2)
+            let kind = match kinds.value_at(row).ok()? {
+                Value::String(kind) if kind == "gauge" => {
+                    ProfileEventKind::Gauge
+                }
+                Value::Int64(2) => ProfileEventKind::Gauge,
+                _ => ProfileEventKind::Increment,
+            };
+            let Value::String(name) = names.value_at(row).ok()? else {
+                return None;
+            };
+            // Some server versions send the value as UInt64
+            let value = match values.value_at(row).ok()? {
+                Value::Int64(value) => value,
+                Value::UInt64(value) => value as i64,
+                _ => return None,
+            };
+            Some(ProfileEvent { thread_id, name, value, kind })
+        })
+        .collect()
+}
+
+/// Severity of a server [`LogEntry`], from most to least severe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// `Fatal`
+    Fatal,
+    /// `Critical`
+    Critical,
+    /// `Error`
+    Error,
+    /// `Warning`
+    Warning,
+    /// `Notice`
+    Notice,
+    /// `Information`
+    Information,
+    /// `Debug`
+    Debug,
+    /// `Trace`
+    Trace,
+    /// `Test`, only logged by server test builds
+    Test,
+}
+
+impl LogLevel {
+    /// Map the `priority` column of a log block to its level
+    fn from_priority(priority: i64) -> Option<Self> {
+        Some(match priority {
+            1 => LogLevel::Fatal,
+            2 => LogLevel::Critical,
+            3 => LogLevel::Error,
+            4 => LogLevel::Warning,
+            5 => LogLevel::Notice,
+            6 => LogLevel::Information,
+            7 => LogLevel::Debug,
+            8 => LogLevel::Trace,
+            9 => LogLevel::Test,
+            _ => return None,
+        })
+    }
+}
+
+/// One line of a server Log packet
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    /// Seconds since the Unix epoch.
+    pub time: i64,
+    /// Microseconds within `time`.
+    pub time_microseconds: u32,
+    /// Server host that logged the line.
+    pub host_name: String,
+    /// ID of the query the line belongs to.
+    pub query_id: String,
+    /// Server thread that logged the line.
+    pub thread_id: u64,
+    /// Severity of the line.
+    pub level: LogLevel,
+    /// Logger name, e.g. `executeQuery`.
+    pub source: String,
+    /// The message.
+    pub text: String,
+}
+
+/// Decode the block of a server Log packet, as passed to
+/// [`Query::on_server_log`]
+///
+/// Rows that cannot be read are skipped, and a block without the standard
+/// log columns yields no entries.
+pub fn parse_server_logs(block: &Block) -> Vec<LogEntry> {
+    let column = |name| block.column_by_name(name);
+    let columns = (
+        column("event_time"),
+        column("event_time_microseconds"),
+        column("host_name"),
+        column("query_id"),
+        column("thread_id"),
+        column("priority"),
+        column("source"),
+        column("text"),
+    );
+    let (
+        Some(times),
+        Some(microseconds),
+        Some(host_names),
+        Some(query_ids),
+        Some(thread_ids),
+        Some(priorities),
+        Some(sources),
+        Some(texts),
+    ) = columns
+    else {
+        return Vec::new();
+    };
+
+    let string = |value: Value| match value {
+        Value::String(text) => Some(text),
+        _ => None,
+    };
+    let unsigned = |value: Value| match value {
+        Value::UInt64(value) => Some(value),
+        _ => None,
+    };
+    (0..block.row_count())
+        .filter_map(|row| {
+            let Value::DateTime(time) = times.value_at(row).ok()? else {
+                return None;
+            };
+            let Value::Int64(priority) = priorities.value_at(row).ok()? else {
+                return None;
+            };
+            Some(LogEntry {
+                time,
+                time_microseconds: unsigned(microseconds.value_at(row).ok()?)?
+                    as u32,
+                host_name: string(host_names.value_at(row).ok()?)?,
+                query_id: string(query_ids.value_at(row).ok()?)?,
+                thread_id: unsigned(thread_ids.value_at(row).ok()?)?,
+                level: LogLevel::from_priority(priority)?,
+                source: string(sources.value_at(row).ok()?)?,
+                text: string(texts.value_at(row).ok()?)?,
+            })
+        })
+        .collect()
+}
+
 /// External table for JOIN operations
 ///
 /// External tables allow passing temporary in-memory data to queries for JOINs
@@ -638,6 +1291,19 @@ pub type DataCallback = Arc<dyn Fn(&Block) + Send + Sync>;
 pub type DataCancelableCallback = Arc<dyn Fn(&Block) -> bool + Send + Sync>;
 
 impl Progress {
+    /// Accumulate a Progress packet into this running total
+    ///
+    /// Row and byte counts are summed. `total_rows` is only an estimate
+    /// that the server may revise in either direction, so the largest
+    /// value seen is kept to stay monotonic.
+    pub fn merge(&mut self, other: &Progress) {
+        self.rows += other.rows;
+        self.bytes += other.bytes;
+        self.total_rows = self.total_rows.max(other.total_rows);
+        self.written_rows += other.written_rows;
+        self.written_bytes += other.written_bytes;
+    }
+
     /// Serialize to buffer
     pub fn write_to(
         &self,
@@ -729,6 +1395,12 @@ pub struct Exception {
     pub nested: Option<Box<Exception>>,
 }
 
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code {}): {}", self.name, self.code, self.display_text)
+    }
+}
+
 impl Exception {
     /// Serialize to buffer
     pub fn write_to(&self, buffer: &mut BytesMut) -> Result<()> {
@@ -794,6 +1466,14 @@ impl Exception {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_client_info_reports_machine_hostname() {
+        let expected =
+            std::fs::read_to_string("/proc/sys/kernel/hostname").unwrap();
+        assert_eq!(ClientInfo::default().client_hostname, expected.trim());
+    }
+
     #[test]
     fn test_query_creation() {
         let query = Query::new("SELECT 1");
@@ -822,6 +1502,66 @@ mod tests {
         assert_eq!(query.settings().get("max_threads").unwrap().flags, 0);
     }
 
+    #[test]
+    fn test_tracing_context_from_traceparent() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TracingContext::from_traceparent(header).unwrap();
+        assert_eq!(context.trace_id, 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(context.span_id, 0x00f067aa0ba902b7);
+        assert_eq!(context.trace_flags, 1);
+        assert!(context.tracestate.is_empty());
+        assert_eq!(context.to_traceparent(), header);
+
+        // A later version may carry more fields
+        let future =
+            "cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-x";
+        assert!(TracingContext::from_traceparent(future).is_ok());
+
+        assert_eq!(
+            TracingContext::with_ids(1, 2).to_traceparent(),
+            "00-00000000000000000000000000000001-0000000000000002-00"
+        );
+    }
+
+    #[test]
+    fn test_tracing_context_rejects_malformed_traceparent() {
+        for header in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "0-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-+bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-1",
+            "00_4bf92f3577b34da6a3ce929d0e0e4736_00f067aa0ba902b7_01",
+        ] {
+            let err = TracingContext::from_traceparent(header).unwrap_err();
+            assert!(
+                matches!(err, Error::InvalidArgument(_)),
+                "{header}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_with_typed_settings() {
+        let query = Query::new("SELECT 1")
+            .with_setting_num("max_block_size", 1234u64)
+            .with_setting_num("max_execution_time", 2.5)
+            .with_setting_bool("optimize_aggregation_in_order", true)
+            .with_setting_bool("use_uncompressed_cache", false);
+
+        let value = |key| query.settings().get(key).map(|f| f.value.as_str());
+        assert_eq!(value("max_block_size"), Some("1234"));
+        assert_eq!(value("max_execution_time"), Some("2.5"));
+        assert_eq!(value("optimize_aggregation_in_order"), Some("1"));
+        assert_eq!(value("use_uncompressed_cache"), Some("0"));
+    }
+
     #[test]
     fn test_query_with_important_settings() {
         let query = Query::new("SELECT 1")
@@ -845,6 +1585,176 @@ mod tests {
         assert!(!custom.is_important());
     }
 
+    #[test]
+    fn test_custom_setting_wire_value_is_quoted() {
+        let plain = QuerySettingsField::important("4");
+        assert_eq!(plain.wire_value(), "4");
+
+        let custom = QuerySettingsField::custom("bar");
+        assert_eq!(custom.wire_value(), "'bar'");
+
+        let escaped = QuerySettingsField::custom(r"it's a\b");
+        assert_eq!(escaped.wire_value(), r"'it\'s a\\b'");
+
+        let multiline = QuerySettingsField::custom("a\nb\tc");
+        assert_eq!(multiline.wire_value(), r"'a\nb\tc'");
+    }
+
+    #[test]
+    fn test_with_param_formats_typed_literals() {
+        let query = Query::new("SELECT 1")
+            .with_param("arr", vec![1u32, 2, 3])
+            .with_param("n", -7i64)
+            .with_param("s", "it's \\ here")
+            .with_param("names", vec!["it's", "a\\b", "x\ny"])
+            .with_param("nested", vec![vec![1.5f64], vec![]])
+            .with_param("flag", true)
+            .with_param("missing", None::<u8>)
+            .with_param("maybe", vec![Some(1u8), None]);
+
+        let params = query.parameters();
+        assert_eq!(params["arr"], "[1,2,3]");
+        assert_eq!(params["n"], "-7");
+        assert_eq!(params["s"], "it's \\ here");
+        assert_eq!(params["names"], r"['it\'s','a\\b','x\ny']");
+        assert_eq!(params["nested"], "[[1.5],[]]");
+        assert_eq!(params["flag"], "true");
+        assert_eq!(params["missing"], r"\N");
+        assert_eq!(params["maybe"], "[1,NULL]");
+    }
+
+    #[test]
+    fn test_serialize_for_test_settings_and_parameters() {
+        let query = Query::new("SELECT {n:UInt8}")
+            .with_query_id("q1")
+            .with_setting("max_threads", "2")
+            .with_parameter("n", "7");
+
+        let bytes = query.serialize_for_test(54459);
+        assert_eq!(&bytes[..4], &[ClientCode::Query as u8, 2, b'q', b'1']);
+
+        let mut expected = Vec::new();
+        // Setting: name, flags, value, then end of settings
+        expected.push(11);
+        expected.extend_from_slice(b"max_threads");
+        expected.extend_from_slice(&[0, 1, b'2', 0]);
+        // Interserver secret, stage, compression
+        expected.extend_from_slice(&[0, 2, 0]);
+        expected.push(16);
+        expected.extend_from_slice(b"SELECT {n:UInt8}");
+        // Parameter: name, custom flag, quoted value, then end of
+        // parameters
+        expected.extend_from_slice(&[1, b'n', 2, 3]);
+        expected.extend_from_slice(b"'7'");
+        expected.push(0);
+        assert!(bytes.ends_with(&expected), "{:?}", bytes);
+
+        // Before client info existed only the bare packet is sent
+        assert_eq!(
+            Query::new("SELECT 1").serialize_for_test(54000),
+            [&[ClientCode::Query as u8, 0, 0, 2, 0, 8][..], b"SELECT 1"]
+                .concat()
+        );
+    }
+
+    #[test]
+    fn test_write_packet_query_kind_and_interserver_secret() {
+        let info = ClientInfo {
+            query_kind: QueryKind::Secondary,
+            initial_user: "alice".to_string(),
+            interserver_secret: "digest".to_string(),
+            ..ClientInfo::default()
+        };
+        let packet = |revision| {
+            let mut buffer = BytesMut::new();
+            Query::new("SELECT 1").write_packet(
+                &mut buffer,
+                revision,
+                &info,
+                false,
+            );
+            buffer.to_vec()
+        };
+
+        let bytes = packet(54459);
+        // Empty query ID, then the client info starting with the kind
+        assert_eq!(&bytes[..3], &[ClientCode::Query as u8, 0, 2]);
+        assert_eq!(&bytes[3..9], &[5, b'a', b'l', b'i', b'c', b'e']);
+        let mut tail = vec![0, 6];
+        tail.extend_from_slice(b"digest");
+        tail.extend_from_slice(&[2, 0, 8]);
+        tail.extend_from_slice(b"SELECT 1");
+        tail.push(0);
+        assert!(bytes.ends_with(&tail), "{:?}", bytes);
+
+        // Servers before the secret field get none
+        let bytes = packet(54440);
+        assert!(!bytes.windows(6).any(|w| w == b"digest"));
+        assert_eq!(bytes[2], 2);
+    }
+
+    #[test]
+    fn test_query_with_resources() {
+        let query = Query::new("SELECT 1")
+            .with_resources(ResourceHints {
+                max_threads: Some(1),
+                max_memory_usage: Some(10 * 1024 * 1024 * 1024),
+                priority: None,
+            })
+            .unwrap();
+
+        assert_eq!(query.settings().len(), 2);
+        assert_eq!(query.settings().get("max_threads").unwrap().value, "1");
+        assert_eq!(
+            query.settings().get("max_memory_usage").unwrap().value,
+            "10737418240"
+        );
+        assert!(query.settings().get("priority").is_none());
+    }
+
+    #[test]
+    fn test_query_with_resources_validation() {
+        let zero_threads =
+            ResourceHints { max_threads: Some(0), ..Default::default() };
+        assert!(matches!(
+            Query::new("SELECT 1").with_resources(zero_threads),
+            Err(Error::Validation(_))
+        ));
+
+        let negative_priority =
+            ResourceHints { priority: Some(-1), ..Default::default() };
+        assert!(matches!(
+            Query::new("SELECT 1").with_resources(negative_priority),
+            Err(Error::Validation(_))
+        ));
+
+        let query = Query::new("SELECT 1")
+            .with_resources(ResourceHints {
+                priority: Some(0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(query.settings().get("priority").unwrap().value, "0");
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("id"), "`id`");
+        assert_eq!(quote_identifier("we`ird"), "`we``ird`");
+        assert_eq!(quote_identifier("`"), "````");
+        assert_eq!(quote_identifier("with space.dot"), "`with space.dot`");
+    }
+
+    #[test]
+    fn test_quote_string_literal() {
+        assert_eq!(quote_string_literal("abc"), "'abc'");
+        assert_eq!(quote_string_literal("it's"), r"'it\'s'");
+        assert_eq!(quote_string_literal(r"a\b"), r"'a\\b'");
+        assert_eq!(quote_string_literal(r"\'"), r"'\\\''");
+        assert_eq!(quote_string_literal("a\nb\t\0"), r"'a\nb\t\0'");
+        assert_eq!(quote_string_literal(""), "''");
+    }
+
     #[test]
     fn test_client_info_roundtrip() {
         let info = ClientInfo::default();
@@ -881,6 +1791,35 @@ mod tests {
         assert_eq!(decoded.timezone, "UTC");
     }
 
+    #[test]
+    fn test_server_info_truncated() {
+        let info = ServerInfo {
+            name: "ClickHouse".to_string(),
+            version_major: 21,
+            version_minor: 8,
+            version_patch: 5,
+            revision: 54449,
+            timezone: "UTC".to_string(),
+            display_name: "ClickHouse server".to_string(),
+        };
+
+        let mut buffer = BytesMut::new();
+        info.write_to(&mut buffer).unwrap();
+
+        // Every proper prefix must error instead of panicking
+        for len in 0..buffer.len() {
+            let mut reader = &buffer[..len];
+            assert!(ServerInfo::read_from(&mut reader).is_err(), "len {len}");
+        }
+
+        // A name length far past the end of the buffer
+        let mut bogus = BytesMut::new();
+        buffer_utils::write_varint(&mut bogus, u64::MAX >> 1);
+        bogus.extend_from_slice(&buffer[1..]);
+        let mut reader = &bogus[..];
+        assert!(ServerInfo::read_from(&mut reader).is_err());
+    }
+
     #[test]
     fn test_progress_roundtrip() {
         let progress = Progress {
@@ -902,6 +1841,31 @@ mod tests {
         assert_eq!(decoded.written_rows, 50);
     }
 
+    #[test]
+    fn test_progress_merge() {
+        let mut total = Progress::default();
+        total.merge(&Progress {
+            rows: 10,
+            bytes: 100,
+            total_rows: 50,
+            written_rows: 1,
+            written_bytes: 8,
+        });
+        total.merge(&Progress {
+            rows: 5,
+            bytes: 40,
+            total_rows: 30,
+            written_rows: 2,
+            written_bytes: 16,
+        });
+
+        assert_eq!(total.rows, 15);
+        assert_eq!(total.bytes, 140);
+        assert_eq!(total.total_rows, 50);
+        assert_eq!(total.written_rows, 3);
+        assert_eq!(total.written_bytes, 24);
+    }
+
     #[test]
     fn test_exception_simple() {
         let exc = Exception {
@@ -951,4 +1915,146 @@ mod tests {
         assert!(decoded.nested.is_some());
         assert_eq!(decoded.nested.as_ref().unwrap().code, 1);
     }
+
+    #[test]
+    fn test_parse_profile_events() {
+        use crate::{
+            column::{
+                enum_column::ColumnEnum8,
+                numeric::{
+                    ColumnInt64,
+                    ColumnUInt64,
+                },
+                ColumnString,
+            },
+            types::{
+                EnumItem,
+                Type,
+            },
+        };
+
+        let kinds = Type::enum8(vec![
+            EnumItem { name: "increment".to_string(), value: 1 },
+            EnumItem { name: "gauge".to_string(), value: 2 },
+        ]);
+        let mut block = Block::new();
+        block
+            .append_column(
+                "thread_id",
+                Arc::new(ColumnUInt64::from_vec(Type::uint64(), vec![0, 17])),
+            )
+            .unwrap();
+        block
+            .append_column(
+                "type",
+                Arc::new(ColumnEnum8::new(kinds).with_data(vec![1, 2])),
+            )
+            .unwrap();
+        block
+            .append_column(
+                "name",
+                Arc::new(ColumnString::from(vec![
+                    "SelectedRows",
+                    "MemoryTrackerUsage",
+                ])),
+            )
+            .unwrap();
+        block
+            .append_column(
+                "value",
+                Arc::new(ColumnInt64::from_vec(Type::int64(), vec![10, 4096])),
+            )
+            .unwrap();
+
+        assert_eq!(
+            parse_profile_events(&block),
+            vec![
+                ProfileEvent {
+                    thread_id: 0,
+                    name: "SelectedRows".to_string(),
+                    value: 10,
+                    kind: ProfileEventKind::Increment,
+                },
+                ProfileEvent {
+                    thread_id: 17,
+                    name: "MemoryTrackerUsage".to_string(),
+                    value: 4096,
+                    kind: ProfileEventKind::Gauge,
+                },
+            ]
+        );
+        assert!(parse_profile_events(&Block::new()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_server_logs() {
+        use crate::{
+            column::{
+                date::ColumnDateTime,
+                numeric::{
+                    ColumnInt8,
+                    ColumnUInt32,
+                    ColumnUInt64,
+                },
+                ColumnRef,
+                ColumnString,
+            },
+            types::Type,
+        };
+
+        let strings = |values: Vec<&str>| -> ColumnRef {
+            Arc::new(ColumnString::from(values))
+        };
+        let columns: Vec<(&str, ColumnRef)> = vec![
+            (
+                "event_time",
+                Arc::new(
+                    ColumnDateTime::new(Type::datetime(None))
+                        .with_data(vec![1_700_000_000, 1_700_000_001]),
+                ),
+            ),
+            (
+                "event_time_microseconds",
+                Arc::new(ColumnUInt32::from_vec(
+                    Type::uint32(),
+                    vec![250, 999_999],
+                )),
+            ),
+            ("host_name", strings(vec!["ch1", "ch1"])),
+            ("query_id", strings(vec!["q-1", "q-1"])),
+            (
+                "thread_id",
+                Arc::new(ColumnUInt64::from_vec(Type::uint64(), vec![42, 43])),
+            ),
+            (
+                "priority",
+                Arc::new(ColumnInt8::from_vec(Type::int8(), vec![6, 8])),
+            ),
+            ("source", strings(vec!["executeQuery", "MemoryTracker"])),
+            ("text", strings(vec!["Read 1 rows", "Peak memory usage"])),
+        ];
+        let mut block = Block::new();
+        for (name, column) in columns {
+            block.append_column(name, column).unwrap();
+        }
+
+        let logs = parse_server_logs(&block);
+        assert_eq!(logs.len(), 2);
+        assert_eq!(
+            logs[0],
+            LogEntry {
+                time: 1_700_000_000,
+                time_microseconds: 250,
+                host_name: "ch1".to_string(),
+                query_id: "q-1".to_string(),
+                thread_id: 42,
+                level: LogLevel::Information,
+                source: "executeQuery".to_string(),
+                text: "Read 1 rows".to_string(),
+            }
+        );
+        assert_eq!(logs[1].level, LogLevel::Trace);
+        assert!(logs[1].level > LogLevel::Warning);
+        assert!(parse_server_logs(&Block::new()).is_empty());
+    }
 }
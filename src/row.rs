@@ -0,0 +1,575 @@
+//! Mapping between Rust structs and blocks.
+//!
+//! [`ToSql`] and [`FromSql`] convert whole columns to and from vectors of
+//! Rust values. [`Row`] builds on them to map a struct to one column per
+//! field; with the `derive` feature it can be derived:
+//!
+//! ```ignore
+//! use clickhouse_native_client::Row;
+//!
+//! #[derive(Row)]
+//! struct Event {
+//!     id: u64,
+//!     #[column(rename = "event_name")]
+//!     name: String,
+//!     country: Option<String>,
+//! }
+//!
+//! client.insert("events", Event::to_block(&events)?).await?;
+//! let events = Event::from_result(&client.query("SELECT * FROM events").await?)?;
+//! ```
+//!
+//! Fields are matched to columns by name, so the column order of a query
+//! does not matter. `Option<T>` fields map to `Nullable(T)` columns,
+//! `Vec<T>` fields to `Array(T)` columns, and `LowCardinality` columns are
+//! read like the type they wrap.
+//!
+//! [`ToSql`] also formats query parameters for
+//! [`Query::with_param`](crate::Query::with_param).
+
+use crate::{
+    client::QueryResult,
+    column::{
+        ColumnArray,
+        ColumnBool,
+        ColumnFixedString,
+        ColumnLowCardinality,
+        ColumnNullable,
+        ColumnRef,
+        ColumnString,
+        ColumnUInt8,
+        ColumnVector,
+    },
+    query::quote_string_literal,
+    Block,
+    Error,
+    Result,
+};
+use std::sync::Arc;
+
+/// A Rust type that can be written as a column or bound as a query
+/// parameter.
+pub trait ToSql: Sized {
+    /// Build a column holding `values`.
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef>;
+
+    /// Write `self` as a literal inside a parameter value, the way an array
+    /// element is written: strings quoted, `NULL` for a missing value.
+    fn write_param_literal(&self, out: &mut String);
+
+    /// The text of a query parameter bound to `self`, such as `42`,
+    /// `[1,2,3]` or an unquoted string.
+    fn to_param(&self) -> String {
+        let mut out = String::new();
+        self.write_param_literal(&mut out);
+        out
+    }
+}
+
+/// A Rust type that can be read from a column.
+pub trait FromSql: Sized {
+    /// Decode every row of `column`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::TypeMismatch` if the column type does not map to
+    /// `Self`.
+    fn from_column(column: &ColumnRef) -> Result<Vec<Self>>;
+}
+
+/// A struct stored as one column per field.
+///
+/// Usually derived with `#[derive(Row)]` (requires the `derive` feature).
+pub trait Row: Sized {
+    /// Column names, one per field, in field order.
+    const COLUMNS: &'static [&'static str];
+
+    /// Build a block from `rows`, with one column per field.
+    fn to_block(rows: &[Self]) -> Result<Block>;
+
+    /// Decode the rows of `block`, matching fields to columns by name.
+    fn from_block(block: &Block) -> Result<Vec<Self>>;
+
+    /// Decode the rows of every block in `result`.
+    fn from_result(result: &QueryResult) -> Result<Vec<Self>> {
+        let mut rows = Vec::with_capacity(result.total_rows());
+        for block in result.blocks() {
+            rows.extend(Self::from_block(block)?);
+        }
+        Ok(rows)
+    }
+}
+
+/// Rows of a fixed schema `S`, converted to a [`Block`] in one go.
+///
+/// Rows are pushed as values of `S`, so every row has the columns of `S`
+/// with the types of its fields. Reading goes the other way: the columns
+/// of a block must be exactly [`Row::COLUMNS`], in that order, which is
+/// checked once per block rather than per row.
+///
+/// ```ignore
+/// let mut batch = TypedBlock::<Event>::new();
+/// batch.push(event);
+/// client.insert("events", batch.to_block()?).await?;
+///
+/// let events = TypedBlock::<Event>::from_result(&result)?;
+/// ```
+pub struct TypedBlock<S> {
+    rows: Vec<S>,
+}
+
+impl<S: Row> TypedBlock<S> {
+    /// Create an empty block.
+    pub fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Create an empty block with room for `capacity` rows.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { rows: Vec::with_capacity(capacity) }
+    }
+
+    /// Append a row.
+    pub fn push(&mut self, row: S) {
+        self.rows.push(row);
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether there are no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The rows, in insertion order.
+    pub fn rows(&self) -> &[S] {
+        &self.rows
+    }
+
+    /// Take the rows out of the block.
+    pub fn into_rows(self) -> Vec<S> {
+        self.rows
+    }
+
+    /// Build the dynamic block to send, with one column per field.
+    pub fn to_block(&self) -> Result<Block> {
+        S::to_block(&self.rows)
+    }
+
+    /// Decode `block`, whose columns must be exactly `S::COLUMNS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if the column names or their order
+    /// differ from the schema, and `Error::TypeMismatch` if a column type
+    /// does not map to its field.
+    pub fn from_block(block: &Block) -> Result<Self> {
+        let names: Vec<&str> = (0..block.column_count())
+            .filter_map(|i| block.column_name(i))
+            .collect();
+        if names != S::COLUMNS {
+            return Err(Error::Validation(format!(
+                "Block columns {:?} do not match the schema {:?}",
+                names,
+                S::COLUMNS
+            )));
+        }
+        Ok(Self { rows: S::from_block(block)? })
+    }
+
+    /// Decode the rows of every block in `result`.
+    pub fn from_result(result: &QueryResult) -> Result<Self> {
+        let mut rows = Vec::with_capacity(result.total_rows());
+        for block in result.blocks() {
+            rows.extend(Self::from_block(block)?.rows);
+        }
+        Ok(Self { rows })
+    }
+}
+
+impl<S: Row> Default for TypedBlock<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Row> From<Vec<S>> for TypedBlock<S> {
+    fn from(rows: Vec<S>) -> Self {
+        Self { rows }
+    }
+}
+
+/// Look up the column `name` of `block` for a derived [`Row`]
+#[doc(hidden)]
+pub fn block_column(block: &Block, name: &str) -> Result<ColumnRef> {
+    block.column_by_name(name).ok_or_else(|| {
+        Error::Validation(format!("Block has no column named '{}'", name))
+    })
+}
+
+fn type_mismatch<T>(column: &ColumnRef) -> Error {
+    Error::TypeMismatch {
+        expected: std::any::type_name::<T>().to_string(),
+        actual: column.column_type().name(),
+    }
+}
+
+/// Decode a `LowCardinality` column by decoding its dictionary once and
+/// picking the entry of every row
+fn from_lowcardinality<T: FromSql + Clone>(
+    column: &ColumnLowCardinality,
+) -> Result<Vec<T>> {
+    let dictionary = T::from_column(&column.dictionary_ref())?;
+    (0..column.len())
+        .map(|row| {
+            column
+                .get_index(row)
+                .and_then(|index| dictionary.get(index).cloned())
+                .ok_or_else(|| {
+                    Error::Protocol(format!(
+                        "LowCardinality index of row {} is out of range",
+                        row
+                    ))
+                })
+        })
+        .collect()
+}
+
+macro_rules! impl_sql_numeric {
+    ($($type:ty),*) => {
+        $(
+            impl ToSql for $type {
+                fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+                    Ok(Arc::new(ColumnVector::<$type>::from(values)))
+                }
+
+                fn write_param_literal(&self, out: &mut String) {
+                    out.push_str(&self.to_string());
+                }
+            }
+
+            impl FromSql for $type {
+                fn from_column(column: &ColumnRef) -> Result<Vec<Self>> {
+                    let any = column.as_any();
+                    if let Some(col) = any.downcast_ref::<ColumnVector<$type>>() {
+                        return Ok(col.data().to_vec());
+                    }
+                    if let Some(col) = any.downcast_ref::<ColumnLowCardinality>() {
+                        return from_lowcardinality(col);
+                    }
+                    Err(type_mismatch::<$type>(column))
+                }
+            }
+        )*
+    };
+}
+
+impl_sql_numeric!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl ToSql for bool {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        Ok(Arc::new(ColumnBool::from(values)))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+impl FromSql for bool {
+    fn from_column(column: &ColumnRef) -> Result<Vec<Self>> {
+        column
+            .as_any()
+            .downcast_ref::<ColumnBool>()
+            .map(|col| col.iter().collect())
+            .ok_or_else(|| type_mismatch::<bool>(column))
+    }
+}
+
+impl ToSql for String {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        Ok(Arc::new(ColumnString::from(values)))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push_str(&quote_string_literal(self));
+    }
+
+    /// A top-level string parameter is sent as is.
+    fn to_param(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ToSql for &str {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        Ok(Arc::new(ColumnString::from(values)))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push_str(&quote_string_literal(self));
+    }
+
+    fn to_param(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Reads `String`, `FixedString` and `LowCardinality(String)` columns.
+impl FromSql for String {
+    fn from_column(column: &ColumnRef) -> Result<Vec<Self>> {
+        let any = column.as_any();
+        if let Some(col) = any.downcast_ref::<ColumnString>() {
+            return Ok(col.iter().map(str::to_string).collect());
+        }
+        if let Some(col) = any.downcast_ref::<ColumnFixedString>() {
+            return Ok((0..col.len()).map(|row| col.at(row)).collect());
+        }
+        if let Some(col) = any.downcast_ref::<ColumnLowCardinality>() {
+            return from_lowcardinality(col);
+        }
+        Err(type_mismatch::<String>(column))
+    }
+}
+
+/// Writes a `Nullable` column, storing `T::default()` under each NULL.
+impl<T: ToSql + Default> ToSql for Option<T> {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        let nulls: Vec<u8> =
+            values.iter().map(|value| u8::from(value.is_none())).collect();
+        let nested = T::to_column(
+            values.into_iter().map(Option::unwrap_or_default).collect(),
+        )?;
+        Ok(Arc::new(ColumnNullable::from_parts(
+            nested,
+            Arc::new(ColumnUInt8::from(nulls)),
+        )?))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        match self {
+            Some(value) => value.write_param_literal(out),
+            None => out.push_str("NULL"),
+        }
+    }
+
+    /// A top-level NULL is sent as `\N`.
+    fn to_param(&self) -> String {
+        match self {
+            Some(value) => value.to_param(),
+            None => "\\N".to_string(),
+        }
+    }
+}
+
+/// Reads `Nullable` and `LowCardinality(Nullable)` columns.
+impl<T: FromSql + Clone> FromSql for Option<T> {
+    fn from_column(column: &ColumnRef) -> Result<Vec<Self>> {
+        let any = column.as_any();
+        if let Some(col) = any.downcast_ref::<ColumnNullable>() {
+            let values = T::from_column(&col.nested_ref())?;
+            return Ok(values
+                .into_iter()
+                .enumerate()
+                .map(|(row, value)| (!col.is_null(row)).then_some(value))
+                .collect());
+        }
+        if let Some(col) = any.downcast_ref::<ColumnLowCardinality>() {
+            return from_lowcardinality(col);
+        }
+        Err(type_mismatch::<Option<T>>(column))
+    }
+}
+
+/// Writes an `Array` column.
+impl<T: ToSql> ToSql for Vec<T> {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        let lengths: Vec<u64> =
+            values.iter().map(|value| value.len() as u64).collect();
+        let nested = T::to_column(values.into_iter().flatten().collect())?;
+        let mut array = ColumnArray::with_nested(nested);
+        for len in lengths {
+            array.append_len(len);
+        }
+        Ok(Arc::new(array))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push('[');
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            value.write_param_literal(out);
+        }
+        out.push(']');
+    }
+}
+
+/// Reads `Array` columns.
+impl<T: FromSql> FromSql for Vec<T> {
+    fn from_column(column: &ColumnRef) -> Result<Vec<Self>> {
+        let col = column
+            .as_any()
+            .downcast_ref::<ColumnArray>()
+            .ok_or_else(|| type_mismatch::<Vec<T>>(column))?;
+        let mut elements = T::from_column(&col.nested_ref())?.into_iter();
+        let mut previous = 0;
+        col.offsets()
+            .iter()
+            .map(|&offset| {
+                let len = (offset - previous) as usize;
+                previous = offset;
+                let array: Vec<T> = elements.by_ref().take(len).collect();
+                if array.len() != len {
+                    return Err(Error::Protocol(
+                        "Array offsets exceed its elements".to_string(),
+                    ));
+                }
+                Ok(array)
+            })
+            .collect()
+    }
+}
+
+/// Writes a `Date` column; parameters are written as `YYYY-MM-DD`.
+#[cfg(feature = "chrono")]
+impl ToSql for chrono::NaiveDate {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        let mut column =
+            crate::column::ColumnDate::new(crate::types::Type::date());
+        for value in values {
+            column.append_date(value);
+        }
+        Ok(Arc::new(column))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push_str(&quote_string_literal(&self.to_param()));
+    }
+
+    fn to_param(&self) -> String {
+        self.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Writes a `DateTime` column; parameters are written as a Unix timestamp
+/// so the server time zone does not shift them.
+#[cfg(feature = "chrono")]
+impl ToSql for chrono::DateTime<chrono::Utc> {
+    fn to_column(values: Vec<Self>) -> Result<ColumnRef> {
+        let mut column = crate::column::ColumnDateTime::new(
+            crate::types::Type::datetime(None),
+        );
+        for value in values {
+            column.append_datetime(value);
+        }
+        Ok(Arc::new(column))
+    }
+
+    fn write_param_literal(&self, out: &mut String) {
+        out.push_str(&quote_string_literal(&self.to_param()));
+    }
+
+    fn to_param(&self) -> String {
+        self.timestamp().to_string()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::{
+        column::{
+            column_value::ColumnValue,
+            Column,
+        },
+        types::Type,
+    };
+    use bytes::{
+        BufMut,
+        BytesMut,
+    };
+
+    #[test]
+    fn test_vec_roundtrip() {
+        let values = vec![vec![1u32, 2], vec![], vec![3]];
+        let column = Vec::<u32>::to_column(values.clone()).unwrap();
+        assert_eq!(column.column_type(), &Type::array(Type::uint32()));
+        assert_eq!(Vec::<u32>::from_column(&column).unwrap(), values);
+    }
+
+    #[test]
+    fn test_numeric_and_string_roundtrip() {
+        let column = u64::to_column(vec![1, 2, 3]).unwrap();
+        assert_eq!(column.column_type(), &Type::uint64());
+        assert_eq!(u64::from_column(&column).unwrap(), [1, 2, 3]);
+
+        let column = String::to_column(vec!["a".into(), "b".into()]).unwrap();
+        assert_eq!(String::from_column(&column).unwrap(), ["a", "b"]);
+
+        let column = bool::to_column(vec![true, false]).unwrap();
+        assert_eq!(bool::from_column(&column).unwrap(), [true, false]);
+
+        assert!(matches!(
+            i32::from_column(&column),
+            Err(Error::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_option_maps_to_nullable() {
+        let values = vec![Some(-1i64), None, Some(3)];
+        let column = Option::<i64>::to_column(values.clone()).unwrap();
+        assert_eq!(column.column_type(), &Type::nullable(Type::int64()));
+        assert_eq!(Option::<i64>::from_column(&column).unwrap(), values);
+
+        // A non-nullable column is not silently accepted
+        let column = i64::to_column(vec![1]).unwrap();
+        assert!(Option::<i64>::from_column(&column).is_err());
+    }
+
+    #[test]
+    fn test_strings_from_lowcardinality() {
+        let mut col = ColumnLowCardinality::new(Type::LowCardinality {
+            nested_type: Box::new(Type::string()),
+        });
+        for status in ["active", "inactive", "active"] {
+            col.append_unsafe(&ColumnValue::from_string(status)).unwrap();
+        }
+        let column: ColumnRef = Arc::new(col);
+        assert_eq!(
+            String::from_column(&column).unwrap(),
+            ["active", "inactive", "active"]
+        );
+
+        // LowCardinality(Nullable(String)) as the server sends it, with the
+        // NULL key at dictionary index 0
+        let mut buffer = BytesMut::new();
+        buffer.put_u64_le(1); // key_version
+        buffer.put_u64_le(1 << 9); // UInt8 indices, additional keys
+        buffer.put_u64_le(2); // number_of_keys
+        for key in ["", "US"] {
+            buffer.put_u8(key.len() as u8);
+            buffer.put_slice(key.as_bytes());
+        }
+        buffer.put_u64_le(2); // number_of_rows
+        buffer.put_slice(&[1, 0]);
+
+        let mut col = ColumnLowCardinality::new(Type::LowCardinality {
+            nested_type: Box::new(Type::nullable(Type::string())),
+        });
+        let mut slice = &buffer[..];
+        col.load_prefix(&mut slice, 2).unwrap();
+        col.load_from_buffer(&mut slice, 2).unwrap();
+        let column: ColumnRef = Arc::new(col);
+        assert_eq!(
+            Option::<String>::from_column(&column).unwrap(),
+            [Some("US".to_string()), None]
+        );
+    }
+}
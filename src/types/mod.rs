@@ -11,7 +11,7 @@
 //! - [Floating-Point Types](https://clickhouse.com/docs/en/sql-reference/data-types/float)
 //!   - Float32, Float64
 //! - [Decimal Types](https://clickhouse.com/docs/en/sql-reference/data-types/decimal)
-//!   - Decimal, Decimal32/64/128
+//!   - Decimal, Decimal32/64/128/256
 //!
 //! ### String Types
 //! - [String](https://clickhouse.com/docs/en/sql-reference/data-types/string)
@@ -28,6 +28,8 @@
 //!   - Unix timestamp (UInt32)
 //! - [DateTime64](https://clickhouse.com/docs/en/sql-reference/data-types/datetime64)
 //!   - High precision timestamp (Int64)
+//! - [Interval](https://clickhouse.com/docs/en/sql-reference/data-types/special-data-types/interval)
+//!   - IntervalSecond, IntervalDay, ... (Int64 count of the unit)
 //!
 //! ### Compound Types
 //! - [Array](https://clickhouse.com/docs/en/sql-reference/data-types/array) -
@@ -139,6 +141,12 @@ impl ToType for i128 {
     }
 }
 
+impl ToType for bool {
+    fn to_type() -> Type {
+        Type::bool()
+    }
+}
+
 impl ToType for u8 {
     fn to_type() -> Type {
         Type::uint8()
@@ -198,7 +206,7 @@ pub enum TypeCode {
     Int32,
     /// Signed 64-bit integer.
     Int64,
-    /// Unsigned 8-bit integer (0 to 255), also used as Bool.
+    /// Unsigned 8-bit integer (0 to 255).
     UInt8,
     /// Unsigned 16-bit integer (0 to 65535).
     UInt16,
@@ -263,6 +271,20 @@ pub enum TypeCode {
     Polygon,
     /// Collection of polygons as Array(Polygon).
     MultiPolygon,
+    /// Boolean stored as one byte (0 = false, 1 = true).
+    Bool,
+    /// Signed 256-bit integer.
+    Int256,
+    /// Unsigned 256-bit integer.
+    UInt256,
+    /// Decimal with up to 76 digits of precision (stored as Int256).
+    Decimal256,
+    /// Time interval (`IntervalSecond`, `IntervalDay`, ...) stored as Int64.
+    Interval,
+    /// JSON document, exchanged as its text like a String.
+    Json,
+    /// Named parallel arrays, stored as `Array(Tuple(...))`.
+    Nested,
 }
 
 impl TypeCode {
@@ -306,10 +328,82 @@ impl TypeCode {
             TypeCode::Ring => "Ring",
             TypeCode::Polygon => "Polygon",
             TypeCode::MultiPolygon => "MultiPolygon",
+            TypeCode::Bool => "Bool",
+            TypeCode::Int256 => "Int256",
+            TypeCode::UInt256 => "UInt256",
+            TypeCode::Decimal256 => "Decimal256",
+            TypeCode::Interval => "Interval",
+            TypeCode::Json => "JSON",
+            TypeCode::Nested => "Nested",
         }
     }
 }
 
+/// Unit of an `Interval*` type, e.g. `Day` for `IntervalDay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalUnit {
+    /// `IntervalNanosecond`
+    Nanosecond,
+    /// `IntervalMicrosecond`
+    Microsecond,
+    /// `IntervalMillisecond`
+    Millisecond,
+    /// `IntervalSecond`
+    Second,
+    /// `IntervalMinute`
+    Minute,
+    /// `IntervalHour`
+    Hour,
+    /// `IntervalDay`
+    Day,
+    /// `IntervalWeek`
+    Week,
+    /// `IntervalMonth`
+    Month,
+    /// `IntervalQuarter`
+    Quarter,
+    /// `IntervalYear`
+    Year,
+}
+
+impl IntervalUnit {
+    /// Returns the unit name as used in the type name, e.g. `"Day"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntervalUnit::Nanosecond => "Nanosecond",
+            IntervalUnit::Microsecond => "Microsecond",
+            IntervalUnit::Millisecond => "Millisecond",
+            IntervalUnit::Second => "Second",
+            IntervalUnit::Minute => "Minute",
+            IntervalUnit::Hour => "Hour",
+            IntervalUnit::Day => "Day",
+            IntervalUnit::Week => "Week",
+            IntervalUnit::Month => "Month",
+            IntervalUnit::Quarter => "Quarter",
+            IntervalUnit::Year => "Year",
+        }
+    }
+
+    /// Returns the unit of an `Interval*` type name such as `"IntervalDay"`.
+    pub fn from_type_name(type_name: &str) -> Option<Self> {
+        let unit = match type_name.strip_prefix("Interval")? {
+            "Nanosecond" => IntervalUnit::Nanosecond,
+            "Microsecond" => IntervalUnit::Microsecond,
+            "Millisecond" => IntervalUnit::Millisecond,
+            "Second" => IntervalUnit::Second,
+            "Minute" => IntervalUnit::Minute,
+            "Hour" => IntervalUnit::Hour,
+            "Day" => IntervalUnit::Day,
+            "Week" => IntervalUnit::Week,
+            "Month" => IntervalUnit::Month,
+            "Quarter" => IntervalUnit::Quarter,
+            "Year" => IntervalUnit::Year,
+            _ => return None,
+        };
+        Some(unit)
+    }
+}
+
 /// Enum item for Enum8/Enum16 types, mapping a name to its integer value.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumItem {
@@ -386,6 +480,19 @@ pub enum Type {
         /// The type of map values.
         value_type: Box<Type>,
     },
+    /// Named sub-fields of equal-length arrays (`Nested(a T1, b T2)`),
+    /// stored as `Array(Tuple(T1, T2))`.
+    Nested {
+        /// The name of each sub-field.
+        names: Vec<String>,
+        /// The element type of each sub-field, in the order of `names`.
+        item_types: Vec<Type>,
+    },
+    /// Time interval with the given unit, stored as Int64.
+    Interval {
+        /// The unit the stored count is expressed in.
+        unit: IntervalUnit,
+    },
 }
 
 impl Type {
@@ -404,6 +511,8 @@ impl Type {
             Type::Tuple { .. } => TypeCode::Tuple,
             Type::LowCardinality { .. } => TypeCode::LowCardinality,
             Type::Map { .. } => TypeCode::Map,
+            Type::Nested { .. } => TypeCode::Nested,
+            Type::Interval { .. } => TypeCode::Interval,
         }
     }
 
@@ -448,6 +557,15 @@ impl Type {
             Type::Map { key_type, value_type } => {
                 format!("Map({}, {})", key_type.name(), value_type.name())
             }
+            Type::Nested { names, item_types } => {
+                let fields: Vec<String> = names
+                    .iter()
+                    .zip(item_types)
+                    .map(|(name, t)| format!("{} {}", name, t.name()))
+                    .collect();
+                format!("Nested({})", fields.join(", "))
+            }
+            Type::Interval { unit } => format!("Interval{}", unit.name()),
         }
     }
 
@@ -469,7 +587,7 @@ impl Type {
     pub fn storage_size_bytes(&self) -> Option<usize> {
         match self {
             Type::Simple(code) => match code {
-                TypeCode::Int8 | TypeCode::UInt8 => Some(1),
+                TypeCode::Int8 | TypeCode::UInt8 | TypeCode::Bool => Some(1),
                 TypeCode::Int16 | TypeCode::UInt16 => Some(2),
                 TypeCode::Int32 | TypeCode::UInt32 | TypeCode::Float32 => {
                     Some(4)
@@ -480,6 +598,7 @@ impl Type {
                 TypeCode::Int128 | TypeCode::UInt128 | TypeCode::UUID => {
                     Some(16)
                 }
+                TypeCode::Int256 | TypeCode::UInt256 => Some(32),
                 TypeCode::Date => Some(2),   // UInt16
                 TypeCode::Date32 => Some(4), // Int32
                 TypeCode::IPv4 => Some(4),
@@ -493,14 +612,17 @@ impl Type {
             Type::DateTime64 { .. } => Some(8), // Int64 timestamp
             Type::Enum8 { .. } => Some(1),    // Stored as Int8
             Type::Enum16 { .. } => Some(2),   // Stored as Int16
+            Type::Interval { .. } => Some(8), // Stored as Int64
             Type::Decimal { precision, .. } => {
                 // Decimal storage depends on precision
                 if *precision <= 9 {
                     Some(4) // Decimal32
                 } else if *precision <= 18 {
                     Some(8) // Decimal64
-                } else {
+                } else if *precision <= 38 {
                     Some(16) // Decimal128
+                } else {
+                    Some(32) // Decimal256
                 }
             }
             // Complex types don't have fixed storage size
@@ -508,7 +630,8 @@ impl Type {
             | Type::Nullable { .. }
             | Type::Tuple { .. }
             | Type::LowCardinality { .. }
-            | Type::Map { .. } => None,
+            | Type::Map { .. }
+            | Type::Nested { .. } => None,
         }
     }
 
@@ -537,11 +660,21 @@ impl Type {
         Type::Simple(TypeCode::Int128)
     }
 
+    /// Creates an Int256 type.
+    pub fn int256() -> Self {
+        Type::Simple(TypeCode::Int256)
+    }
+
     /// Creates a UInt8 type.
     pub fn uint8() -> Self {
         Type::Simple(TypeCode::UInt8)
     }
 
+    /// Creates a Bool type.
+    pub fn bool() -> Self {
+        Type::Simple(TypeCode::Bool)
+    }
+
     /// Creates a UInt16 type.
     pub fn uint16() -> Self {
         Type::Simple(TypeCode::UInt16)
@@ -562,6 +695,11 @@ impl Type {
         Type::Simple(TypeCode::UInt128)
     }
 
+    /// Creates a UInt256 type.
+    pub fn uint256() -> Self {
+        Type::Simple(TypeCode::UInt256)
+    }
+
     /// Creates a Float32 type.
     pub fn float32() -> Self {
         Type::Simple(TypeCode::Float32)
@@ -577,6 +715,11 @@ impl Type {
         Type::Simple(TypeCode::String)
     }
 
+    /// Creates a JSON type.
+    pub fn json() -> Self {
+        Type::Simple(TypeCode::Json)
+    }
+
     /// Creates a FixedString type with the given size in bytes.
     pub fn fixed_string(size: usize) -> Self {
         Type::FixedString { size }
@@ -661,6 +804,26 @@ impl Type {
         }
     }
 
+    /// Creates a Nested type from `(name, type)` sub-fields.
+    pub fn nested(fields: Vec<(String, Type)>) -> Self {
+        let (names, item_types) = fields.into_iter().unzip();
+        Type::Nested { names, item_types }
+    }
+
+    /// The `Array(Tuple(...))` layout a `Nested` type is stored as.
+    ///
+    /// Returns `None` for any other type.
+    pub fn nested_storage_type(&self) -> Option<Type> {
+        match self {
+            Type::Nested { item_types, .. } => Some(Type::Array {
+                item_type: Box::new(Type::Tuple {
+                    item_types: item_types.clone(),
+                }),
+            }),
+            _ => None,
+        }
+    }
+
     /// Returns true if this enum type contains a variant with the given
     /// integer value.
     pub fn has_enum_value(&self, value: i16) -> bool {
@@ -750,6 +913,11 @@ impl Type {
         Type::Simple(TypeCode::MultiPolygon)
     }
 
+    /// Creates an Interval type with the given unit.
+    pub fn interval(unit: IntervalUnit) -> Self {
+        Type::Interval { unit }
+    }
+
     /// Creates a Nothing/Void type, used for NULL-only columns.
     pub fn nothing() -> Self {
         Type::Simple(TypeCode::Void)
@@ -778,20 +946,41 @@ impl Type {
             TypeMeta::Terminal => {
                 // Simple terminal types
                 match ast.code {
+                    // Unknown names also map to Void; only `Void` and
+                    // `Nothing` really are
+                    // `Array()`, `Tuple(,)` and similar
+                    TypeCode::Void if ast.name.is_empty() => Err(
+                        crate::Error::Protocol("Empty type parameter".into()),
+                    ),
+
+                    TypeCode::Void
+                        if !ast.name.is_empty()
+                            && !ast.name.eq_ignore_ascii_case("void")
+                            && ast.name != "Nothing" =>
+                    {
+                        Err(crate::Error::UnsupportedType {
+                            type_string: ast.name.clone(),
+                        })
+                    }
+
                     TypeCode::Void
                     | TypeCode::Int8
                     | TypeCode::Int16
                     | TypeCode::Int32
                     | TypeCode::Int64
                     | TypeCode::Int128
+                    | TypeCode::Int256
                     | TypeCode::UInt8
+                    | TypeCode::Bool
                     | TypeCode::UInt16
                     | TypeCode::UInt32
                     | TypeCode::UInt64
                     | TypeCode::UInt128
+                    | TypeCode::UInt256
                     | TypeCode::Float32
                     | TypeCode::Float64
                     | TypeCode::String
+                    | TypeCode::Json
                     | TypeCode::Date
                     | TypeCode::Date32
                     | TypeCode::UUID
@@ -802,6 +991,14 @@ impl Type {
                     | TypeCode::Polygon
                     | TypeCode::MultiPolygon => Ok(Type::Simple(ast.code)),
 
+                    TypeCode::Interval => {
+                        IntervalUnit::from_type_name(&ast.name)
+                            .map(|unit| Type::Interval { unit })
+                            .ok_or_else(|| crate::Error::UnsupportedType {
+                                type_string: ast.name.clone(),
+                            })
+                    }
+
                     TypeCode::FixedString => {
                         // First element should be the size (Number)
                         if ast.elements.is_empty() {
@@ -810,7 +1007,7 @@ impl Type {
                                     .to_string(),
                             ));
                         }
-                        let size = ast.elements[0].value as usize;
+                        let size = number_param(&ast.elements[0], "size")?;
                         Ok(Type::FixedString { size })
                     }
 
@@ -833,7 +1030,8 @@ impl Type {
                                     .to_string(),
                             ));
                         }
-                        let precision = ast.elements[0].value as usize;
+                        let precision =
+                            number_param(&ast.elements[0], "precision")?;
                         let timezone = if ast.elements.len() > 1 {
                             Some(ast.elements[1].value_string.clone())
                         } else {
@@ -845,20 +1043,27 @@ impl Type {
                     TypeCode::Decimal
                     | TypeCode::Decimal32
                     | TypeCode::Decimal64
-                    | TypeCode::Decimal128 => {
+                    | TypeCode::Decimal128
+                    | TypeCode::Decimal256 => {
                         if ast.elements.len() >= 2 {
-                            let precision = ast.elements[0].value as usize;
-                            let scale = ast.elements[1].value as usize;
+                            let precision =
+                                number_param(&ast.elements[0], "precision")?;
+                            let scale =
+                                number_param(&ast.elements[1], "scale")?;
                             Ok(Type::Decimal { precision, scale })
                         } else if ast.elements.len() == 1 {
-                            // For Decimal32/64/128, scale may default to the
-                            // last element
-                            let scale = ast.elements[0].value as usize;
-                            let precision = match ast.code {
-                                TypeCode::Decimal32 => 9,
-                                TypeCode::Decimal64 => 18,
-                                TypeCode::Decimal128 => 38,
-                                _ => scale,
+                            // Decimal32/64/128/256(S) take the scale, while
+                            // Decimal(P) takes the precision with scale 0
+                            let param = number_param(
+                                &ast.elements[0],
+                                "precision or scale",
+                            )?;
+                            let (precision, scale) = match ast.code {
+                                TypeCode::Decimal32 => (9, param),
+                                TypeCode::Decimal64 => (18, param),
+                                TypeCode::Decimal128 => (38, param),
+                                TypeCode::Decimal256 => (76, param),
+                                _ => (param, 0),
                             };
                             Ok(Type::Decimal { precision, scale })
                         } else {
@@ -868,10 +1073,9 @@ impl Type {
                         }
                     }
 
-                    _ => Err(crate::Error::Protocol(format!(
-                        "Unsupported terminal type: {:?}",
-                        ast.code
-                    ))),
+                    _ => Err(crate::Error::UnsupportedType {
+                        type_string: ast.name.clone(),
+                    }),
                 }
             }
 
@@ -896,6 +1100,12 @@ impl Type {
             }
 
             TypeMeta::Tuple => {
+                // `Tuple()` is the empty tuple
+                if let [only] = ast.elements.as_slice() {
+                    if *only == TypeAst::default() {
+                        return Ok(Type::Tuple { item_types: Vec::new() });
+                    }
+                }
                 let mut item_types = Vec::new();
                 for elem in &ast.elements {
                     item_types.push(Type::from_ast(elem)?);
@@ -906,14 +1116,43 @@ impl Type {
             TypeMeta::Enum => {
                 // Enum elements are stored as: name1, value1, name2, value2,
                 // ...
+                let range = match ast.code {
+                    TypeCode::Enum8 => i8::MIN as i64..=i8::MAX as i64,
+                    _ => i16::MIN as i64..=i16::MAX as i64,
+                };
+                if ast.elements.is_empty() {
+                    return Err(crate::Error::Protocol(format!(
+                        "{} requires at least one item",
+                        ast.name
+                    )));
+                }
                 let mut items = Vec::new();
-                for i in (0..ast.elements.len()).step_by(2) {
-                    if i + 1 >= ast.elements.len() {
-                        break;
+                for pair in ast.elements.chunks(2) {
+                    let [name, value] = pair else {
+                        return Err(crate::Error::Protocol(format!(
+                            "Enum item without value in {}",
+                            ast.name
+                        )));
+                    };
+                    if name.meta != TypeMeta::String
+                        || value.meta != TypeMeta::Number
+                    {
+                        return Err(crate::Error::Protocol(format!(
+                            "Invalid enum item format (expected 'name' = \
+                             value) in {}",
+                            ast.name
+                        )));
+                    }
+                    if !range.contains(&value.value) {
+                        return Err(crate::Error::Protocol(format!(
+                            "Enum value {} out of range for {}",
+                            value.value, ast.name
+                        )));
                     }
-                    let name = ast.elements[i].value_string.clone();
-                    let value = ast.elements[i + 1].value as i16;
-                    items.push(EnumItem { name, value });
+                    items.push(EnumItem {
+                        name: name.value_string.clone(),
+                        value: value.value as i16,
+                    });
                 }
 
                 match ast.code {
@@ -950,6 +1189,22 @@ impl Type {
                 })
             }
 
+            TypeMeta::Nested => {
+                if ast.elements.iter().any(|elem| elem.field_name.is_empty()) {
+                    return Err(crate::Error::Protocol(format!(
+                        "Nested fields must be named: {}",
+                        ast.name
+                    )));
+                }
+                let mut names = Vec::new();
+                let mut item_types = Vec::new();
+                for elem in &ast.elements {
+                    names.push(elem.field_name.clone());
+                    item_types.push(Type::from_ast(elem)?);
+                }
+                Ok(Type::Nested { names, item_types })
+            }
+
             TypeMeta::SimpleAggregateFunction => {
                 // SimpleAggregateFunction(func, Type) -> unwrap to Type
                 // Last element is the actual type
@@ -1073,7 +1328,7 @@ impl Type {
                     })?;
                     Ok(Type::decimal(precision, scale))
                 }
-                "Decimal32" | "Decimal64" | "Decimal128" => {
+                "Decimal32" | "Decimal64" | "Decimal128" | "Decimal256" => {
                     // Decimal32(7) - single precision parameter, scale
                     // defaults to 0
                     let precision =
@@ -1144,14 +1399,13 @@ impl Type {
                     // contain internal aggregation state which requires
                     // specialized deserialization logic for each aggregate
                     // function
-                    Err(crate::Error::Protocol(
-                        "AggregateFunction columns are not supported. Use SimpleAggregateFunction or finalize the aggregation with -State combinators.".to_string()
-                    ))
+                    Err(crate::Error::UnsupportedType {
+                        type_string: type_str.to_string(),
+                    })
                 }
-                _ => Err(crate::Error::Protocol(format!(
-                    "Unknown parametric type: {}",
-                    type_name
-                ))),
+                _ => Err(crate::Error::UnsupportedType {
+                    type_string: type_str.to_string(),
+                }),
             };
         }
 
@@ -1162,11 +1416,13 @@ impl Type {
             "UInt32" => Ok(Type::uint32()),
             "UInt64" => Ok(Type::uint64()),
             "UInt128" => Ok(Type::Simple(TypeCode::UInt128)),
+            "UInt256" => Ok(Type::uint256()),
             "Int8" => Ok(Type::int8()),
             "Int16" => Ok(Type::int16()),
             "Int32" => Ok(Type::int32()),
             "Int64" => Ok(Type::int64()),
             "Int128" => Ok(Type::Simple(TypeCode::Int128)),
+            "Int256" => Ok(Type::int256()),
             "Float32" => Ok(Type::float32()),
             "Float64" => Ok(Type::float64()),
             "String" => Ok(Type::string()),
@@ -1176,22 +1432,37 @@ impl Type {
             "UUID" => Ok(Type::uuid()),
             "IPv4" => Ok(Type::ipv4()),
             "IPv6" => Ok(Type::ipv6()),
-            "Bool" => Ok(Type::uint8()), // Bool is an alias for UInt8
+            "Bool" => Ok(Type::bool()),
             "Nothing" => Ok(Type::Simple(TypeCode::Void)), /* Nothing type for NULL columns */
             "Point" => Ok(Type::point()), // Point is Tuple(Float64, Float64)
             "Ring" => Ok(Type::ring()),   // Ring is Array(Point)
             "Polygon" => Ok(Type::polygon()), // Polygon is Array(Ring)
             "MultiPolygon" => Ok(Type::multi_polygon()), /* MultiPolygon is Array(Polygon) */
-            _ => Err(crate::Error::Protocol(format!(
-                "Unknown type: {}",
-                type_str
-            ))),
+            _ if IntervalUnit::from_type_name(type_str).is_some() => {
+                Ok(Type::Interval {
+                    unit: IntervalUnit::from_type_name(type_str).unwrap(),
+                })
+            }
+            _ => Err(crate::Error::UnsupportedType {
+                type_string: type_str.to_string(),
+            }),
         }
     }
 }
 
 // Helper functions for type parsing
 
+/// Read a non-negative numeric type parameter such as a size or precision
+fn number_param(ast: &TypeAst, what: &str) -> crate::Result<usize> {
+    if ast.meta != TypeMeta::Number || ast.value < 0 {
+        return Err(crate::Error::Protocol(format!(
+            "Expected a non-negative number for {}",
+            what
+        )));
+    }
+    Ok(ast.value as usize)
+}
+
 /// Parse a string literal from 'quoted' or "quoted" format
 fn parse_string_literal(s: &str) -> crate::Result<String> {
     let s = s.trim();
@@ -1253,27 +1524,44 @@ fn parse_comma_separated(s: &str) -> crate::Result<Vec<String>> {
     Ok(params)
 }
 
+/// Byte position of the last `=` that is not inside a quoted string
+fn last_unquoted_eq(s: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut last = None;
+    for (i, ch) in s.char_indices() {
+        match quote {
+            _ if escaped => escaped = false,
+            Some(_) if ch == '\\' => escaped = true,
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '\'' || ch == '"' => quote = Some(ch),
+            None if ch == '=' => last = Some(i),
+            None => {}
+        }
+    }
+    last
+}
+
 /// Parse enum items from string like "'red' = 1, 'green' = 2, 'blue' = 3"
 fn parse_enum_items(s: &str) -> crate::Result<Vec<EnumItem>> {
     let mut items = Vec::new();
     let parts = parse_comma_separated(s)?;
 
     for part in parts {
-        // Each part should be 'name' = value
-        let eq_parts: Vec<&str> = part.split('=').collect();
-        if eq_parts.len() != 2 {
+        // Each part should be 'name' = value; the name may itself contain
+        // '=', so split on the last one outside quotes
+        let Some(eq_pos) = last_unquoted_eq(&part) else {
             return Err(crate::Error::Protocol(format!(
                 "Invalid enum item format (expected 'name' = value): {}",
                 part
             )));
-        }
+        };
+        let (name, value) = (&part[..eq_pos], &part[eq_pos + 1..]);
 
-        let name = parse_string_literal(eq_parts[0].trim())?;
-        let value = eq_parts[1].trim().parse::<i16>().map_err(|_| {
-            crate::Error::Protocol(format!(
-                "Invalid enum value: {}",
-                eq_parts[1]
-            ))
+        let name = parse_string_literal(name.trim())?;
+        let value = value.trim().parse::<i16>().map_err(|_| {
+            crate::Error::Protocol(format!("Invalid enum value: {}", value))
         })?;
 
         items.push(EnumItem { name, value });
@@ -1321,6 +1609,11 @@ impl PartialEq for Type {
                 Type::Map { key_type: k_a, value_type: v_a },
                 Type::Map { key_type: k_b, value_type: v_b },
             ) => k_a == k_b && v_a == v_b,
+            (
+                Type::Nested { names: n_a, item_types: t_a },
+                Type::Nested { names: n_b, item_types: t_b },
+            ) => n_a == n_b && t_a == t_b,
+            (Type::Interval { unit: a }, Type::Interval { unit: b }) => a == b,
             _ => false,
         }
     }
@@ -1334,7 +1627,13 @@ pub type TypeRef = Arc<Type>;
 fn format_enum_items(items: &[EnumItem]) -> String {
     let formatted: Vec<String> = items
         .iter()
-        .map(|item| format!("'{}' = {}", item.name, item.value))
+        .map(|item| {
+            format!(
+                "{} = {}",
+                crate::query::quote_string_literal(&item.name),
+                item.value
+            )
+        })
         .collect();
     formatted.join(", ")
 }
@@ -1344,6 +1643,19 @@ fn format_enum_items(items: &[EnumItem]) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_enum_items_splits_on_last_unquoted_eq() {
+        let items = parse_enum_items("'a=b' = 1, 'x' = -2").unwrap();
+        assert_eq!(
+            items,
+            vec![
+                EnumItem { name: "a=b".to_string(), value: 1 },
+                EnumItem { name: "x".to_string(), value: -2 },
+            ]
+        );
+        assert!(parse_enum_items("'a=b'").is_err());
+    }
+
     #[test]
     fn test_type_code_name() {
         assert_eq!(TypeCode::Int32.name(), "Int32");
@@ -1356,6 +1668,27 @@ mod tests {
         assert_eq!(Type::int32().name(), "Int32");
         assert_eq!(Type::uint64().name(), "UInt64");
         assert_eq!(Type::string().name(), "String");
+        assert_eq!(Type::json().name(), "JSON");
+        assert_eq!(Type::parse("JSON").unwrap(), Type::json());
+    }
+
+    #[test]
+    fn test_nested_type() {
+        let t =
+            Type::parse("Nested(name String, value Array(UInt64))").unwrap();
+        assert_eq!(
+            t,
+            Type::nested(vec![
+                ("name".to_string(), Type::string()),
+                ("value".to_string(), Type::array(Type::uint64())),
+            ])
+        );
+        assert_eq!(t.name(), "Nested(name String, value Array(UInt64))");
+        assert_eq!(
+            t.nested_storage_type().unwrap().name(),
+            "Array(Tuple(String, Array(UInt64)))"
+        );
+        assert!(Type::parse("Nested(String, UInt64)").is_err());
     }
 
     #[test]
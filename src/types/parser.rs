@@ -5,7 +5,10 @@
 //!
 //! **Reference:** `cpp/clickhouse-cpp/clickhouse/types/type_parser.{h,cpp}`
 
-use super::TypeCode;
+use super::{
+    IntervalUnit,
+    TypeCode,
+};
 use crate::{
     Error,
     Result,
@@ -68,6 +71,8 @@ pub enum TypeMeta {
     SimpleAggregateFunction,
     /// Key-value map type (`Map(K, V)`).
     Map,
+    /// Nested structure (`Nested(name1 T1, name2 T2, ...)`).
+    Nested,
 }
 
 /// Abstract Syntax Tree for a type definition
@@ -86,6 +91,8 @@ pub struct TypeAst {
     pub value_string: String,
     /// Sub-elements of the type (for composite types, enum items)
     pub elements: Vec<TypeAst>,
+    /// Field name preceding the type inside `Tuple`/`Nested` (`name T`)
+    pub field_name: String,
 }
 
 impl Default for TypeAst {
@@ -97,6 +104,7 @@ impl Default for TypeAst {
             value: 0,
             value_string: String::new(),
             elements: Vec::new(),
+            field_name: String::new(),
         }
     }
 }
@@ -148,29 +156,37 @@ impl<'a> TypeParser<'a> {
                         let current = self.current_type.unwrap();
                         (*current).meta = TypeMeta::String; // Use String meta for quoted strings
                                                             // Remove quotes from value
-                        if token.value.len() >= 2 {
-                            (*current).value_string = token.value
-                                [1..token.value.len() - 1]
-                                .to_string();
-                        } else {
-                            (*current).value_string = String::new();
-                        }
+                        (*current).value_string = unescape_quoted(
+                            &token.value[1..token.value.len() - 1],
+                        );
                         (*current).code = TypeCode::String;
                     }
                 }
 
                 TokenType::Name => unsafe {
                     let current = self.current_type.unwrap();
+                    // A second name in one element means the first was a
+                    // field name, as in `Nested(id UInt64)`
+                    let node = &mut *current;
+                    if !node.name.is_empty() && node.elements.is_empty() {
+                        node.field_name = std::mem::take(&mut node.name);
+                    }
                     (*current).meta = get_type_meta(token.value);
                     (*current).name = token.value.to_string();
                     (*current).code = get_type_code(token.value);
                 },
 
-                TokenType::Number => unsafe {
-                    let current = self.current_type.unwrap();
-                    (*current).meta = TypeMeta::Number;
-                    (*current).value = token.value.parse::<i64>().unwrap_or(0);
-                },
+                TokenType::Number => {
+                    // Out-of-range or lone '-' numbers are malformed
+                    let Ok(value) = token.value.parse::<i64>() else {
+                        return false;
+                    };
+                    unsafe {
+                        let current = self.current_type.unwrap();
+                        (*current).meta = TypeMeta::Number;
+                        (*current).value = value;
+                    }
+                }
 
                 TokenType::String => unsafe {
                     let current = self.current_type.unwrap();
@@ -192,23 +208,26 @@ impl<'a> TypeParser<'a> {
                 }
 
                 TokenType::RPar => {
-                    self.open_elements.pop();
-                    if let Some(&parent) = self.open_elements.last() {
-                        self.current_type = Some(parent);
+                    // The root element is never popped, so an extra ')'
+                    // leaves the stack unbalanced and fails at Eos
+                    if self.open_elements.len() <= 1 {
+                        return false;
                     }
+                    self.current_type = self.open_elements.pop();
                 }
 
                 TokenType::Assign | TokenType::Comma => {
-                    self.open_elements.pop();
-                    if let Some(&parent) = self.open_elements.last() {
-                        unsafe {
-                            (*parent).elements.push(TypeAst::default());
-                            self.open_elements.push(parent);
-                            let last_idx = (*parent).elements.len() - 1;
-                            let elements_ptr = (*parent).elements.as_mut_ptr();
-                            let new_current = elements_ptr.add(last_idx);
-                            self.current_type = Some(new_current);
-                        }
+                    // Start a new sibling inside the innermost open element
+                    if self.open_elements.len() <= 1 {
+                        return false;
+                    }
+                    let parent = *self.open_elements.last().unwrap();
+                    unsafe {
+                        (*parent).elements.push(TypeAst::default());
+                        let last_idx = (*parent).elements.len() - 1;
+                        let elements_ptr = (*parent).elements.as_mut_ptr();
+                        let new_current = elements_ptr.add(last_idx);
+                        self.current_type = Some(new_current);
                     }
                 }
 
@@ -284,32 +303,39 @@ impl<'a> TypeParser<'a> {
                     let start = self.cur;
                     self.cur += 1;
 
-                    // Fast forward to closing quote
+                    // Fast forward to closing quote, skipping escaped
+                    // characters such as \'
                     while self.cur < bytes.len() {
-                        if bytes[self.cur] as char == '\'' {
-                            self.cur += 1;
-                            return Token {
-                                token_type: TokenType::QuotedString,
-                                value: &self.input[start..self.cur],
-                            };
+                        match bytes[self.cur] {
+                            b'\\' => self.cur += 2,
+                            b'\'' => {
+                                self.cur += 1;
+                                return Token {
+                                    token_type: TokenType::QuotedString,
+                                    value: &self.input[start..self.cur],
+                                };
+                            }
+                            _ => self.cur += 1,
                         }
-                        self.cur += 1;
                     }
 
+                    // Unterminated string
+                    self.cur = bytes.len();
                     return Token {
-                        token_type: TokenType::QuotedString,
-                        value: &self.input[start..self.cur],
+                        token_type: TokenType::Invalid,
+                        value: "",
                     };
                 }
                 _ => {
                     let start = self.cur;
                     let ch = bytes[self.cur] as char;
 
-                    // Identifier (name)
-                    if ch.is_alphabetic() || ch == '_' {
+                    // Identifier (name). Only ASCII is accepted so that
+                    // slicing never splits a multi-byte character.
+                    if ch.is_ascii_alphabetic() || ch == '_' {
                         while self.cur < bytes.len() {
                             let c = bytes[self.cur] as char;
-                            if !c.is_alphanumeric() && c != '_' {
+                            if !c.is_ascii_alphanumeric() && c != '_' {
                                 break;
                             }
                             self.cur += 1;
@@ -321,10 +347,10 @@ impl<'a> TypeParser<'a> {
                     }
 
                     // Number
-                    if ch.is_numeric() || ch == '-' {
+                    if ch.is_ascii_digit() || ch == '-' {
                         self.cur += 1;
                         while self.cur < bytes.len() {
-                            if !(bytes[self.cur] as char).is_numeric() {
+                            if !bytes[self.cur].is_ascii_digit() {
                                 break;
                             }
                             self.cur += 1;
@@ -347,6 +373,22 @@ impl<'a> TypeParser<'a> {
     }
 }
 
+/// Resolve backslash escapes in the body of a quoted string
+fn unescape_quoted(body: &str) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                out.push(escaped);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 /// Get TypeMeta from type name
 /// Mirrors C++ `GetTypeMeta(const StringView& name)`
 fn get_type_meta(name: &str) -> TypeMeta {
@@ -359,6 +401,7 @@ fn get_type_meta(name: &str) -> TypeMeta {
         "LowCardinality" => TypeMeta::LowCardinality,
         "SimpleAggregateFunction" => TypeMeta::SimpleAggregateFunction,
         "Map" => TypeMeta::Map,
+        "Nested" => TypeMeta::Nested,
         _ => TypeMeta::Terminal,
     }
 }
@@ -372,13 +415,15 @@ fn get_type_code(name: &str) -> TypeCode {
         "Int16" => TypeCode::Int16,
         "Int32" => TypeCode::Int32,
         "Int64" => TypeCode::Int64,
-        "Bool" | "UInt8" => TypeCode::UInt8,
+        "Bool" => TypeCode::Bool,
+        "UInt8" => TypeCode::UInt8,
         "UInt16" => TypeCode::UInt16,
         "UInt32" => TypeCode::UInt32,
         "UInt64" => TypeCode::UInt64,
         "Float32" => TypeCode::Float32,
         "Float64" => TypeCode::Float64,
         "String" => TypeCode::String,
+        "JSON" => TypeCode::Json,
         "FixedString" => TypeCode::FixedString,
         "DateTime" => TypeCode::DateTime,
         "DateTime64" => TypeCode::DateTime64,
@@ -387,6 +432,7 @@ fn get_type_code(name: &str) -> TypeCode {
         "Array" => TypeCode::Array,
         "Nullable" => TypeCode::Nullable,
         "Tuple" => TypeCode::Tuple,
+        "Nested" => TypeCode::Nested,
         "Enum8" => TypeCode::Enum8,
         "Enum16" => TypeCode::Enum16,
         "UUID" => TypeCode::UUID,
@@ -394,16 +440,22 @@ fn get_type_code(name: &str) -> TypeCode {
         "IPv6" => TypeCode::IPv6,
         "Int128" => TypeCode::Int128,
         "UInt128" => TypeCode::UInt128,
+        "Int256" => TypeCode::Int256,
+        "UInt256" => TypeCode::UInt256,
         "Decimal" => TypeCode::Decimal,
         "Decimal32" => TypeCode::Decimal32,
         "Decimal64" => TypeCode::Decimal64,
         "Decimal128" => TypeCode::Decimal128,
+        "Decimal256" => TypeCode::Decimal256,
         "LowCardinality" => TypeCode::LowCardinality,
         "Map" => TypeCode::Map,
         "Point" => TypeCode::Point,
         "Ring" => TypeCode::Ring,
         "Polygon" => TypeCode::Polygon,
         "MultiPolygon" => TypeCode::MultiPolygon,
+        _ if IntervalUnit::from_type_name(name).is_some() => {
+            TypeCode::Interval
+        }
         _ => TypeCode::Void,
     }
 }
@@ -446,6 +498,12 @@ pub fn parse_type_name(type_name: &str) -> Result<TypeAst> {
         let mut parser = TypeParser::new(type_name);
 
         if !parser.parse(&mut ast) {
+            // A well-formed name we just don't know is not a protocol error
+            if !validate_ast(&ast) {
+                return Err(Error::UnsupportedType {
+                    type_string: type_name.to_string(),
+                });
+            }
             return Err(Error::Protocol(format!(
                 "Failed to parse type: {}",
                 type_name
@@ -514,6 +572,24 @@ mod tests {
         assert_eq!(ast.elements.len(), 4); // 'red', 1, 'green', 2
     }
 
+    #[test]
+    fn test_nested_tuple_elements() {
+        let ast =
+            parse_type_name("Array(Nullable(Tuple(String, Int8, Date)))")
+                .unwrap();
+        let tuple = &ast.elements[0].elements[0];
+        assert_eq!(tuple.meta, TypeMeta::Tuple);
+        assert_eq!(tuple.elements.len(), 3);
+        assert_eq!(tuple.elements[2].code, TypeCode::Date);
+    }
+
+    #[test]
+    fn test_unbalanced_parentheses() {
+        assert!(parse_type_name("Array(String))").is_err());
+        assert!(parse_type_name("Array(String").is_err());
+        assert!(parse_type_name("String, Int8").is_err());
+    }
+
     #[test]
     fn test_caching() {
         let ast1 = parse_type_name("String").unwrap();
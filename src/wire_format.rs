@@ -1,7 +1,22 @@
 use crate::{
+    block::Block,
+    column::{
+        ColumnRef,
+        Value,
+    },
+    io::{
+        buffer_utils,
+        row_binary::RowLayout,
+    },
+    row::Row,
+    types::Type,
     Error,
     Result,
 };
+use bytes::{
+    Buf,
+    BytesMut,
+};
 use tokio::io::{
     AsyncRead,
     AsyncReadExt,
@@ -22,6 +37,10 @@ impl WireFormat {
 
         loop {
             let byte = reader.read_u8().await?;
+            // The 10th byte holds only the top bit of a u64
+            if shift == 63 && byte > 1 {
+                return Err(Error::Protocol("Varint overflow".to_string()));
+            }
             result |= ((byte & 0x7F) as u64) << shift;
 
             if byte & 0x80 == 0 {
@@ -29,9 +48,6 @@ impl WireFormat {
             }
 
             shift += 7;
-            if shift >= 64 {
-                return Err(Error::Protocol("Varint overflow".to_string()));
-            }
         }
 
         Ok(result)
@@ -161,72 +177,128 @@ impl WireFormat {
     /// - \t → \\t
     /// - \n → \\n
     /// - '  → \x27
-    /// - \  → \\\
+    /// - \  → \\\\
     pub async fn write_quoted_string<W: AsyncWrite + Unpin>(
         writer: &mut W,
         value: &str,
     ) -> Result<()> {
-        const QUOTED_CHARS: &[u8] = b"\0\x08\t\n'\\";
-
-        // Check if we need escaping (fast path)
-        let bytes = value.as_bytes();
-        let first_special =
-            bytes.iter().position(|&b| QUOTED_CHARS.contains(&b));
-
-        if first_special.is_none() {
-            // Fast path: no special characters
-            Self::write_varint64(writer, (value.len() + 2) as u64).await?;
-            writer.write_all(b"'").await?;
-            writer.write_all(bytes).await?;
-            writer.write_all(b"'").await?;
-            return Ok(());
+        let mut buffer = BytesMut::with_capacity(value.len() + 8);
+        buffer_utils::write_quoted_string(&mut buffer, value);
+        writer.write_all(&buffer).await?;
+        Ok(())
+    }
+}
+
+/// Reader of rows in the `RowBinary` format from an async stream
+///
+/// `RowBinary` has no header: the column types are given up front, and
+/// every row is the values of its columns back to back, as described in
+/// [`crate::io::row_binary`]. Rows are buffered until complete, so a row
+/// split across reads is decoded once the rest arrives.
+///
+/// ```ignore
+/// let types = vec![Type::uint64(), Type::string()];
+/// let mut reader = RowBinaryReader::new(stream, types)?;
+/// while let Some(row) = reader.next_row().await? {
+///     println!("{:?}", row);
+/// }
+/// ```
+pub struct RowBinaryReader<R> {
+    reader: R,
+    layout: RowLayout,
+    buffer: BytesMut,
+}
+
+impl<R: AsyncRead + Unpin> RowBinaryReader<R> {
+    /// Create a reader of rows with columns of `types`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `types` is empty or a type is not
+    /// supported in `RowBinary`.
+    pub fn new(reader: R, types: Vec<Type>) -> Result<Self> {
+        if types.is_empty() {
+            return Err(Error::Validation(
+                "RowBinary rows need at least one column".to_string(),
+            ));
         }
+        Ok(Self {
+            reader,
+            layout: RowLayout::new(types)?,
+            buffer: BytesMut::new(),
+        })
+    }
 
-        // Count special characters for length calculation
-        let quoted_count =
-            bytes.iter().filter(|&&b| QUOTED_CHARS.contains(&b)).count();
+    /// Column types of every row.
+    pub fn types(&self) -> &[Type] {
+        self.layout.types()
+    }
 
-        // Write length: original + 2 quotes + 3 bytes per special char
-        let total_len = value.len() + 2 + 3 * quoted_count;
-        Self::write_varint64(writer, total_len as u64).await?;
+    /// Read the next row as one value per column, or `None` at the end of
+    /// the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Protocol` if the stream ends inside a row or the
+    /// row is malformed, and `Error::Io` if reading fails.
+    pub async fn next_row(&mut self) -> Result<Option<Vec<Value>>> {
+        let Some(columns) = self.next_columns().await? else {
+            return Ok(None);
+        };
+        columns
+            .iter()
+            .map(|column| column.value_at(0))
+            .collect::<Result<_>>()
+            .map(Some)
+    }
 
-        // Write opening quote
-        writer.write_all(b"'").await?;
+    /// Read the next row as a `T`, or `None` at the end of the stream.
+    ///
+    /// The columns are named after [`Row::COLUMNS`], in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Validation` if `T` does not have one field per
+    /// column, the errors of [`next_row`](Self::next_row), and the errors
+    /// of [`Row::from_block`] if a column does not convert to its field.
+    pub async fn next_typed<T: Row>(&mut self) -> Result<Option<T>> {
+        if T::COLUMNS.len() != self.layout.types().len() {
+            return Err(Error::Validation(format!(
+                "Row has {} fields but RowBinary rows have {} columns",
+                T::COLUMNS.len(),
+                self.layout.types().len()
+            )));
+        }
+        let Some(columns) = self.next_columns().await? else {
+            return Ok(None);
+        };
+        let mut block = Block::new();
+        for (name, column) in T::COLUMNS.iter().zip(columns) {
+            block.append_column(*name, column)?;
+        }
+        Ok(T::from_block(&block)?.pop())
+    }
 
-        // Write string with escaping
-        let mut start = 0;
-        for (i, &byte) in bytes.iter().enumerate() {
-            if QUOTED_CHARS.contains(&byte) {
-                // Write chunk before special char
-                if i > start {
-                    writer.write_all(&bytes[start..i]).await?;
+    /// Read the next row as one-row columns
+    async fn next_columns(&mut self) -> Result<Option<Vec<ColumnRef>>> {
+        loop {
+            if !self.buffer.is_empty() {
+                if let Some(size) = self.layout.row_size(&self.buffer)? {
+                    let columns =
+                        self.layout.decode_row(&self.buffer[..size])?;
+                    self.buffer.advance(size);
+                    return Ok(Some(columns));
                 }
-
-                // Write escape sequence
-                writer.write_all(b"\\").await?;
-                match byte {
-                    b'\0' => writer.write_all(b"x00").await?,
-                    b'\x08' => writer.write_all(b"x08").await?,
-                    b'\t' => writer.write_all(b"\\t").await?,
-                    b'\n' => writer.write_all(b"\\n").await?,
-                    b'\'' => writer.write_all(b"x27").await?,
-                    b'\\' => writer.write_all(b"\\\\").await?,
-                    _ => unreachable!(),
+            }
+            if self.reader.read_buf(&mut self.buffer).await? == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
                 }
-
-                start = i + 1;
+                return Err(Error::Protocol(
+                    "Unexpected end of RowBinary data".to_string(),
+                ));
             }
         }
-
-        // Write final chunk
-        if start < bytes.len() {
-            writer.write_all(&bytes[start..]).await?;
-        }
-
-        // Write closing quote
-        writer.write_all(b"'").await?;
-
-        Ok(())
     }
 }
 
@@ -314,6 +386,140 @@ impl FixedSize for u128 {
 mod tests {
     use super::*;
 
+    fn row_binary_types() -> Vec<Type> {
+        vec![
+            Type::uint32(),
+            Type::string(),
+            Type::nullable(Type::int8()),
+            Type::array(Type::uint8()),
+        ]
+    }
+
+    fn row_binary_rows() -> Vec<u8> {
+        let mut data = Vec::new();
+        // 7, "ab", -3, [1, 2]
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&[2, b'a', b'b']);
+        data.extend_from_slice(&[0, 0xFD]);
+        data.extend_from_slice(&[2, 1, 2]);
+        // 300, "", NULL, []
+        data.extend_from_slice(&300u32.to_le_bytes());
+        data.push(0);
+        data.push(1);
+        data.push(0);
+        data
+    }
+
+    #[tokio::test]
+    async fn test_row_binary_reader_mixed_types() {
+        let data = row_binary_rows();
+        let mut reader =
+            RowBinaryReader::new(&data[..], row_binary_types()).unwrap();
+
+        assert_eq!(
+            reader.next_row().await.unwrap(),
+            Some(vec![
+                Value::UInt64(7),
+                Value::String("ab".to_string()),
+                Value::Int64(-3),
+                Value::Array(vec![Value::UInt64(1), Value::UInt64(2)]),
+            ])
+        );
+        assert_eq!(
+            reader.next_row().await.unwrap(),
+            Some(vec![
+                Value::UInt64(300),
+                Value::String(String::new()),
+                Value::Null,
+                Value::Array(Vec::new()),
+            ])
+        );
+        assert_eq!(reader.next_row().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_row_binary_reader_rows_split_across_reads() {
+        let data = row_binary_rows();
+        let (mut server, client) = tokio::io::duplex(64);
+        let writer = tokio::spawn(async move {
+            for byte in data {
+                server.write_u8(byte).await.unwrap();
+                server.flush().await.unwrap();
+            }
+        });
+
+        let mut reader =
+            RowBinaryReader::new(client, row_binary_types()).unwrap();
+        let mut rows = 0;
+        while reader.next_row().await.unwrap().is_some() {
+            rows += 1;
+        }
+        writer.await.unwrap();
+        assert_eq!(rows, 2);
+    }
+
+    #[tokio::test]
+    async fn test_row_binary_reader_truncated_row() {
+        let data = row_binary_rows();
+        let mut reader =
+            RowBinaryReader::new(&data[..data.len() - 1], row_binary_types())
+                .unwrap();
+
+        assert!(reader.next_row().await.unwrap().is_some());
+        assert!(matches!(reader.next_row().await, Err(Error::Protocol(_))));
+    }
+
+    #[tokio::test]
+    async fn test_row_binary_reader_typed() {
+        struct Pair {
+            id: u64,
+            name: String,
+        }
+
+        impl Row for Pair {
+            const COLUMNS: &'static [&'static str] = &["id", "name"];
+
+            fn to_block(_rows: &[Self]) -> Result<Block> {
+                unimplemented!()
+            }
+
+            fn from_block(block: &Block) -> Result<Vec<Self>> {
+                let ids = crate::row::FromSql::from_column(
+                    &block.column_by_name("id").unwrap(),
+                )?;
+                let names: Vec<String> = crate::row::FromSql::from_column(
+                    &block.column_by_name("name").unwrap(),
+                )?;
+                Ok(ids
+                    .into_iter()
+                    .zip(names)
+                    .map(|(id, name)| Pair { id, name })
+                    .collect())
+            }
+        }
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&[3, b'f', b'o', b'o']);
+        let mut reader = RowBinaryReader::new(
+            &data[..],
+            vec![Type::uint64(), Type::string()],
+        )
+        .unwrap();
+
+        let pair = reader.next_typed::<Pair>().await.unwrap().unwrap();
+        assert_eq!((pair.id, pair.name.as_str()), (42, "foo"));
+        assert!(reader.next_typed::<Pair>().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_row_binary_reader_rejects_empty_schema() {
+        assert!(matches!(
+            RowBinaryReader::new(&[][..], Vec::new()),
+            Err(Error::Validation(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_varint64_encoding() {
         let test_cases =
@@ -360,6 +566,42 @@ mod tests {
         assert_eq!(value, decoded);
     }
 
+    #[tokio::test]
+    async fn test_fixed_little_endian_layout() {
+        async fn bytes_of<T: FixedSize>(value: T) -> Vec<u8> {
+            let mut buf = Vec::new();
+            WireFormat::write_fixed(&mut buf, value).await.unwrap();
+            buf
+        }
+
+        assert_eq!(bytes_of(1u8).await, [1]);
+        assert_eq!(bytes_of(-1i8).await, [0xff]);
+        assert_eq!(bytes_of(0x0102u16).await, [0x02, 0x01]);
+        assert_eq!(bytes_of(-1i16).await, [0xff, 0xff]);
+        assert_eq!(bytes_of(-2i16).await, [0xfe, 0xff]);
+        assert_eq!(bytes_of(1u32).await, [1, 0, 0, 0]);
+        assert_eq!(bytes_of(-2i32).await, [0xfe, 0xff, 0xff, 0xff]);
+        assert_eq!(
+            bytes_of(0x0102030405060708u64).await,
+            [8, 7, 6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(bytes_of(i64::MIN).await, [0, 0, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(bytes_of(1.0f32).await, [0, 0, 0x80, 0x3f]);
+        assert_eq!(bytes_of(-2.0f64).await, [0, 0, 0, 0, 0, 0, 0, 0xc0]);
+
+        let mut expected = [0u8; 16];
+        expected[0] = 1;
+        assert_eq!(bytes_of(1u128).await, expected);
+        assert_eq!(bytes_of(-1i128).await, [0xff; 16]);
+        let value = 0x0102030405060708090a0b0c0d0e0f10u128;
+        let expected: Vec<u8> = (1..=16).rev().collect();
+        assert_eq!(bytes_of(value).await, expected);
+        assert_eq!(bytes_of(value as i128).await, expected);
+        let mut expected = [0u8; 16];
+        expected[15] = 0x80;
+        assert_eq!(bytes_of(i128::MIN).await, expected);
+    }
+
     #[tokio::test]
     async fn test_fixed_i64() {
         let value = -12345i64;
@@ -416,10 +658,10 @@ mod tests {
         let mut buf = Vec::new();
         WireFormat::write_quoted_string(&mut buf, "a\tb").await.unwrap();
 
-        // Length: original(3) + 2(quotes) + 3(one special char) = 8
+        // Length: original(3) + 2(quotes) + 2(one tab escaped twice) = 7
         // Content: 'a\\tb'
         let mut expected = Vec::new();
-        WireFormat::write_varint64(&mut expected, 8).await.unwrap();
+        WireFormat::write_varint64(&mut expected, 7).await.unwrap();
         expected.extend_from_slice(b"'a\\\\tb'");
 
         assert_eq!(buf, expected);
@@ -445,11 +687,11 @@ mod tests {
         let mut buf = Vec::new();
         WireFormat::write_quoted_string(&mut buf, test_str).await.unwrap();
 
-        // 6 chars, each becomes 4 bytes: 6 + 2 + 3*6 = 26
+        // \t and \n become 3 bytes, the rest 4 bytes: 4*4 + 2*3 + 2 = 24
         let mut expected = Vec::new();
-        WireFormat::write_varint64(&mut expected, 26).await.unwrap();
-        // \0 → \x00, \b → \x08, \t → \\t, \n → \\n, ' → \x27, \ → \\\
-        expected.extend_from_slice(b"'\\x00\\x08\\\\t\\\\n\\x27\\\\\\'");
+        WireFormat::write_varint64(&mut expected, 24).await.unwrap();
+        // \0 → \x00, \b → \x08, \t → \\t, \n → \\n, ' → \x27, \ → \\\\
+        expected.extend_from_slice(b"'\\x00\\x08\\\\t\\\\n\\x27\\\\\\\\'");
 
         assert_eq!(buf, expected);
     }
@@ -474,10 +716,10 @@ mod tests {
         WireFormat::write_quoted_string(&mut buf, "a\\b").await.unwrap();
 
         // Length: 3 + 2 + 3 = 8
-        // Content: 'a\\\b' (backslash becomes \\\ which is 3 backslashes)
+        // Content: 'a\\\\b' (escaped for the text format, then quoted)
         let mut expected = Vec::new();
         WireFormat::write_varint64(&mut expected, 8).await.unwrap();
-        expected.extend_from_slice(b"'a\\\\\\b'");
+        expected.extend_from_slice(b"'a\\\\\\\\b'");
 
         assert_eq!(buf, expected);
     }
@@ -15,13 +15,18 @@
 
 use clickhouse_native_client::{
     column::{
-        numeric::ColumnUInt64,
+        numeric::{
+            ColumnUInt64,
+            ColumnUInt8,
+        },
         string::ColumnString,
     },
     types::Type,
     Block,
     Client,
     ClientOptions,
+    Query,
+    QuerySettingsField,
 };
 use std::sync::Arc;
 
@@ -112,6 +117,101 @@ async fn test_client_with_compression_lz4() {
     client.query("DROP TABLE IF EXISTS test_compression_lz4").await.ok();
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_insert_block_larger_than_compression_chunk() {
+    use clickhouse_native_client::protocol::CompressionMethod;
+
+    let opts = ClientOptions::new("localhost", 9000)
+        .database("default")
+        .user("default")
+        .password("")
+        .compression(Some(CompressionMethod::Lz4))
+        .max_compression_chunk_size(4096);
+
+    let mut client = Client::connect(opts).await.expect("Failed to connect");
+
+    client.query("DROP TABLE IF EXISTS test_compression_chunks").await.ok();
+    client
+        .query(
+            "CREATE TABLE test_compression_chunks (id UInt64, text String) \
+             ENGINE = Memory",
+        )
+        .await
+        .expect("Failed to create table");
+
+    // About 300 KB serialized, so dozens of 4 KB frames
+    let mut id_col = ColumnUInt64::new();
+    let mut text_col = ColumnString::new(Type::string());
+    for i in 0..10_000 {
+        id_col.append(i);
+        text_col.append(format!("Text row {:020}", i));
+    }
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(id_col)).unwrap();
+    block.append_column("text", Arc::new(text_col)).unwrap();
+
+    client
+        .insert("test_compression_chunks", block)
+        .await
+        .expect("Failed to insert chunked block");
+
+    let result = client
+        .query("SELECT sum(id) FROM test_compression_chunks")
+        .await
+        .expect("Failed to query");
+    let sum = result.blocks()[0].column(0).unwrap();
+    let sum = sum.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(sum.at(0), 49_995_000);
+
+    client.query("DROP TABLE IF EXISTS test_compression_chunks").await.ok();
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_uncompressed_select_complex_types() {
+    use clickhouse_native_client::column::{
+        array::ColumnArray,
+        map::ColumnMap,
+        tuple::ColumnTuple,
+    };
+
+    let opts = ClientOptions::new("localhost", 9000)
+        .database("default")
+        .user("default")
+        .password("")
+        .compression(None);
+
+    let mut client = Client::connect(opts).await.expect("Failed to connect");
+
+    let result = client
+        .query(
+            "SELECT ['a', 'bc'] AS arr, map('k', 1) AS m, \
+             tuple(1, 'x') AS t, \
+             toLowCardinality('lc') AS lc, \
+             CAST(NULL AS Nullable(String)) AS n",
+        )
+        .await
+        .expect("Failed to select without compression");
+    assert_eq!(result.total_rows(), 1);
+
+    let block = &result.blocks()[0];
+    let arr = block.column(0).unwrap();
+    let arr = arr.as_any().downcast_ref::<ColumnArray>().unwrap();
+    let items = arr.at(0);
+    let items = items.as_any().downcast_ref::<ColumnString>().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items.at(0), "a");
+    assert_eq!(items.at(1), "bc");
+
+    let map = block.column(1).unwrap();
+    assert_eq!(map.as_any().downcast_ref::<ColumnMap>().unwrap().len(), 1);
+    let tuple = block.column(2).unwrap();
+    assert!(tuple.as_any().downcast_ref::<ColumnTuple>().is_some());
+    assert_eq!(block.column(3).unwrap().size(), 1);
+    assert_eq!(block.column(4).unwrap().size(), 1);
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_query_with_settings() {
@@ -128,6 +228,54 @@ async fn test_query_with_settings() {
     assert_eq!(result.total_rows(), 1);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_query_with_typed_settings_roundtrip() {
+    let mut client = create_test_client().await.expect("Failed to connect");
+
+    let query = Query::new(
+        "SELECT toUInt64(getSetting('max_block_size')), \
+         toUInt8(getSetting('optimize_aggregation_in_order'))",
+    )
+    .with_setting_num("max_block_size", 1234)
+    .with_setting_bool("optimize_aggregation_in_order", true);
+    let result = client.query(query).await.expect("Failed to query");
+
+    let block = result.first_block().expect("No rows returned");
+    let max_block_size = block.column(0).unwrap();
+    let max_block_size =
+        max_block_size.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(max_block_size.at(0), 1234);
+    let in_order = block.column(1).unwrap();
+    let in_order = in_order.as_any().downcast_ref::<ColumnUInt8>().unwrap();
+    assert_eq!(in_order.at(0), 1);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_with_custom_setting_roundtrip() {
+    let mut client = create_test_client().await.expect("Failed to connect");
+
+    // The stock server config only accepts custom settings starting with
+    // `SQL_` (see `custom_settings_prefixes`)
+    for value in ["bar", "it's \\ quoted"] {
+        let query = Query::new("SELECT getSetting('SQL_custom_foo')")
+            .with_setting_flags(
+                "SQL_custom_foo",
+                value,
+                QuerySettingsField::CUSTOM,
+            );
+        let result = client
+            .query(query)
+            .await
+            .expect("Server should accept the custom setting");
+
+        let col = result.blocks()[0].column(0).unwrap();
+        let col = col.as_any().downcast_ref::<ColumnString>().unwrap();
+        assert_eq!(col.at(0), value);
+    }
+}
+
 // ============================================================================
 // Column Name Escaping Tests
 // ============================================================================
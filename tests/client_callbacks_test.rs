@@ -12,7 +12,10 @@
 //! - Progress callbacks during long-running queries
 //! - Profile info callbacks with query statistics
 //! - Profile events callbacks with performance counters
+//! - Profile events parsed and totalled on the query result
+//! - Profile events callbacks during INSERT
 //! - Server log callbacks
+//! - Server log lines collected on the query result
 //! - Exception callbacks for errors
 //! - Data callbacks for result streaming
 //! - Cancelable data callbacks for query cancellation
@@ -21,13 +24,25 @@
 //! - Callbacks combined with settings
 
 use clickhouse_native_client::{
+    column::{
+        numeric::ColumnUInt64,
+        ColumnString,
+    },
+    Block,
     Client,
     ClientOptions,
+    LogLevel,
     Query,
 };
-use std::sync::{
-    Arc,
-    Mutex,
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
 };
 
 /// Helper to create a test client
@@ -121,6 +136,73 @@ async fn test_on_profile_events_callback() {
     println!("ProfileEvents callback invoked {} times", count);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_profile_events_collected_into_query_result() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let result = client
+        .query("SELECT * FROM system.numbers LIMIT 10000")
+        .await
+        .expect("Query failed");
+
+    assert!(!result.profile_events.is_empty());
+    let selected_rows =
+        result.profile_event("SelectedRows").expect("No SelectedRows event");
+    assert!(selected_rows >= 10000, "SelectedRows = {}", selected_rows);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_on_profile_events_callback_during_insert() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_profile_events_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64) ENGINE = MergeTree ORDER BY id",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let event_names = Arc::new(Mutex::new(Vec::new()));
+    let event_names_clone = event_names.clone();
+    let query = Query::new(table.as_str()).on_profile_events(move |block| {
+        if let Some(col) = block.column_by_name("name") {
+            let names = col.as_any().downcast_ref::<ColumnString>().unwrap();
+            let mut event_names = event_names_clone.lock().unwrap();
+            event_names.extend((0..names.len()).map(|i| names.at(i)));
+        }
+        true
+    });
+
+    let mut ids = ColumnUInt64::new();
+    for id in 1..=3 {
+        ids.append(id);
+    }
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(ids)).unwrap();
+    client.insert_query(query, block).await.expect("Insert failed");
+
+    let event_names = event_names.lock().unwrap().clone();
+    println!("Insert profile events: {:?}", event_names);
+    assert!(
+        event_names.iter().any(|name| name == "InsertedRows"),
+        "expected InsertedRows among insert profile events"
+    );
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_on_server_log_callback() {
@@ -143,6 +225,22 @@ async fn test_on_server_log_callback() {
     println!("Server log callback invoked {} times", count);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_server_logs_collected_into_query_result() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let query = Query::new("SELECT * FROM system.numbers LIMIT 1000")
+        .with_setting("send_logs_level", "trace")
+        .collect_logs(true);
+    let result = client.query(query).await.expect("Query failed");
+
+    assert!(!result.logs.is_empty(), "expected server log lines");
+    assert!(result.logs.iter().all(|entry| !entry.text.is_empty()));
+    assert!(result.logs.iter().any(|entry| entry.level >= LogLevel::Debug));
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_on_exception_callback() {
@@ -244,6 +342,11 @@ async fn test_on_data_cancelable_callback() {
         rows < 1000000,
         "Query should have been cancelled before completion"
     );
+
+    // The cancelled query was drained, so the connection is reused as is
+    assert!(!client.is_dirty());
+    let result = client.query("SELECT 1").await.expect("Second query failed");
+    assert_eq!(result.total_rows(), 1);
 }
 
 #[tokio::test]
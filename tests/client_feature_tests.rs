@@ -17,18 +17,39 @@
 //! - SimpleAggregateFunction column type
 //! - Query cancellation
 //! - Connection reset
+//! - Waiting for ALTER TABLE mutations
+//! - Tables stored with sparse serialization
+//! - Recovery after a dropped query future
+//! - SHOW TABLES / SHOW DATABASES / SHOW CREATE TABLE helpers
+//! - Client hostname override in system.processes
+//! - Written row counts of `CREATE TABLE ... AS SELECT`
+//! - Session-scoped temporary tables across calls
+//! - Pull-based cursor over a multi-block result
+//! - Streaming result blocks with `query_stream`
+//! - Partial inserts leaving omitted columns to server defaults
+//! - Queries with parallel reading from replicas enabled
+//! - INSERT sessions writing several blocks
+//! - Per-query timeouts cancelling a slow query
 
 use clickhouse_native_client::{
+    column::{
+        column_value::ColumnValue,
+        ColumnString,
+    },
     Client,
     ClientOptions,
+    Error,
     Query,
 };
+use futures_util::StreamExt;
 use std::{
     sync::{
         Arc,
         Mutex,
     },
     time::{
+        Duration,
+        Instant,
         SystemTime,
         UNIX_EPOCH,
     },
@@ -457,3 +478,823 @@ async fn test_ping_functionality() {
 
     println!("✓ Ping functionality test passed");
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_query_count_only_matches_full_query() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    // Wide rows with variable-length, nullable, array and LowCardinality data
+    let sql = "SELECT number, toString(number) AS s, \
+               if(number % 3 = 0, NULL, number) AS n, \
+               range(number % 5) AS arr, \
+               toLowCardinality(toString(number % 7)) AS lc, \
+               map(toString(number), number) AS m \
+               FROM system.numbers LIMIT 200000";
+
+    let start = std::time::Instant::now();
+    let full = client.query(sql).await.expect("Full query failed");
+    let full_elapsed = start.elapsed();
+
+    let start = std::time::Instant::now();
+    let count =
+        client.query_count_only(sql).await.expect("Count-only query failed");
+    let count_elapsed = start.elapsed();
+
+    println!(
+        "full: {} rows in {:?}, count-only: {} rows in {:?}",
+        full.total_rows(),
+        full_elapsed,
+        count,
+        count_elapsed
+    );
+    assert_eq!(count, full.total_rows());
+    assert_eq!(count, 200000);
+
+    // The connection must remain aligned after skipping payloads
+    client.ping().await.expect("Ping after count-only query failed");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_alter_delete_and_wait() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_mutation_wait_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64) ENGINE = MergeTree ORDER BY id",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+    client
+        .execute(format!(
+            "INSERT INTO {} SELECT number FROM numbers(100)",
+            table
+        ))
+        .await
+        .expect("Failed to insert rows");
+
+    let ids = client
+        .alter_and_wait(
+            &format!(
+                "ALTER TABLE `default`.`{}` DELETE WHERE id % 2 = 0",
+                table
+            ),
+            std::time::Duration::from_millis(100),
+            std::time::Duration::from_secs(30),
+        )
+        .await
+        .expect("Mutation did not finish");
+    assert_eq!(ids.len(), 1);
+
+    // Waiting on an already finished mutation returns immediately
+    client
+        .wait_mutation(
+            &table,
+            &ids[0],
+            std::time::Duration::from_millis(100),
+            std::time::Duration::from_secs(1),
+        )
+        .await
+        .expect("Finished mutation should report done");
+
+    let count = client
+        .query_count_only(format!("SELECT id FROM {}", table))
+        .await
+        .expect("Count failed");
+    assert_eq!(count, 50);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_read_sparse_serialized_table() {
+    use clickhouse_native_client::column::numeric::ColumnUInt64;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_sparse_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    // A ratio of 0 stores every column with defaults as sparse
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64, v UInt64, s String) \
+             ENGINE = MergeTree ORDER BY id \
+             SETTINGS ratio_of_defaults_for_sparse_serialization = 0",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+    client
+        .execute(format!(
+            "INSERT INTO {} SELECT number, if(number % 10 = 0, number, 0), \
+             if(number % 10 = 0, 'x', '') FROM numbers(1000)",
+            table
+        ))
+        .await
+        .expect("Failed to insert rows");
+
+    let result = client
+        .query(format!("SELECT v, s FROM {} ORDER BY id", table))
+        .await
+        .expect("Query on sparse columns failed");
+    let mut row = 0u64;
+    for block in result.blocks() {
+        let v = block.column(0).unwrap();
+        let v = v.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+        let s = block.column(1).unwrap();
+        let s = s.as_any().downcast_ref::<ColumnString>().unwrap();
+        for i in 0..block.row_count() {
+            let stored = row.is_multiple_of(10);
+            assert_eq!(v.at(i), if stored { row } else { 0 });
+            assert_eq!(s.at(i), if stored { "x" } else { "" });
+            row += 1;
+        }
+    }
+    assert_eq!(row, 1000);
+
+    let count = client
+        .query_count_only(format!("SELECT v, s FROM {}", table))
+        .await
+        .expect("Count failed");
+    assert_eq!(count, 1000);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_dropped_query_future_recovers() {
+    use clickhouse_native_client::column::numeric::ColumnUInt64;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    // Stream many blocks slowly so the future is dropped mid-response
+    let interrupted = tokio::time::timeout(
+        std::time::Duration::from_millis(200),
+        client.query(
+            "SELECT number, sleepEachRow(0.001) FROM numbers(100000) \
+             SETTINGS max_block_size = 100",
+        ),
+    )
+    .await;
+    assert!(interrupted.is_err(), "query should have timed out");
+    assert!(client.is_dirty());
+
+    // The next query must not see leftover packets from the dropped one
+    let result = client
+        .query("SELECT toUInt64(42) AS answer")
+        .await
+        .expect("Query after interruption failed");
+    assert!(!client.is_dirty());
+    assert_eq!(result.total_rows(), 1);
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    assert_eq!(block.column_name(0), Some("answer"));
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 42);
+
+    client.ping().await.expect("Ping after recovery failed");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_show_introspection_helpers() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_show_tables_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!("CREATE TABLE {} (id UInt64) ENGINE = Memory", table))
+        .await
+        .expect("Failed to create table");
+
+    let tables =
+        client.show_tables("default").await.expect("SHOW TABLES failed");
+    assert!(tables.contains(&table), "{} not in {:?}", table, tables);
+
+    let databases =
+        client.show_databases().await.expect("SHOW DATABASES failed");
+    assert!(databases.iter().any(|db| db == "default"));
+    assert!(databases.iter().any(|db| db == "system"));
+
+    let create = client
+        .show_create_table(&table)
+        .await
+        .expect("SHOW CREATE TABLE failed");
+    assert!(create.starts_with("CREATE TABLE"));
+    assert!(create.contains(&table));
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_client_hostname_override() {
+    let opts = ClientOptions::new("localhost", 9000)
+        .database("default")
+        .user("default")
+        .password("")
+        .client_hostname("audit-host-override");
+    let mut client =
+        Client::connect(opts).await.expect("Failed to connect to ClickHouse");
+
+    let result = client
+        .query(
+            "SELECT client_hostname FROM system.processes \
+             WHERE query_id = queryID()",
+        )
+        .await
+        .expect("Query failed");
+
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("Expected one row");
+    let col = block.column(0).expect("Missing column");
+    let hostnames = col.as_any().downcast_ref::<ColumnString>().unwrap();
+    assert_eq!(hostnames.at(0), "audit-host-override");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_execute_with_progress_reports_ctas_rows() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_ctas_progress_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    let progress = client
+        .execute_with_progress(format!(
+            "CREATE TABLE {} ENGINE = Memory AS SELECT number FROM \
+             numbers(100)",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+    assert_eq!(progress.written_rows, 100);
+
+    client.execute(format!("DROP TABLE {}", table)).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_temporary_table_persists_across_calls() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        Block,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    client
+        .create_temporary_table("tmp_session_ids", &[("id", "UInt64")])
+        .await
+        .expect("Failed to create temporary table");
+
+    let mut block = Block::new();
+    block
+        .append_column(
+            "id",
+            Arc::new(ColumnUInt64::from_vec(
+                clickhouse_native_client::types::Type::uint64(),
+                vec![1, 2, 3],
+            )),
+        )
+        .unwrap();
+    client
+        .insert("tmp_session_ids", block)
+        .await
+        .expect("Failed to insert into temporary table");
+
+    // A separate query on the same session still sees the rows
+    let result = client
+        .query("SELECT sum(id) FROM tmp_session_ids")
+        .await
+        .expect("Failed to select from temporary table");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 6);
+
+    client
+        .drop_temporary_table("tmp_session_ids")
+        .await
+        .expect("Failed to drop temporary table");
+    assert!(client.query("SELECT * FROM tmp_session_ids").await.is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_cursor_rows_across_blocks() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let mut cursor = client
+        .query_cursor(
+            "SELECT number FROM system.numbers LIMIT 1000 \
+             SETTINGS max_block_size = 100",
+        )
+        .await
+        .expect("Failed to start cursor");
+
+    let mut expected = 0u64;
+    while let Some(row) = cursor.next_row().await.expect("next_row failed") {
+        assert_eq!(row.len(), 1);
+        assert_eq!(
+            row[0].as_bytes(),
+            ColumnValue::from_u64(expected).as_bytes()
+        );
+        expected += 1;
+    }
+    assert_eq!(expected, 1000);
+    assert!(cursor.is_finished());
+    assert!(cursor.next_block().await.unwrap().is_none());
+    drop(cursor);
+    assert!(!client.is_dirty());
+
+    // Dropping a cursor early cancels the query before the next one runs
+    let mut cursor = client
+        .query_cursor(
+            "SELECT number FROM system.numbers \
+             SETTINGS max_block_size = 100",
+        )
+        .await
+        .expect("Failed to start cursor");
+    let block = cursor.next_block().await.unwrap().expect("No data block");
+    assert!(block.row_count() > 0);
+    drop(cursor);
+
+    let result = client
+        .query("SELECT toUInt64(42) AS answer")
+        .await
+        .expect("Query after dropped cursor failed");
+    assert_eq!(result.total_rows(), 1);
+    assert!(!client.is_dirty());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_stream_yields_blocks_as_they_arrive() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let mut stream = client
+        .query_stream(
+            "SELECT number FROM system.numbers LIMIT 10000 \
+             SETTINGS max_block_size = 1000",
+        )
+        .await
+        .expect("Failed to start stream");
+
+    let mut blocks = 0;
+    let mut rows = 0;
+    while let Some(block) = stream.next().await {
+        let block = block.expect("Stream item failed");
+        assert!(block.row_count() > 0);
+        blocks += 1;
+        rows += block.row_count();
+    }
+    assert_eq!(rows, 10000);
+    assert!(blocks > 1, "expected several blocks, got {}", blocks);
+    assert!(stream.progress().unwrap().rows >= 10000);
+    drop(stream);
+
+    // A server exception is a single Err item that ends the stream
+    let mut stream = client
+        .query_stream("SELECT throwIf(number = 3) FROM numbers(10)")
+        .await
+        .expect("Failed to start stream");
+    let mut saw_error = false;
+    while let Some(item) = stream.next().await {
+        if item.is_err() {
+            saw_error = true;
+        }
+    }
+    assert!(saw_error);
+    drop(stream);
+
+    client.ping().await.expect("Ping after stream failed");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_partial_uses_server_defaults() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        types::Type,
+        Block,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_partial_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64, created_at DateTime DEFAULT now(), \
+             note String DEFAULT 'unset') ENGINE = Memory",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    // `note` is in the block but not listed, so it is not sent either
+    let mut block = Block::new();
+    block
+        .append_column(
+            "id",
+            Arc::new(ColumnUInt64::from_vec(Type::uint64(), vec![1, 2])),
+        )
+        .unwrap();
+    block
+        .append_column(
+            "note",
+            Arc::new(ColumnString::from_vec(
+                Type::string(),
+                vec!["a".to_string(), "b".to_string()],
+            )),
+        )
+        .unwrap();
+    client
+        .insert_partial(&table, block, &["id"])
+        .await
+        .expect("Partial insert failed");
+
+    let result = client
+        .query(format!(
+            "SELECT countIf(created_at > toDateTime('2000-01-01') \
+             AND note = 'unset') FROM {}",
+            table
+        ))
+        .await
+        .expect("Failed to select");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 2);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_parallel_replicas_setting_still_returns_results() {
+    use clickhouse_native_client::column::numeric::ColumnUInt64;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_parallel_replicas_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64) ENGINE = MergeTree ORDER BY id",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+    client
+        .execute(format!(
+            "INSERT INTO {} SELECT number FROM numbers(1000)",
+            table
+        ))
+        .await
+        .expect("Failed to fill table");
+
+    // Read task requests from the server must not abort the query
+    let query = Query::new(format!("SELECT sum(id) FROM {}", table))
+        .with_setting("allow_experimental_parallel_reading_from_replicas", "1")
+        .with_setting("max_parallel_replicas", "2");
+    let result = client.query(query).await.expect("Query failed");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 499500);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_session_writes_several_blocks() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        types::Type,
+        Block,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_session_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64, note String DEFAULT 'unset') \
+             ENGINE = Memory",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let mut session = client
+        .begin_insert(table.as_str(), &["id"])
+        .await
+        .expect("Failed to begin insert");
+    assert_eq!(session.header().column_count(), 1);
+    for ids in [vec![1, 2], vec![3]] {
+        let mut block = Block::new();
+        block
+            .append_column(
+                "id",
+                Arc::new(ColumnUInt64::from_vec(Type::uint64(), ids)),
+            )
+            .unwrap();
+        session.write(&block).await.expect("Failed to write block");
+    }
+    assert_eq!(session.blocks_written(), 2);
+    session.finish().await.expect("Failed to finish insert");
+
+    let result = client
+        .query(format!("SELECT sum(id) FROM {}", table))
+        .await
+        .expect("Failed to select");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 6);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_stream_writes_several_blocks() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        BlockBuilder,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_stream_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!("CREATE TABLE {} (id UInt64) ENGINE = Memory", table))
+        .await
+        .expect("Failed to create table");
+
+    let blocks = (0..10u64).map(|chunk| {
+        BlockBuilder::new()
+            .column::<ColumnUInt64>(
+                "id",
+                (chunk * 100..(chunk + 1) * 100).collect::<Vec<_>>(),
+            )
+            .build()
+            .unwrap()
+    });
+    client
+        .insert_stream(table.as_str(), blocks)
+        .await
+        .expect("Failed to stream insert");
+
+    let result = client
+        .query(format!("SELECT count(), sum(id) FROM {}", table))
+        .await
+        .expect("Failed to select");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let count = block.column(0).unwrap();
+    let count = count.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(count.at(0), 1000);
+    let sum = block.column(1).unwrap();
+    let sum = sum.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(sum.at(0), 499500);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_timeout_cancels_slow_query() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let started = Instant::now();
+    let query = Query::new("SELECT sleepEachRow(1) FROM numbers(10)")
+        .with_timeout(Duration::from_millis(1500));
+    let err = client.query(query).await.err().expect("Query should time out");
+    let elapsed = started.elapsed();
+
+    println!("Timed out after {:?}: {}", elapsed, err);
+    assert!(matches!(err, Error::Timeout(_)));
+    // The timeout plus at most the cancellation grace period
+    assert!(elapsed < Duration::from_secs(4), "took {:?}", elapsed);
+
+    let result = client.query("SELECT 1").await.expect("Query failed");
+    assert_eq!(result.total_rows(), 1);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_with_query_uses_given_column_order() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        types::Type,
+        Block,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_with_query_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64, name String, note String DEFAULT \
+             'unset') ENGINE = Memory",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    // Columns in a different order than the table, `note` left out
+    let mut block = Block::new();
+    block
+        .append_column(
+            "name",
+            Arc::new(ColumnString::from_vec(
+                Type::string(),
+                vec!["a".to_string(), "b".to_string()],
+            )),
+        )
+        .unwrap();
+    block
+        .append_column(
+            "id",
+            Arc::new(ColumnUInt64::from_vec(Type::uint64(), vec![1, 2])),
+        )
+        .unwrap();
+    client
+        .insert_with_query(
+            &format!("INSERT INTO {} (name, id) FORMAT Native", table),
+            block,
+        )
+        .await
+        .expect("Insert failed");
+
+    let result = client
+        .query(format!(
+            "SELECT countIf(id = 2 AND name = 'b' AND note = 'unset') FROM {}",
+            table
+        ))
+        .await
+        .expect("Failed to select");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(col.at(0), 1);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_allow_defaults_fills_default_now_column() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        types::Type,
+        Block,
+    };
+
+    let opts =
+        ClientOptions::new("localhost", 9000).insert_allow_defaults(true);
+    let mut client =
+        Client::connect(opts).await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_allow_defaults_{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64, created_at DateTime DEFAULT now()) \
+             ENGINE = Memory",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    // No column list: the server expects `created_at` too
+    let mut block = Block::new();
+    block
+        .append_column(
+            "id",
+            Arc::new(ColumnUInt64::from_vec(Type::uint64(), vec![1, 2])),
+        )
+        .unwrap();
+    client
+        .insert_with_query(
+            &format!("INSERT INTO {} FORMAT Native", table),
+            block,
+        )
+        .await
+        .expect("Insert failed");
+
+    let filled: u64 = client
+        .query_scalar(format!(
+            "SELECT countIf(created_at > toDateTime('2000-01-01')) FROM {}",
+            table
+        ))
+        .await
+        .expect("Failed to select");
+    assert_eq!(filled, 2);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
@@ -5,7 +5,11 @@
 use clickhouse_native_client::{
     column::*,
     io::block_stream::create_column,
-    types::Type,
+    types::{
+        self,
+        Type,
+    },
+    Error,
 };
 
 // ============================================================================
@@ -129,6 +133,34 @@ fn test_create_float64() {
     assert!(col.as_any().downcast_ref::<ColumnFloat64>().is_some());
 }
 
+#[test]
+fn test_create_int256() {
+    let type_ = Type::parse("Int256").unwrap();
+    let col = create_column(&type_).unwrap();
+
+    assert_eq!(col.column_type().name(), "Int256");
+    assert!(col.as_any().downcast_ref::<ColumnInt256>().is_some());
+}
+
+#[test]
+fn test_create_uint256() {
+    let type_ = Type::parse("UInt256").unwrap();
+    let col = create_column(&type_).unwrap();
+
+    assert_eq!(col.column_type().name(), "UInt256");
+    assert!(col.as_any().downcast_ref::<ColumnUInt256>().is_some());
+}
+
+#[test]
+fn test_create_interval() {
+    let type_ = Type::parse("IntervalMinute").unwrap();
+    let col = create_column(&type_).unwrap();
+
+    assert_eq!(col.column_type().name(), "IntervalMinute");
+    let col = col.as_any().downcast_ref::<ColumnInterval>().unwrap();
+    assert_eq!(col.unit(), types::IntervalUnit::Minute);
+}
+
 // ============================================================================
 // Parametrized Type Tests
 // ============================================================================
@@ -304,21 +336,58 @@ fn test_simple_aggregate_function() {
 }
 
 // ============================================================================
-// Bool Tests (Bool is an alias for UInt8)
+// Bool Tests (Bool has its own type code, stored as one byte)
 // ============================================================================
 
 #[test]
-fn test_bool_is_uint8() {
+fn test_bool_type() {
     let type_ = Type::parse("Bool").unwrap();
 
-    // Bool should be parsed as UInt8
     match type_ {
         Type::Simple(code) => {
             use clickhouse_native_client::types::TypeCode;
-            assert_eq!(code, TypeCode::UInt8);
+            assert_eq!(code, TypeCode::Bool);
         }
-        _ => panic!("Bool should be a Simple type (UInt8)"),
+        _ => panic!("Bool should be a Simple type"),
     }
+    assert_eq!(type_.name(), "Bool");
+    assert_eq!(type_.storage_size_bytes(), Some(1));
+}
+
+#[test]
+fn test_bool_in_composite_types() {
+    use clickhouse_native_client::column::{
+        ColumnArray,
+        ColumnBool,
+        ColumnMap,
+        ColumnNullable,
+    };
+
+    let col = create_column(&Type::parse("Bool").unwrap()).unwrap();
+    assert!(col.as_any().downcast_ref::<ColumnBool>().is_some());
+
+    let col = create_column(&Type::parse("Array(Bool)").unwrap()).unwrap();
+    assert_eq!(col.column_type().name(), "Array(Bool)");
+    let array = col.as_any().downcast_ref::<ColumnArray>().unwrap();
+    assert!(array
+        .nested_ref()
+        .as_any()
+        .downcast_ref::<ColumnBool>()
+        .is_some());
+
+    let col = create_column(&Type::parse("Nullable(Bool)").unwrap()).unwrap();
+    assert_eq!(col.column_type().name(), "Nullable(Bool)");
+    let nullable = col.as_any().downcast_ref::<ColumnNullable>().unwrap();
+    assert!(nullable
+        .nested_ref()
+        .as_any()
+        .downcast_ref::<ColumnBool>()
+        .is_some());
+
+    let col =
+        create_column(&Type::parse("Map(String, Bool)").unwrap()).unwrap();
+    assert_eq!(col.column_type().name(), "Map(String, Bool)");
+    assert!(col.as_any().downcast_ref::<ColumnMap>().is_some());
 }
 
 // ============================================================================
@@ -434,6 +503,26 @@ fn test_invalid_type_name() {
     assert!(result.is_err(), "Should fail on invalid type name");
 }
 
+#[test]
+fn test_unknown_type_is_unsupported_type_error() {
+    match Type::parse("Bogus") {
+        Err(Error::UnsupportedType { type_string }) => {
+            assert_eq!(type_string, "Bogus")
+        }
+        other => panic!("Expected UnsupportedType, got {:?}", other),
+    }
+
+    match Type::parse("Array(Nullable(Bogus))") {
+        Err(Error::UnsupportedType { type_string }) => {
+            assert_eq!(type_string, "Bogus")
+        }
+        other => panic!("Expected UnsupportedType, got {:?}", other),
+    }
+
+    // Syntax errors are still protocol errors
+    assert!(matches!(Type::parse("Array(Int32"), Err(Error::Protocol(_))));
+}
+
 #[test]
 fn test_empty_type_string() {
     let result = Type::parse("");
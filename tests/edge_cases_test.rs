@@ -316,6 +316,8 @@ async fn test_table_not_found_exception() {
             "Expected table not found error, got: {}",
             error_msg
         );
+        // UNKNOWN_TABLE
+        assert_eq!(error.code(), Some(60));
     }
 }
 
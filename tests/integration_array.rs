@@ -2,9 +2,13 @@
 /// Tests: Array(Float32), Array(Float64), Array(String), Array(Int32),
 /// Array(Int64),        Array(Date), Array(Date32), Array(DateTime),
 /// Array(DateTime64),        Array(LowCardinality(String)), Array(Decimal(10,
-/// 2))
+/// 2)), Array(Array(Int32))
 mod common;
 
+use clickhouse_native_client::column::{
+    array::ColumnArray,
+    numeric::ColumnInt32,
+};
 use common::{
     cleanup_test_database,
     create_isolated_test_client,
@@ -423,3 +427,50 @@ async fn test_array_decimal_roundtrip() {
 
     cleanup_test_database(&db_name).await;
 }
+
+// ============================================================================
+// Array(Array(Int32))
+// ============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_array_of_arrays_read_rows() {
+    let (mut client, db_name) =
+        create_isolated_test_client("array_array_int32")
+            .await
+            .expect("Failed to create test client");
+
+    let result = client
+        .query("SELECT [[1, 2], [3]]::Array(Array(Int32)) AS values")
+        .await
+        .expect("Failed to select");
+    assert_eq!(result.total_rows(), 1);
+
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let column = block.column(0).expect("Column not found");
+    let outer = column
+        .as_any()
+        .downcast_ref::<ColumnArray>()
+        .expect("Invalid column type");
+
+    let row = outer.get_array(0).expect("Row not found");
+    let row =
+        row.as_any().downcast_ref::<ColumnArray>().expect("Invalid row type");
+    assert_eq!(row.len(), 2);
+
+    let expected: [&[i32]; 2] = [&[1, 2], &[3]];
+    for (idx, values) in expected.iter().enumerate() {
+        let inner = row.get_array(idx).expect("Sub-array not found");
+        let inner = inner
+            .as_any()
+            .downcast_ref::<ColumnInt32>()
+            .expect("Invalid element type");
+        assert_eq!(inner.data(), *values);
+    }
+
+    cleanup_test_database(&db_name).await;
+}
@@ -1,3 +1,4 @@
+#![allow(clippy::approx_constant)]
 /// Integration tests for Array(Float32) column using Block insertion
 mod common;
 
@@ -198,7 +199,7 @@ proptest! {
     #[ignore]
     fn test_array_float32_block_insert_random(
         arrays in prop::collection::vec(
-            prop::collection::vec(any::<f32>().prop_filter("No NaN or Inf", |v| v.is_finite()), 0..20),
+            prop::collection::vec(any::<f32>(), 0..20),
             1..10
         )
     ) {
@@ -60,7 +60,7 @@ async fn test_date_block_insert_basic() {
         .downcast_ref::<ColumnDate>()
         .expect("Invalid column type");
 
-    let mut expected = vec![0, 19000, 30000];
+    let mut expected = [0, 19000, 30000];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
@@ -60,7 +60,7 @@ async fn test_date32_block_insert_basic() {
         .downcast_ref::<ColumnDate32>()
         .expect("Invalid column type");
 
-    let mut expected = vec![-25567, 0, 19000];
+    let mut expected = [-25567, 0, 19000];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
@@ -60,7 +60,7 @@ async fn test_datetime_block_insert_basic() {
         .downcast_ref::<ColumnDateTime>()
         .expect("Invalid column type");
 
-    let mut expected = vec![0, 1640995200, 2147483647];
+    let mut expected = [0, 1640995200, 2147483647];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
@@ -60,7 +60,7 @@ async fn test_datetime64_block_insert_basic() {
         .downcast_ref::<ColumnDateTime64>()
         .expect("Invalid column type");
 
-    let mut expected = vec![0, 1640995200000, 1640995200123];
+    let mut expected = [0, 1640995200000, 1640995200123];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
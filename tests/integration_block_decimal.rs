@@ -60,7 +60,7 @@ async fn test_decimal_block_insert_basic() {
         .downcast_ref::<ColumnDecimal>()
         .expect("Invalid column type");
 
-    let mut expected = vec![12345678, -98765432, 0];
+    let mut expected = [12345678, -98765432, 0];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
@@ -147,6 +147,71 @@ async fn test_enum8_block_insert_boundary() {
     cleanup_test_database(&db_name).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_enum8_block_insert_by_name() {
+    let (mut client, db_name) =
+        create_isolated_test_client("enum8_block_by_name")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (id UInt8, status Enum8('active' = 1, 'inactive' = 2)) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let status_type = Type::enum8(vec![
+        EnumItem { name: "active".to_string(), value: 1 },
+        EnumItem { name: "inactive".to_string(), value: 2 },
+    ]);
+    let mut id_col =
+        clickhouse_native_client::column::numeric::ColumnUInt8::new();
+    for id in 0..3 {
+        id_col.append(id);
+    }
+    let status_col = ColumnEnum8::from_names(
+        status_type,
+        vec!["active", "inactive", "active"],
+    )
+    .expect("Failed to resolve enum names");
+
+    let mut block = Block::new();
+    block
+        .append_column("id", Arc::new(id_col))
+        .expect("Failed to append id column");
+    block
+        .append_column("status", Arc::new(status_col))
+        .expect("Failed to append status column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!(
+            "SELECT toInt8(status) FROM {}.test_table ORDER BY id",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 3);
+    let block = &result.blocks()[0];
+    let column = block.column(0).expect("Column not found");
+    let codes = column
+        .as_any()
+        .downcast_ref::<clickhouse_native_client::column::numeric::ColumnInt8>(
+        )
+        .expect("Invalid column type");
+    assert_eq!(codes.data(), &[1, 2, 1]);
+
+    cleanup_test_database(&db_name).await;
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -1,3 +1,4 @@
+#![allow(clippy::approx_constant)]
 /// Integration tests for Float32 column using Block insertion
 mod common;
 
@@ -58,7 +59,7 @@ async fn test_float32_block_insert_basic() {
         .downcast_ref::<ColumnFloat32>()
         .expect("Invalid column type");
 
-    let mut expected = vec![3.14159, f32::MIN, f32::MAX];
+    let mut expected = [3.14159, f32::MIN, f32::MAX];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
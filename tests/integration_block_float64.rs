@@ -1,3 +1,4 @@
+#![allow(clippy::approx_constant)]
 /// Integration tests for Float64 column using Block insertion
 mod common;
 
@@ -58,7 +59,7 @@ async fn test_float64_block_insert_basic() {
         .downcast_ref::<ColumnFloat64>()
         .expect("Invalid column type");
 
-    let mut expected = vec![3.141592653589793, f64::MIN, f64::MAX];
+    let mut expected = [3.141592653589793, f64::MIN, f64::MAX];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -58,7 +58,7 @@ async fn test_int128_block_insert_basic() {
         .downcast_ref::<ColumnInt128>()
         .expect("Invalid column type");
 
-    let mut expected = vec![
+    let mut expected = [
         1000000000000,
         -170141183460469231731687303715884105728,
         170141183460469231731687303715884105727,
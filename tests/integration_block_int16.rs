@@ -58,7 +58,7 @@ async fn test_int16_block_insert_basic() {
         .downcast_ref::<ColumnInt16>()
         .expect("Invalid column type");
 
-    let mut expected = vec![1000, -32768, 32767];
+    let mut expected = [1000, -32768, 32767];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -58,7 +58,7 @@ async fn test_int32_block_insert_basic() {
         .downcast_ref::<ColumnInt32>()
         .expect("Invalid column type");
 
-    let mut expected = vec![100000, -2147483648, 2147483647];
+    let mut expected = [100000, -2147483648, 2147483647];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
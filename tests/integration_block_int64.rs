@@ -58,8 +58,7 @@ async fn test_int64_block_insert_basic() {
         .downcast_ref::<ColumnInt64>()
         .expect("Invalid column type");
 
-    let mut expected =
-        vec![1000000000, -9223372036854775808, 9223372036854775807];
+    let mut expected = [1000000000, -9223372036854775808, 9223372036854775807];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
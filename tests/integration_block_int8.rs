@@ -58,7 +58,7 @@ async fn test_int8_block_insert_basic() {
         .downcast_ref::<ColumnInt8>()
         .expect("Invalid column type");
 
-    let mut expected = vec![42, -128, 127];
+    let mut expected = [42, -128, 127];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -2,7 +2,10 @@
 mod common;
 
 use clickhouse_native_client::{
-    column::ipv4::ColumnIpv4,
+    column::{
+        ipv4::ColumnIpv4,
+        ipv6::ColumnIpv6,
+    },
     types::Type,
     Block,
 };
@@ -11,7 +14,13 @@ use common::{
     create_isolated_test_client,
 };
 use proptest::prelude::*;
-use std::sync::Arc;
+use std::{
+    net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    },
+    sync::Arc,
+};
 
 #[tokio::test]
 #[ignore]
@@ -60,7 +69,7 @@ async fn test_ipv4_block_insert_basic() {
         .downcast_ref::<ColumnIpv4>()
         .expect("Invalid column type");
 
-    let mut expected = vec![0x7F000001, 0xC0A80001, 0x08080808];
+    let mut expected = [0x7F000001, 0xC0A80001, 0x08080808];
     expected.sort();
 
     for (idx, exp) in expected.iter().enumerate() {
@@ -136,6 +145,73 @@ async fn test_ipv4_block_insert_boundary() {
     cleanup_test_database(&db_name).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_ip_block_std_net_roundtrip() {
+    let (mut client, db_name) =
+        create_isolated_test_client("ip_block_std_net")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (v4 IPv4, v6 IPv6) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let mut v4 = ColumnIpv4::new(Type::ipv4());
+    v4.append_addr(Ipv4Addr::new(192, 168, 1, 1));
+    let mut v6 = ColumnIpv6::new(Type::ipv6());
+    v6.append_addr(Ipv6Addr::LOCALHOST);
+
+    let mut block = Block::new();
+    block.append_column("v4", Arc::new(v4)).expect("Failed to append v4");
+    block.append_column("v6", Arc::new(v6)).expect("Failed to append v6");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!(
+            "SELECT v4, v6, toString(v4), toString(v6) FROM {}.test_table",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 1);
+    let block = &result.blocks()[0];
+    let v4 = block.column(0).expect("Column not found");
+    let v4 =
+        v4.as_any().downcast_ref::<ColumnIpv4>().expect("Invalid column type");
+    let v6 = block.column(1).expect("Column not found");
+    let v6 =
+        v6.as_any().downcast_ref::<ColumnIpv6>().expect("Invalid column type");
+
+    assert_eq!(v4.get(0), Some(Ipv4Addr::new(192, 168, 1, 1)));
+    assert_eq!(v6.get(0), Some(Ipv6Addr::LOCALHOST));
+
+    // The server reads the same addresses back
+    let text = block.column(2).expect("Column not found");
+    let text = text
+        .as_any()
+        .downcast_ref::<clickhouse_native_client::column::ColumnString>()
+        .expect("Invalid column type");
+    assert_eq!(text.at(0), "192.168.1.1");
+    let text = block.column(3).expect("Column not found");
+    let text = text
+        .as_any()
+        .downcast_ref::<clickhouse_native_client::column::ColumnString>()
+        .expect("Invalid column type");
+    assert_eq!(text.at(0), "::1");
+
+    cleanup_test_database(&db_name).await;
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
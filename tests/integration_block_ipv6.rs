@@ -112,8 +112,8 @@ async fn test_ipv6_block_insert_boundary() {
         }),
         ("Test value", {
             let mut addr = [0u8; 16];
-            for i in 0..16 {
-                addr[i] = i as u8;
+            for (i, byte) in addr.iter_mut().enumerate() {
+                *byte = i as u8;
             }
             addr
         }),
@@ -127,7 +127,7 @@ async fn test_nullable_ipv6_block_insert_boundary() {
                 nullable_col.append_non_null();
                 nullable_col
                     .nested_mut::<ColumnIpv6>()
-                    .append_from_string(*value)
+                    .append_from_string(value)
                     .expect("Failed to parse IPv6");
             }
             None => {
@@ -59,7 +59,7 @@ async fn test_uint128_block_insert_basic() {
         .expect("Invalid column type");
 
     let mut expected =
-        vec![1000000000000, 0, 340282366920938463463374607431768211455];
+        [1000000000000, 0, 340282366920938463463374607431768211455];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
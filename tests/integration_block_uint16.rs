@@ -58,7 +58,7 @@ async fn test_uint16_block_insert_basic() {
         .downcast_ref::<ColumnUInt16>()
         .expect("Invalid column type");
 
-    let mut expected = vec![1000, 0, 65535];
+    let mut expected = [1000, 0, 65535];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
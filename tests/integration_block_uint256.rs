@@ -0,0 +1,147 @@
+/// Integration tests for Int256/UInt256 columns using Block insertion
+mod common;
+
+use clickhouse_native_client::{
+    column::{
+        numeric::{
+            ColumnInt256,
+            ColumnUInt256,
+            ColumnUInt8,
+            Int256,
+            UInt256,
+        },
+        ColumnString,
+    },
+    Block,
+};
+use common::{
+    cleanup_test_database,
+    create_isolated_test_client,
+};
+use std::sync::Arc;
+
+#[tokio::test]
+#[ignore]
+async fn test_uint256_block_insert_boundary() {
+    let (mut client, db_name) =
+        create_isolated_test_client("uint256_block_boundary")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (id UInt8, value UInt256) \
+             ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let expected = [UInt256::ZERO, UInt256::from(1u128 << 100), UInt256::MAX];
+
+    let mut ids = ColumnUInt8::new();
+    let mut col = ColumnUInt256::new();
+    for (i, value) in expected.iter().enumerate() {
+        ids.append(i as u8);
+        col.append(*value);
+    }
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(ids)).expect("Failed to append id");
+    block
+        .append_column("value", Arc::new(col))
+        .expect("Failed to append column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    // The server must agree on the value, not just echo the bytes back
+    let result = client
+        .query(format!(
+            "SELECT value, toString(value) FROM {}.test_table ORDER BY id",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 3);
+    let block = result.first_block().expect("No data block");
+    let col_ref = block.column(0).expect("Column not found");
+    let result_col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnUInt256>()
+        .expect("Invalid column type");
+    for (idx, exp) in expected.iter().enumerate() {
+        assert_eq!(result_col.at(idx), *exp);
+    }
+
+    let text = block.column(1).expect("Column not found");
+    let text = text
+        .as_any()
+        .downcast_ref::<ColumnString>()
+        .expect("Invalid column type");
+    assert_eq!(text.at(0), "0");
+    assert_eq!(text.at(1), "1267650600228229401496703205376");
+    assert_eq!(
+        text.at(2),
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+    );
+
+    cleanup_test_database(&db_name).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_int256_block_insert_boundary() {
+    let (mut client, db_name) =
+        create_isolated_test_client("int256_block_boundary")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (value Int256) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let expected =
+        [Int256::MIN, Int256::from(-1i128), Int256::ZERO, Int256::MAX];
+
+    let mut col = ColumnInt256::new();
+    for value in expected {
+        col.append(value);
+    }
+    let mut block = Block::new();
+    block
+        .append_column("value", Arc::new(col))
+        .expect("Failed to append column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!(
+            "SELECT value FROM {}.test_table ORDER BY value",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 4);
+    let block = result.first_block().expect("No data block");
+    let col_ref = block.column(0).expect("Column not found");
+    let result_col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnInt256>()
+        .expect("Invalid column type");
+    for (idx, exp) in expected.iter().enumerate() {
+        assert_eq!(result_col.at(idx), *exp);
+    }
+
+    cleanup_test_database(&db_name).await;
+}
@@ -58,7 +58,7 @@ async fn test_uint32_block_insert_basic() {
         .downcast_ref::<ColumnUInt32>()
         .expect("Invalid column type");
 
-    let mut expected = vec![100000, 0, 4294967295];
+    let mut expected = [100000, 0, 4294967295];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
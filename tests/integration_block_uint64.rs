@@ -58,7 +58,7 @@ async fn test_uint64_block_insert_basic() {
         .downcast_ref::<ColumnUInt64>()
         .expect("Invalid column type");
 
-    let mut expected = vec![1000000000, 0, 18446744073709551615];
+    let mut expected = [1000000000, 0, 18446744073709551615];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -58,7 +58,7 @@ async fn test_uint8_block_insert_basic() {
         .downcast_ref::<ColumnUInt8>()
         .expect("Invalid column type");
 
-    let mut expected = vec![42, 0, 255];
+    let mut expected = [42, 0, 255];
     expected
         .sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
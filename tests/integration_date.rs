@@ -244,6 +244,55 @@ async fn test_datetime64_roundtrip() {
     cleanup_test_database(&db_name).await;
 }
 
+#[cfg(feature = "chrono")]
+#[tokio::test]
+#[ignore]
+async fn test_datetime64_chrono_roundtrip() {
+    use chrono::{
+        DateTime,
+        Utc,
+    };
+
+    let (mut client, db_name) =
+        create_isolated_test_client("datetime64_chrono_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (dt DateTime64(3, 'UTC')) \
+             ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let instant: DateTime<Utc> = "2024-05-17T08:30:15.250Z".parse().unwrap();
+    let mut block = Block::new();
+    let mut col =
+        ColumnDateTime64::new(Type::datetime64(3, Some("UTC".to_string())));
+    col.append_datetime(instant);
+    block.append_column("dt", Arc::new(col)).expect("Failed to append column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!("SELECT dt FROM {}.test_table", db_name))
+        .await
+        .expect("Failed to select");
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    let result_col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnDateTime64>()
+        .expect("Invalid column type");
+    assert_eq!(result_col.get_datetime(0), instant);
+
+    cleanup_test_database(&db_name).await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_datetime64_various_precisions() {
@@ -124,3 +124,58 @@ async fn test_decimal_various_scales() {
         cleanup_test_database(&db_name).await;
     }
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_decimal256_roundtrip_beyond_i128() {
+    let (mut client, db_name) =
+        create_isolated_test_client("decimal256_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (value Decimal256(10)) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    // Does not fit in i128 once scaled by 10^10
+    let big = "1234567890123456789012345678901234567890.0123456789";
+
+    let mut block = Block::new();
+    let mut col = ColumnDecimal::new(Type::parse("Decimal256(10)").unwrap());
+    col.append_from_string(big).unwrap();
+    col.append_from_string(&format!("-{}", big)).unwrap();
+    block
+        .append_column("value", Arc::new(col))
+        .expect("Failed to append column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!(
+            "SELECT value, toString(value) FROM {}.test_table ORDER BY value",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 2);
+    let result_block = &result.blocks()[0];
+    let col_ref = result_block.column(0).expect("Column not found");
+    let result_col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnDecimal>()
+        .expect("Invalid column type");
+
+    assert_eq!(result_col.precision(), 76);
+    assert_eq!(result_col.as_string(0), format!("-{}", big));
+    assert_eq!(result_col.as_string(1), big);
+
+    cleanup_test_database(&db_name).await;
+}
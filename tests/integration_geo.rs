@@ -0,0 +1,95 @@
+/// Integration tests for geo types (Point, Ring, Polygon, MultiPolygon)
+mod common;
+
+use clickhouse_native_client::{
+    column::{
+        geo::{
+            ColumnPoint,
+            ColumnPolygon,
+            Ring,
+        },
+        ColumnUInt8,
+    },
+    Block,
+};
+use common::{
+    cleanup_test_database,
+    create_isolated_test_client,
+};
+use std::sync::Arc;
+
+#[tokio::test]
+#[ignore]
+async fn test_polygon_roundtrip() {
+    let (mut client, db_name) =
+        create_isolated_test_client("polygon_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (id UInt8, shape Polygon) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let outer: Ring =
+        vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)];
+    let hole: Ring = vec![(2.0, 2.0), (4.0, 2.0), (4.0, 4.0), (2.0, 2.0)];
+
+    let mut ids = ColumnUInt8::new();
+    ids.append(1);
+    ids.append(2);
+    let mut shapes = ColumnPolygon::polygon();
+    shapes.append(&[outer.clone(), hole.clone()]);
+    shapes.append(std::slice::from_ref(&outer));
+
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(ids)).expect("Failed to append id");
+    block
+        .append_column("shape", Arc::new(shapes))
+        .expect("Failed to append shape");
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!("SELECT shape FROM {}.test_table ORDER BY id", db_name))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 2);
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    let col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnPolygon>()
+        .expect("Invalid column type");
+    assert_eq!(col.get(0), Some(vec![outer.clone(), hole]));
+    assert_eq!(col.get(1), Some(vec![outer]));
+
+    cleanup_test_database(&db_name).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_point_select() {
+    let (mut client, db_name) = create_isolated_test_client("point_select")
+        .await
+        .expect("Failed to create test client");
+
+    let result = client
+        .query("SELECT CAST((1.5, -2.0), 'Point') AS p")
+        .await
+        .expect("Failed to select");
+
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    let col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnPoint>()
+        .expect("Invalid column type");
+    assert_eq!(col.get(0), Some((1.5, -2.0)));
+
+    cleanup_test_database(&db_name).await;
+}
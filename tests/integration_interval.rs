@@ -0,0 +1,40 @@
+/// Integration tests for Interval columns returned by SELECT
+mod common;
+
+use clickhouse_native_client::{
+    column::ColumnInterval,
+    types::IntervalUnit,
+};
+use common::create_test_client;
+
+#[tokio::test]
+#[ignore]
+async fn test_select_interval_values() {
+    let mut client =
+        create_test_client().await.expect("Failed to create test client");
+
+    let result = client
+        .query(
+            "SELECT INTERVAL 3 DAY AS days, toIntervalSecond(-5) AS secs, \
+             toIntervalMonth(number) AS months FROM numbers(2)",
+        )
+        .await
+        .expect("Failed to select intervals");
+
+    assert_eq!(result.total_rows(), 2);
+    let block = result.first_block().expect("No data block");
+
+    let days = block.column(0).unwrap();
+    let days = days.as_any().downcast_ref::<ColumnInterval>().unwrap();
+    assert_eq!(days.unit(), IntervalUnit::Day);
+    assert_eq!(days.at(0), (3, IntervalUnit::Day));
+
+    let secs = block.column(1).unwrap();
+    let secs = secs.as_any().downcast_ref::<ColumnInterval>().unwrap();
+    assert_eq!(secs.at(1), (-5, IntervalUnit::Second));
+
+    let months = block.column(2).unwrap();
+    let months = months.as_any().downcast_ref::<ColumnInterval>().unwrap();
+    assert_eq!(months.at(0), (0, IntervalUnit::Month));
+    assert_eq!(months.at(1), (1, IntervalUnit::Month));
+}
@@ -0,0 +1,64 @@
+/// Integration tests for the JSON type (String-backed representation)
+mod common;
+
+use clickhouse_native_client::{
+    column::json::ColumnJson,
+    Query,
+};
+use common::{
+    cleanup_test_database,
+    create_isolated_test_client,
+};
+
+#[tokio::test]
+#[ignore]
+async fn test_json_document_roundtrip() {
+    let (mut client, db_name) = create_isolated_test_client("json_roundtrip")
+        .await
+        .expect("Failed to create test client");
+
+    client
+        .query(
+            Query::new(format!(
+                "CREATE TABLE {}.test_table (id UInt32, doc JSON) ENGINE = Memory",
+                db_name
+            ))
+            .with_setting("allow_experimental_json_type", "1"),
+        )
+        .await
+        .expect("Failed to create table");
+
+    client
+        .query(format!(
+            r#"INSERT INTO {}.test_table VALUES (1, '{{"user": {{"name": "alice", "id": 7}}}}')"#,
+            db_name
+        ))
+        .await
+        .expect("Failed to insert");
+
+    // Without this setting the server sends its binary JSON serialization
+    let result = client
+        .query(
+            Query::new(format!("SELECT doc FROM {}.test_table", db_name))
+                .with_setting(
+                    "output_format_native_write_json_as_string",
+                    "1",
+                ),
+        )
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 1);
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    let col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnJson>()
+        .expect("Invalid column type");
+    let document = col.get(0).expect("Missing document");
+    assert!(document.contains("alice"), "unexpected document: {}", document);
+
+    #[cfg(feature = "json")]
+    assert_eq!(col.get_json(0).unwrap()["user"]["id"], 7);
+
+    cleanup_test_database(&db_name).await;
+}
@@ -1,9 +1,14 @@
 /// Integration tests for Map compound types
 /// Tests: Map(Int8, String), Map(String, Array(Array(Int8))),
 ///        Map(UUID, Nullable(String)), Map(UUID,
-/// Nullable(LowCardinality(String)))
+/// Nullable(LowCardinality(String))), Map(String, UInt64)
 mod common;
 
+use clickhouse_native_client::column::{
+    map::ColumnMap,
+    numeric::ColumnUInt64,
+    ColumnString,
+};
 use common::{
     cleanup_test_database,
     create_isolated_test_client,
@@ -209,3 +214,67 @@ async fn test_map_empty() {
 
     cleanup_test_database(&db_name).await;
 }
+
+// ============================================================================
+// Map(String, UInt64)
+// ============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_map_string_uint64_entries() {
+    let (mut client, db_name) =
+        create_isolated_test_client("map_string_uint64")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (data Map(String, UInt64)) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    client
+        .query(format!(
+            "INSERT INTO {}.test_table VALUES ({{'a': 1, 'b': 2}})",
+            db_name
+        ))
+        .await
+        .expect("Failed to insert");
+
+    let result = client
+        .query(format!("SELECT data FROM {}.test_table", db_name))
+        .await
+        .expect("Failed to select");
+    assert_eq!(result.total_rows(), 1);
+
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let column = block.column(0).expect("Column not found");
+    let map = column
+        .as_any()
+        .downcast_ref::<ColumnMap>()
+        .expect("Invalid column type");
+    assert_eq!(map.get_map_len(0), Some(2));
+
+    let keys = map.keys_at(0).expect("Failed to read keys");
+    let keys = keys
+        .as_any()
+        .downcast_ref::<ColumnString>()
+        .expect("Invalid key type");
+    let values = map.values_at(0).expect("Failed to read values");
+    let values = values
+        .as_any()
+        .downcast_ref::<ColumnUInt64>()
+        .expect("Invalid value type");
+
+    let entries: Vec<(&str, u64)> =
+        keys.iter().zip(values.data().iter().copied()).collect();
+    assert_eq!(entries, vec![("a", 1), ("b", 2)]);
+
+    cleanup_test_database(&db_name).await;
+}
@@ -0,0 +1,91 @@
+/// Integration tests for the Nested type
+mod common;
+
+use clickhouse_native_client::{
+    column::{
+        array::ColumnArray,
+        nested::ColumnNested,
+        numeric::ColumnUInt64,
+        ColumnString,
+    },
+    types::Type,
+    Block,
+    Query,
+};
+use common::{
+    cleanup_test_database,
+    create_isolated_test_client,
+};
+use std::sync::Arc;
+
+#[tokio::test]
+#[ignore]
+async fn test_nested_two_fields_roundtrip() {
+    let (mut client, db_name) =
+        create_isolated_test_client("nested_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    // With flatten_nested = 0 the table keeps a real Nested column instead
+    // of splitting it into `attrs.name` / `attrs.value`
+    client
+        .query(
+            Query::new(format!(
+                "CREATE TABLE {}.test_table (attrs Nested(name String, value UInt64)) ENGINE = Memory",
+                db_name
+            ))
+            .with_setting("flatten_nested", "0"),
+        )
+        .await
+        .expect("Failed to create table");
+
+    let nested_type = Type::parse("Nested(name String, value UInt64)")
+        .expect("Failed to parse type");
+    let mut names = ColumnArray::new(Type::array(Type::string()));
+    names.append_array(Arc::new(ColumnString::from(vec!["a", "b"])));
+    names.append_array(Arc::new(ColumnString::from(vec!["c"])));
+    let mut values = ColumnArray::new(Type::array(Type::uint64()));
+    values.append_array(Arc::new(ColumnUInt64::from_vec(
+        Type::uint64(),
+        vec![1, 2],
+    )));
+    values.append_array(Arc::new(ColumnUInt64::from_vec(
+        Type::uint64(),
+        vec![3],
+    )));
+    let col = ColumnNested::from_fields(nested_type, vec![names, values])
+        .expect("Failed to build nested column");
+
+    let mut block = Block::new();
+    block
+        .append_column("attrs", Arc::new(col))
+        .expect("Failed to append column");
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!("SELECT attrs FROM {}.test_table", db_name))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 2);
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    let col = col_ref
+        .as_any()
+        .downcast_ref::<ColumnNested>()
+        .expect("Invalid column type");
+    assert_eq!(col.names(), ["name", "value"]);
+
+    let names = col.field("name").expect("Missing name field");
+    assert_eq!(names.offsets(), [2, 3]);
+    let strings: &ColumnString = names.nested();
+    assert_eq!(strings.get(2), Some("c"));
+
+    let values = col.field("value").expect("Missing value field");
+    let numbers: &ColumnUInt64 = values.nested();
+    assert_eq!(numbers.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    cleanup_test_database(&db_name).await;
+}
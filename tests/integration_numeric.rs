@@ -4,8 +4,12 @@
 mod common;
 
 use clickhouse_native_client::{
-    column::numeric::*,
+    column::{
+        numeric::*,
+        ColumnBool,
+    },
     Block,
+    FromSql,
 };
 use common::{
     cleanup_test_database,
@@ -707,8 +711,80 @@ async fn test_float64_roundtrip() {
     cleanup_test_database(&db_name).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_float_nan_inf_roundtrip() {
+    let (mut client, db_name) =
+        create_isolated_test_client("float_nan_inf_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (id UInt8, f32 Float32, f64 Float64) \
+             ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let mut block = Block::new();
+    let mut ids = ColumnUInt8::new();
+    let mut f32s = ColumnFloat32::new();
+    let mut f64s = ColumnFloat64::new();
+    let specials = [
+        (f32::NAN, f64::NAN),
+        (f32::INFINITY, f64::INFINITY),
+        (f32::NEG_INFINITY, f64::NEG_INFINITY),
+    ];
+    for (id, &(f32_value, f64_value)) in specials.iter().enumerate() {
+        ids.append(id as u8);
+        f32s.append(f32_value);
+        f64s.append(f64_value);
+    }
+    block.append_column("id", Arc::new(ids)).expect("Failed to append id");
+    block.append_column("f32", Arc::new(f32s)).expect("Failed to append f32");
+    block.append_column("f64", Arc::new(f64s)).expect("Failed to append f64");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!(
+            "SELECT f32, f64 FROM {}.test_table ORDER BY id",
+            db_name
+        ))
+        .await
+        .expect("Failed to select");
+
+    assert_eq!(result.total_rows(), 3);
+    let result_block = &result.blocks()[0];
+    let f32_ref = result_block.column(0).expect("Column not found");
+    let f32_col = f32_ref
+        .as_any()
+        .downcast_ref::<ColumnFloat32>()
+        .expect("Invalid column type");
+    let f64_ref = result_block.column(1).expect("Column not found");
+    let f64_col = f64_ref
+        .as_any()
+        .downcast_ref::<ColumnFloat64>()
+        .expect("Invalid column type");
+
+    // NaN never equals itself, so check the classification instead of `==`
+    assert!(f32_col.at(0).is_nan());
+    assert!(f64_col.at(0).is_nan());
+    assert!(f32_col.at(1).is_infinite() && f32_col.at(1).is_sign_positive());
+    assert!(f64_col.at(1).is_infinite() && f64_col.at(1).is_sign_positive());
+    assert!(f32_col.at(2).is_infinite() && f32_col.at(2).is_sign_negative());
+    assert!(f64_col.at(2).is_infinite() && f64_col.at(2).is_sign_negative());
+
+    cleanup_test_database(&db_name).await;
+}
+
 // ============================================================================
-// Bool Tests (Bool is UInt8 with 0/1 values)
+// Bool Tests (Bool has its own column, one byte of 0/1 per row)
 // ============================================================================
 
 #[tokio::test]
@@ -750,15 +826,60 @@ async fn test_bool_roundtrip() {
     let result_block = &result.blocks()[0];
     let col_ref = result_block.column(0).expect("Column not found");
 
+    assert_eq!(col_ref.column_type().name(), "Bool");
     let result_col = col_ref
         .as_any()
-        .downcast_ref::<ColumnUInt8>()
+        .downcast_ref::<ColumnBool>()
         .expect("Invalid column type");
 
-    assert_eq!(result_col.at(0), 0);
-    assert_eq!(result_col.at(1), 1);
-    assert_eq!(result_col.at(2), 0);
-    assert_eq!(result_col.at(3), 1);
+    assert!(!result_col.at(0));
+    assert!(result_col.at(1));
+    assert!(!result_col.at(2));
+    assert!(result_col.at(3));
+
+    cleanup_test_database(&db_name).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_bool_column_roundtrip() {
+    let (mut client, db_name) =
+        create_isolated_test_client("bool_column_roundtrip")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (id UInt8, flag Bool) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let mut block = Block::new();
+    block
+        .append_column("id", Arc::new(ColumnUInt8::from(vec![1, 2, 3])))
+        .expect("Failed to append column");
+    block
+        .append_column(
+            "flag",
+            Arc::new(ColumnBool::from(vec![true, false, true])),
+        )
+        .expect("Failed to append column");
+
+    client
+        .insert(&format!("{}.test_table", db_name), block)
+        .await
+        .expect("Failed to insert block");
+
+    let result = client
+        .query(format!("SELECT flag FROM {}.test_table ORDER BY id", db_name))
+        .await
+        .expect("Failed to select");
+
+    let col_ref = result.blocks()[0].column(0).expect("Column not found");
+    assert_eq!(col_ref.column_type().name(), "Bool");
+    assert_eq!(bool::from_column(&col_ref).unwrap(), [true, false, true]);
 
     cleanup_test_database(&db_name).await;
 }
@@ -856,7 +977,7 @@ proptest! {
 
     #[test]
     #[ignore]
-    fn prop_test_float64_values(values in prop::collection::vec(any::<f64>().prop_filter("filter NaN/Inf", |x| x.is_finite()), 1..100)) {
+    fn prop_test_float64_values(values in prop::collection::vec(any::<f64>(), 1..100)) {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
             let (mut client, db_name) = create_isolated_test_client("prop_float64")
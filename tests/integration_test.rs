@@ -1,6 +1,6 @@
 use clickhouse_native_client::{
     column::{
-        nullable::ColumnNullable,
+        nullable::ColumnNullableT,
         numeric::ColumnUInt64,
         string::ColumnString,
     },
@@ -459,29 +459,12 @@ async fn test_nullable_column_insertion() {
     id_col.append(2);
     id_col.append(3);
 
-    // Create nullable column
-    let nested_col = Arc::new(ColumnUInt64::new());
-    let mut nullable_col = ColumnNullable::with_nested(nested_col);
-
-    // Append values: Some(100), None, Some(300)
-    // Manually append since append_nullable is only for UInt32
-    nullable_col.append_non_null();
-    {
-        let col = nullable_col.nested_mut::<ColumnUInt64>();
-        col.append(100);
-    }
-
-    nullable_col.append_null();
-    {
-        let col = nullable_col.nested_mut::<ColumnUInt64>();
-        col.append(0); // Placeholder for NULL
-    }
-
-    nullable_col.append_non_null();
-    {
-        let col = nullable_col.nested_mut::<ColumnUInt64>();
-        col.append(300);
-    }
+    // Create nullable column with values Some(100), None, Some(300)
+    let mut nullable_col =
+        ColumnNullableT::from_nested(Arc::new(ColumnUInt64::new()));
+    nullable_col.append(Some(100u64));
+    nullable_col.append::<u64>(None);
+    nullable_col.append(Some(300u64));
 
     block
         .append_column("id", Arc::new(id_col))
@@ -1651,6 +1634,53 @@ async fn test_lowcardinality_deduplication() {
     println!("  ✓ Query succeeded, rows: {}", result.total_rows());
     assert_eq!(result.total_rows(), 10);
 
+    // Read the logical values back through the dictionaries
+    use clickhouse_native_client::column::ColumnLowCardinality;
+
+    let mut statuses = Vec::new();
+    let mut countries = Vec::new();
+    for block in result.blocks() {
+        let status = block.column(1).expect("Missing status column");
+        let status = status
+            .as_any()
+            .downcast_ref::<ColumnLowCardinality>()
+            .expect("status should be LowCardinality");
+        let country = block.column(2).expect("Missing country column");
+        let country = country
+            .as_any()
+            .downcast_ref::<ColumnLowCardinality>()
+            .expect("country should be LowCardinality");
+        for row in 0..block.row_count() {
+            statuses.push(status.get::<ColumnString, String>(row).unwrap());
+            countries.push(country.get::<ColumnString, String>(row));
+        }
+    }
+
+    assert_eq!(
+        statuses,
+        [
+            "active", "inactive", "active", "pending", "active", "inactive",
+            "active", "pending", "active", "inactive",
+        ]
+    );
+    assert_eq!(
+        countries,
+        [
+            Some("US"),
+            Some("UK"),
+            Some("US"),
+            None,
+            Some("US"),
+            Some("DE"),
+            Some("UK"),
+            Some("US"),
+            Some("US"),
+            None,
+        ]
+        .map(|country| country.map(String::from))
+    );
+    println!("  ✓ Values decoded correctly");
+
     // Verify deduplication by checking unique values
     let unique_status = client
         .query(format!(
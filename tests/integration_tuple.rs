@@ -1,8 +1,12 @@
 /// Integration tests for Tuple compound types
 /// Tests: Tuple(Float32, Float64), Tuple(Int32, Int64), Tuple(String, Int64),
-///        Tuple(String, Int64, Array(String))
+///        Tuple(String, Int64, Array(String)), Tuple(Float64, Float64)
 mod common;
 
+use clickhouse_native_client::column::{
+    numeric::ColumnFloat64,
+    tuple::ColumnTuple,
+};
 use common::{
     cleanup_test_database,
     create_isolated_test_client,
@@ -204,3 +208,65 @@ async fn test_tuple_with_empty_values() {
 
     cleanup_test_database(&db_name).await;
 }
+
+// ============================================================================
+// Tuple(Float64, Float64) element access
+// ============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_tuple_float64_element_access() {
+    let (mut client, db_name) =
+        create_isolated_test_client("tuple_float64_elements")
+            .await
+            .expect("Failed to create test client");
+
+    client
+        .query(format!(
+            "CREATE TABLE {}.test_table (point Tuple(Float64, Float64)) ENGINE = Memory",
+            db_name
+        ))
+        .await
+        .expect("Failed to create table");
+
+    client
+        .query(format!(
+            "INSERT INTO {}.test_table VALUES ((1.5, 2.5))",
+            db_name
+        ))
+        .await
+        .expect("Failed to insert");
+
+    let result = client
+        .query(format!("SELECT point FROM {}.test_table", db_name))
+        .await
+        .expect("Failed to select");
+    assert_eq!(result.total_rows(), 1);
+
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let column = block.column(0).expect("Column not found");
+    let tuple = column
+        .as_any()
+        .downcast_ref::<ColumnTuple>()
+        .expect("Invalid column type");
+    assert_eq!(tuple.column_count(), 2);
+
+    let values: Vec<f64> = (0..tuple.column_count())
+        .map(|i| {
+            tuple
+                .element(i)
+                .expect("Missing element")
+                .as_any()
+                .downcast_ref::<ColumnFloat64>()
+                .expect("Invalid element type")
+                .at(0)
+        })
+        .collect();
+    assert_eq!(values, vec![1.5, 2.5]);
+
+    cleanup_test_database(&db_name).await;
+}
@@ -682,3 +682,59 @@ async fn test_tuple_with_array_lowcardinality() {
     // Cleanup
     let _ = client.query("DROP TABLE test_tuple_array_lc").await;
 }
+
+// ============================================================================
+// Nested column flattening
+// ============================================================================
+
+#[tokio::test]
+#[ignore]
+async fn test_nested_columns_grouped_by_prefix() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    client
+        .query("DROP TABLE IF EXISTS test_nested_groups")
+        .await
+        .expect("Failed to drop table");
+    client
+        .query(
+            "CREATE TABLE test_nested_groups (
+                id UInt64,
+                a Nested(x UInt64, y String)
+            ) ENGINE = Memory",
+        )
+        .await
+        .expect("Failed to create table");
+    client
+        .query(
+            "INSERT INTO test_nested_groups VALUES \
+             (1, [1, 2], ['one', 'two']), (2, [3], ['three'])",
+        )
+        .await
+        .expect("Failed to insert data");
+
+    let result = client
+        .query(Query::new(
+            "SELECT id, a.x, a.y FROM test_nested_groups ORDER BY id",
+        ))
+        .await
+        .expect("Failed to select nested columns");
+
+    let block = &result.blocks()[0];
+    let groups = block.nested_groups();
+    assert_eq!(groups.len(), 1);
+
+    let fields: Vec<&str> =
+        groups["a"].iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(fields, vec!["x", "y"]);
+    for (_, column) in &groups["a"] {
+        assert_eq!(column.size(), 2);
+        assert!(column.column_type().name().starts_with("Array("));
+    }
+
+    client
+        .query("DROP TABLE test_nested_groups")
+        .await
+        .expect("Failed to drop table");
+}
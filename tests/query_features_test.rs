@@ -16,13 +16,25 @@
 //! - Query ID with INSERT operations
 //! - Settings affecting execution
 //! - NULL parameter handling
+//! - Typed parameter binding (arrays, escaped strings)
+//! - Single-column, scalar and key/value results collected into typed values
+//! - Typed resource hints
+//! - Identifier and string literal quoting
+//! - INSERT with server-side CAST of column types
+//! - WITH TOTALS and extremes rows kept apart from the result
+//! - Rows before LIMIT from the profile info on the result
 
 use clickhouse_native_client::{
+    quote_identifier,
+    quote_string_literal,
     Client,
     ClientOptions,
+    FromSql,
     Query,
+    ResourceHints,
     TracingContext,
 };
+use std::collections::HashMap;
 
 /// Helper to create a test client
 async fn create_test_client() -> Result<Client, Box<dyn std::error::Error>> {
@@ -240,6 +252,93 @@ async fn test_multiple_parameters() {
     assert_eq!(result.total_rows(), 1);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_typed_parameters() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let query = Query::new(
+        "SELECT {arr:Array(UInt32)} AS arr, {names:Array(String)} AS names, \
+         {name:String} AS name",
+    )
+    .with_param("arr", vec![1u32, 2, 3])
+    .with_param("names", vec!["it's", "back\\slash"])
+    .with_param("name", "tab\there");
+
+    let result = client.query(query).await.expect("Typed parameters failed");
+    let block = &result.blocks()[0];
+
+    let arr = Vec::<u32>::from_column(&block.column(0).unwrap()).unwrap();
+    assert_eq!(arr, vec![vec![1, 2, 3]]);
+    let names = Vec::<String>::from_column(&block.column(1).unwrap()).unwrap();
+    assert_eq!(
+        names,
+        vec![vec!["it's".to_string(), "back\\slash".to_string()]]
+    );
+    let name = String::from_column(&block.column(2).unwrap()).unwrap();
+    assert_eq!(name, vec!["tab\there".to_string()]);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_column_and_scalar() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    // Small blocks so the column spans several of them
+    let query = Query::new("SELECT number FROM system.numbers LIMIT 10")
+        .with_setting_num("max_block_size", 3);
+    let numbers: Vec<u64> =
+        client.query_column(query).await.expect("query_column failed");
+    assert_eq!(numbers, (0..10).collect::<Vec<u64>>());
+
+    let names: Vec<String> = client
+        .query_column("SELECT toString(number) FROM system.numbers LIMIT 2")
+        .await
+        .expect("query_column failed");
+    assert_eq!(names, ["0", "1"]);
+
+    let count: u64 = client
+        .query_scalar("SELECT count() FROM system.one")
+        .await
+        .expect("query_scalar failed");
+    assert_eq!(count, 1);
+
+    assert!(client.query_column::<u64>("SELECT 1, 2").await.is_err());
+    assert!(client.query_scalar::<String>("SELECT 1").await.is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_query_map() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let settings: HashMap<String, String> = client
+        .query_map(
+            Query::new(
+                "SELECT name, value FROM system.settings \
+                 WHERE name IN ('max_threads', 'max_block_size')",
+            )
+            .with_setting_num("max_block_size", 4321),
+        )
+        .await
+        .expect("query_map failed");
+    assert_eq!(settings.len(), 2);
+    assert_eq!(settings["max_block_size"], "4321");
+
+    let squares: HashMap<u64, u64> = client
+        .query_map(
+            "SELECT number, number * number FROM system.numbers LIMIT 4",
+        )
+        .await
+        .expect("query_map failed");
+    assert_eq!(squares, HashMap::from([(0, 0), (1, 1), (2, 4), (3, 9)]));
+
+    assert!(client.query_map::<u64, u64>("SELECT 1").await.is_err());
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_combined_features() {
@@ -311,3 +410,299 @@ async fn test_settings_readonly() {
         Err(e) => println!("Expected error with readonly: {}", e),
     }
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_preserves_settings_clause() {
+    use clickhouse_native_client::{
+        column::numeric::ColumnUInt64,
+        Block,
+    };
+    use std::sync::Arc;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_settings_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id UInt64) ENGINE = MergeTree ORDER BY id",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    let mut col = ColumnUInt64::new();
+    col.append(1);
+    col.append(2);
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(col)).unwrap();
+
+    let query_id = format!("{}_insert", table);
+    let query = Query::new(format!(
+        "INSERT INTO {} SETTINGS async_insert=1, wait_for_async_insert=1 VALUES",
+        table
+    ))
+    .with_query_id(&query_id);
+    client.insert_query(query, block).await.expect("Insert failed");
+
+    let result = client
+        .query(format!("SELECT id FROM {}", table))
+        .await
+        .expect("Select failed");
+    assert_eq!(result.total_rows(), 2);
+
+    // The SETTINGS clause must reach the server with the INSERT
+    client.execute("SYSTEM FLUSH LOGS").await.expect("Flush logs failed");
+    let result = client
+        .query(format!(
+            "SELECT Settings['async_insert'] FROM system.query_log \
+             WHERE query_id = '{}' AND type = 'QueryFinish'",
+            query_id
+        ))
+        .await
+        .expect("Failed to query query_log");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No query_log row for the INSERT");
+    let col = block.column(0).unwrap();
+    let col = col
+        .as_any()
+        .downcast_ref::<clickhouse_native_client::column::ColumnString>()
+        .unwrap();
+    assert_eq!(col.at(0), "1");
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_resource_hints_max_threads() {
+    use clickhouse_native_client::column::{
+        ColumnString,
+        ColumnUInt64,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let query_id = format!(
+        "test_resource_hints_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    let query = Query::new("SELECT sum(number) FROM numbers(10000000)")
+        .with_query_id(&query_id)
+        .with_resources(ResourceHints {
+            max_threads: Some(1),
+            max_memory_usage: Some(1024 * 1024 * 1024),
+            priority: Some(1),
+        })
+        .expect("Valid hints");
+    client.query(query).await.expect("Query failed");
+
+    client.execute("SYSTEM FLUSH LOGS").await.expect("Flush logs failed");
+    let result = client
+        .query(format!(
+            "SELECT Settings['max_threads'], toUInt64(length(thread_ids)) \
+             FROM system.query_log \
+             WHERE query_id = '{}' AND type = 'QueryFinish'",
+            query_id
+        ))
+        .await
+        .expect("Failed to query query_log");
+
+    let block = result.blocks().first().expect("query_log row missing");
+    let max_threads = block.column(0).unwrap();
+    let max_threads =
+        max_threads.as_any().downcast_ref::<ColumnString>().unwrap();
+    assert_eq!(max_threads.at(0), "1");
+
+    // Only the main thread and a single pipeline thread may participate
+    let threads = block.column(1).unwrap();
+    let threads = threads.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert!(threads.at(0) <= 2, "used {} threads", threads.at(0));
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_quoting_helpers_produce_valid_sql() {
+    use clickhouse_native_client::column::ColumnString;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = quote_identifier(&format!(
+        "test`quote {}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    let column = quote_identifier("it`s");
+    let value = "O'Reilly \\ 'quoted'\n";
+
+    client
+        .execute(format!(
+            "CREATE TABLE {} ({} String) ENGINE = Memory",
+            table, column
+        ))
+        .await
+        .expect("Failed to create table with quoted names");
+    client
+        .execute(format!(
+            "INSERT INTO {} VALUES ({})",
+            table,
+            quote_string_literal(value)
+        ))
+        .await
+        .expect("Failed to insert quoted literal");
+
+    let result = client
+        .query(format!(
+            "SELECT {} FROM {} WHERE {} = {}",
+            column,
+            table,
+            column,
+            quote_string_literal(value)
+        ))
+        .await
+        .expect("Select failed");
+    assert_eq!(result.total_rows(), 1);
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnString>().unwrap();
+    assert_eq!(col.at(0), value);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_insert_casting_string_to_int32() {
+    use clickhouse_native_client::{
+        column::{
+            ColumnInt32,
+            ColumnString,
+        },
+        types::Type,
+        Block,
+    };
+    use std::{
+        collections::HashMap,
+        sync::Arc,
+    };
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let table = format!(
+        "test_insert_casting_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    client
+        .execute(format!(
+            "CREATE TABLE {} (id Int32) ENGINE = MergeTree ORDER BY id",
+            table
+        ))
+        .await
+        .expect("Failed to create table");
+
+    // String data bound for an Int32 column
+    let mut col = ColumnString::new(Type::string());
+    col.append("7");
+    col.append("-12");
+    let mut block = Block::new();
+    block.append_column("id", Arc::new(col)).unwrap();
+
+    let casts = HashMap::from([("id".to_string(), "Int32".to_string())]);
+    client
+        .insert_casting(&table, block, casts)
+        .await
+        .expect("Casting insert failed");
+
+    let result = client
+        .query(format!("SELECT id FROM {} ORDER BY id", table))
+        .await
+        .expect("Select failed");
+    let block = result
+        .blocks()
+        .iter()
+        .find(|b| b.row_count() > 0)
+        .expect("No data block");
+    let col = block.column(0).unwrap();
+    let col = col.as_any().downcast_ref::<ColumnInt32>().unwrap();
+    assert_eq!((0..2).map(|i| col.at(i)).collect::<Vec<_>>(), vec![-12, 7]);
+
+    client
+        .execute(format!("DROP TABLE {}", table))
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+#[ignore] // Requires running ClickHouse server
+async fn test_query_with_totals_and_extremes() {
+    use clickhouse_native_client::column::numeric::ColumnUInt64;
+
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let query = Query::new(
+        "SELECT number % 2 AS k, count() AS c FROM numbers(10) \
+         GROUP BY k WITH TOTALS ORDER BY k",
+    )
+    .with_setting_bool("extremes", true);
+    let result = client.query(query).await.expect("Query failed");
+
+    assert_eq!(result.total_rows(), 2);
+    let totals = result.totals.as_ref().expect("No totals block");
+    assert_eq!(totals.row_count(), 1);
+    let c = totals.column_by_name("c").unwrap();
+    let c = c.as_any().downcast_ref::<ColumnUInt64>().unwrap();
+    assert_eq!(c.at(0), 10);
+    let extremes = result.extremes.as_ref().expect("No extremes block");
+    assert_eq!(extremes.row_count(), 2);
+}
+
+#[tokio::test]
+#[ignore] // Requires running ClickHouse server
+async fn test_query_result_rows_before_limit() {
+    let mut client =
+        create_test_client().await.expect("Failed to connect to ClickHouse");
+
+    let result = client
+        .query(
+            "SELECT number FROM numbers(1000) WHERE number % 2 = 0 LIMIT 10",
+        )
+        .await
+        .expect("Query failed");
+
+    assert_eq!(result.total_rows(), 10);
+    let profile = result.profile().expect("No profile info");
+    assert!(profile.applied_limit);
+    assert_eq!(profile.rows_before_limit, 500);
+}
@@ -2,9 +2,12 @@
 // These tests verify that we can correctly parse all ClickHouse type strings
 
 use clickhouse_native_client::types::{
+    EnumItem,
+    IntervalUnit,
     Type,
     TypeCode,
 };
+use proptest::prelude::*;
 
 #[test]
 fn test_parse_terminals() {
@@ -146,9 +149,10 @@ fn test_parse_decimal32() {
     let t = Type::parse("Decimal32(7)").expect("Failed to parse Decimal32");
 
     match t {
-        Type::Decimal { precision, .. } => {
-            // Decimal32 is represented as Decimal with default scale
-            assert_eq!(precision, 7);
+        Type::Decimal { precision, scale } => {
+            // Decimal32(S) is Decimal(9, S)
+            assert_eq!(precision, 9);
+            assert_eq!(scale, 7);
         }
         _ => panic!("Expected Decimal type"),
     }
@@ -159,8 +163,10 @@ fn test_parse_decimal64() {
     let t = Type::parse("Decimal64(1)").expect("Failed to parse Decimal64");
 
     match t {
-        Type::Decimal { precision, .. } => {
-            assert_eq!(precision, 1);
+        Type::Decimal { precision, scale } => {
+            // Decimal64(S) is Decimal(18, S)
+            assert_eq!(precision, 18);
+            assert_eq!(scale, 1);
         }
         _ => panic!("Expected Decimal type"),
     }
@@ -171,13 +177,45 @@ fn test_parse_decimal128() {
     let t = Type::parse("Decimal128(3)").expect("Failed to parse Decimal128");
 
     match t {
-        Type::Decimal { precision, .. } => {
-            assert_eq!(precision, 3);
+        Type::Decimal { precision, scale } => {
+            // Decimal128(S) is Decimal(38, S)
+            assert_eq!(precision, 38);
+            assert_eq!(scale, 3);
         }
         _ => panic!("Expected Decimal type"),
     }
 }
 
+#[test]
+fn test_parse_interval() {
+    let t = Type::parse("IntervalDay").expect("Failed to parse IntervalDay");
+    assert_eq!(t.code(), TypeCode::Interval);
+    assert_eq!(t, Type::interval(IntervalUnit::Day));
+    assert_eq!(t.name(), "IntervalDay");
+    assert_eq!(t.storage_size_bytes(), Some(8));
+
+    for unit in ["Nanosecond", "Second", "Week", "Quarter", "Year"] {
+        let name = format!("Interval{}", unit);
+        assert_eq!(Type::parse(&name).unwrap().name(), name);
+    }
+
+    let t = Type::parse("Array(IntervalSecond)").unwrap();
+    assert_eq!(t.name(), "Array(IntervalSecond)");
+    assert!(Type::parse("IntervalFortnight").is_err());
+}
+
+#[test]
+fn test_parse_decimal256() {
+    let t = Type::parse("Decimal256(10)").expect("Failed to parse Decimal256");
+    assert_eq!(t.code(), TypeCode::Decimal);
+    // Decimal256(S) is Decimal(76, S)
+    assert_eq!(t, Type::Decimal { precision: 76, scale: 10 });
+    assert_eq!(t.storage_size_bytes(), Some(32));
+
+    let t = Type::parse("Decimal(50, 10)").unwrap();
+    assert_eq!(t.storage_size_bytes(), Some(32));
+}
+
 #[test]
 fn test_parse_datetime_no_timezone() {
     let t = Type::parse("DateTime").expect("Failed to parse DateTime");
@@ -306,3 +344,64 @@ fn test_parse_complex_nested() {
         _ => panic!("Expected Array type"),
     }
 }
+
+#[test]
+fn test_parse_enum_name_with_separators() {
+    let t = Type::parse("Enum8('a=b' = 1, 'c,d' = 2, 'it\\'s' = 3)")
+        .expect("Failed to parse Enum8");
+    match &t {
+        Type::Enum8 { items } => assert_eq!(
+            items,
+            &vec![
+                EnumItem { name: "a=b".to_string(), value: 1 },
+                EnumItem { name: "c,d".to_string(), value: 2 },
+                EnumItem { name: "it's".to_string(), value: 3 },
+            ]
+        ),
+        _ => panic!("Expected Enum8 type, got {:?}", t),
+    }
+
+    // The name survives a round trip through Type::name()
+    assert_eq!(Type::parse(&t.name()).unwrap(), t);
+}
+
+#[test]
+fn test_parse_malformed_params() {
+    for bad in [
+        "Array()",
+        "Tuple(,)",
+        "Nullable()",
+        "FixedString()",
+        "FixedString(-1)",
+        "DateTime64()",
+        "Decimal()",
+        "Enum8('a')",
+        "Enum8(= 1)",
+        "Enum8('a' = 128)",
+        "Enum8('a' = 99999999999999999999)",
+        "Enum8('unterminated",
+        "Ünknown",
+    ] {
+        assert!(Type::parse(bad).is_err(), "{} should not parse", bad);
+    }
+}
+
+#[test]
+fn test_parse_decimal_precision_only() {
+    assert_eq!(
+        Type::parse("Decimal(10)").unwrap(),
+        Type::Decimal { precision: 10, scale: 0 }
+    );
+}
+
+proptest! {
+    #[test]
+    fn prop_parse_never_panics(s in "[A-Za-z0-9_(),'= \\\\\"é-]{0,40}") {
+        let _ = Type::parse(&s);
+    }
+
+    #[test]
+    fn prop_parse_arbitrary_unicode_never_panics(s in "\\PC{0,24}") {
+        let _ = Type::parse(&s);
+    }
+}